@@ -28,10 +28,13 @@ where
      * may not apply sharpening. Therefore, a row from this matrix may not be identical to one
      * fetched using getBlackRow(), so don't mix and match between them.
      *
+     * Implementations need not cache the result; {@link BinaryBitmap} keeps the matrix it
+     * was handed for the lifetime of the image.
+     *
      * @return The 2D array of bits for the image (true means black).
      * @throws NotFoundException if image can't be binarized to make a matrix
      */
-    fn get_black_matrix(&mut self) -> ResultError<BitMatrix>;
+    fn get_black_matrix(&self) -> ResultError<BitMatrix>;
 
     /**
      * Creates a new object with the same type as this Binarizer implementation, but with pristine
@@ -2,13 +2,22 @@ use crate::common::{BitArray, BitMatrix};
 use crate::{Binarizer, LuminanceSource};
 use crate::{Error, ResultError};
 
-use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::sync::OnceLock;
 
+/**
+ * This class is the core bitmap class used by ZXing to represent 1 bit data. Reader objects
+ * accept a BinaryBitmap and attempt to decode it.
+ *
+ * The black matrix and black rows are computed the first time they are requested and kept for
+ * the lifetime of the bitmap, so any number of readers, on any number of threads, share one
+ * binarization of the image.
+ */
 pub struct BinaryBitmap<B, S> {
-    binarizer: RefCell<B>,
-    _tmp: Option<S>,
-    matrix: RefCell<BitMatrix>,
-    matrix_inited: bool,
+    binarizer: B,
+    matrix: OnceLock<BitMatrix>,
+    rows: Vec<OnceLock<BitArray>>,
+    _source: PhantomData<S>,
 }
 
 impl<B, S> BinaryBitmap<B, S>
@@ -17,11 +26,12 @@ where
     S: LuminanceSource,
 {
     pub fn new(binarizer: B) -> BinaryBitmap<B, S> {
+        let height = binarizer.get_height() as usize;
         BinaryBitmap {
-            binarizer: RefCell::new(binarizer),
-            matrix: RefCell::new(BitMatrix::new2(1, 1).unwrap()),
-            _tmp: None,
-            matrix_inited: false,
+            binarizer,
+            matrix: OnceLock::new(),
+            rows: (0..height).map(|_| OnceLock::new()).collect(),
+            _source: PhantomData,
         }
     }
 
@@ -29,61 +39,69 @@ where
      * @return The width of the bitmap.
      */
     pub fn get_width(&self) -> u32 {
-        self.binarizer.borrow().get_width()
+        self.binarizer.get_width()
     }
 
     /**
      * @return The height of the bitmap.
      */
     pub fn get_height(&self) -> u32 {
-        self.binarizer.borrow().get_height()
+        self.binarizer.get_height()
     }
 
     /**
-     * Converts one row of luminance data to 1 bit data. May actually do the conversion, or return
-     * cached data. Callers should assume this method is expensive and call it as seldom as possible.
+     * @return The luminance source the binarizer works on.
+     */
+    pub fn get_luminance_source(&self) -> ResultError<&S> {
+        self.binarizer.get_luminance_source()
+    }
+
+    /**
+     * Converts one row of luminance data to 1 bit data. The row is computed on the first request
+     * and cached, so callers which scan the same rows more than once only pay for it once.
      * This method is intended for decoding 1D barcodes and may choose to apply sharpening.
      *
      * @param y The row to fetch, which must be in [0, bitmap height)
-     * @param row An optional preallocated array. If null or too small, it will be ignored.
-     *            If used, the Binarizer will call BitArray.clear(). Always use the returned object.
      * @return The array of bits for this row (true means black).
      * @throws NotFoundException if row can't be binarized
      */
-    pub fn get_black_row(&self, y: u32, row: &BitArray) -> ResultError<BitArray> {
-        self.binarizer.borrow().get_black_row(y, row)
+    pub fn get_black_row(&self, y: u32) -> ResultError<&BitArray> {
+        let cell = self.rows.get(y as usize).ok_or_else(|| {
+            Error::IllegalArgumentException(format!("Requested row is outside the image: {}", y))
+        })?;
+        if let Some(row) = cell.get() {
+            return Ok(row);
+        }
+        let row = self.binarizer.get_black_row(y, &BitArray::new())?;
+        Ok(cell.get_or_init(|| row))
     }
 
     /**
-     * Converts a 2D array of luminance data to 1 bit. As above, assume this method is expensive
-     * and do not call it repeatedly. This method is intended for decoding 2D barcodes and may or
-     * may not apply sharpening. Therefore, a row from this matrix may not be identical to one
-     * fetched using getBlackRow(), so don't mix and match between them.
+     * Converts a 2D array of luminance data to 1 bit. This method is intended for decoding 2D
+     * barcodes and may or may not apply sharpening. Therefore, a row from this matrix may not be
+     * identical to one fetched using getBlackRow(), so don't mix and match between them.
      *
      * @return The 2D array of bits for the image (true means black).
      * @throws NotFoundException if image can't be binarized to make a matrix
      */
-    pub fn getBlackMatrix(&self) -> ResultError<BitMatrix> {
+    pub fn getBlackMatrix(&self) -> ResultError<&BitMatrix> {
         // The matrix is created on demand the first time it is requested, then cached. There are two
         // reasons for this:
         // 1. This work will never be done if the caller only installs 1D Reader objects, or if a
         //    1D Reader finds a barcode before the 2D Readers run.
         // 2. This work will only be done once even if the caller installs multiple 2D Readers.
-        if !self.matrix_inited {
-            *self.matrix.borrow_mut() = self.binarizer.borrow_mut().get_black_matrix()?;
+        if let Some(matrix) = self.matrix.get() {
+            return Ok(matrix);
         }
-        Ok(self.matrix.borrow().clone())
+        let matrix = self.binarizer.get_black_matrix()?;
+        Ok(self.matrix.get_or_init(|| matrix))
     }
 
     /**
      * @return Whether this bitmap can be cropped.
      */
     pub fn is_crop_supported(&self) -> ResultError<bool> {
-        Ok(self
-            .binarizer
-            .borrow()
-            .get_luminance_source()?
-            .is_crop_supported())
+        Ok(self.binarizer.get_luminance_source()?.is_crop_supported())
     }
 
     /**
@@ -105,10 +123,80 @@ where
     ) -> ResultError<BinaryBitmap<B, S>> {
         let newSource = self
             .binarizer
-            .borrow()
             .get_luminance_source()?
             .crop(left, top, width, height)?;
-        // Ok(BinaryBitmap::new(self.binarizer.borrow().create_binarizer(*newSource)))
+        // Ok(BinaryBitmap::new(self.binarizer.create_binarizer(*newSource)))
         todo!()
     }
 }
+
+#[cfg(test)]
+mod binary_bitmap_tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct CountingBinarizer {
+        width: u32,
+        height: u32,
+        matrices: AtomicUsize,
+        rows: AtomicUsize,
+    }
+
+    impl Binarizer<crate::InvertedLuminanceSource> for CountingBinarizer {
+        fn get_black_row(&self, _y: u32, _row: &BitArray) -> ResultError<BitArray> {
+            self.rows.fetch_add(1, Ordering::SeqCst);
+            Ok(BitArray::new1(self.width))
+        }
+
+        fn get_black_matrix(&self) -> ResultError<BitMatrix> {
+            self.matrices.fetch_add(1, Ordering::SeqCst);
+            BitMatrix::new2(self.width as i32, self.height as i32)
+        }
+
+        fn create_binarizer(&self, _source: crate::InvertedLuminanceSource) -> Self {
+            unimplemented!()
+        }
+
+        fn get_luminance_source(&self) -> ResultError<&crate::InvertedLuminanceSource> {
+            Err(Error::UnsupportedOperationException(String::new()))
+        }
+
+        fn get_width(&self) -> u32 {
+            self.width
+        }
+
+        fn get_height(&self) -> u32 {
+            self.height
+        }
+    }
+
+    #[test]
+    fn binarizes_once() {
+        let bitmap = Arc::new(BinaryBitmap::new(CountingBinarizer {
+            width: 40,
+            height: 30,
+            matrices: AtomicUsize::new(0),
+            rows: AtomicUsize::new(0),
+        }));
+        assert_eq!(40, bitmap.getBlackMatrix().unwrap().getWidth());
+        assert_eq!(40, bitmap.get_black_row(7).unwrap().get_size());
+        assert!(bitmap.get_black_row(30).is_err());
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let bitmap = Arc::clone(&bitmap);
+                std::thread::spawn(move || {
+                    bitmap.getBlackMatrix().unwrap();
+                    bitmap.get_black_row(7).unwrap();
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(1, bitmap.binarizer.matrices.load(Ordering::SeqCst));
+        assert_eq!(1, bitmap.binarizer.rows.load(Ordering::SeqCst));
+    }
+}
@@ -45,6 +45,10 @@ impl BufferedImage {
         self.img.height()
     }
 
+    pub fn get_image(&self) -> &DynamicImage {
+        &self.img
+    }
+
     pub fn get_type(&self) -> ColorType {
         self.img.color()
     }
@@ -53,10 +57,19 @@ impl BufferedImage {
         self.img.pixels()
     }
 
+    /**
+     * Returns the luminance of the pixels in the given rectangle, one byte per pixel in
+     * row-major order.
+     */
     pub fn get_rect_data(&self, x: u32, y: u32, w: u32, h: u32) -> Vec<u8> {
-        let mut img = self.img.clone();
-        let subimg = imageops::crop(&mut img, x, y, w, h).to_image().to_vec();
-        subimg
+        match &self.img {
+            DynamicImage::ImageLuma8(gray) => {
+                imageops::crop_imm(gray, x, y, w, h).to_image().into_raw()
+            }
+            img => imageops::crop_imm(&img.to_luma8(), x, y, w, h)
+                .to_image()
+                .into_raw(),
+        }
     }
 
     pub fn to_gray(&mut self) {
@@ -72,6 +85,12 @@ impl BufferedImage {
     }
 }
 
+impl From<DynamicImage> for BufferedImage {
+    fn from(img: DynamicImage) -> Self {
+        BufferedImage { img }
+    }
+}
+
 // RUST_TEST_NOCAPTURE=1 cargo test image_tests
 #[cfg(test)]
 mod image_tests {
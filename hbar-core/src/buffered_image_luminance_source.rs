@@ -0,0 +1,280 @@
+use crate::BufferedImage;
+use crate::Error;
+use crate::GrayLuminanceSource;
+use crate::InvertedLuminanceSource;
+use crate::LuminanceSource;
+use crate::ResultError;
+
+use image::{DynamicImage, GenericImageView, GrayImage, RgbaImage};
+
+use std::f64::consts::FRAC_1_SQRT_2;
+use std::sync::Arc;
+
+/**
+ * This LuminanceSource implementation is meant for images loaded with the image crate.
+ *
+ * The image is converted to 8-bit luminance once, when the source is built. Colour pixels are
+ * weighted (306 R + 601 G + 117 B) / 1024, and partially transparent pixels are blended over
+ * white, so fully transparent ones read as white. Only the region given to {@link #new1} is
+ * converted. Crops share the converted pixels, and rotation only copies the cropped region.
+ */
+#[derive(Debug, Clone)]
+pub struct BufferedImageLuminanceSource {
+    luminances: GrayLuminanceSource<'static>,
+}
+
+impl BufferedImageLuminanceSource {
+    pub fn new(image: &BufferedImage) -> ResultError<BufferedImageLuminanceSource> {
+        Ok(BufferedImageLuminanceSource::from(image.get_image()))
+    }
+
+    /**
+     * @param image the image to decode
+     * @param left left edge of the region to decode
+     * @param top top edge of the region to decode
+     * @param width width of the region to decode
+     * @param height height of the region to decode
+     * @throws IllegalArgumentException if the region does not fit within the image
+     */
+    pub fn new1(
+        image: &BufferedImage,
+        left: u32,
+        top: u32,
+        width: u32,
+        height: u32,
+    ) -> ResultError<BufferedImageLuminanceSource> {
+        let image = image.get_image();
+        if width == 0
+            || height == 0
+            || left as u64 + width as u64 > image.width() as u64
+            || top as u64 + height as u64 > image.height() as u64
+        {
+            return Err(Error::IllegalArgumentException(String::from(
+                "Crop rectangle does not fit within image data.",
+            )));
+        }
+        let luminances = image
+            .view(left, top, width, height)
+            .pixels()
+            .map(|(_, _, pixel)| {
+                let [r, g, b, a] = pixel.0;
+                BufferedImageLuminanceSource::luminance(r, g, b, a)
+            })
+            .collect();
+        Ok(BufferedImageLuminanceSource::from_luminances(
+            luminances, width, height,
+        ))
+    }
+
+    fn view(
+        &self,
+        left: u32,
+        top: u32,
+        width: u32,
+        height: u32,
+    ) -> ResultError<BufferedImageLuminanceSource> {
+        Ok(BufferedImageLuminanceSource {
+            luminances: self.luminances.view(left, top, width, height)?,
+        })
+    }
+
+    fn from_luminances(luminances: Vec<u8>, width: u32, height: u32) -> Self {
+        BufferedImageLuminanceSource {
+            luminances: GrayLuminanceSource::from_luminances(luminances, width, height),
+        }
+    }
+
+    // The same weights as Java's TYPE_BYTE_GRAY conversion, with alpha blended over white.
+    fn luminance(r: u8, g: u8, b: u8, a: u8) -> u8 {
+        let luminance = (306 * r as u32 + 601 * g as u32 + 117 * b as u32 + 0x200) >> 10;
+        let a = a as u32;
+        ((luminance * a + 0xFF * (0xFF - a) + 0x7F) / 0xFF) as u8
+    }
+}
+
+impl From<GrayImage> for BufferedImageLuminanceSource {
+    fn from(image: GrayImage) -> Self {
+        let (width, height) = image.dimensions();
+        BufferedImageLuminanceSource::from_luminances(image.into_raw(), width, height)
+    }
+}
+
+impl From<&RgbaImage> for BufferedImageLuminanceSource {
+    fn from(image: &RgbaImage) -> Self {
+        let (width, height) = image.dimensions();
+        let luminances = image
+            .pixels()
+            .map(|pixel| {
+                let [r, g, b, a] = pixel.0;
+                BufferedImageLuminanceSource::luminance(r, g, b, a)
+            })
+            .collect();
+        BufferedImageLuminanceSource::from_luminances(luminances, width, height)
+    }
+}
+
+impl From<RgbaImage> for BufferedImageLuminanceSource {
+    fn from(image: RgbaImage) -> Self {
+        BufferedImageLuminanceSource::from(&image)
+    }
+}
+
+impl From<&DynamicImage> for BufferedImageLuminanceSource {
+    fn from(image: &DynamicImage) -> Self {
+        match image {
+            DynamicImage::ImageLuma8(gray) => BufferedImageLuminanceSource::from(gray.clone()),
+            DynamicImage::ImageLumaA8(gray) => {
+                let (width, height) = gray.dimensions();
+                let luminances = gray
+                    .pixels()
+                    .map(|pixel| {
+                        let [l, a] = pixel.0;
+                        BufferedImageLuminanceSource::luminance(l, l, l, a)
+                    })
+                    .collect();
+                BufferedImageLuminanceSource::from_luminances(luminances, width, height)
+            }
+            DynamicImage::ImageRgba8(rgba) => BufferedImageLuminanceSource::from(rgba),
+            image => BufferedImageLuminanceSource::from(&image.to_rgba8()),
+        }
+    }
+}
+
+impl From<DynamicImage> for BufferedImageLuminanceSource {
+    fn from(image: DynamicImage) -> Self {
+        match image {
+            DynamicImage::ImageLuma8(gray) => BufferedImageLuminanceSource::from(gray),
+            image => BufferedImageLuminanceSource::from(&image),
+        }
+    }
+}
+
+impl LuminanceSource for BufferedImageLuminanceSource {
+    fn get_row(&self, y: i32, row: &Vec<u8>) -> ResultError<Vec<u8>> {
+        self.luminances.get_row(y, row)
+    }
+
+    fn get_matrix(&self) -> ResultError<Vec<u8>> {
+        self.luminances.get_matrix()
+    }
+
+    fn get_width(&self) -> u32 {
+        self.luminances.get_width()
+    }
+
+    fn get_height(&self) -> u32 {
+        self.luminances.get_height()
+    }
+
+    fn is_crop_supported(&self) -> bool {
+        true
+    }
+
+    fn crop(
+        &self,
+        left: u32,
+        top: u32,
+        width: u32,
+        height: u32,
+    ) -> ResultError<Arc<dyn LuminanceSource>> {
+        Ok(Arc::new(self.view(left, top, width, height)?))
+    }
+
+    /**
+     * This is always true, since the image is a gray-scale image.
+     *
+     * @return true
+     */
+    fn is_rotate_supported(&self) -> bool {
+        true
+    }
+
+    fn invert(&self) -> ResultError<Arc<dyn LuminanceSource>> {
+        Ok(Arc::new(InvertedLuminanceSource::new(Arc::new(
+            self.clone(),
+        ))))
+    }
+
+    fn rotate_counter_clockwise(&self) -> ResultError<Arc<dyn LuminanceSource>> {
+        self.luminances.rotate_counter_clockwise()
+    }
+
+    /**
+     * Rotates the region by 45 degrees counterclockwise about its centre, into a square whose
+     * side is the longer side of the region. Corners which fall outside the region are white.
+     */
+    fn rotate_counter_clockwise45(&self) -> ResultError<Arc<dyn LuminanceSource>> {
+        let width = self.get_width() as i64;
+        let height = self.get_height() as i64;
+        let matrix = self.get_matrix()?;
+        let dimension = width.max(height);
+        let center_x = (width - 1) as f64 / 2.0;
+        let center_y = (height - 1) as f64 / 2.0;
+        let offset = (dimension - 1) as f64 / 2.0;
+        let mut rotated = vec![0xFF; (dimension * dimension) as usize];
+        for y in 0..dimension {
+            let v = y as f64 - offset;
+            for x in 0..dimension {
+                let u = x as f64 - offset;
+                let source_x = (center_x + (u - v) * FRAC_1_SQRT_2).round() as i64;
+                let source_y = (center_y + (u + v) * FRAC_1_SQRT_2).round() as i64;
+                if (0..width).contains(&source_x) && (0..height).contains(&source_y) {
+                    rotated[(y * dimension + x) as usize] =
+                        matrix[(source_y * width + source_x) as usize];
+                }
+            }
+        }
+        Ok(Arc::new(BufferedImageLuminanceSource::from_luminances(
+            rotated,
+            dimension as u32,
+            dimension as u32,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod buffered_image_luminance_source_tests {
+    use super::*;
+
+    use image::{Luma, Rgba};
+
+    #[test]
+    fn weights_colour_and_alpha() {
+        let mut image = RgbaImage::new(4, 1);
+        image.put_pixel(0, 0, Rgba([0, 0, 0, 0xFF]));
+        image.put_pixel(1, 0, Rgba([0, 0, 0, 0]));
+        image.put_pixel(2, 0, Rgba([0xFF, 0, 0, 0xFF]));
+        image.put_pixel(3, 0, Rgba([0, 0, 0, 0x80]));
+        let source = BufferedImageLuminanceSource::from(image);
+        assert_eq!(vec![0, 0xFF, 76, 0x7F], source.get_matrix().unwrap());
+    }
+
+    #[test]
+    fn crop_and_rotate() {
+        let image = GrayImage::from_fn(3, 2, |x, y| Luma([(y * 3 + x) as u8]));
+        let source = BufferedImageLuminanceSource::from(DynamicImage::ImageLuma8(image));
+        let cropped = source.crop(1, 0, 2, 2).unwrap();
+        assert_eq!(vec![1, 2, 4, 5], cropped.get_matrix().unwrap());
+        let rotated = cropped.rotate_counter_clockwise().unwrap();
+        assert_eq!(vec![2, 5, 1, 4], rotated.get_matrix().unwrap());
+        assert!(source.crop(2, 0, 2, 2).is_err());
+
+        let mut image = RgbaImage::new(3, 2);
+        image.put_pixel(1, 0, Rgba([0xFF, 0, 0, 0xFF]));
+        image.put_pixel(2, 1, Rgba([0, 0, 0, 0x80]));
+        let image = BufferedImage::from(DynamicImage::ImageRgba8(image));
+        let region = BufferedImageLuminanceSource::new1(&image, 1, 0, 2, 2).unwrap();
+        assert_eq!(vec![76, 0xFF, 0xFF, 0x7F], region.get_matrix().unwrap());
+        assert!(BufferedImageLuminanceSource::new1(&image, 2, 0, 2, 2).is_err());
+
+        let diagonal = BufferedImageLuminanceSource::from(GrayImage::from_fn(5, 5, |x, y| {
+            Luma([if x == y { 0 } else { 0xFF }])
+        }));
+        let rotated = diagonal.rotate_counter_clockwise45().unwrap();
+        // The main diagonal becomes a horizontal line through the centre.
+        assert_eq!(
+            vec![0; 3],
+            rotated.get_row(2, &Vec::new()).unwrap()[1..4].to_vec()
+        );
+    }
+}
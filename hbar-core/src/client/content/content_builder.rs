@@ -0,0 +1,366 @@
+#[cfg(feature = "encode")]
+use crate::EncodeOptions;
+use crate::ErrorCorrectionLevel;
+
+// The most bytes a version 10 symbol, 57 modules wide, holds in byte mode at levels H and Q.
+// Payloads up to these sizes stay small enough to scan easily even with the extra redundancy.
+const VERSION_10_H_BYTES: usize = 119;
+const VERSION_10_Q_BYTES: usize = 151;
+// The most bytes any symbol holds at level M; larger payloads only fit at level L.
+const VERSION_40_M_BYTES: usize = 2331;
+
+/**
+ * Builds the text of a structured QR Code payload, like a Wi-Fi configuration or a contact,
+ * with every value escaped as its format requires. The text reads back as the matching
+ * {@link ParsedResult}.
+ *
+ * <pre>
+ * use hbar_core::client::content::{ContentBuilder, WifiContentBuilder};
+ * use hbar_core::{BarcodeFormat, QRCodeWriter, Writer};
+ *
+ * let wifi = WifiContentBuilder::new("home").encryption("WPA").password("secret");
+ * let matrix = QRCodeWriter::new().encode_options(
+ *     &wifi.build(), &BarcodeFormat::QRCode, 200, 200, &wifi.get_encode_options())?;
+ * </pre>
+ */
+pub trait ContentBuilder {
+    /**
+     * @return the payload, to encode as the contents of a QR Code
+     */
+    fn build(&self) -> String;
+
+    /**
+     * @return the error correction level suited to the size of the payload
+     */
+    fn get_error_correction_level(&self) -> ErrorCorrectionLevel {
+        suggest_error_correction_level(&self.build())
+    }
+
+    /**
+     * @return options to encode the payload with, at its suggested error correction level
+     */
+    #[cfg(feature = "encode")]
+    fn get_encode_options(&self) -> EncodeOptions {
+        EncodeOptions::new().error_correction(self.get_error_correction_level())
+    }
+}
+
+/**
+ * Suggests an error correction level for a payload of the given size. Short payloads get the
+ * most redundancy while the symbol stays small; as they grow, less is used so that the symbol
+ * does not become too dense to scan.
+ *
+ * @param contents the text to encode, measured in UTF-8 bytes
+ * @return H, Q or M while the payload fits a version 10 symbol at that level or any symbol at
+ *  M, and L beyond
+ */
+pub fn suggest_error_correction_level(contents: &str) -> ErrorCorrectionLevel {
+    match contents.len() {
+        length if length <= VERSION_10_H_BYTES => ErrorCorrectionLevel::H,
+        length if length <= VERSION_10_Q_BYTES => ErrorCorrectionLevel::Q,
+        length if length <= VERSION_40_M_BYTES => ErrorCorrectionLevel::M,
+        _ => ErrorCorrectionLevel::L,
+    }
+}
+
+/**
+ * Escapes the characters which end or separate fields in "WIFI:" and "MECARD:" payloads,
+ * with a backslash.
+ */
+pub(crate) fn escape_backslash(value: &str, special: &[char]) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c == '\\' || special.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/**
+ * Escapes a vCard or iCalendar text value: '\', ',' and ';' with a backslash, and line breaks
+ * as "\n".
+ */
+pub(crate) fn escape_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' | ',' | ';' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/**
+ * Percent-encodes everything but the unreserved characters of RFC 3986, as UTF-8.
+ */
+pub(crate) fn url_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for b in value.bytes() {
+        if b.is_ascii_alphanumeric() || b == b'-' || b == b'.' || b == b'_' || b == b'~' {
+            encoded.push(b as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", b));
+        }
+    }
+    encoded
+}
+
+/**
+ * Appends "name=value" query parameters to a URI. The values must already be percent-encoded.
+ */
+pub(crate) fn append_query(uri: &mut String, parameters: &[(&str, Option<String>)]) {
+    let mut separator = '?';
+    for (name, value) in parameters {
+        if let Some(value) = value {
+            uri.push(separator);
+            uri.push_str(name);
+            uri.push('=');
+            uri.push_str(value);
+            separator = '&';
+        }
+    }
+}
+
+#[cfg(test)]
+mod content_builder_tests {
+    use super::{suggest_error_correction_level, ContentBuilder};
+    use crate::client::content::{
+        EmailContentBuilder, GeoContentBuilder, MeCardContentBuilder, SmsContentBuilder,
+        VCardContentBuilder, VCardVersion, VEventContentBuilder, WifiContentBuilder,
+    };
+    use crate::client::result::ParsedResult;
+    use crate::{BarcodeFormat, ErrorCorrectionLevel};
+
+    fn round_trip(builder: &dyn ContentBuilder) -> ParsedResult {
+        ParsedResult::parse_text(&builder.build(), &BarcodeFormat::QRCode)
+    }
+
+    #[test]
+    fn escapes_wifi() {
+        let wifi = WifiContentBuilder::new(r#"my;net:"5G""#)
+            .encryption("WPA")
+            .password(r"pa\ss,word")
+            .hidden(true);
+        assert_eq!(
+            wifi.build(),
+            r#"WIFI:T:WPA;S:my\;net\:\"5G\";P:pa\\ss\,word;H:true;;"#
+        );
+        match round_trip(&wifi) {
+            ParsedResult::Wifi(parsed) => {
+                assert_eq!(parsed.get_ssid(), r#"my;net:"5G""#);
+                assert_eq!(parsed.get_password(), Some(r"pa\ss,word"));
+                assert_eq!(parsed.get_network_encryption(), "WPA");
+                assert!(parsed.is_hidden());
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn round_trips_contacts() {
+        for &version in &[VCardVersion::V3, VCardVersion::V4] {
+            let vcard = VCardContentBuilder::new(version, "Sean Owen")
+                .structured_name("Owen", "Sean")
+                .phone_number("+12125551212", Some("cell"))
+                .email("srowen@example.org", None)
+                .address("123 Main St\nAnytown; CA", Some("home"))
+                .org("Google, Inc.")
+                .note("a\\b")
+                .birthday("1976-05-20")
+                .geo(37.5, -122.25);
+            match round_trip(&vcard) {
+                ParsedResult::AddressBook(parsed) => {
+                    assert_eq!(parsed.get_names(), ["Sean Owen"]);
+                    assert_eq!(parsed.get_phone_numbers(), ["+12125551212"]);
+                    assert_eq!(parsed.get_phone_types(), [Some("cell".to_string())]);
+                    assert_eq!(parsed.get_emails(), ["srowen@example.org"]);
+                    assert_eq!(parsed.get_addresses(), ["123 Main St\nAnytown; CA"]);
+                    assert_eq!(parsed.get_org(), Some("Google, Inc."));
+                    assert_eq!(parsed.get_note(), Some("a\\b"));
+                    assert_eq!(parsed.get_birthday(), Some("1976-05-20"));
+                    assert_eq!(parsed.get_geo(), ["37.5", "-122.25"]);
+                }
+                other => panic!("unexpected {:?} for {:?}", other, version),
+            }
+        }
+
+        let mecard = MeCardContentBuilder::new("Owen,Sean")
+            .phone_number("+12125551212")
+            .email("srowen@example.org")
+            .url("https://example.org/a;b")
+            .note("TEL:not a number");
+        match round_trip(&mecard) {
+            ParsedResult::AddressBook(parsed) => {
+                assert_eq!(parsed.get_names(), ["Sean Owen"]);
+                assert_eq!(parsed.get_phone_numbers(), ["+12125551212"]);
+                assert_eq!(parsed.get_urls(), ["https://example.org/a;b"]);
+                assert_eq!(parsed.get_note(), Some("TEL:not a number"));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn round_trips_events() {
+        let event = VEventContentBuilder::new("Launch; party", 1_209_904_496_000)
+            .end(1_210_031_155_000)
+            .location("Miami, FL")
+            .organizer("bob@example.org")
+            .attendee("al@example.org")
+            .geo(-12.345, -45.678);
+        assert!(event.build().contains("DTSTART:20080504T123456Z\r\n"));
+        match round_trip(&event) {
+            ParsedResult::Calendar(parsed) => {
+                assert_eq!(parsed.get_summary(), Some("Launch; party"));
+                assert_eq!(parsed.get_start_timestamp(), 1_209_904_496_000);
+                assert_eq!(parsed.get_end_timestamp(), 1_210_031_155_000);
+                assert_eq!(parsed.get_location(), Some("Miami, FL"));
+                assert_eq!(parsed.get_organizer(), Some("bob@example.org"));
+                assert_eq!(parsed.get_attendees(), ["al@example.org"]);
+                assert_eq!(parsed.get_latitude(), -12.345);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let all_day = VEventContentBuilder::new("Holiday", 1_209_859_200_000).all_day(true);
+        match round_trip(&all_day) {
+            ParsedResult::Calendar(parsed) => {
+                assert!(parsed.is_start_all_day());
+                assert_eq!(parsed.get_start_timestamp(), 1_209_859_200_000);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn round_trips_uris() {
+        let geo = GeoContentBuilder::new(47.375, 8.5, Some(408.0))
+            .unwrap()
+            .query("Zurich HB");
+        assert_eq!(geo.build(), "geo:47.375,8.5,408?q=Zurich%20HB");
+        match round_trip(&geo) {
+            ParsedResult::Geo(parsed) => {
+                assert_eq!(parsed.get_latitude(), 47.375);
+                assert_eq!(parsed.get_longitude(), 8.5);
+                assert_eq!(parsed.get_altitude(), 408.0);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(GeoContentBuilder::new(91.0, 0.0, None).is_err());
+        assert!(GeoContentBuilder::new(0.0, 0.0, Some(-1.0)).is_err());
+
+        let sms = SmsContentBuilder::mms("+15551212")
+            .and_then(|sms| sms.number("+15551213"))
+            .unwrap()
+            .subject("Hi & bye")
+            .body("1+1=2");
+        match round_trip(&sms) {
+            ParsedResult::Sms(parsed) => {
+                assert_eq!(parsed.get_numbers(), ["+15551212", "+15551213"]);
+                assert_eq!(parsed.get_subject(), Some("Hi & bye"));
+                assert_eq!(parsed.get_body(), Some("1+1=2"));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let email = EmailContentBuilder::new("sean+qr@example.org")
+            .and_then(|email| email.cc("a@example.org"))
+            .and_then(|email| email.cc("b@example.org"))
+            .unwrap()
+            .subject("Grüße")
+            .body("line one\nline two");
+        assert!(email
+            .build()
+            .starts_with("mailto:sean%2Bqr@example.org?cc="));
+        match round_trip(&email) {
+            ParsedResult::EmailAddress(parsed) => {
+                assert_eq!(parsed.get_tos(), ["sean+qr@example.org"]);
+                assert_eq!(parsed.get_ccs(), ["a@example.org", "b@example.org"]);
+                assert_eq!(parsed.get_subject(), Some("Grüße"));
+                assert_eq!(parsed.get_body(), Some("line one\nline two"));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn rejects_or_escapes_separators() {
+        assert!(SmsContentBuilder::sms("+1 555;1212").is_err());
+        assert!(SmsContentBuilder::mms("+15551212,+15551213").is_err());
+        let sms = SmsContentBuilder::sms("+1 555-1212").unwrap();
+        assert!(sms.clone().number("?body=x").is_err());
+        match round_trip(&sms) {
+            ParsedResult::Sms(parsed) => assert_eq!(parsed.get_numbers(), ["+1 555-1212"]),
+            other => panic!("unexpected {:?}", other),
+        }
+
+        assert!(EmailContentBuilder::new("a,b@example.org").is_err());
+        let email = EmailContentBuilder::new("a@example.org").unwrap();
+        assert!(email.clone().cc("b@example.org,c@example.org").is_err());
+        assert!(email.clone().bcc("").is_err());
+        match round_trip(&email.to("\"a;b\"@example.org").unwrap()) {
+            ParsedResult::EmailAddress(parsed) => {
+                assert_eq!(parsed.get_tos(), ["a@example.org", "\"a;b\"@example.org"]);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let vcard = VCardContentBuilder::new(VCardVersion::V3, "Sean Owen")
+            .address("C:\\", None)
+            .address("a\\;b;", Some("work"));
+        match round_trip(&vcard) {
+            ParsedResult::AddressBook(parsed) => {
+                assert_eq!(parsed.get_addresses(), ["C:\\", "a\\;b;"]);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn suggests_less_error_correction_for_larger_payloads() {
+        assert_eq!(
+            WifiContentBuilder::new("home").get_error_correction_level(),
+            ErrorCorrectionLevel::H
+        );
+        let sizes = [
+            (119, ErrorCorrectionLevel::H),
+            (120, ErrorCorrectionLevel::Q),
+            (151, ErrorCorrectionLevel::Q),
+            (152, ErrorCorrectionLevel::M),
+            (2331, ErrorCorrectionLevel::M),
+            (2332, ErrorCorrectionLevel::L),
+        ];
+        for (size, ec_level) in sizes.iter() {
+            assert_eq!(
+                suggest_error_correction_level(&"x".repeat(*size)),
+                *ec_level,
+                "{} bytes",
+                size
+            );
+        }
+    }
+
+    #[cfg(all(feature = "encode", feature = "qrcode"))]
+    #[test]
+    fn encodes_with_suggested_options() {
+        use crate::{QRCodeWriter, Writer};
+
+        let vcard = VCardContentBuilder::new(VCardVersion::V3, "Sean Owen")
+            .note(&"x".repeat(200))
+            .email("srowen@example.org", None);
+        let options = vcard.get_encode_options();
+        assert_eq!(vcard.get_error_correction_level(), ErrorCorrectionLevel::M);
+        let matrix = QRCodeWriter::new()
+            .encode_options(&vcard.build(), &BarcodeFormat::QRCode, 1, 1, &options)
+            .unwrap();
+        assert!(matrix.getWidth() > 0);
+    }
+}
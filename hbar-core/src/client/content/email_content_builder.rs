@@ -0,0 +1,107 @@
+use crate::{Error, ResultError};
+
+use super::content_builder::{append_query, url_encode, ContentBuilder};
+
+/**
+ * Builds a "mailto:" URI, RFC 6068, like "mailto:sean@example.org?subject=Hi". Addresses,
+ * subject and body are percent-encoded as UTF-8. Readers split the addresses at commas after
+ * decoding them, so an address may not contain one.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailContentBuilder {
+    tos: Vec<String>,
+    ccs: Vec<String>,
+    bccs: Vec<String>,
+    subject: Option<String>,
+    body: Option<String>,
+}
+
+impl EmailContentBuilder {
+    /**
+     * @throws IllegalArgumentException if the address is empty or contains a comma
+     */
+    pub fn new(to: &str) -> ResultError<Self> {
+        Ok(EmailContentBuilder {
+            tos: vec![EmailContentBuilder::check_address(to)?],
+            ccs: Vec::new(),
+            bccs: Vec::new(),
+            subject: None,
+            body: None,
+        })
+    }
+
+    /**
+     * Adds another recipient.
+     *
+     * @throws IllegalArgumentException if the address is empty or contains a comma
+     */
+    pub fn to(mut self, to: &str) -> ResultError<Self> {
+        self.tos.push(EmailContentBuilder::check_address(to)?);
+        Ok(self)
+    }
+
+    /**
+     * @throws IllegalArgumentException if the address is empty or contains a comma
+     */
+    pub fn cc(mut self, cc: &str) -> ResultError<Self> {
+        self.ccs.push(EmailContentBuilder::check_address(cc)?);
+        Ok(self)
+    }
+
+    /**
+     * @throws IllegalArgumentException if the address is empty or contains a comma
+     */
+    pub fn bcc(mut self, bcc: &str) -> ResultError<Self> {
+        self.bccs.push(EmailContentBuilder::check_address(bcc)?);
+        Ok(self)
+    }
+
+    pub fn subject(mut self, subject: &str) -> Self {
+        self.subject = Some(subject.to_string());
+        self
+    }
+
+    pub fn body(mut self, body: &str) -> Self {
+        self.body = Some(body.to_string());
+        self
+    }
+
+    fn check_address(address: &str) -> ResultError<String> {
+        if address.is_empty() || address.contains(',') {
+            return Err(Error::IllegalArgumentException(format!(
+                "Bad e-mail address: {:?}",
+                address
+            )));
+        }
+        Ok(address.to_string())
+    }
+
+    // Encodes addresses, separated by commas. '@' is left as it is, for readability.
+    fn encode_addresses(addresses: &[String]) -> Option<String> {
+        if addresses.is_empty() {
+            return None;
+        }
+        let encoded: Vec<String> = addresses
+            .iter()
+            .map(|address| url_encode(address).replace("%40", "@"))
+            .collect();
+        Some(encoded.join(","))
+    }
+}
+
+impl ContentBuilder for EmailContentBuilder {
+    fn build(&self) -> String {
+        let mut result = String::from("mailto:");
+        result.push_str(&EmailContentBuilder::encode_addresses(&self.tos).unwrap_or_default());
+        append_query(
+            &mut result,
+            &[
+                ("cc", EmailContentBuilder::encode_addresses(&self.ccs)),
+                ("bcc", EmailContentBuilder::encode_addresses(&self.bccs)),
+                ("subject", self.subject.as_deref().map(url_encode)),
+                ("body", self.body.as_deref().map(url_encode)),
+            ],
+        );
+        result
+    }
+}
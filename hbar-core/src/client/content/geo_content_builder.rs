@@ -0,0 +1,64 @@
+use crate::{Error, ResultError};
+
+use super::content_builder::{append_query, url_encode, ContentBuilder};
+
+/**
+ * Builds a "geo:" URI, RFC 5870, like "geo:47.3769,8.5417,408?q=Zurich".
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoContentBuilder {
+    latitude: f64,
+    longitude: f64,
+    altitude: Option<f64>,
+    query: Option<String>,
+}
+
+impl GeoContentBuilder {
+    /**
+     * @param latitude degrees north, from -90 to 90
+     * @param longitude degrees east, from -180 to 180
+     * @param altitude meters above sea level, which readers only accept when not negative
+     * @throws IllegalArgumentException if a coordinate is out of range
+     */
+    pub fn new(latitude: f64, longitude: f64, altitude: Option<f64>) -> ResultError<Self> {
+        if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+            return Err(Error::IllegalArgumentException(format!(
+                "Coordinates out of range: {}, {}",
+                latitude, longitude
+            )));
+        }
+        if let Some(altitude) = altitude {
+            if !(altitude >= 0.0 && altitude.is_finite()) {
+                return Err(Error::IllegalArgumentException(format!(
+                    "Altitude out of range: {}",
+                    altitude
+                )));
+            }
+        }
+        Ok(GeoContentBuilder {
+            latitude,
+            longitude,
+            altitude,
+            query: None,
+        })
+    }
+
+    /**
+     * Sets a search term, like the name of the place, written as the "q" parameter.
+     */
+    pub fn query(mut self, query: &str) -> Self {
+        self.query = Some(query.to_string());
+        self
+    }
+}
+
+impl ContentBuilder for GeoContentBuilder {
+    fn build(&self) -> String {
+        let mut result = format!("geo:{},{}", self.latitude, self.longitude);
+        if let Some(altitude) = self.altitude {
+            result.push_str(&format!(",{}", altitude));
+        }
+        append_query(&mut result, &[("q", self.query.as_deref().map(url_encode))]);
+        result
+    }
+}
@@ -0,0 +1,141 @@
+use super::content_builder::{escape_backslash, ContentBuilder};
+
+// Characters reserved by the MECARD format, escaped in values.
+const SPECIAL_CHARACTERS: [char; 3] = [';', ':', ','];
+
+/**
+ * Builds a NTT DoCoMo "MECARD:" contact, like "MECARD:N:Owen,Sean;TEL:+12125551212;;". It is
+ * more compact than a vCard but holds fewer fields. Values are escaped with a backslash where
+ * they contain '\', ';', ':' or ',', except for the comma between last and first name.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeCardContentBuilder {
+    name: String,
+    pronunciation: Option<String>,
+    phone_numbers: Vec<String>,
+    emails: Vec<String>,
+    note: Option<String>,
+    birthday: Option<String>,
+    addresses: Vec<String>,
+    urls: Vec<String>,
+    org: Option<String>,
+}
+
+impl MeCardContentBuilder {
+    /**
+     * @param name the name, as written or "Last,First"; readers show the latter as "First Last"
+     */
+    pub fn new(name: &str) -> Self {
+        MeCardContentBuilder {
+            name: name.to_string(),
+            pronunciation: None,
+            phone_numbers: Vec::new(),
+            emails: Vec::new(),
+            note: None,
+            birthday: None,
+            addresses: Vec::new(),
+            urls: Vec::new(),
+            org: None,
+        }
+    }
+
+    /**
+     * Sets how the name is read, the "SOUND" field.
+     */
+    pub fn pronunciation(mut self, pronunciation: &str) -> Self {
+        self.pronunciation = Some(pronunciation.to_string());
+        self
+    }
+
+    /**
+     * Adds a phone number; may be called more than once.
+     */
+    pub fn phone_number(mut self, phone_number: &str) -> Self {
+        self.phone_numbers.push(phone_number.to_string());
+        self
+    }
+
+    /**
+     * Adds an e-mail address; may be called more than once.
+     */
+    pub fn email(mut self, email: &str) -> Self {
+        self.emails.push(email.to_string());
+        self
+    }
+
+    pub fn note(mut self, note: &str) -> Self {
+        self.note = Some(note.to_string());
+        self
+    }
+
+    /**
+     * Sets the birthday, as yyyyMMdd.
+     */
+    pub fn birthday(mut self, birthday: &str) -> Self {
+        self.birthday = Some(birthday.to_string());
+        self
+    }
+
+    /**
+     * Adds an address; may be called more than once.
+     */
+    pub fn address(mut self, address: &str) -> Self {
+        self.addresses.push(address.to_string());
+        self
+    }
+
+    /**
+     * Adds a web site; may be called more than once.
+     */
+    pub fn url(mut self, url: &str) -> Self {
+        self.urls.push(url.to_string());
+        self
+    }
+
+    pub fn org(mut self, org: &str) -> Self {
+        self.org = Some(org.to_string());
+        self
+    }
+
+    fn append_field(result: &mut String, prefix: &str, value: &str) {
+        result.push_str(prefix);
+        result.push_str(&escape_backslash(value, &SPECIAL_CHARACTERS));
+        result.push(';');
+    }
+}
+
+impl ContentBuilder for MeCardContentBuilder {
+    fn build(&self) -> String {
+        let mut result = String::from("MECARD:");
+        // The name is kept as "Last,First" is written, so its comma is not escaped
+        result.push_str("N:");
+        result.push_str(&escape_backslash(&self.name, &[';', ':']));
+        result.push(';');
+        if let Some(pronunciation) = &self.pronunciation {
+            MeCardContentBuilder::append_field(&mut result, "SOUND:", pronunciation);
+        }
+        for phone_number in &self.phone_numbers {
+            MeCardContentBuilder::append_field(&mut result, "TEL:", phone_number);
+        }
+        for email in &self.emails {
+            MeCardContentBuilder::append_field(&mut result, "EMAIL:", email);
+        }
+        if let Some(note) = &self.note {
+            MeCardContentBuilder::append_field(&mut result, "NOTE:", note);
+        }
+        if let Some(birthday) = &self.birthday {
+            MeCardContentBuilder::append_field(&mut result, "BDAY:", birthday);
+        }
+        for address in &self.addresses {
+            MeCardContentBuilder::append_field(&mut result, "ADR:", address);
+        }
+        for url in &self.urls {
+            MeCardContentBuilder::append_field(&mut result, "URL:", url);
+        }
+        if let Some(org) = &self.org {
+            MeCardContentBuilder::append_field(&mut result, "ORG:", org);
+        }
+        result.push(';');
+        result
+    }
+}
@@ -0,0 +1,17 @@
+mod content_builder;
+mod email_content_builder;
+mod geo_content_builder;
+mod mecard_content_builder;
+mod sms_content_builder;
+mod vcard_content_builder;
+mod vevent_content_builder;
+mod wifi_content_builder;
+
+pub use content_builder::{suggest_error_correction_level, ContentBuilder};
+pub use email_content_builder::EmailContentBuilder;
+pub use geo_content_builder::GeoContentBuilder;
+pub use mecard_content_builder::MeCardContentBuilder;
+pub use sms_content_builder::SmsContentBuilder;
+pub use vcard_content_builder::{VCardContentBuilder, VCardVersion};
+pub use vevent_content_builder::VEventContentBuilder;
+pub use wifi_content_builder::WifiContentBuilder;
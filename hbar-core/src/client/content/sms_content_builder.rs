@@ -0,0 +1,88 @@
+use crate::{Error, ResultError};
+
+use super::content_builder::{append_query, url_encode, ContentBuilder};
+
+/**
+ * Builds an "sms:" or "mms:" URI, RFC 5724, like "sms:+15551212?body=Hello". The subject and
+ * body are percent-encoded as UTF-8. Numbers are written as they are, since readers do not
+ * decode them, so they may not contain the separators ',', ';' and '?'.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct SmsContentBuilder {
+    mms: bool,
+    numbers: Vec<String>,
+    subject: Option<String>,
+    body: Option<String>,
+}
+
+impl SmsContentBuilder {
+    /**
+     * @param number the phone number to send a text message to, like "+15551212"
+     * @throws IllegalArgumentException if the number is empty or contains ',', ';' or '?'
+     */
+    pub fn sms(number: &str) -> ResultError<Self> {
+        Ok(SmsContentBuilder {
+            mms: false,
+            numbers: vec![SmsContentBuilder::check_number(number)?],
+            subject: None,
+            body: None,
+        })
+    }
+
+    /**
+     * @param number the phone number to send a multimedia message to, like "+15551212"
+     * @throws IllegalArgumentException if the number is empty or contains ',', ';' or '?'
+     */
+    pub fn mms(number: &str) -> ResultError<Self> {
+        Ok(SmsContentBuilder {
+            mms: true,
+            ..SmsContentBuilder::sms(number)?
+        })
+    }
+
+    /**
+     * Adds another recipient.
+     *
+     * @throws IllegalArgumentException if the number is empty or contains ',', ';' or '?'
+     */
+    pub fn number(mut self, number: &str) -> ResultError<Self> {
+        self.numbers.push(SmsContentBuilder::check_number(number)?);
+        Ok(self)
+    }
+
+    pub fn subject(mut self, subject: &str) -> Self {
+        self.subject = Some(subject.to_string());
+        self
+    }
+
+    pub fn body(mut self, body: &str) -> Self {
+        self.body = Some(body.to_string());
+        self
+    }
+
+    // Readers end the numbers at '?', and each number at ',' or at ';', which starts a "via"
+    fn check_number(number: &str) -> ResultError<String> {
+        if number.is_empty() || number.contains([',', ';', '?']) {
+            return Err(Error::IllegalArgumentException(format!(
+                "Bad phone number: {:?}",
+                number
+            )));
+        }
+        Ok(number.to_string())
+    }
+}
+
+impl ContentBuilder for SmsContentBuilder {
+    fn build(&self) -> String {
+        let mut result = String::from(if self.mms { "mms:" } else { "sms:" });
+        result.push_str(&self.numbers.join(","));
+        append_query(
+            &mut result,
+            &[
+                ("subject", self.subject.as_deref().map(url_encode)),
+                ("body", self.body.as_deref().map(url_encode)),
+            ],
+        );
+        result
+    }
+}
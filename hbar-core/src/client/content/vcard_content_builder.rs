@@ -0,0 +1,238 @@
+use super::content_builder::{escape_text, ContentBuilder};
+
+/**
+ * The vCard versions {@link VCardContentBuilder} writes.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VCardVersion {
+    /** vCard 3.0, RFC 2426, which most readers understand */
+    V3,
+    /** vCard 4.0, RFC 6350 */
+    V4,
+}
+
+/**
+ * Builds a vCard contact. Text values are escaped as the vCard format requires: '\', ',' and
+ * ';' with a backslash, and line breaks as "\n". Lines are not folded, which keeps the payload
+ * short; readers accept long lines.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct VCardContentBuilder {
+    version: VCardVersion,
+    formatted_name: String,
+    structured_name: Option<(String, String)>,
+    nicknames: Vec<String>,
+    phone_numbers: Vec<(String, Option<String>)>,
+    emails: Vec<(String, Option<String>)>,
+    addresses: Vec<(String, Option<String>)>,
+    org: Option<String>,
+    title: Option<String>,
+    urls: Vec<String>,
+    note: Option<String>,
+    birthday: Option<String>,
+    geo: Option<(f64, f64)>,
+}
+
+impl VCardContentBuilder {
+    /**
+     * @param formatted_name the name as it is displayed, the "FN" property
+     */
+    pub fn new(version: VCardVersion, formatted_name: &str) -> Self {
+        VCardContentBuilder {
+            version,
+            formatted_name: formatted_name.to_string(),
+            structured_name: None,
+            nicknames: Vec::new(),
+            phone_numbers: Vec::new(),
+            emails: Vec::new(),
+            addresses: Vec::new(),
+            org: None,
+            title: None,
+            urls: Vec::new(),
+            note: None,
+            birthday: None,
+            geo: None,
+        }
+    }
+
+    /**
+     * Sets the family and given names, the "N" property. Without it, vCard 3.0 payloads carry
+     * the formatted name as the family name, since 3.0 requires the property.
+     */
+    pub fn structured_name(mut self, family_name: &str, given_name: &str) -> Self {
+        self.structured_name = Some((family_name.to_string(), given_name.to_string()));
+        self
+    }
+
+    /**
+     * Adds a nickname; may be called more than once.
+     */
+    pub fn nickname(mut self, nickname: &str) -> Self {
+        self.nicknames.push(nickname.to_string());
+        self
+    }
+
+    /**
+     * Adds a phone number; may be called more than once.
+     *
+     * @param phone_type the kind of number, like "cell" or "work", if known
+     */
+    pub fn phone_number(mut self, phone_number: &str, phone_type: Option<&str>) -> Self {
+        self.phone_numbers
+            .push((phone_number.to_string(), phone_type.map(String::from)));
+        self
+    }
+
+    /**
+     * Adds an e-mail address; may be called more than once.
+     *
+     * @param email_type the kind of address, like "home" or "work", if known
+     */
+    pub fn email(mut self, email: &str, email_type: Option<&str>) -> Self {
+        self.emails
+            .push((email.to_string(), email_type.map(String::from)));
+        self
+    }
+
+    /**
+     * Adds a postal address, written as the street part of the "ADR" property; may be called
+     * more than once.
+     *
+     * @param address_type the kind of address, like "home" or "work", if known
+     */
+    pub fn address(mut self, address: &str, address_type: Option<&str>) -> Self {
+        self.addresses
+            .push((address.to_string(), address_type.map(String::from)));
+        self
+    }
+
+    pub fn org(mut self, org: &str) -> Self {
+        self.org = Some(org.to_string());
+        self
+    }
+
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /**
+     * Adds a web site; may be called more than once.
+     */
+    pub fn url(mut self, url: &str) -> Self {
+        self.urls.push(url.to_string());
+        self
+    }
+
+    pub fn note(mut self, note: &str) -> Self {
+        self.note = Some(note.to_string());
+        self
+    }
+
+    /**
+     * Sets the birthday, as yyyy-MM-dd or yyyyMMdd.
+     */
+    pub fn birthday(mut self, birthday: &str) -> Self {
+        self.birthday = Some(birthday.to_string());
+        self
+    }
+
+    /**
+     * Sets the location, in degrees.
+     */
+    pub fn geo(mut self, latitude: f64, longitude: f64) -> Self {
+        self.geo = Some((latitude, longitude));
+        self
+    }
+
+    fn append_line(result: &mut String, name: &str, value: &str) {
+        result.push_str(name);
+        result.push(':');
+        result.push_str(value);
+        result.push_str("\r\n");
+    }
+
+    fn type_parameter(name: &str, value_type: &Option<String>) -> String {
+        match value_type {
+            Some(value_type) => format!("{};TYPE={}", name, value_type),
+            None => name.to_string(),
+        }
+    }
+}
+
+impl ContentBuilder for VCardContentBuilder {
+    fn build(&self) -> String {
+        let mut result = String::from("BEGIN:VCARD\r\n");
+        let v4 = self.version == VCardVersion::V4;
+        VCardContentBuilder::append_line(&mut result, "VERSION", if v4 { "4.0" } else { "3.0" });
+        VCardContentBuilder::append_line(&mut result, "FN", &escape_text(&self.formatted_name));
+        match &self.structured_name {
+            Some((family_name, given_name)) => {
+                let name = format!(
+                    "{};{};;;",
+                    escape_text(family_name),
+                    escape_text(given_name)
+                );
+                VCardContentBuilder::append_line(&mut result, "N", &name);
+            }
+            None if !v4 => {
+                let name = format!("{};;;;", escape_text(&self.formatted_name));
+                VCardContentBuilder::append_line(&mut result, "N", &name);
+            }
+            None => {}
+        }
+        if !self.nicknames.is_empty() {
+            let nicknames: Vec<String> = self
+                .nicknames
+                .iter()
+                .map(|nickname| escape_text(nickname))
+                .collect();
+            VCardContentBuilder::append_line(&mut result, "NICKNAME", &nicknames.join(","));
+        }
+        for (phone_number, phone_type) in &self.phone_numbers {
+            if v4 {
+                let name = VCardContentBuilder::type_parameter("TEL;VALUE=uri", phone_type);
+                let value = format!("tel:{}", phone_number);
+                VCardContentBuilder::append_line(&mut result, &name, &value);
+            } else {
+                let name = VCardContentBuilder::type_parameter("TEL", phone_type);
+                VCardContentBuilder::append_line(&mut result, &name, phone_number);
+            }
+        }
+        for (email, email_type) in &self.emails {
+            let name = VCardContentBuilder::type_parameter("EMAIL", email_type);
+            VCardContentBuilder::append_line(&mut result, &name, &escape_text(email));
+        }
+        for (address, address_type) in &self.addresses {
+            // Post office box, extended address, street, locality, region, postal code, country
+            let value = format!(";;{};;;;", escape_text(address));
+            let name = VCardContentBuilder::type_parameter("ADR", address_type);
+            VCardContentBuilder::append_line(&mut result, &name, &value);
+        }
+        if let Some(org) = &self.org {
+            VCardContentBuilder::append_line(&mut result, "ORG", &escape_text(org));
+        }
+        if let Some(title) = &self.title {
+            VCardContentBuilder::append_line(&mut result, "TITLE", &escape_text(title));
+        }
+        for url in &self.urls {
+            VCardContentBuilder::append_line(&mut result, "URL", url);
+        }
+        if let Some(note) = &self.note {
+            VCardContentBuilder::append_line(&mut result, "NOTE", &escape_text(note));
+        }
+        if let Some(birthday) = &self.birthday {
+            VCardContentBuilder::append_line(&mut result, "BDAY", birthday);
+        }
+        if let Some((latitude, longitude)) = self.geo {
+            let geo = if v4 {
+                format!("geo:{},{}", latitude, longitude)
+            } else {
+                format!("{};{}", latitude, longitude)
+            };
+            VCardContentBuilder::append_line(&mut result, "GEO", &geo);
+        }
+        result.push_str("END:VCARD");
+        result
+    }
+}
@@ -0,0 +1,163 @@
+use crate::client::result::CalendarParsedResult;
+
+use super::content_builder::{escape_text, ContentBuilder};
+
+/**
+ * Builds an iCalendar "VEVENT" calendar event, RFC 5545. Barcode readers take the event on its
+ * own, without the enclosing "VCALENDAR". Times are written in UTC. Text values are escaped:
+ * '\', ',' and ';' with a backslash, and line breaks as "\n".
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct VEventContentBuilder {
+    summary: String,
+    start: i64,
+    end: Option<i64>,
+    all_day: bool,
+    location: Option<String>,
+    description: Option<String>,
+    organizer: Option<String>,
+    attendees: Vec<String>,
+    geo: Option<(f64, f64)>,
+}
+
+impl VEventContentBuilder {
+    /**
+     * @param summary what the event is
+     * @param start when it starts, in milliseconds since the epoch
+     */
+    pub fn new(summary: &str, start: i64) -> Self {
+        VEventContentBuilder {
+            summary: summary.to_string(),
+            start,
+            end: None,
+            all_day: false,
+            location: None,
+            description: None,
+            organizer: None,
+            attendees: Vec::new(),
+            geo: None,
+        }
+    }
+
+    /**
+     * Sets when the event ends, in milliseconds since the epoch. For an all day event this is
+     * the start of the day after it.
+     */
+    pub fn end(mut self, end: i64) -> Self {
+        self.end = Some(end);
+        self
+    }
+
+    /**
+     * Makes the event last whole days: only the dates of the start and end are written.
+     */
+    pub fn all_day(mut self, all_day: bool) -> Self {
+        self.all_day = all_day;
+        self
+    }
+
+    pub fn location(mut self, location: &str) -> Self {
+        self.location = Some(location.to_string());
+        self
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    /**
+     * Sets the e-mail address of the organizer.
+     */
+    pub fn organizer(mut self, organizer: &str) -> Self {
+        self.organizer = Some(organizer.to_string());
+        self
+    }
+
+    /**
+     * Adds the e-mail address of an attendee; may be called more than once.
+     */
+    pub fn attendee(mut self, attendee: &str) -> Self {
+        self.attendees.push(attendee.to_string());
+        self
+    }
+
+    /**
+     * Sets the location, in degrees.
+     */
+    pub fn geo(mut self, latitude: f64, longitude: f64) -> Self {
+        self.geo = Some((latitude, longitude));
+        self
+    }
+
+    // Writes a DTSTART or DTEND property, as a date or a UTC date and time.
+    fn append_time(&self, result: &mut String, name: &str, millis: i64) {
+        let (year, month, day, seconds) = CalendarParsedResult::civil_from_millis(millis);
+        result.push_str(name);
+        if self.all_day {
+            result.push_str(&format!(";VALUE=DATE:{:04}{:02}{:02}", year, month, day));
+        } else {
+            result.push_str(&format!(
+                ":{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+                year,
+                month,
+                day,
+                seconds / 3600,
+                seconds / 60 % 60,
+                seconds % 60
+            ));
+        }
+        result.push_str("\r\n");
+    }
+
+    fn append_line(result: &mut String, name: &str, value: &str) {
+        result.push_str(name);
+        result.push(':');
+        result.push_str(value);
+        result.push_str("\r\n");
+    }
+}
+
+impl ContentBuilder for VEventContentBuilder {
+    fn build(&self) -> String {
+        let mut result = String::from("BEGIN:VEVENT\r\n");
+        VEventContentBuilder::append_line(&mut result, "SUMMARY", &escape_text(&self.summary));
+        self.append_time(&mut result, "DTSTART", self.start);
+        if let Some(end) = self.end {
+            self.append_time(&mut result, "DTEND", end);
+        }
+        if let Some(location) = &self.location {
+            VEventContentBuilder::append_line(&mut result, "LOCATION", &escape_text(location));
+        }
+        if let Some(description) = &self.description {
+            VEventContentBuilder::append_line(
+                &mut result,
+                "DESCRIPTION",
+                &escape_text(description),
+            );
+        }
+        if let Some(organizer) = &self.organizer {
+            VEventContentBuilder::append_line(
+                &mut result,
+                "ORGANIZER",
+                &format!("mailto:{}", organizer),
+            );
+        }
+        for attendee in &self.attendees {
+            VEventContentBuilder::append_line(
+                &mut result,
+                "ATTENDEE",
+                &format!("mailto:{}", attendee),
+            );
+        }
+        if let Some((latitude, longitude)) = self.geo {
+            VEventContentBuilder::append_line(
+                &mut result,
+                "GEO",
+                &format!("{};{}", latitude, longitude),
+            );
+        }
+        result.push_str("END:VEVENT");
+        result
+    }
+}
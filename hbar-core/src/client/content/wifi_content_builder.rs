@@ -0,0 +1,105 @@
+use super::content_builder::{escape_backslash, ContentBuilder};
+
+// Characters which separate the fields of a "WIFI:" payload, escaped in values.
+const SPECIAL_CHARACTERS: [char; 4] = [';', ',', ':', '"'];
+
+/**
+ * Builds a "WIFI:" network configuration, like "WIFI:T:WPA;S:mynetwork;P:mypass;;". Values are
+ * escaped with a backslash where they contain '\', ';', ',', ':' or '"'.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct WifiContentBuilder {
+    ssid: String,
+    encryption: Option<String>,
+    password: Option<String>,
+    hidden: bool,
+    identity: Option<String>,
+    anonymous_identity: Option<String>,
+    eap_method: Option<String>,
+    phase2_method: Option<String>,
+}
+
+impl WifiContentBuilder {
+    pub fn new(ssid: &str) -> Self {
+        WifiContentBuilder {
+            ssid: ssid.to_string(),
+            encryption: None,
+            password: None,
+            hidden: false,
+            identity: None,
+            anonymous_identity: None,
+            eap_method: None,
+            phase2_method: None,
+        }
+    }
+
+    /**
+     * Sets the authentication type, like "WEP", "WPA", "WPA2-EAP" or "nopass".
+     */
+    pub fn encryption(mut self, encryption: &str) -> Self {
+        self.encryption = Some(encryption.to_string());
+        self
+    }
+
+    pub fn password(mut self, password: &str) -> Self {
+        self.password = Some(password.to_string());
+        self
+    }
+
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    pub fn identity(mut self, identity: &str) -> Self {
+        self.identity = Some(identity.to_string());
+        self
+    }
+
+    pub fn anonymous_identity(mut self, anonymous_identity: &str) -> Self {
+        self.anonymous_identity = Some(anonymous_identity.to_string());
+        self
+    }
+
+    /**
+     * Sets the WPA2 Enterprise EAP method, like "TTLS" or "PEAP".
+     */
+    pub fn eap_method(mut self, eap_method: &str) -> Self {
+        self.eap_method = Some(eap_method.to_string());
+        self
+    }
+
+    /**
+     * Sets the WPA2 Enterprise phase 2 method, like "MSCHAPV2".
+     */
+    pub fn phase2_method(mut self, phase2_method: &str) -> Self {
+        self.phase2_method = Some(phase2_method.to_string());
+        self
+    }
+
+    fn append_field(result: &mut String, prefix: &str, value: Option<&str>) {
+        if let Some(value) = value {
+            result.push_str(prefix);
+            result.push_str(&escape_backslash(value, &SPECIAL_CHARACTERS));
+            result.push(';');
+        }
+    }
+}
+
+impl ContentBuilder for WifiContentBuilder {
+    fn build(&self) -> String {
+        let mut result = String::from("WIFI:");
+        WifiContentBuilder::append_field(&mut result, "T:", self.encryption.as_deref());
+        WifiContentBuilder::append_field(&mut result, "S:", Some(&self.ssid));
+        WifiContentBuilder::append_field(&mut result, "P:", self.password.as_deref());
+        if self.hidden {
+            result.push_str("H:true;");
+        }
+        WifiContentBuilder::append_field(&mut result, "E:", self.eap_method.as_deref());
+        WifiContentBuilder::append_field(&mut result, "A:", self.anonymous_identity.as_deref());
+        WifiContentBuilder::append_field(&mut result, "I:", self.identity.as_deref());
+        WifiContentBuilder::append_field(&mut result, "PH2:", self.phase2_method.as_deref());
+        result.push(';');
+        result
+    }
+}
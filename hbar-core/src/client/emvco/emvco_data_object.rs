@@ -0,0 +1,186 @@
+use crate::{Error, ResultError};
+
+/**
+ * The value of an EMVCo data object: text, or a template of nested data objects.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum EmvcoValue {
+    Primitive(String),
+    Template(Vec<EmvcoDataObject>),
+}
+
+/**
+ * One ID-length-value data object of an EMVCo merchant-presented QR Code payload. The ID and
+ * the length are two decimal digits each; the length counts characters.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmvcoDataObject {
+    id: u8,
+    value: EmvcoValue,
+}
+
+impl EmvcoDataObject {
+    pub fn primitive(id: u8, value: &str) -> Self {
+        EmvcoDataObject {
+            id,
+            value: EmvcoValue::Primitive(value.to_string()),
+        }
+    }
+
+    pub fn template(id: u8, objects: Vec<EmvcoDataObject>) -> Self {
+        EmvcoDataObject {
+            id,
+            value: EmvcoValue::Template(objects),
+        }
+    }
+
+    pub fn get_id(&self) -> u8 {
+        self.id
+    }
+
+    pub fn get_value(&self) -> &EmvcoValue {
+        &self.value
+    }
+
+    /**
+     * @return the text of a primitive data object, or None for a template
+     */
+    pub fn get_primitive(&self) -> Option<&str> {
+        match &self.value {
+            EmvcoValue::Primitive(value) => Some(value),
+            EmvcoValue::Template(_) => None,
+        }
+    }
+
+    /**
+     * @return the nested data objects of a template, or None for a primitive data object
+     */
+    pub fn get_template(&self) -> Option<&[EmvcoDataObject]> {
+        match &self.value {
+            EmvcoValue::Primitive(_) => None,
+            EmvcoValue::Template(objects) => Some(objects),
+        }
+    }
+
+    /**
+     * @return the nested data object of a template with the given ID
+     */
+    pub fn get(&self, id: u8) -> Option<&EmvcoDataObject> {
+        self.get_template()?
+            .iter()
+            .find(|object| object.get_id() == id)
+    }
+
+    /**
+     * Whether the data object with the given ID holds a template, by where it is nested.
+     *
+     * @param parent the ID of the enclosing template, or None at the top level
+     */
+    pub(crate) fn is_template(parent: Option<u8>, id: u8) -> bool {
+        match parent {
+            // Merchant account information, additional data field, merchant information
+            // language and unreserved templates
+            None => matches!(id, 26..=51 | 62 | 64 | 80..=99),
+            // Payment system specific templates of the additional data field
+            Some(62) => id >= 50,
+            Some(_) => false,
+        }
+    }
+
+    /**
+     * Appends this data object, as ID, length and value, checking that it can be written.
+     *
+     * @param parent the ID of the enclosing template, or None at the top level
+     * @throws IllegalArgumentException if the ID or the length exceeds two digits, or the
+     *  value is a template where a primitive is expected or the other way around
+     */
+    pub(crate) fn write(&self, parent: Option<u8>, result: &mut String) -> ResultError<()> {
+        if self.id > 99 {
+            return Err(Error::IllegalArgumentException(format!(
+                "Data object ID {} exceeds 99",
+                self.id
+            )));
+        }
+        let value = match &self.value {
+            EmvcoValue::Primitive(value) => {
+                if EmvcoDataObject::is_template(parent, self.id) {
+                    return Err(Error::IllegalArgumentException(format!(
+                        "Data object {:02} must be a template",
+                        self.id
+                    )));
+                }
+                value.clone()
+            }
+            EmvcoValue::Template(objects) => {
+                if !EmvcoDataObject::is_template(parent, self.id) {
+                    return Err(Error::IllegalArgumentException(format!(
+                        "Data object {:02} can not be a template",
+                        self.id
+                    )));
+                }
+                let mut value = String::new();
+                for object in objects {
+                    object.write(Some(self.id), &mut value)?;
+                }
+                value
+            }
+        };
+        let length = value.chars().count();
+        if length > 99 {
+            return Err(Error::IllegalArgumentException(format!(
+                "Data object {:02} is {} characters long, more than 99",
+                self.id, length
+            )));
+        }
+        result.push_str(&format!("{:02}{:02}", self.id, length));
+        result.push_str(&value);
+        Ok(())
+    }
+
+    /**
+     * Parses consecutive data objects.
+     *
+     * @param parent the ID of the enclosing template, or None at the top level
+     * @throws FormatException if an ID or a length is not two digits, or a value runs past the
+     *  end of the text
+     */
+    pub(crate) fn parse_all(text: &str, parent: Option<u8>) -> ResultError<Vec<EmvcoDataObject>> {
+        let chars: Vec<char> = text.chars().collect();
+        let mut objects = Vec::new();
+        let mut offset = 0;
+        while offset < chars.len() {
+            let id = EmvcoDataObject::parse_two_digits(&chars, offset)?;
+            let length = EmvcoDataObject::parse_two_digits(&chars, offset + 2)? as usize;
+            let start = offset + 4;
+            if start + length > chars.len() {
+                return Err(Error::FormatException(format!(
+                    "Data object {:02} needs {} characters, but only {} are left",
+                    id,
+                    length,
+                    chars.len() - start
+                )));
+            }
+            let value: String = chars[start..start + length].iter().collect();
+            let value = if EmvcoDataObject::is_template(parent, id) {
+                EmvcoValue::Template(EmvcoDataObject::parse_all(&value, Some(id))?)
+            } else {
+                EmvcoValue::Primitive(value)
+            };
+            objects.push(EmvcoDataObject { id, value });
+            offset = start + length;
+        }
+        Ok(objects)
+    }
+
+    fn parse_two_digits(chars: &[char], offset: usize) -> ResultError<u8> {
+        match chars.get(offset..offset + 2) {
+            Some(&[tens, ones]) if tens.is_ascii_digit() && ones.is_ascii_digit() => {
+                Ok((tens as u8 - b'0') * 10 + (ones as u8 - b'0'))
+            }
+            _ => Err(Error::FormatException(format!(
+                "Expected two digits at {}",
+                offset
+            ))),
+        }
+    }
+}
@@ -0,0 +1,411 @@
+use crate::client::content::ContentBuilder;
+use crate::client::result::ParsedResult;
+use crate::{Error, ErrorCorrectionLevel, ResultError};
+
+use super::EmvcoDataObject;
+
+pub(crate) const PAYLOAD_FORMAT_INDICATOR: u8 = 0;
+pub(crate) const POINT_OF_INITIATION_METHOD: u8 = 1;
+pub(crate) const MERCHANT_CATEGORY_CODE: u8 = 52;
+pub(crate) const TRANSACTION_CURRENCY: u8 = 53;
+pub(crate) const TRANSACTION_AMOUNT: u8 = 54;
+pub(crate) const COUNTRY_CODE: u8 = 58;
+pub(crate) const MERCHANT_NAME: u8 = 59;
+pub(crate) const MERCHANT_CITY: u8 = 60;
+pub(crate) const POSTAL_CODE: u8 = 61;
+pub(crate) const ADDITIONAL_DATA_FIELD: u8 = 62;
+pub(crate) const CRC: u8 = 63;
+
+/**
+ * An EMVCo merchant-presented QR Code payload, as in the EMV QR Code Specification for Payment
+ * Systems: data objects with IDs 00 to 99, starting with the payload format indicator "000201"
+ * and ending with a CRC-16/CCITT checksum in data object 63.
+ *
+ * <p>Payloads are built with {@link EmvcoPayloadBuilder}, or parsed from decoded text with
+ * {@link #parse}. Either way the text to encode, checksum included, is {@link #get_text}.
+ * Encoded as a {@link ContentBuilder}, it asks for level M; the QR Code encoder writes it in
+ * alphanumeric mode when it holds only digits, capitals and the symbols that mode allows, and
+ * in byte mode otherwise.</p>
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmvcoPayload {
+    objects: Vec<EmvcoDataObject>,
+    text: String,
+}
+
+impl EmvcoPayload {
+    pub(crate) fn new(objects: Vec<EmvcoDataObject>, text: String) -> EmvcoPayload {
+        EmvcoPayload { objects, text }
+    }
+
+    /**
+     * Parses and checks a payload.
+     *
+     * @throws FormatException if the text is not a sequence of data objects beginning with the
+     *  payload format indicator and ending with the CRC
+     * @throws ChecksumException if the CRC does not match
+     */
+    pub fn parse(text: &str) -> ResultError<EmvcoPayload> {
+        let objects = EmvcoDataObject::parse_all(text, None)?;
+        match objects.first() {
+            Some(first)
+                if first.get_id() == PAYLOAD_FORMAT_INDICATOR
+                    && first.get_primitive() == Some("01") => {}
+            _ => {
+                return Err(Error::FormatException(String::from(
+                    "Payload does not start with payload format indicator 01",
+                )))
+            }
+        }
+        let crc = match objects.last() {
+            Some(last) if last.get_id() == CRC => last.get_primitive().unwrap_or_default(),
+            _ => {
+                return Err(Error::FormatException(String::from(
+                    "Payload does not end with a CRC",
+                )))
+            }
+        };
+        let expected = match u16::from_str_radix(crc, 16) {
+            Ok(expected) if crc.len() == 4 => expected,
+            _ => {
+                return Err(Error::FormatException(format!(
+                    "CRC is not four hexadecimal digits: {}",
+                    crc
+                )))
+            }
+        };
+        // The CRC covers everything up to and including the ID and length of its own data object
+        let actual = EmvcoPayload::crc16(&text.as_bytes()[..text.len() - 4]);
+        if actual != expected {
+            return Err(Error::ChecksumException(format!(
+                "CRC is {:04X}, but the payload gives {:04X}",
+                expected, actual
+            )));
+        }
+        Ok(EmvcoPayload::new(objects, text.to_string()))
+    }
+
+    /**
+     * Computes the CRC-16/CCITT-FALSE checksum, polynomial 0x1021 and initial value 0xFFFF.
+     */
+    pub fn crc16(data: &[u8]) -> u16 {
+        let mut crc: u16 = 0xFFFF;
+        for &b in data {
+            crc ^= (b as u16) << 8;
+            for _ in 0..8 {
+                crc = if crc & 0x8000 != 0 {
+                    (crc << 1) ^ 0x1021
+                } else {
+                    crc << 1
+                };
+            }
+        }
+        crc
+    }
+
+    /**
+     * @return the payload, checksum included
+     */
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    /**
+     * @return the top level data objects, in the order of the payload
+     */
+    pub fn get_data_objects(&self) -> &[EmvcoDataObject] {
+        &self.objects
+    }
+
+    /**
+     * @return the top level data object with the given ID
+     */
+    pub fn get(&self, id: u8) -> Option<&EmvcoDataObject> {
+        self.objects.iter().find(|object| object.get_id() == id)
+    }
+
+    /**
+     * @return the text of the top level primitive data object with the given ID
+     */
+    pub fn get_primitive(&self, id: u8) -> Option<&str> {
+        self.get(id)?.get_primitive()
+    }
+
+    /**
+     * @return true for a payload which changes with every transaction, point of initiation
+     *  method "12", false for a static one
+     */
+    pub fn is_dynamic(&self) -> bool {
+        self.get_primitive(POINT_OF_INITIATION_METHOD) == Some("12")
+    }
+
+    /**
+     * @return the merchant account information, data objects 02 to 51: primitives for the IDs
+     *  the card schemes reserved, up to 25, and templates after
+     */
+    pub fn get_merchant_accounts(&self) -> Vec<&EmvcoDataObject> {
+        self.objects
+            .iter()
+            .filter(|object| (2..=51).contains(&object.get_id()))
+            .collect()
+    }
+
+    /**
+     * @return the ISO 18245 merchant category code
+     */
+    pub fn get_merchant_category_code(&self) -> Option<&str> {
+        self.get_primitive(MERCHANT_CATEGORY_CODE)
+    }
+
+    /**
+     * @return the ISO 4217 numeric currency code, like "978" for euros
+     */
+    pub fn get_transaction_currency(&self) -> Option<&str> {
+        self.get_primitive(TRANSACTION_CURRENCY)
+    }
+
+    pub fn get_transaction_amount(&self) -> Option<&str> {
+        self.get_primitive(TRANSACTION_AMOUNT)
+    }
+
+    /**
+     * @return the ISO 3166-1 alpha-2 country code of the merchant
+     */
+    pub fn get_country_code(&self) -> Option<&str> {
+        self.get_primitive(COUNTRY_CODE)
+    }
+
+    pub fn get_merchant_name(&self) -> Option<&str> {
+        self.get_primitive(MERCHANT_NAME)
+    }
+
+    pub fn get_merchant_city(&self) -> Option<&str> {
+        self.get_primitive(MERCHANT_CITY)
+    }
+
+    pub fn get_postal_code(&self) -> Option<&str> {
+        self.get_primitive(POSTAL_CODE)
+    }
+
+    /**
+     * @return the data objects of the additional data field template, like the bill number
+     *  (01) or the reference label (05)
+     */
+    pub fn get_additional_data(&self) -> Option<&[EmvcoDataObject]> {
+        self.get(ADDITIONAL_DATA_FIELD)?.get_template()
+    }
+
+    pub fn get_crc(&self) -> &str {
+        &self.text[self.text.len() - 4..]
+    }
+
+    pub fn get_display_result(&self) -> String {
+        let mut result = String::with_capacity(100);
+        ParsedResult::maybe_append(self.get_merchant_name(), &mut result);
+        ParsedResult::maybe_append(self.get_merchant_city(), &mut result);
+        if let Some(amount) = self.get_transaction_amount() {
+            let amount = match self.get_transaction_currency() {
+                Some(currency) => format!("{} {}", amount, currency),
+                None => amount.to_string(),
+            };
+            ParsedResult::maybe_append(Some(&amount), &mut result);
+        }
+        result
+    }
+}
+
+impl ContentBuilder for EmvcoPayload {
+    fn build(&self) -> String {
+        self.text.clone()
+    }
+
+    /**
+     * EMVCo calls for level M, whatever the size of the payload.
+     */
+    fn get_error_correction_level(&self) -> ErrorCorrectionLevel {
+        ErrorCorrectionLevel::M
+    }
+}
+
+#[cfg(test)]
+mod emvco_payload_tests {
+    use super::EmvcoPayload;
+    use crate::client::content::ContentBuilder;
+    use crate::client::emvco::{EmvcoDataObject, EmvcoPayloadBuilder};
+    use crate::client::result::ParsedResult;
+    use crate::{BarcodeFormat, Error, ErrorCorrectionLevel};
+
+    // The example of the EMV QR Code Specification for Payment Systems, with a language
+    // template in Chinese.
+    const SPECIFICATION_EXAMPLE: &str = "00020101021229300012D156000000000510A93FO3230Q\
+         31280012D15600000001030812345678520441115802CN5914BEST TRANSPORT6007BEIJING\
+         64200002ZH0104最佳运输0202北京540523.7253031565502016233030412340603***0708A6008667\
+         0902ME91320016A0112233449988770708123456786304A13A";
+
+    #[test]
+    fn computes_crc16_ccitt() {
+        assert_eq!(0x29B1, EmvcoPayload::crc16(b"123456789"));
+    }
+
+    #[test]
+    fn parses_the_specification_example() {
+        let payload = EmvcoPayload::parse(SPECIFICATION_EXAMPLE).unwrap();
+        assert!(payload.is_dynamic());
+        assert_eq!(Some("BEST TRANSPORT"), payload.get_merchant_name());
+        assert_eq!(Some("BEIJING"), payload.get_merchant_city());
+        assert_eq!(Some("4111"), payload.get_merchant_category_code());
+        assert_eq!(Some("156"), payload.get_transaction_currency());
+        assert_eq!(Some("23.72"), payload.get_transaction_amount());
+        assert_eq!(Some("CN"), payload.get_country_code());
+        assert_eq!("A13A", payload.get_crc());
+
+        let accounts = payload.get_merchant_accounts();
+        assert_eq!(2, accounts.len());
+        assert_eq!(
+            Some("D15600000000"),
+            accounts[0].get(0).unwrap().get_primitive()
+        );
+        assert_eq!(
+            Some("A93FO3230Q"),
+            accounts[0].get(5).unwrap().get_primitive()
+        );
+
+        let language = payload.get(64).unwrap();
+        assert_eq!(Some("最佳运输"), language.get(1).unwrap().get_primitive());
+        let additional_data = payload.get_additional_data().unwrap();
+        assert_eq!(
+            Some("A6008667"),
+            additional_data[2].get_primitive(),
+            "{:?}",
+            additional_data
+        );
+
+        match ParsedResult::parse_text(SPECIFICATION_EXAMPLE, &BarcodeFormat::QRCode) {
+            ParsedResult::Emvco(parsed) => assert_eq!(payload, parsed),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn rejects_corrupted_payloads() {
+        let corrupted = SPECIFICATION_EXAMPLE.replace("23.72", "23.73");
+        assert!(matches!(
+            EmvcoPayload::parse(&corrupted),
+            Err(Error::ChecksumException(_))
+        ));
+        assert!(matches!(
+            ParsedResult::parse_text(&corrupted, &BarcodeFormat::QRCode),
+            ParsedResult::Text(_)
+        ));
+        let truncated = &SPECIFICATION_EXAMPLE[..40];
+        assert!(matches!(
+            EmvcoPayload::parse(truncated),
+            Err(Error::FormatException(_))
+        ));
+    }
+
+    fn builder() -> EmvcoPayloadBuilder {
+        EmvcoPayloadBuilder::new()
+            .dynamic(false)
+            .merchant_account(
+                26,
+                "D15600000000",
+                vec![EmvcoDataObject::primitive(5, "A93FO3230Q")],
+            )
+            .merchant_category_code("5812")
+            .transaction_currency("978")
+            .country_code("DE")
+            .merchant_name("Cafe Wien")
+            .merchant_city("Berlin")
+    }
+
+    #[test]
+    fn builds_payloads_which_parse_back() {
+        let payload = builder()
+            .transaction_amount("12.50")
+            .additional_data(vec![EmvcoDataObject::primitive(1, "INV-42")])
+            .build()
+            .unwrap();
+        assert!(payload.get_text().starts_with(
+            "000201010211263000\
+             12D156000000000510A93FO3230Q52045812"
+        ));
+        assert!(payload.get_text().contains("62100106INV-42"));
+        assert_eq!(payload, EmvcoPayload::parse(payload.get_text()).unwrap());
+        assert!(!payload.is_dynamic());
+        assert_eq!(
+            ErrorCorrectionLevel::M,
+            payload.get_error_correction_level()
+        );
+        assert_eq!("Cafe Wien\nBerlin\n12.50 978", payload.get_display_result());
+    }
+
+    #[test]
+    fn validates_data_objects() {
+        let missing_account = EmvcoPayloadBuilder::new()
+            .merchant_category_code("5812")
+            .transaction_currency("978")
+            .country_code("DE")
+            .merchant_name("Cafe Wien")
+            .merchant_city("Berlin");
+        assert!(missing_account.build().is_err());
+        assert!(builder().merchant_category_code("581").build().is_err());
+        assert!(builder().transaction_amount("12,50").build().is_err());
+        assert!(builder()
+            .merchant_city("Berlin-Charlottenburg")
+            .build()
+            .is_err());
+        assert!(builder().merchant_name("Café").build().is_err());
+        assert!(builder()
+            .data_object(EmvcoDataObject::primitive(26, "not a template"))
+            .build()
+            .is_err());
+        assert!(builder()
+            .data_object(EmvcoDataObject::primitive(80, &"x".repeat(100)))
+            .build()
+            .is_err());
+    }
+
+    #[cfg(all(feature = "encode", feature = "decode", feature = "qrcode"))]
+    #[test]
+    fn decodes_encoded_payloads() {
+        use crate::qrcode::QRCodeWriter;
+        use crate::{
+            BinaryBitmap, GrayLuminanceSource, HybridBinarizer, MultiFormatReader, Reader, Writer,
+        };
+
+        let payload = builder().build().unwrap();
+        let matrix = QRCodeWriter::new()
+            .encode_options(
+                &payload.build(),
+                &BarcodeFormat::QRCode,
+                300,
+                300,
+                &payload.get_encode_options(),
+            )
+            .unwrap();
+        let (width, height) = (matrix.getWidth() as u32, matrix.getHeight() as u32);
+        let luminances = (0..width * height)
+            .map(|offset| {
+                if matrix.get(offset % width, offset / width) {
+                    0
+                } else {
+                    0xFF
+                }
+            })
+            .collect();
+        let source = GrayLuminanceSource::from_vec(luminances, width, height).unwrap();
+        let result = MultiFormatReader::new()
+            .unwrap()
+            .decode(&BinaryBitmap::new(HybridBinarizer::new(source)))
+            .unwrap();
+        assert_eq!(
+            Some(&ErrorCorrectionLevel::M),
+            result.get_error_correction_level()
+        );
+        match ParsedResult::parse_result(&result) {
+            ParsedResult::Emvco(parsed) => assert_eq!(payload, parsed),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use crate::{Error, ResultError};
+
+use super::emvco_payload::{
+    ADDITIONAL_DATA_FIELD, COUNTRY_CODE, CRC, MERCHANT_CATEGORY_CODE, MERCHANT_CITY, MERCHANT_NAME,
+    PAYLOAD_FORMAT_INDICATOR, POINT_OF_INITIATION_METHOD, POSTAL_CODE, TRANSACTION_AMOUNT,
+    TRANSACTION_CURRENCY,
+};
+use super::{EmvcoDataObject, EmvcoPayload};
+
+use std::collections::BTreeMap;
+
+// The data object holding the language template, whose values need not be ASCII.
+const MERCHANT_INFORMATION_LANGUAGE: u8 = 64;
+
+/**
+ * Builds an EMVCo merchant-presented QR Code payload. Data objects are written in order of
+ * their IDs, after the payload format indicator and before the CRC, which are both added.
+ *
+ * <pre>
+ * let payload = EmvcoPayloadBuilder::new()
+ *     .merchant_account(26, "D15600000000", vec![EmvcoDataObject::primitive(1, "A93FO3230Q")])
+ *     .merchant_category_code("5812")
+ *     .transaction_currency("978")
+ *     .country_code("DE")
+ *     .merchant_name("Cafe Wien")
+ *     .merchant_city("Berlin")
+ *     .build()?;
+ * let matrix = QRCodeWriter::new().encode_options(&payload.build(), &BarcodeFormat::QRCode,
+ *     200, 200, &payload.get_encode_options())?;
+ * </pre>
+ */
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EmvcoPayloadBuilder {
+    objects: BTreeMap<u8, EmvcoDataObject>,
+}
+
+impl EmvcoPayloadBuilder {
+    pub fn new() -> Self {
+        EmvcoPayloadBuilder::default()
+    }
+
+    /**
+     * Sets any data object, replacing one with the same ID.
+     */
+    pub fn data_object(mut self, object: EmvcoDataObject) -> Self {
+        self.objects.insert(object.get_id(), object);
+        self
+    }
+
+    fn primitive(self, id: u8, value: &str) -> Self {
+        self.data_object(EmvcoDataObject::primitive(id, value))
+    }
+
+    /**
+     * Marks the payload as dynamic, point of initiation method "12", for a single transaction,
+     * or static, "11", for reuse.
+     */
+    pub fn dynamic(self, dynamic: bool) -> Self {
+        self.primitive(
+            POINT_OF_INITIATION_METHOD,
+            if dynamic { "12" } else { "11" },
+        )
+    }
+
+    /**
+     * Adds a merchant account information template, the IDs 26 to 51.
+     *
+     * @param globally_unique_identifier the application identifier or reverse domain name of
+     *  the payment network, data object 00 of the template
+     * @param objects the payment network specific data objects, 01 to 99
+     */
+    pub fn merchant_account(
+        self,
+        id: u8,
+        globally_unique_identifier: &str,
+        objects: Vec<EmvcoDataObject>,
+    ) -> Self {
+        let mut template = vec![EmvcoDataObject::primitive(0, globally_unique_identifier)];
+        template.extend(objects);
+        self.data_object(EmvcoDataObject::template(id, template))
+    }
+
+    /**
+     * Sets the ISO 18245 merchant category code, four digits.
+     */
+    pub fn merchant_category_code(self, merchant_category_code: &str) -> Self {
+        self.primitive(MERCHANT_CATEGORY_CODE, merchant_category_code)
+    }
+
+    /**
+     * Sets the ISO 4217 numeric currency code, like "978" for euros.
+     */
+    pub fn transaction_currency(self, transaction_currency: &str) -> Self {
+        self.primitive(TRANSACTION_CURRENCY, transaction_currency)
+    }
+
+    /**
+     * Sets the amount, like "12.50". Without it the customer enters the amount.
+     */
+    pub fn transaction_amount(self, transaction_amount: &str) -> Self {
+        self.primitive(TRANSACTION_AMOUNT, transaction_amount)
+    }
+
+    /**
+     * Sets the ISO 3166-1 alpha-2 country code of the merchant.
+     */
+    pub fn country_code(self, country_code: &str) -> Self {
+        self.primitive(COUNTRY_CODE, country_code)
+    }
+
+    pub fn merchant_name(self, merchant_name: &str) -> Self {
+        self.primitive(MERCHANT_NAME, merchant_name)
+    }
+
+    pub fn merchant_city(self, merchant_city: &str) -> Self {
+        self.primitive(MERCHANT_CITY, merchant_city)
+    }
+
+    pub fn postal_code(self, postal_code: &str) -> Self {
+        self.primitive(POSTAL_CODE, postal_code)
+    }
+
+    /**
+     * Sets the additional data field template, like a bill number (01), mobile number (02) or
+     * reference label (05).
+     */
+    pub fn additional_data(self, objects: Vec<EmvcoDataObject>) -> Self {
+        self.data_object(EmvcoDataObject::template(ADDITIONAL_DATA_FIELD, objects))
+    }
+
+    /**
+     * Checks the data objects, and writes them with the payload format indicator and the CRC.
+     *
+     * @throws IllegalArgumentException if a mandatory data object is missing or malformed, or
+     *  one is too long to write
+     */
+    pub fn build(&self) -> ResultError<EmvcoPayload> {
+        self.validate()?;
+
+        let mut objects = vec![EmvcoDataObject::primitive(PAYLOAD_FORMAT_INDICATOR, "01")];
+        objects.extend(
+            self.objects
+                .values()
+                .filter(|object| object.get_id() != PAYLOAD_FORMAT_INDICATOR)
+                .cloned(),
+        );
+        let mut text = String::with_capacity(200);
+        for object in &objects {
+            object.write(None, &mut text)?;
+        }
+        text.push_str(&format!("{:02}04", CRC));
+        let crc = format!("{:04X}", EmvcoPayload::crc16(text.as_bytes()));
+        text.push_str(&crc);
+        objects.push(EmvcoDataObject::primitive(CRC, &crc));
+        Ok(EmvcoPayload::new(objects, text))
+    }
+
+    fn validate(&self) -> ResultError<()> {
+        if !self.objects.keys().any(|id| (2..=51).contains(id)) {
+            return Err(Error::IllegalArgumentException(String::from(
+                "At least one merchant account information data object, 02 to 51, is needed",
+            )));
+        }
+        if self.objects.contains_key(&CRC) {
+            return Err(Error::IllegalArgumentException(String::from(
+                "The CRC, data object 63, is computed",
+            )));
+        }
+        EmvcoPayloadBuilder::check_format(
+            self.get_primitive(MERCHANT_CATEGORY_CODE),
+            "merchant category code",
+            |value| value.len() == 4 && value.bytes().all(|b| b.is_ascii_digit()),
+        )?;
+        EmvcoPayloadBuilder::check_format(
+            self.get_primitive(TRANSACTION_CURRENCY),
+            "transaction currency",
+            |value| value.len() == 3 && value.bytes().all(|b| b.is_ascii_digit()),
+        )?;
+        EmvcoPayloadBuilder::check_format(
+            self.get_primitive(COUNTRY_CODE),
+            "country code",
+            |value| value.len() == 2 && value.bytes().all(|b| b.is_ascii_uppercase()),
+        )?;
+        EmvcoPayloadBuilder::check_format(
+            self.get_primitive(MERCHANT_NAME),
+            "merchant name",
+            |value| !value.is_empty() && value.len() <= 25,
+        )?;
+        EmvcoPayloadBuilder::check_format(
+            self.get_primitive(MERCHANT_CITY),
+            "merchant city",
+            |value| !value.is_empty() && value.len() <= 15,
+        )?;
+        if let Some(amount) = self.get_primitive(TRANSACTION_AMOUNT) {
+            let mut parts = amount.splitn(2, '.');
+            let units = parts.next().unwrap_or_default();
+            let cents = parts.next().unwrap_or_default();
+            let is_amount = amount.len() <= 13
+                && !units.is_empty()
+                && units
+                    .bytes()
+                    .chain(cents.bytes())
+                    .all(|b| b.is_ascii_digit());
+            if !is_amount {
+                return Err(Error::IllegalArgumentException(format!(
+                    "Malformed transaction amount: {}",
+                    amount
+                )));
+            }
+        }
+        for object in self.objects.values() {
+            if object.get_id() != MERCHANT_INFORMATION_LANGUAGE
+                && !EmvcoPayloadBuilder::is_printable_ascii(object)
+            {
+                return Err(Error::IllegalArgumentException(format!(
+                    "Data object {:02} may only hold printable ASCII characters",
+                    object.get_id()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn get_primitive(&self, id: u8) -> Option<&str> {
+        self.objects.get(&id)?.get_primitive()
+    }
+
+    // Checks that a mandatory data object is there and well formed.
+    fn check_format(
+        value: Option<&str>,
+        name: &str,
+        is_valid: impl Fn(&str) -> bool,
+    ) -> ResultError<()> {
+        match value {
+            Some(value) if is_valid(value) => Ok(()),
+            Some(value) => Err(Error::IllegalArgumentException(format!(
+                "Malformed {}: {}",
+                name, value
+            ))),
+            None => Err(Error::IllegalArgumentException(format!(
+                "The {} is mandatory",
+                name
+            ))),
+        }
+    }
+
+    fn is_printable_ascii(object: &EmvcoDataObject) -> bool {
+        match object.get_template() {
+            Some(objects) => objects.iter().all(EmvcoPayloadBuilder::is_printable_ascii),
+            None => object
+                .get_primitive()
+                .unwrap_or_default()
+                .bytes()
+                .all(|b| (0x20..=0x7E).contains(&b)),
+        }
+    }
+}
@@ -0,0 +1,7 @@
+mod emvco_data_object;
+mod emvco_payload;
+mod emvco_payload_builder;
+
+pub use emvco_data_object::{EmvcoDataObject, EmvcoValue};
+pub use emvco_payload::EmvcoPayload;
+pub use emvco_payload_builder::EmvcoPayloadBuilder;
@@ -0,0 +1,4 @@
+pub mod content;
+pub mod emvco;
+pub mod payment;
+pub mod result;
@@ -0,0 +1,78 @@
+/**
+ * Checks an IBAN, ISO 13616: two letters of country code, two check digits and up to 30
+ * letters and digits of account, which taken as a number with the first four characters moved
+ * to the end, and letters counted from A = 10, leave 1 when divided by 97.
+ *
+ * @param iban the IBAN, without spaces
+ */
+pub fn is_valid_iban(iban: &str) -> bool {
+    let bytes = iban.as_bytes();
+    bytes.len() >= 15
+        && bytes.len() <= 34
+        && bytes[..2].iter().all(u8::is_ascii_uppercase)
+        && bytes[2..4].iter().all(u8::is_ascii_digit)
+        && bytes[4..]
+            .iter()
+            .all(|b| b.is_ascii_digit() || b.is_ascii_uppercase())
+        && mod_97(&iban[4..], &iban[..4]) == Some(1)
+}
+
+/**
+ * Checks an ISO 11649 creditor reference, like "RF18539007547034": "RF", two check digits and
+ * up to 21 letters and digits, with the same mod 97 check as an IBAN.
+ *
+ * @param reference the reference, without spaces
+ */
+pub fn is_valid_creditor_reference(reference: &str) -> bool {
+    let bytes = reference.as_bytes();
+    bytes.len() >= 5
+        && bytes.len() <= 25
+        && reference.starts_with("RF")
+        && bytes[2..4].iter().all(u8::is_ascii_digit)
+        && mod_97(&reference[4..], &reference[..4]) == Some(1)
+}
+
+/**
+ * Checks a Swiss QR reference: 27 digits, the last of them the check digit of the others.
+ *
+ * @param reference the reference, without spaces
+ */
+pub fn is_valid_qr_reference(reference: &str) -> bool {
+    reference.len() == 27
+        && reference.bytes().all(|b| b.is_ascii_digit())
+        && qr_reference_check_digit(&reference[..26]) == Some(reference.as_bytes()[26] - b'0')
+}
+
+/**
+ * Computes the check digit of a Swiss QR reference, or an ISR reference, with the recursive
+ * modulo 10 algorithm.
+ *
+ * @param digits the reference without its check digit
+ * @return the check digit, or None if there is something other than digits
+ */
+pub fn qr_reference_check_digit(digits: &str) -> Option<u8> {
+    const TABLE: [u8; 10] = [0, 9, 4, 6, 8, 2, 7, 1, 3, 5];
+    let mut carry = 0;
+    for b in digits.bytes() {
+        if !b.is_ascii_digit() {
+            return None;
+        }
+        carry = TABLE[((carry + b - b'0') % 10) as usize];
+    }
+    Some((10 - carry) % 10)
+}
+
+// The remainder of dividing by 97 the number written by the body followed by the head, letters
+// counting as two digits from A = 10.
+fn mod_97(body: &str, head: &str) -> Option<u32> {
+    let mut remainder = 0;
+    for b in body.bytes().chain(head.bytes()) {
+        remainder = match b {
+            b'0'..=b'9' => (remainder * 10 + (b - b'0') as u32) % 97,
+            b'A'..=b'Z' => (remainder * 100 + (b - b'A' + 10) as u32) % 97,
+            b'a'..=b'z' => (remainder * 100 + (b - b'a' + 10) as u32) % 97,
+            _ => return None,
+        };
+    }
+    Some(remainder)
+}
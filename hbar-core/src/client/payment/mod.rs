@@ -0,0 +1,68 @@
+mod checksums;
+mod sepa_credit_transfer;
+mod swiss_qr_bill;
+
+pub use checksums::{
+    is_valid_creditor_reference, is_valid_iban, is_valid_qr_reference, qr_reference_check_digit,
+};
+pub use sepa_credit_transfer::{EpcVersion, SepaCreditTransfer, SepaCreditTransferBuilder};
+pub use swiss_qr_bill::{SwissAddress, SwissQrBill, SwissQrBillBuilder, SwissReferenceType};
+
+use crate::{Error, ResultError};
+
+/**
+ * Drops the spaces accounts and references are often printed with, and capitalises the rest.
+ */
+pub(crate) fn normalize(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/**
+ * Checks that a field, if given, is from {@code min} to {@code max} characters long and on one
+ * line.
+ */
+pub(crate) fn check_length(
+    field: &str,
+    value: Option<&str>,
+    min: usize,
+    max: usize,
+) -> ResultError<()> {
+    if let Some(value) = value {
+        let length = value.chars().count();
+        if length < min || length > max || value.contains(['\r', '\n']) {
+            return Err(Error::IllegalArgumentException(format!(
+                "The {} must be a line of {} to {} characters: {}",
+                field, min, max, value
+            )));
+        }
+    }
+    Ok(())
+}
+
+/**
+ * Checks an amount like "12.50": up to nine digits, optionally followed by a point and one or
+ * two decimals, from 0.01 to 999999999.99.
+ */
+pub(crate) fn check_amount(amount: &str) -> ResultError<()> {
+    let (units, cents) = match amount.split_once('.') {
+        Some((units, cents)) => (units, cents),
+        None => (amount, "0"),
+    };
+    let is_digits = |value: &str, max: usize| {
+        !value.is_empty() && value.len() <= max && value.bytes().all(|b| b.is_ascii_digit())
+    };
+    if !is_digits(units, 9)
+        || !is_digits(cents, 2)
+        || units.bytes().chain(cents.bytes()).all(|b| b == b'0')
+    {
+        return Err(Error::IllegalArgumentException(format!(
+            "Amount must be from 0.01 to 999999999.99: {}",
+            amount
+        )));
+    }
+    Ok(())
+}
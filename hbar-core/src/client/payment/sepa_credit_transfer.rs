@@ -0,0 +1,427 @@
+use crate::client::content::ContentBuilder;
+use crate::{Error, ErrorCorrectionLevel, ResultError};
+
+use super::checksums::is_valid_iban;
+use super::{check_amount, check_length, normalize};
+
+// The most bytes a payload may take, so that it fits a version 13 symbol at level M.
+const MAX_PAYLOAD_BYTES: usize = 331;
+
+/**
+ * The versions of the EPC069-12 payload.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EpcVersion {
+    /** Version 001, which needs the BIC of the beneficiary's bank */
+    V1,
+    /** Version 002, where the BIC is optional within the EEA */
+    V2,
+}
+
+/**
+ * Builds a SEPA credit transfer payload after EPC069-12, the "GiroCode" printed on invoices,
+ * like "BCD\n002\n1\nSCT\n\nRed Cross\nBE72000000001616\nEUR12.50". Values are written as
+ * UTF-8.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct SepaCreditTransferBuilder {
+    version: EpcVersion,
+    bic: Option<String>,
+    name: String,
+    iban: String,
+    amount: Option<String>,
+    purpose: Option<String>,
+    creditor_reference: Option<String>,
+    remittance_text: Option<String>,
+    information: Option<String>,
+}
+
+impl SepaCreditTransferBuilder {
+    /**
+     * @param name the name of the beneficiary
+     * @param iban the account of the beneficiary; spaces are dropped
+     */
+    pub fn new(name: &str, iban: &str) -> Self {
+        SepaCreditTransferBuilder {
+            version: EpcVersion::V2,
+            bic: None,
+            name: name.to_string(),
+            iban: normalize(iban),
+            amount: None,
+            purpose: None,
+            creditor_reference: None,
+            remittance_text: None,
+            information: None,
+        }
+    }
+
+    pub fn version(mut self, version: EpcVersion) -> Self {
+        self.version = version;
+        self
+    }
+
+    /**
+     * Sets the BIC of the beneficiary's bank, 8 or 11 characters.
+     */
+    pub fn bic(mut self, bic: &str) -> Self {
+        self.bic = Some(normalize(bic));
+        self
+    }
+
+    /**
+     * Sets the amount in euros, like "12.50", from 0.01 to 999999999.99.
+     */
+    pub fn amount(mut self, amount: &str) -> Self {
+        self.amount = Some(amount.to_string());
+        self
+    }
+
+    /**
+     * Sets the ISO 20022 purpose code, four capitals like "GDDS".
+     */
+    pub fn purpose(mut self, purpose: &str) -> Self {
+        self.purpose = Some(purpose.to_string());
+        self
+    }
+
+    /**
+     * Sets the structured remittance information, usually an ISO 11649 creditor reference.
+     * It excludes a remittance text.
+     */
+    pub fn creditor_reference(mut self, creditor_reference: &str) -> Self {
+        self.creditor_reference = Some(creditor_reference.to_string());
+        self
+    }
+
+    /**
+     * Sets the unstructured remittance information. It excludes a creditor reference.
+     */
+    pub fn remittance_text(mut self, remittance_text: &str) -> Self {
+        self.remittance_text = Some(remittance_text.to_string());
+        self
+    }
+
+    /**
+     * Sets a note from the beneficiary to the originator.
+     */
+    pub fn information(mut self, information: &str) -> Self {
+        self.information = Some(information.to_string());
+        self
+    }
+
+    /**
+     * Checks the fields and writes the payload.
+     *
+     * @throws IllegalArgumentException if a field is missing, malformed or too long, or the
+     *  payload exceeds 331 bytes
+     */
+    pub fn build(&self) -> ResultError<SepaCreditTransfer> {
+        self.validate()?;
+        let lines = [
+            "BCD",
+            match self.version {
+                EpcVersion::V1 => "001",
+                EpcVersion::V2 => "002",
+            },
+            // UTF-8
+            "1",
+            "SCT",
+            self.bic.as_deref().unwrap_or_default(),
+            &self.name,
+            &self.iban,
+            &self
+                .amount
+                .as_ref()
+                .map(|amount| format!("EUR{}", amount))
+                .unwrap_or_default(),
+            self.purpose.as_deref().unwrap_or_default(),
+            self.creditor_reference.as_deref().unwrap_or_default(),
+            self.remittance_text.as_deref().unwrap_or_default(),
+            self.information.as_deref().unwrap_or_default(),
+        ];
+        // Trailing empty lines may be left out
+        let used = lines.iter().rposition(|line| !line.is_empty()).unwrap() + 1;
+        let text = lines[..used].join("\n");
+        if text.len() > MAX_PAYLOAD_BYTES {
+            return Err(Error::IllegalArgumentException(format!(
+                "Payload is {} bytes long, more than {}",
+                text.len(),
+                MAX_PAYLOAD_BYTES
+            )));
+        }
+        Ok(SepaCreditTransfer {
+            fields: self.clone(),
+            text,
+        })
+    }
+
+    fn validate(&self) -> ResultError<()> {
+        check_length("beneficiary name", Some(self.name.as_str()), 1, 70)?;
+        if !is_valid_iban(&self.iban) {
+            return Err(Error::IllegalArgumentException(format!(
+                "Invalid IBAN: {}",
+                self.iban
+            )));
+        }
+        match &self.bic {
+            Some(bic) if !SepaCreditTransferBuilder::is_valid_bic(bic) => {
+                return Err(Error::IllegalArgumentException(format!(
+                    "Invalid BIC: {}",
+                    bic
+                )))
+            }
+            None if self.version == EpcVersion::V1 => {
+                return Err(Error::IllegalArgumentException(String::from(
+                    "Version 001 needs a BIC",
+                )))
+            }
+            _ => {}
+        }
+        if let Some(amount) = &self.amount {
+            check_amount(amount)?;
+        }
+        if let Some(purpose) = &self.purpose {
+            if purpose.len() != 4
+                || !purpose
+                    .bytes()
+                    .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+            {
+                return Err(Error::IllegalArgumentException(format!(
+                    "Invalid purpose code: {}",
+                    purpose
+                )));
+            }
+        }
+        if self.creditor_reference.is_some() && self.remittance_text.is_some() {
+            return Err(Error::IllegalArgumentException(String::from(
+                "Only one of creditor reference and remittance text may be given",
+            )));
+        }
+        check_length(
+            "creditor reference",
+            self.creditor_reference.as_deref(),
+            1,
+            35,
+        )?;
+        check_length("remittance text", self.remittance_text.as_deref(), 1, 140)?;
+        check_length("information", self.information.as_deref(), 1, 70)?;
+        Ok(())
+    }
+
+    // Four letters of bank, two of country, two letters or digits of location and optionally
+    // three of branch.
+    fn is_valid_bic(bic: &str) -> bool {
+        let bytes = bic.as_bytes();
+        (bytes.len() == 8 || bytes.len() == 11)
+            && bytes[..6].iter().all(u8::is_ascii_uppercase)
+            && bytes[6..]
+                .iter()
+                .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+    }
+}
+
+/**
+ * A checked EPC069-12 SEPA credit transfer payload, built or parsed.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct SepaCreditTransfer {
+    fields: SepaCreditTransferBuilder,
+    text: String,
+}
+
+impl SepaCreditTransfer {
+    /**
+     * Parses and checks a payload.
+     *
+     * @throws FormatException if the text is not an EPC069-12 credit transfer
+     * @throws IllegalArgumentException if a field is malformed
+     */
+    pub fn parse(text: &str) -> ResultError<SepaCreditTransfer> {
+        let lines: Vec<&str> = text
+            .split('\n')
+            .map(|line| line.strip_suffix('\r').unwrap_or(line))
+            .collect();
+        if lines.len() < 7 || lines.len() > 12 || lines[0] != "BCD" || lines[3] != "SCT" {
+            return Err(Error::FormatException(String::from(
+                "Not an EPC069-12 credit transfer",
+            )));
+        }
+        let version = match lines[1] {
+            "001" => EpcVersion::V1,
+            "002" => EpcVersion::V2,
+            version => {
+                return Err(Error::FormatException(format!(
+                    "Unknown version: {}",
+                    version
+                )))
+            }
+        };
+        if !matches!(lines[2], "1" | "2" | "3" | "4" | "5" | "6" | "7" | "8") {
+            return Err(Error::FormatException(format!(
+                "Unknown character set: {}",
+                lines[2]
+            )));
+        }
+        let line = |index: usize| {
+            lines
+                .get(index)
+                .filter(|line| !line.is_empty())
+                .map(|line| line.to_string())
+        };
+        let amount = match line(7) {
+            Some(amount) => match amount.strip_prefix("EUR") {
+                Some(amount) => Some(amount.to_string()),
+                None => {
+                    return Err(Error::FormatException(format!(
+                        "Amount is not in euros: {}",
+                        amount
+                    )))
+                }
+            },
+            None => None,
+        };
+        let fields = SepaCreditTransferBuilder {
+            version,
+            bic: line(4),
+            name: lines[5].to_string(),
+            iban: lines[6].to_string(),
+            amount,
+            purpose: line(8),
+            creditor_reference: line(9),
+            remittance_text: line(10),
+            information: line(11),
+        };
+        fields.validate()?;
+        Ok(SepaCreditTransfer {
+            fields,
+            text: text.to_string(),
+        })
+    }
+
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn get_version(&self) -> EpcVersion {
+        self.fields.version
+    }
+
+    pub fn get_bic(&self) -> Option<&str> {
+        self.fields.bic.as_deref()
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.fields.name
+    }
+
+    pub fn get_iban(&self) -> &str {
+        &self.fields.iban
+    }
+
+    /**
+     * @return the amount in euros, like "12.50", or None if the originator enters it
+     */
+    pub fn get_amount(&self) -> Option<&str> {
+        self.fields.amount.as_deref()
+    }
+
+    pub fn get_purpose(&self) -> Option<&str> {
+        self.fields.purpose.as_deref()
+    }
+
+    pub fn get_creditor_reference(&self) -> Option<&str> {
+        self.fields.creditor_reference.as_deref()
+    }
+
+    pub fn get_remittance_text(&self) -> Option<&str> {
+        self.fields.remittance_text.as_deref()
+    }
+
+    pub fn get_information(&self) -> Option<&str> {
+        self.fields.information.as_deref()
+    }
+}
+
+impl ContentBuilder for SepaCreditTransfer {
+    fn build(&self) -> String {
+        self.text.clone()
+    }
+
+    /**
+     * EPC069-12 calls for level M, whatever the size of the payload.
+     */
+    fn get_error_correction_level(&self) -> ErrorCorrectionLevel {
+        ErrorCorrectionLevel::M
+    }
+}
+
+#[cfg(test)]
+mod sepa_credit_transfer_tests {
+    use super::{EpcVersion, SepaCreditTransfer, SepaCreditTransferBuilder};
+    use crate::client::payment::{is_valid_creditor_reference, is_valid_iban};
+
+    #[test]
+    fn checks_iban() {
+        assert!(is_valid_iban("CH9300762011623852957"));
+        assert!(is_valid_iban("DE89370400440532013000"));
+        assert!(!is_valid_iban("DE89370400440532013001"));
+        assert!(!is_valid_iban("de89370400440532013000"));
+        assert!(is_valid_creditor_reference("RF18539007547034"));
+        assert!(!is_valid_creditor_reference("RF19539007547034"));
+    }
+
+    #[test]
+    fn builds_and_parses() {
+        let transfer =
+            SepaCreditTransferBuilder::new("Red Cross of Belgium", "BE72 0000 0000 1616")
+                .bic("BPOTBEB1")
+                .amount("12.50")
+                .purpose("CHAR")
+                .remittance_text("Urgency fund")
+                .build()
+                .unwrap();
+        assert_eq!(
+            transfer.get_text(),
+            "BCD\n002\n1\nSCT\nBPOTBEB1\nRed Cross of Belgium\nBE72000000001616\nEUR12.50\n\
+             CHAR\n\nUrgency fund"
+        );
+        assert_eq!(
+            SepaCreditTransfer::parse(transfer.get_text()).unwrap(),
+            transfer
+        );
+
+        let parsed = SepaCreditTransfer::parse(
+            "BCD\n001\n1\nSCT\nBPOTBEB1\nRed Cross\nBE72000000001616\n\n\nRF18539007547034",
+        )
+        .unwrap();
+        assert_eq!(parsed.get_version(), EpcVersion::V1);
+        assert_eq!(parsed.get_amount(), None);
+        assert_eq!(parsed.get_creditor_reference(), Some("RF18539007547034"));
+    }
+
+    #[test]
+    fn rejects_invalid_fields() {
+        let builder = SepaCreditTransferBuilder::new("Red Cross", "BE72000000001616");
+        assert!(builder.clone().build().is_ok());
+        assert!(builder.clone().version(EpcVersion::V1).build().is_err());
+        assert!(builder.clone().bic("BPOTBE").build().is_err());
+        assert!(builder.clone().amount("0.00").build().is_err());
+        assert!(builder.clone().amount("1000000000").build().is_err());
+        assert!(builder.clone().amount("1.234").build().is_err());
+        assert!(builder
+            .clone()
+            .creditor_reference("RF18539007547034")
+            .remittance_text("Invoice")
+            .build()
+            .is_err());
+        assert!(builder.remittance_text(&"x".repeat(141)).build().is_err());
+        assert!(
+            SepaCreditTransferBuilder::new("Red Cross", "BE72000000001617")
+                .build()
+                .is_err()
+        );
+        assert!(
+            SepaCreditTransfer::parse("BCD\n003\n1\nSCT\n\nRed Cross\nBE72000000001616").is_err()
+        );
+    }
+}
@@ -0,0 +1,736 @@
+use crate::client::content::ContentBuilder;
+#[cfg(all(feature = "encode", feature = "qrcode"))]
+use crate::common::BitMatrix;
+use crate::{Error, ErrorCorrectionLevel, ResultError};
+
+use super::checksums::{is_valid_creditor_reference, is_valid_iban, is_valid_qr_reference};
+use super::{check_amount, check_length, normalize};
+
+// The lines up to and including the trailer "EPD"
+const MANDATORY_LINES: usize = 31;
+const MAX_PAYLOAD_CHARS: usize = 997;
+
+/**
+ * A structured ("S") address of a Swiss QR-bill. Combined ("K") addresses are no longer
+ * allowed from November 2025, and are not supported.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwissAddress {
+    name: String,
+    street: String,
+    building_number: String,
+    postal_code: String,
+    town: String,
+    country: String,
+}
+
+impl SwissAddress {
+    /**
+     * @param name the name, or company
+     * @param street the street, which may be empty
+     * @param building_number the building number, which may be empty
+     * @param postal_code the postal code, without country prefix
+     * @param town the town
+     * @param country the two letter ISO 3166-1 country code
+     */
+    pub fn new(
+        name: &str,
+        street: &str,
+        building_number: &str,
+        postal_code: &str,
+        town: &str,
+        country: &str,
+    ) -> Self {
+        SwissAddress {
+            name: name.to_string(),
+            street: street.to_string(),
+            building_number: building_number.to_string(),
+            postal_code: postal_code.to_string(),
+            town: town.to_string(),
+            country: country.to_string(),
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_street(&self) -> &str {
+        &self.street
+    }
+
+    pub fn get_building_number(&self) -> &str {
+        &self.building_number
+    }
+
+    pub fn get_postal_code(&self) -> &str {
+        &self.postal_code
+    }
+
+    pub fn get_town(&self) -> &str {
+        &self.town
+    }
+
+    pub fn get_country(&self) -> &str {
+        &self.country
+    }
+
+    fn validate(&self, party: &str) -> ResultError<()> {
+        check_length(&format!("{} name", party), Some(self.name.as_str()), 1, 70)?;
+        check_length(
+            &format!("{} street", party),
+            Some(self.street.as_str()),
+            0,
+            70,
+        )?;
+        check_length(
+            &format!("{} building number", party),
+            Some(self.building_number.as_str()),
+            0,
+            16,
+        )?;
+        check_length(
+            &format!("{} postal code", party),
+            Some(self.postal_code.as_str()),
+            1,
+            16,
+        )?;
+        check_length(&format!("{} town", party), Some(self.town.as_str()), 1, 35)?;
+        if self.country.len() != 2 || !self.country.bytes().all(|b| b.is_ascii_uppercase()) {
+            return Err(Error::IllegalArgumentException(format!(
+                "The {} country must be a two letter code: {}",
+                party, self.country
+            )));
+        }
+        Ok(())
+    }
+
+    fn write(address: Option<&SwissAddress>, lines: &mut Vec<String>) {
+        match address {
+            Some(address) => lines.extend(
+                [
+                    "S",
+                    &address.name,
+                    &address.street,
+                    &address.building_number,
+                    &address.postal_code,
+                    &address.town,
+                    &address.country,
+                ]
+                .iter()
+                .map(|line| line.to_string()),
+            ),
+            None => lines.extend((0..7).map(|_| String::new())),
+        }
+    }
+
+    fn parse(lines: &[&str]) -> ResultError<Option<SwissAddress>> {
+        match lines[0] {
+            "" if lines.iter().all(|line| line.is_empty()) => Ok(None),
+            "S" => Ok(Some(SwissAddress::new(
+                lines[1], lines[2], lines[3], lines[4], lines[5], lines[6],
+            ))),
+            "K" => Err(Error::FormatException(String::from(
+                "Combined addresses are not supported",
+            ))),
+            address_type => Err(Error::FormatException(format!(
+                "Unknown address type: {}",
+                address_type
+            ))),
+        }
+    }
+}
+
+/**
+ * The kinds of reference a Swiss QR-bill carries.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwissReferenceType {
+    /** A 27 digit QR reference, which goes with a QR-IBAN */
+    Qrr,
+    /** An ISO 11649 creditor reference */
+    Scor,
+    /** No reference */
+    Non,
+}
+
+impl SwissReferenceType {
+    fn get_code(&self) -> &'static str {
+        match self {
+            SwissReferenceType::Qrr => "QRR",
+            SwissReferenceType::Scor => "SCOR",
+            SwissReferenceType::Non => "NON",
+        }
+    }
+}
+
+/**
+ * Builds the payload of a Swiss QR-bill, the "SPC" payment part after version 2.3 of the Swiss
+ * Implementation Guidelines for the QR-bill, version 0200 of the data. Text is written as UTF-8
+ * and lines end with a line feed.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwissQrBillBuilder {
+    iban: String,
+    creditor: SwissAddress,
+    amount: Option<String>,
+    currency: String,
+    debtor: Option<SwissAddress>,
+    reference: Option<String>,
+    unstructured_message: Option<String>,
+    billing_information: Option<String>,
+    alternative_schemes: Vec<String>,
+}
+
+impl SwissQrBillBuilder {
+    /**
+     * @param iban a Swiss or Liechtenstein IBAN, or QR-IBAN, of the creditor; spaces are dropped
+     * @param creditor the address of the creditor
+     * @param currency "CHF" or "EUR"
+     */
+    pub fn new(iban: &str, creditor: SwissAddress, currency: &str) -> Self {
+        SwissQrBillBuilder {
+            iban: normalize(iban),
+            creditor,
+            amount: None,
+            currency: currency.to_string(),
+            debtor: None,
+            reference: None,
+            unstructured_message: None,
+            billing_information: None,
+            alternative_schemes: Vec::new(),
+        }
+    }
+
+    /**
+     * Sets the amount, like "1949.75", from 0.01 to 999999999.99. Without it the debtor enters
+     * the amount.
+     */
+    pub fn amount(mut self, amount: &str) -> Self {
+        self.amount = Some(amount.to_string());
+        self
+    }
+
+    pub fn debtor(mut self, debtor: SwissAddress) -> Self {
+        self.debtor = Some(debtor);
+        self
+    }
+
+    /**
+     * Sets the reference, whose type follows from its form: a 27 digit QR reference, or an
+     * ISO 11649 creditor reference starting with "RF". Spaces are dropped.
+     */
+    pub fn reference(mut self, reference: &str) -> Self {
+        self.reference = Some(normalize(reference));
+        self
+    }
+
+    pub fn unstructured_message(mut self, unstructured_message: &str) -> Self {
+        self.unstructured_message = Some(unstructured_message.to_string());
+        self
+    }
+
+    /**
+     * Sets the structured billing information, like the Swico "//S1/10/..." syntax.
+     */
+    pub fn billing_information(mut self, billing_information: &str) -> Self {
+        self.billing_information = Some(billing_information.to_string());
+        self
+    }
+
+    /**
+     * Adds the parameters of an alternative payment scheme, like "eBill/B/...". A bill carries
+     * at most two.
+     */
+    pub fn alternative_scheme(mut self, alternative_scheme: &str) -> Self {
+        self.alternative_schemes
+            .push(alternative_scheme.to_string());
+        self
+    }
+
+    /**
+     * Checks the fields and writes the payload.
+     *
+     * @throws IllegalArgumentException if a field is missing, malformed or too long, the
+     *  reference does not suit the IBAN, or the payload exceeds 997 characters
+     */
+    pub fn build(&self) -> ResultError<SwissQrBill> {
+        let reference_type = self.validate()?;
+        let mut lines: Vec<String> = ["SPC", "0200", "1", &self.iban]
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+        SwissAddress::write(Some(&self.creditor), &mut lines);
+        // The ultimate creditor is reserved for future use, and must be left empty
+        SwissAddress::write(None, &mut lines);
+        lines.push(self.amount.clone().unwrap_or_default());
+        lines.push(self.currency.clone());
+        SwissAddress::write(self.debtor.as_ref(), &mut lines);
+        lines.push(reference_type.get_code().to_string());
+        lines.push(self.reference.clone().unwrap_or_default());
+        lines.push(self.unstructured_message.clone().unwrap_or_default());
+        lines.push(String::from("EPD"));
+        if self.billing_information.is_some() || !self.alternative_schemes.is_empty() {
+            lines.push(self.billing_information.clone().unwrap_or_default());
+            lines.extend(self.alternative_schemes.iter().cloned());
+        }
+        let text = lines.join("\n");
+        let length = text.chars().count();
+        if length > MAX_PAYLOAD_CHARS {
+            return Err(Error::IllegalArgumentException(format!(
+                "Payload is {} characters long, more than {}",
+                length, MAX_PAYLOAD_CHARS
+            )));
+        }
+        Ok(SwissQrBill {
+            fields: self.clone(),
+            reference_type,
+            text,
+        })
+    }
+
+    fn validate(&self) -> ResultError<SwissReferenceType> {
+        if self.iban.len() != 21
+            || !(self.iban.starts_with("CH") || self.iban.starts_with("LI"))
+            || !is_valid_iban(&self.iban)
+        {
+            return Err(Error::IllegalArgumentException(format!(
+                "Not a valid Swiss or Liechtenstein IBAN: {}",
+                self.iban
+            )));
+        }
+        self.creditor.validate("creditor")?;
+        if let Some(amount) = &self.amount {
+            check_amount(amount)?;
+        }
+        if self.currency != "CHF" && self.currency != "EUR" {
+            return Err(Error::IllegalArgumentException(format!(
+                "Currency must be CHF or EUR: {}",
+                self.currency
+            )));
+        }
+        if let Some(debtor) = &self.debtor {
+            debtor.validate("debtor")?;
+        }
+        let reference_type = match self.reference.as_deref() {
+            None => SwissReferenceType::Non,
+            Some(reference) if is_valid_qr_reference(reference) => SwissReferenceType::Qrr,
+            Some(reference) if is_valid_creditor_reference(reference) => SwissReferenceType::Scor,
+            Some(reference) => {
+                return Err(Error::IllegalArgumentException(format!(
+                    "Not a valid QR reference or creditor reference: {}",
+                    reference
+                )))
+            }
+        };
+        let is_qr_iban = SwissQrBillBuilder::is_qr_iban(&self.iban);
+        if is_qr_iban != (reference_type == SwissReferenceType::Qrr) {
+            return Err(Error::IllegalArgumentException(String::from(
+                if is_qr_iban {
+                    "A QR-IBAN needs a QR reference"
+                } else {
+                    "A QR reference needs a QR-IBAN"
+                },
+            )));
+        }
+        // The message and the billing information share 140 characters
+        let message = format!(
+            "{}{}",
+            self.unstructured_message.as_deref().unwrap_or_default(),
+            self.billing_information.as_deref().unwrap_or_default()
+        );
+        check_length(
+            "unstructured message",
+            self.unstructured_message.as_deref(),
+            0,
+            140,
+        )?;
+        check_length(
+            "billing information",
+            self.billing_information.as_deref(),
+            0,
+            140,
+        )?;
+        check_length(
+            "unstructured message and billing information",
+            Some(message.as_str()),
+            0,
+            140,
+        )?;
+        if self.alternative_schemes.len() > 2 {
+            return Err(Error::IllegalArgumentException(String::from(
+                "At most two alternative schemes are allowed",
+            )));
+        }
+        for alternative_scheme in &self.alternative_schemes {
+            check_length(
+                "alternative scheme",
+                Some(alternative_scheme.as_str()),
+                1,
+                100,
+            )?;
+        }
+        Ok(reference_type)
+    }
+
+    // A QR-IBAN has an institution ID from 30000 to 31999.
+    fn is_qr_iban(iban: &str) -> bool {
+        iban[4..9]
+            .parse::<u32>()
+            .is_ok_and(|iid| (30000..=31999).contains(&iid))
+    }
+}
+
+/**
+ * A checked Swiss QR-bill payload, built or parsed.
+ *
+ * <p>Encoded as a {@link ContentBuilder} it asks for level M, as the guidelines require. The
+ * guidelines also require the Swiss cross in the middle of the symbol, which {@link #encode}
+ * draws.</p>
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwissQrBill {
+    fields: SwissQrBillBuilder,
+    reference_type: SwissReferenceType,
+    text: String,
+}
+
+impl SwissQrBill {
+    /**
+     * Parses and checks a payload.
+     *
+     * @throws FormatException if the text is not a version 2 "SPC" payload with structured
+     *  addresses
+     * @throws IllegalArgumentException if a field is malformed
+     */
+    pub fn parse(text: &str) -> ResultError<SwissQrBill> {
+        let lines: Vec<&str> = text
+            .split('\n')
+            .map(|line| line.strip_suffix('\r').unwrap_or(line))
+            .collect();
+        if lines.len() < MANDATORY_LINES
+            || lines.len() > MANDATORY_LINES + 3
+            || lines[0] != "SPC"
+            || !lines[1].starts_with("02")
+            || lines[2] != "1"
+            || lines[MANDATORY_LINES - 1] != "EPD"
+        {
+            return Err(Error::FormatException(String::from(
+                "Not a version 2 Swiss QR-bill",
+            )));
+        }
+        let creditor = match SwissAddress::parse(&lines[4..11])? {
+            Some(creditor) => creditor,
+            None => {
+                return Err(Error::FormatException(String::from(
+                    "The creditor address is missing",
+                )))
+            }
+        };
+        if SwissAddress::parse(&lines[11..18])?.is_some() {
+            return Err(Error::FormatException(String::from(
+                "The ultimate creditor must be empty",
+            )));
+        }
+        let line = |index: usize| {
+            lines
+                .get(index)
+                .filter(|line| !line.is_empty())
+                .map(|line| line.to_string())
+        };
+        let fields = SwissQrBillBuilder {
+            iban: lines[3].to_string(),
+            creditor,
+            amount: line(18),
+            currency: lines[19].to_string(),
+            debtor: SwissAddress::parse(&lines[20..27])?,
+            reference: line(28),
+            unstructured_message: line(29),
+            billing_information: line(31),
+            alternative_schemes: lines[(MANDATORY_LINES + 1).min(lines.len())..]
+                .iter()
+                .map(|line| line.to_string())
+                .collect(),
+        };
+        let reference_type = fields.validate()?;
+        if reference_type.get_code() != lines[27] {
+            return Err(Error::FormatException(format!(
+                "Reference type {} does not match the reference",
+                lines[27]
+            )));
+        }
+        Ok(SwissQrBill {
+            fields,
+            reference_type,
+            text: text.to_string(),
+        })
+    }
+
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn get_iban(&self) -> &str {
+        &self.fields.iban
+    }
+
+    pub fn get_creditor(&self) -> &SwissAddress {
+        &self.fields.creditor
+    }
+
+    /**
+     * @return the amount, like "1949.75", or None if the debtor enters it
+     */
+    pub fn get_amount(&self) -> Option<&str> {
+        self.fields.amount.as_deref()
+    }
+
+    pub fn get_currency(&self) -> &str {
+        &self.fields.currency
+    }
+
+    pub fn get_debtor(&self) -> Option<&SwissAddress> {
+        self.fields.debtor.as_ref()
+    }
+
+    pub fn get_reference_type(&self) -> SwissReferenceType {
+        self.reference_type
+    }
+
+    pub fn get_reference(&self) -> Option<&str> {
+        self.fields.reference.as_deref()
+    }
+
+    pub fn get_unstructured_message(&self) -> Option<&str> {
+        self.fields.unstructured_message.as_deref()
+    }
+
+    pub fn get_billing_information(&self) -> Option<&str> {
+        self.fields.billing_information.as_deref()
+    }
+
+    pub fn get_alternative_schemes(&self) -> &[String] {
+        &self.fields.alternative_schemes
+    }
+
+    /**
+     * Encodes the payload as a QR Code at level M, and draws the Swiss cross over its centre:
+     * a black square with a white cross, in a white border, 7/46 of the symbol wide.
+     *
+     * @param width preferred width in pixels
+     * @param height preferred height in pixels
+     * @throws WriterException if the payload does not fit a QR Code
+     */
+    #[cfg(all(feature = "encode", feature = "qrcode"))]
+    pub fn encode(&self, width: i32, height: i32) -> ResultError<BitMatrix> {
+        use crate::qrcode::QRCodeWriter;
+        use crate::{BarcodeFormat, Writer};
+
+        let mut matrix = QRCodeWriter::new().encode_options(
+            &self.text,
+            &BarcodeFormat::QRCode,
+            width,
+            height,
+            &self.get_encode_options(),
+        )?;
+        // The top left module of a QR Code is always black, and so is the whole top row of the
+        // finder patterns, which run to the right edge of the symbol.
+        let top_left = match matrix.getTopLeftOnBit() {
+            Some(top_left) => top_left,
+            None => {
+                return Err(Error::WriterException(String::from(
+                    "Encoded symbol is empty",
+                )))
+            }
+        };
+        let (left, top) = (top_left[0], top_left[1]);
+        let mut right = matrix.getWidth() - 1;
+        while right > left && !matrix.get(right as u32, top as u32) {
+            right -= 1;
+        }
+        let size = right - left + 1;
+
+        let logo = (size * 7 + 23) / 46;
+        let logo_left = left + (size - logo) / 2;
+        let logo_top = top + (size - logo) / 2;
+        SwissQrBill::clear_region(&mut matrix, logo_left, logo_top, logo, logo);
+
+        let square = logo * 6 / 7;
+        let square_left = logo_left + (logo - square) / 2;
+        let square_top = logo_top + (logo - square) / 2;
+        matrix.set_region(square_left, square_top, square, square);
+
+        // The arms of the cross are 6/32 as wide, and span 20/32, of the flag
+        let arm = (square * 6 + 16) / 32;
+        let span = (square * 20 + 16) / 32;
+        let arm_offset = (square - arm) / 2;
+        let span_offset = (square - span) / 2;
+        SwissQrBill::clear_region(
+            &mut matrix,
+            square_left + arm_offset,
+            square_top + span_offset,
+            arm,
+            span,
+        );
+        SwissQrBill::clear_region(
+            &mut matrix,
+            square_left + span_offset,
+            square_top + arm_offset,
+            span,
+            arm,
+        );
+        Ok(matrix)
+    }
+
+    #[cfg(all(feature = "encode", feature = "qrcode"))]
+    fn clear_region(matrix: &mut BitMatrix, left: i32, top: i32, width: i32, height: i32) {
+        for y in top..top + height {
+            for x in left..left + width {
+                matrix.unset(x as u32, y as u32);
+            }
+        }
+    }
+}
+
+impl ContentBuilder for SwissQrBill {
+    fn build(&self) -> String {
+        self.text.clone()
+    }
+
+    /**
+     * The QR-bill guidelines call for level M, whatever the size of the payload.
+     */
+    fn get_error_correction_level(&self) -> ErrorCorrectionLevel {
+        ErrorCorrectionLevel::M
+    }
+}
+
+#[cfg(test)]
+mod swiss_qr_bill_tests {
+    use super::{SwissAddress, SwissQrBill, SwissQrBillBuilder, SwissReferenceType};
+    use crate::client::payment::{is_valid_qr_reference, qr_reference_check_digit};
+
+    fn creditor() -> SwissAddress {
+        SwissAddress::new(
+            "Robert Schneider AG",
+            "Rue du Lac",
+            "1268",
+            "2501",
+            "Biel",
+            "CH",
+        )
+    }
+
+    #[test]
+    fn checks_qr_reference() {
+        assert!(is_valid_qr_reference("210000000003139471430009017"));
+        assert!(!is_valid_qr_reference("210000000003139471430009018"));
+        assert_eq!(
+            qr_reference_check_digit("21000000000313947143000901"),
+            Some(7)
+        );
+    }
+
+    #[test]
+    fn builds_and_parses() {
+        let bill = SwissQrBillBuilder::new("CH44 3199 9123 0008 8901 2", creditor(), "CHF")
+            .amount("1949.75")
+            .debtor(SwissAddress::new(
+                "Pia-Maria Rutschmann-Schnyder",
+                "Grosse Marktgasse",
+                "28",
+                "9400",
+                "Rorschach",
+                "CH",
+            ))
+            .reference("21 00000 00003 13947 14300 09017")
+            .unstructured_message("Order of 15 June 2020")
+            .billing_information("//S1/10/10201409/11/200701/20/140.000-53")
+            .build()
+            .unwrap();
+        assert_eq!(bill.get_reference_type(), SwissReferenceType::Qrr);
+        let lines: Vec<&str> = bill.get_text().split('\n').collect();
+        assert_eq!(lines.len(), 32);
+        assert_eq!(lines[3], "CH4431999123000889012");
+        assert_eq!(lines[27], "QRR");
+        assert_eq!(lines[30], "EPD");
+        assert_eq!(SwissQrBill::parse(bill.get_text()).unwrap(), bill);
+
+        let bill = SwissQrBillBuilder::new("CH9300762011623852957", creditor(), "EUR")
+            .reference("RF18539007547034")
+            .build()
+            .unwrap();
+        assert_eq!(bill.get_reference_type(), SwissReferenceType::Scor);
+        assert_eq!(bill.get_text().split('\n').count(), 31);
+        assert_eq!(SwissQrBill::parse(bill.get_text()).unwrap(), bill);
+    }
+
+    #[test]
+    fn rejects_invalid_fields() {
+        // A QR-IBAN needs a QR reference, and a QR reference a QR-IBAN
+        assert!(
+            SwissQrBillBuilder::new("CH4431999123000889012", creditor(), "CHF")
+                .build()
+                .is_err()
+        );
+        assert!(
+            SwissQrBillBuilder::new("CH9300762011623852957", creditor(), "CHF")
+                .reference("210000000003139471430009017")
+                .build()
+                .is_err()
+        );
+        assert!(
+            SwissQrBillBuilder::new("DE89370400440532013000", creditor(), "CHF")
+                .build()
+                .is_err()
+        );
+        assert!(
+            SwissQrBillBuilder::new("CH9300762011623852957", creditor(), "USD")
+                .build()
+                .is_err()
+        );
+        let bill = SwissQrBillBuilder::new("CH9300762011623852957", creditor(), "CHF")
+            .build()
+            .unwrap();
+        let combined = bill.get_text().replacen("\nS\n", "\nK\n", 1);
+        assert!(SwissQrBill::parse(&combined).is_err());
+    }
+
+    #[cfg(all(feature = "encode", feature = "qrcode"))]
+    #[test]
+    fn draws_swiss_cross() {
+        let bill = SwissQrBillBuilder::new("CH9300762011623852957", creditor(), "CHF")
+            .amount("50.00")
+            .build()
+            .unwrap();
+        let matrix = bill.encode(460, 460).unwrap();
+        let center = (matrix.getWidth() / 2) as u32;
+        let top_left = matrix.getTopLeftOnBit().unwrap();
+        let size = matrix.getWidth() - 2 * top_left[0];
+        let logo = (size * 7 / 46) as u32;
+        // White cross at the centre, black square around it, white border around that
+        assert!(!matrix.get(center, center));
+        assert!(matrix.get(center - logo * 5 / 16, center - logo * 5 / 16));
+        assert!(!matrix.get(center - logo / 2 + 1, center));
+
+        // The cross covers modules that error correction has to restore
+        #[cfg(feature = "decode")]
+        {
+            use crate::common::HybridBinarizer;
+            use crate::{BinaryBitmap, GrayLuminanceSource, QRCodeReader, Reader};
+
+            let width = matrix.getWidth() as u32;
+            let height = matrix.getHeight() as u32;
+            let luminances = (0..height)
+                .flat_map(|y| (0..width).map(move |x| (x, y)))
+                .map(|(x, y)| if matrix.get(x, y) { 0 } else { 0xFF })
+                .collect();
+            let source = GrayLuminanceSource::from_vec(luminances, width, height).unwrap();
+            let result = QRCodeReader::new()
+                .decode(&BinaryBitmap::new(HybridBinarizer::new(source)))
+                .unwrap();
+            assert_eq!(result.get_text(), bill.get_text());
+            assert_eq!(SwissQrBill::parse(result.get_text()).unwrap(), bill);
+        }
+    }
+}
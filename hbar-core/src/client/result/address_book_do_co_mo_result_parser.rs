@@ -0,0 +1,73 @@
+use crate::BarcodeFormat;
+
+use super::result_parser::{
+    is_string_of_digits, match_do_co_mo_prefixed_field, match_single_do_co_mo_prefixed_field,
+    ResultParser,
+};
+use super::{AddressBookParsedResult, ParsedResult};
+
+/**
+ * <p>Implements the "MECARD" address book entry format.</p>
+ *
+ * <p>Supported keys: N, SOUND, TEL, EMAIL, NOTE, ADR, BDAY, URL, plus ORG
+ * Unsupported keys: TEL-AV, NICKNAME</p>
+ *
+ * <p>Except for TEL, multiple values for keys are also not supported;
+ * the first one found takes precedence.</p>
+ *
+ * <p>Our understanding of the MECARD format is based on this document:</p>
+ *
+ * <p>http://www.mobicode.org.tw/files/OMIA%20Mobile%20Bar%20Code%20Standard%20v3.2.1.doc</p>
+ */
+pub struct AddressBookDoCoMoResultParser;
+
+impl ResultParser for AddressBookDoCoMoResultParser {
+    fn parse(&self, text: &str, _format: &BarcodeFormat) -> Option<ParsedResult> {
+        if !text.starts_with("MECARD:") {
+            return None;
+        }
+        let raw_name = match_do_co_mo_prefixed_field("N:", text)?;
+        let name = AddressBookDoCoMoResultParser::parse_name(&raw_name[0]);
+        let pronunciation = match_single_do_co_mo_prefixed_field("SOUND:", text, true);
+        let phone_numbers = match_do_co_mo_prefixed_field("TEL:", text).unwrap_or_default();
+        let emails = match_do_co_mo_prefixed_field("EMAIL:", text).unwrap_or_default();
+        let note = match_single_do_co_mo_prefixed_field("NOTE:", text, false);
+        let addresses = match_do_co_mo_prefixed_field("ADR:", text).unwrap_or_default();
+        let birthday = match_single_do_co_mo_prefixed_field("BDAY:", text, true)
+            .filter(|birthday| is_string_of_digits(Some(birthday), 8));
+        let urls = match_do_co_mo_prefixed_field("URL:", text).unwrap_or_default();
+
+        // Although ORG may not be strictly legal in MECARD, it does exist in VCARD and we might as
+        // well honor it when found in the wild.
+        let org = match_single_do_co_mo_prefixed_field("ORG:", text, true);
+
+        Some(ParsedResult::AddressBook(AddressBookParsedResult::new(
+            vec![name],
+            Vec::new(),
+            pronunciation,
+            phone_numbers,
+            Vec::new(),
+            emails,
+            Vec::new(),
+            None,
+            note,
+            addresses,
+            Vec::new(),
+            org,
+            birthday,
+            None,
+            urls,
+            Vec::new(),
+        )))
+    }
+}
+
+impl AddressBookDoCoMoResultParser {
+    // Turns "Owen,Sean" into "Sean Owen".
+    fn parse_name(name: &str) -> String {
+        match name.find(',') {
+            Some(comma) => format!("{} {}", &name[comma + 1..], &name[..comma]),
+            None => name.to_string(),
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use super::ParsedResult;
+
+/**
+ * A contact, as in a vCard, MECARD or BIZCARD. Lists of values are empty when the contact has
+ * none; the type of each phone number, e-mail address and address, like "HOME" or "WORK", is
+ * given alongside it where known.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct AddressBookParsedResult {
+    names: Vec<String>,
+    nicknames: Vec<String>,
+    pronunciation: Option<String>,
+    phone_numbers: Vec<String>,
+    phone_types: Vec<Option<String>>,
+    emails: Vec<String>,
+    email_types: Vec<Option<String>>,
+    instant_messenger: Option<String>,
+    note: Option<String>,
+    addresses: Vec<String>,
+    address_types: Vec<Option<String>>,
+    org: Option<String>,
+    birthday: Option<String>,
+    title: Option<String>,
+    urls: Vec<String>,
+    geo: Vec<String>,
+}
+
+impl AddressBookParsedResult {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        names: Vec<String>,
+        nicknames: Vec<String>,
+        pronunciation: Option<String>,
+        phone_numbers: Vec<String>,
+        phone_types: Vec<Option<String>>,
+        emails: Vec<String>,
+        email_types: Vec<Option<String>>,
+        instant_messenger: Option<String>,
+        note: Option<String>,
+        addresses: Vec<String>,
+        address_types: Vec<Option<String>>,
+        org: Option<String>,
+        birthday: Option<String>,
+        title: Option<String>,
+        urls: Vec<String>,
+        geo: Vec<String>,
+    ) -> AddressBookParsedResult {
+        AddressBookParsedResult {
+            names,
+            nicknames,
+            pronunciation,
+            phone_numbers,
+            phone_types,
+            emails,
+            email_types,
+            instant_messenger,
+            note,
+            addresses,
+            address_types,
+            org,
+            birthday,
+            title,
+            urls,
+            geo,
+        }
+    }
+
+    pub fn get_names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn get_nicknames(&self) -> &[String] {
+        &self.nicknames
+    }
+
+    /**
+     * In Japanese, the name is written in kanji, which can have multiple readings. Therefore a
+     * hint is often provided, called furigana, which spells the name phonetically.
+     *
+     * @return The pronunciation of the names, if provided
+     */
+    pub fn get_pronunciation(&self) -> Option<&str> {
+        self.pronunciation.as_deref()
+    }
+
+    pub fn get_phone_numbers(&self) -> &[String] {
+        &self.phone_numbers
+    }
+
+    /**
+     * @return optional descriptions of the type of each phone number, like "HOME", in the order
+     *  of {@link #get_phone_numbers}, or empty if none are known
+     */
+    pub fn get_phone_types(&self) -> &[Option<String>] {
+        &self.phone_types
+    }
+
+    pub fn get_emails(&self) -> &[String] {
+        &self.emails
+    }
+
+    /**
+     * @return optional descriptions of the type of each e-mail, like "WORK", in the order of
+     *  {@link #get_emails}, or empty if none are known
+     */
+    pub fn get_email_types(&self) -> &[Option<String>] {
+        &self.email_types
+    }
+
+    pub fn get_instant_messenger(&self) -> Option<&str> {
+        self.instant_messenger.as_deref()
+    }
+
+    pub fn get_note(&self) -> Option<&str> {
+        self.note.as_deref()
+    }
+
+    pub fn get_addresses(&self) -> &[String] {
+        &self.addresses
+    }
+
+    /**
+     * @return optional descriptions of the type of each address, like "HOME", in the order of
+     *  {@link #get_addresses}, or empty if none are known
+     */
+    pub fn get_address_types(&self) -> &[Option<String>] {
+        &self.address_types
+    }
+
+    pub fn get_title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    pub fn get_org(&self) -> Option<&str> {
+        self.org.as_deref()
+    }
+
+    pub fn get_urls(&self) -> &[String] {
+        &self.urls
+    }
+
+    /**
+     * @return birthday formatted as yyyyMMdd (e.g. 19780917)
+     */
+    pub fn get_birthday(&self) -> Option<&str> {
+        self.birthday.as_deref()
+    }
+
+    /**
+     * @return a location as a latitude/longitude pair, or empty
+     */
+    pub fn get_geo(&self) -> &[String] {
+        &self.geo
+    }
+
+    pub fn get_display_result(&self) -> String {
+        let mut result = String::with_capacity(100);
+        ParsedResult::maybe_append_all(&self.names, &mut result);
+        ParsedResult::maybe_append_all(&self.nicknames, &mut result);
+        ParsedResult::maybe_append(self.pronunciation.as_deref(), &mut result);
+        ParsedResult::maybe_append(self.title.as_deref(), &mut result);
+        ParsedResult::maybe_append(self.org.as_deref(), &mut result);
+        ParsedResult::maybe_append_all(&self.addresses, &mut result);
+        ParsedResult::maybe_append_all(&self.phone_numbers, &mut result);
+        ParsedResult::maybe_append_all(&self.emails, &mut result);
+        ParsedResult::maybe_append(self.instant_messenger.as_deref(), &mut result);
+        ParsedResult::maybe_append_all(&self.urls, &mut result);
+        ParsedResult::maybe_append(self.birthday.as_deref(), &mut result);
+        ParsedResult::maybe_append_all(&self.geo, &mut result);
+        ParsedResult::maybe_append(self.note.as_deref(), &mut result);
+        result
+    }
+}
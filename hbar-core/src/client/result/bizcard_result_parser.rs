@@ -0,0 +1,63 @@
+use crate::BarcodeFormat;
+
+use super::result_parser::{
+    match_do_co_mo_prefixed_field, match_single_do_co_mo_prefixed_field, ResultParser,
+};
+use super::{AddressBookParsedResult, ParsedResult};
+
+/**
+ * Implements the "BIZCARD" address book entry format, though this has been
+ * largely reverse-engineered from examples observed in the wild -- still
+ * looking for a definitive reference.
+ */
+pub struct BizcardResultParser;
+
+impl ResultParser for BizcardResultParser {
+    // The format is very much like the DoCoMo MECARD format, and is matched the same way, but
+    // this is not technically one of DoCoMo's proposed formats
+    fn parse(&self, text: &str, _format: &BarcodeFormat) -> Option<ParsedResult> {
+        if !text.starts_with("BIZCARD:") {
+            return None;
+        }
+        let first_name = match_single_do_co_mo_prefixed_field("N:", text, true);
+        let last_name = match_single_do_co_mo_prefixed_field("X:", text, true);
+        let full_name = BizcardResultParser::build_name(first_name, last_name);
+        let title = match_single_do_co_mo_prefixed_field("T:", text, true);
+        let org = match_single_do_co_mo_prefixed_field("C:", text, true);
+        let addresses = match_do_co_mo_prefixed_field("A:", text).unwrap_or_default();
+        let phone_numbers = ["B:", "M:", "F:"]
+            .iter()
+            .filter_map(|prefix| match_single_do_co_mo_prefixed_field(prefix, text, true))
+            .collect();
+        let email = match_single_do_co_mo_prefixed_field("E:", text, true);
+
+        Some(ParsedResult::AddressBook(AddressBookParsedResult::new(
+            full_name.into_iter().collect(),
+            Vec::new(),
+            None,
+            phone_numbers,
+            Vec::new(),
+            email.into_iter().collect(),
+            Vec::new(),
+            None,
+            None,
+            addresses,
+            Vec::new(),
+            org,
+            None,
+            title,
+            Vec::new(),
+            Vec::new(),
+        )))
+    }
+}
+
+impl BizcardResultParser {
+    fn build_name(first_name: Option<String>, last_name: Option<String>) -> Option<String> {
+        match (first_name, last_name) {
+            (Some(first_name), Some(last_name)) => Some(format!("{} {}", first_name, last_name)),
+            (first_name, None) => first_name,
+            (None, last_name) => last_name,
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use crate::BarcodeFormat;
+
+use super::result_parser::{
+    match_do_co_mo_prefixed_field, match_single_do_co_mo_prefixed_field, ResultParser,
+};
+use super::{ParsedResult, UriParsedResult, UriResultParser};
+
+/**
+ * Parses NTT DoCoMo bookmarks, like "MEBKM:TITLE:ZXing;URL:https://example.com;;".
+ */
+pub struct BookmarkDoCoMoResultParser;
+
+impl ResultParser for BookmarkDoCoMoResultParser {
+    fn parse(&self, text: &str, _format: &BarcodeFormat) -> Option<ParsedResult> {
+        if !text.starts_with("MEBKM:") {
+            return None;
+        }
+        let title = match_single_do_co_mo_prefixed_field("TITLE:", text, true);
+        let raw_uri = match_do_co_mo_prefixed_field("URL:", text)?;
+        let uri = &raw_uri[0];
+        if UriResultParser::is_basically_valid_uri(uri) {
+            Some(ParsedResult::Uri(UriParsedResult::new(uri, title)))
+        } else {
+            None
+        }
+    }
+}
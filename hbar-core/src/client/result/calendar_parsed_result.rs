@@ -0,0 +1,291 @@
+use crate::{Error, ResultError};
+
+use super::ParsedResult;
+
+const MILLIS_PER_DAY: i64 = 24 * 60 * 60 * 1000;
+// The milliseconds in a week, day, hour, minute and second, the units of a duration.
+const DURATION_UNITS: [(char, i64); 5] = [
+    ('W', 7 * MILLIS_PER_DAY),
+    ('D', MILLIS_PER_DAY),
+    ('H', 60 * 60 * 1000),
+    ('M', 60 * 1000),
+    ('S', 1000),
+];
+
+/**
+ * An event, as in an iCalendar VEVENT. Times are milliseconds since the epoch; times given
+ * without a time zone are taken to be UTC.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarParsedResult {
+    summary: Option<String>,
+    start: i64,
+    start_all_day: bool,
+    end: i64,
+    end_all_day: bool,
+    location: Option<String>,
+    organizer: Option<String>,
+    attendees: Vec<String>,
+    description: Option<String>,
+    latitude: f64,
+    longitude: f64,
+}
+
+impl CalendarParsedResult {
+    /**
+     * @param start_string the start, as yyyyMMdd or yyyyMMdd'T'HHmmss with an optional 'Z'
+     * @param end_string the end, in the same form, if known
+     * @param duration_string the duration as in RFC 2445, like "PT1H30M", used when there is no
+     *  end
+     * @throws IllegalArgumentException if a date is malformed
+     */
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        summary: Option<String>,
+        start_string: &str,
+        end_string: Option<&str>,
+        duration_string: Option<&str>,
+        location: Option<String>,
+        organizer: Option<String>,
+        attendees: Vec<String>,
+        description: Option<String>,
+        latitude: f64,
+        longitude: f64,
+    ) -> ResultError<CalendarParsedResult> {
+        let start = CalendarParsedResult::parse_date(start_string)?;
+        let end = match end_string {
+            Some(end_string) => CalendarParsedResult::parse_date(end_string)?,
+            None => duration_string
+                .and_then(CalendarParsedResult::parse_duration_ms)
+                .and_then(|duration| start.checked_add(duration))
+                .unwrap_or(-1),
+        };
+        Ok(CalendarParsedResult {
+            summary,
+            start,
+            start_all_day: start_string.len() == 8,
+            end,
+            end_all_day: end_string.is_some_and(|end_string| end_string.len() == 8),
+            location,
+            organizer,
+            attendees,
+            description,
+            latitude,
+            longitude,
+        })
+    }
+
+    pub fn get_summary(&self) -> Option<&str> {
+        self.summary.as_deref()
+    }
+
+    /**
+     * @return start time, in milliseconds since the epoch
+     */
+    pub fn get_start_timestamp(&self) -> i64 {
+        self.start
+    }
+
+    /**
+     * @return true if start time was specified as a whole day
+     */
+    pub fn is_start_all_day(&self) -> bool {
+        self.start_all_day
+    }
+
+    /**
+     * @return event end, in milliseconds since the epoch, or -1 if event has no duration
+     */
+    pub fn get_end_timestamp(&self) -> i64 {
+        self.end
+    }
+
+    /**
+     * @return true if end time was specified as a whole day
+     */
+    pub fn is_end_all_day(&self) -> bool {
+        self.end_all_day
+    }
+
+    pub fn get_location(&self) -> Option<&str> {
+        self.location.as_deref()
+    }
+
+    pub fn get_organizer(&self) -> Option<&str> {
+        self.organizer.as_deref()
+    }
+
+    pub fn get_attendees(&self) -> &[String] {
+        &self.attendees
+    }
+
+    pub fn get_description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /**
+     * @return latitude in degrees, or NaN if there is none
+     */
+    pub fn get_latitude(&self) -> f64 {
+        self.latitude
+    }
+
+    /**
+     * @return longitude in degrees, or NaN if there is none
+     */
+    pub fn get_longitude(&self) -> f64 {
+        self.longitude
+    }
+
+    pub fn get_display_result(&self) -> String {
+        let mut result = String::with_capacity(100);
+        ParsedResult::maybe_append(self.summary.as_deref(), &mut result);
+        ParsedResult::maybe_append(
+            Some(&CalendarParsedResult::format(
+                self.start_all_day,
+                self.start,
+            )),
+            &mut result,
+        );
+        if self.end >= 0 {
+            ParsedResult::maybe_append(
+                Some(&CalendarParsedResult::format(self.end_all_day, self.end)),
+                &mut result,
+            );
+        }
+        ParsedResult::maybe_append(self.location.as_deref(), &mut result);
+        ParsedResult::maybe_append(self.organizer.as_deref(), &mut result);
+        ParsedResult::maybe_append_all(&self.attendees, &mut result);
+        ParsedResult::maybe_append(self.description.as_deref(), &mut result);
+        result
+    }
+
+    /**
+     * Parses a string as a date: yyyyMMdd for a whole day, starting at midnight UTC, or
+     * yyyyMMdd'T'HHmmss with an optional 'Z'.
+     *
+     * @return milliseconds since the epoch
+     * @throws IllegalArgumentException if the string is not a date
+     */
+    fn parse_date(when: &str) -> ResultError<i64> {
+        let bytes = when.as_bytes();
+        let digits = |range: std::ops::Range<usize>| bytes[range].iter().all(u8::is_ascii_digit);
+        let well_formed = match bytes.len() {
+            8 => digits(0..8),
+            15 | 16 => {
+                digits(0..8)
+                    && bytes[8] == b'T'
+                    && digits(9..15)
+                    && (bytes.len() == 15 || bytes[15] == b'Z')
+            }
+            _ => false,
+        };
+        let number = |range: std::ops::Range<usize>| when[range].parse::<i64>().unwrap();
+        if !well_formed || !(1..=12).contains(&number(4..6)) || !(1..=31).contains(&number(6..8)) {
+            return Err(Error::IllegalArgumentException(format!(
+                "Invalid date: {}",
+                when
+            )));
+        }
+        let mut millis =
+            CalendarParsedResult::days_from_civil(number(0..4), number(4..6), number(6..8))
+                * MILLIS_PER_DAY;
+        if bytes.len() > 8 {
+            millis += ((number(9..11) * 60 + number(11..13)) * 60 + number(13..15)) * 1000;
+        }
+        Ok(millis)
+    }
+
+    /**
+     * Parses an RFC 2445 duration, "P" followed by weeks and days, then optionally "T" and
+     * hours, minutes and seconds, each a number and a unit letter.
+     */
+    fn parse_duration_ms(duration: &str) -> Option<i64> {
+        let mut rest = duration.strip_prefix('P')?;
+        let mut units = DURATION_UNITS.iter().peekable();
+        let mut in_time = false;
+        let mut millis = 0i64;
+        while !rest.is_empty() {
+            if rest.starts_with('T') && !in_time {
+                // Time units follow, so no weeks or days any more
+                rest = &rest[1..];
+                in_time = true;
+                while units
+                    .peek()
+                    .is_some_and(|&&(unit, _)| unit == 'W' || unit == 'D')
+                {
+                    units.next();
+                }
+                continue;
+            }
+            let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+            let value = rest[..digits].parse::<i64>().ok()?;
+            let unit = rest[digits..].chars().next()?;
+            // Each unit may come once, in order, and hours to seconds only after 'T'
+            let &(_, unit_millis) = loop {
+                let &&(candidate, _) = units.peek()?;
+                let is_time_unit = candidate == 'H' || candidate == 'M' || candidate == 'S';
+                if candidate == unit && is_time_unit == in_time {
+                    break units.next()?;
+                }
+                units.next();
+            };
+            // A duration too long to count in milliseconds is as good as unparseable
+            millis = value
+                .checked_mul(unit_millis)
+                .and_then(|unit_total| millis.checked_add(unit_total))?;
+            rest = &rest[digits + 1..];
+        }
+        Some(millis)
+    }
+
+    // Days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+    fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+        let year = if month <= 2 { year - 1 } else { year };
+        let era = if year >= 0 { year } else { year - 399 } / 400;
+        let year_of_era = year - era * 400;
+        let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146097 + day_of_era - 719468
+    }
+
+    /**
+     * Splits a timestamp into its UTC date and the seconds since midnight.
+     *
+     * @return year, month (1 to 12), day of the month and second of the day
+     */
+    pub(crate) fn civil_from_millis(millis: i64) -> (i64, i64, i64, i64) {
+        let days = millis.div_euclid(MILLIS_PER_DAY);
+        let z = days + 719468;
+        let era = if z >= 0 { z } else { z - 146096 } / 146097;
+        let day_of_era = z - era * 146097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+        (year, month, day, millis.rem_euclid(MILLIS_PER_DAY) / 1000)
+    }
+
+    // Formats a timestamp as yyyy-MM-dd, followed by HH:mm:ss unless it is a whole day.
+    fn format(all_day: bool, millis: i64) -> String {
+        let (year, month, day, seconds) = CalendarParsedResult::civil_from_millis(millis);
+        let date = format!("{:04}-{:02}-{:02}", year, month, day);
+        if all_day {
+            return date;
+        }
+        format!(
+            "{} {:02}:{:02}:{:02}",
+            date,
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        )
+    }
+}
@@ -0,0 +1,61 @@
+use super::ParsedResult;
+
+/**
+ * An e-mail message: its recipients, subject and body.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailAddressParsedResult {
+    tos: Vec<String>,
+    ccs: Vec<String>,
+    bccs: Vec<String>,
+    subject: Option<String>,
+    body: Option<String>,
+}
+
+impl EmailAddressParsedResult {
+    pub fn new(
+        tos: Vec<String>,
+        ccs: Vec<String>,
+        bccs: Vec<String>,
+        subject: Option<String>,
+        body: Option<String>,
+    ) -> EmailAddressParsedResult {
+        EmailAddressParsedResult {
+            tos,
+            ccs,
+            bccs,
+            subject,
+            body,
+        }
+    }
+
+    pub fn get_tos(&self) -> &[String] {
+        &self.tos
+    }
+
+    pub fn get_ccs(&self) -> &[String] {
+        &self.ccs
+    }
+
+    pub fn get_bccs(&self) -> &[String] {
+        &self.bccs
+    }
+
+    pub fn get_subject(&self) -> Option<&str> {
+        self.subject.as_deref()
+    }
+
+    pub fn get_body(&self) -> Option<&str> {
+        self.body.as_deref()
+    }
+
+    pub fn get_display_result(&self) -> String {
+        let mut result = String::with_capacity(30);
+        ParsedResult::maybe_append_all(&self.tos, &mut result);
+        ParsedResult::maybe_append_all(&self.ccs, &mut result);
+        ParsedResult::maybe_append_all(&self.bccs, &mut result);
+        ParsedResult::maybe_append(self.subject.as_deref(), &mut result);
+        ParsedResult::maybe_append(self.body.as_deref(), &mut result);
+        result
+    }
+}
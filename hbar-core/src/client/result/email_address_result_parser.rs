@@ -0,0 +1,65 @@
+use crate::BarcodeFormat;
+
+use super::result_parser::{parse_name_value_pairs, url_decode, ResultParser};
+use super::{EmailAddressParsedResult, EmailDoCoMoResultParser, ParsedResult};
+
+/**
+ * Represents a result that encodes an e-mail address, either as a plain address
+ * like "joe@example.org" or a mailto: URL like "mailto:joe@example.org".
+ */
+pub struct EmailAddressResultParser;
+
+impl ResultParser for EmailAddressResultParser {
+    fn parse(&self, text: &str, _format: &BarcodeFormat) -> Option<ParsedResult> {
+        if !text.starts_with("mailto:") && !text.starts_with("MAILTO:") {
+            if !EmailDoCoMoResultParser::is_basically_valid_email_address(text) {
+                return None;
+            }
+            return Some(ParsedResult::EmailAddress(EmailAddressParsedResult::new(
+                vec![text.to_string()],
+                Vec::new(),
+                Vec::new(),
+                None,
+                None,
+            )));
+        }
+        // If it starts with mailto:, assume it is definitely trying to be an email address
+        let mut host_email = &text[7..];
+        if let Some(query_start) = host_email.find('?') {
+            host_email = &host_email[..query_start];
+        }
+        let host_email = url_decode(host_email)?;
+        let mut tos = EmailAddressResultParser::split_addresses(&host_email);
+        let mut ccs = Vec::new();
+        let mut bccs = Vec::new();
+        let mut subject = None;
+        let mut body = None;
+        if let Some(mut name_values) = parse_name_value_pairs(text) {
+            if tos.is_empty() {
+                if let Some(tos_string) = name_values.get("to") {
+                    tos = EmailAddressResultParser::split_addresses(tos_string);
+                }
+            }
+            if let Some(cc_string) = name_values.get("cc") {
+                ccs = EmailAddressResultParser::split_addresses(cc_string);
+            }
+            if let Some(bcc_string) = name_values.get("bcc") {
+                bccs = EmailAddressResultParser::split_addresses(bcc_string);
+            }
+            subject = name_values.remove("subject");
+            body = name_values.remove("body");
+        }
+        Some(ParsedResult::EmailAddress(EmailAddressParsedResult::new(
+            tos, ccs, bccs, subject, body,
+        )))
+    }
+}
+
+impl EmailAddressResultParser {
+    fn split_addresses(addresses: &str) -> Vec<String> {
+        if addresses.is_empty() {
+            return Vec::new();
+        }
+        addresses.split(',').map(String::from).collect()
+    }
+}
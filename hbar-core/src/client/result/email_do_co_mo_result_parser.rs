@@ -0,0 +1,53 @@
+use crate::BarcodeFormat;
+
+use super::result_parser::{
+    match_do_co_mo_prefixed_field, match_single_do_co_mo_prefixed_field, ResultParser,
+};
+use super::{EmailAddressParsedResult, ParsedResult};
+
+/**
+ * Implements the "MATMSG" email message entry format.
+ *
+ * Supported keys: TO, SUB, BODY
+ */
+pub struct EmailDoCoMoResultParser;
+
+impl ResultParser for EmailDoCoMoResultParser {
+    fn parse(&self, text: &str, _format: &BarcodeFormat) -> Option<ParsedResult> {
+        if !text.starts_with("MATMSG:") {
+            return None;
+        }
+        let tos = match_do_co_mo_prefixed_field("TO:", text)?;
+        if !tos
+            .iter()
+            .all(|to| EmailDoCoMoResultParser::is_basically_valid_email_address(to))
+        {
+            return None;
+        }
+        let subject = match_single_do_co_mo_prefixed_field("SUB:", text, false);
+        let body = match_single_do_co_mo_prefixed_field("BODY:", text, false);
+        Some(ParsedResult::EmailAddress(EmailAddressParsedResult::new(
+            tos,
+            Vec::new(),
+            Vec::new(),
+            subject,
+            body,
+        )))
+    }
+}
+
+impl EmailDoCoMoResultParser {
+    /**
+     * This implements only the most basic checking for an email address's validity -- that it
+     * contains an '@' and contains no characters disallowed by RFC 2822. This is an overly
+     * lenient definition of validity. We want to generally be lenient here since this class is
+     * only intended to encapsulate what's in a barcode, not "judge" it.
+     */
+    pub fn is_basically_valid_email_address(email: &str) -> bool {
+        !email.is_empty()
+            && email.contains('@')
+            && email
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "@.!#$%&'*+-/=?^_`{|}~".contains(c))
+    }
+}
@@ -0,0 +1,20 @@
+use crate::client::emvco::EmvcoPayload;
+use crate::BarcodeFormat;
+
+use super::result_parser::ResultParser;
+use super::ParsedResult;
+
+/**
+ * Parses EMVCo merchant-presented payment payloads, which start with the payload format
+ * indicator "000201". Payloads with a bad checksum are left as text.
+ */
+pub struct EmvcoResultParser;
+
+impl ResultParser for EmvcoResultParser {
+    fn parse(&self, text: &str, _format: &BarcodeFormat) -> Option<ParsedResult> {
+        if !text.starts_with("000201") {
+            return None;
+        }
+        EmvcoPayload::parse(text).ok().map(ParsedResult::Emvco)
+    }
+}
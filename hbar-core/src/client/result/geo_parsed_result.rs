@@ -0,0 +1,84 @@
+use super::ParsedResult;
+
+/**
+ * A geographic location, as in a "geo:" URI (RFC 5870).
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoParsedResult {
+    latitude: f64,
+    longitude: f64,
+    altitude: f64,
+    query: Option<String>,
+}
+
+impl GeoParsedResult {
+    pub fn new(
+        latitude: f64,
+        longitude: f64,
+        altitude: f64,
+        query: Option<String>,
+    ) -> GeoParsedResult {
+        GeoParsedResult {
+            latitude,
+            longitude,
+            altitude,
+            query,
+        }
+    }
+
+    /**
+     * @return the location as a "geo:" URI
+     */
+    pub fn get_geo_uri(&self) -> String {
+        let mut result = format!("geo:{},{}", self.latitude, self.longitude);
+        if self.altitude > 0.0 {
+            result.push_str(&format!(",{}", self.altitude));
+        }
+        if let Some(query) = &self.query {
+            result.push('?');
+            result.push_str(query);
+        }
+        result
+    }
+
+    /**
+     * @return latitude in degrees
+     */
+    pub fn get_latitude(&self) -> f64 {
+        self.latitude
+    }
+
+    /**
+     * @return longitude in degrees
+     */
+    pub fn get_longitude(&self) -> f64 {
+        self.longitude
+    }
+
+    /**
+     * @return altitude in meters. If not specified, in the geo URI, returns 0.0
+     */
+    pub fn get_altitude(&self) -> f64 {
+        self.altitude
+    }
+
+    /**
+     * @return query string associated with geo URI or None if none exists
+     */
+    pub fn get_query(&self) -> Option<&str> {
+        self.query.as_deref()
+    }
+
+    pub fn get_display_result(&self) -> String {
+        let mut result = format!("{}, {}", self.latitude, self.longitude);
+        if self.altitude > 0.0 {
+            result.push_str(&format!(", {}m", self.altitude));
+        }
+        if let Some(query) = &self.query {
+            result.push_str(&format!(" ({})", query));
+        }
+        let mut display = String::with_capacity(result.len());
+        ParsedResult::maybe_append(Some(&result), &mut display);
+        display
+    }
+}
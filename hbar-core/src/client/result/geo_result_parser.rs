@@ -0,0 +1,57 @@
+use crate::BarcodeFormat;
+
+use super::result_parser::ResultParser;
+use super::{GeoParsedResult, ParsedResult};
+
+/**
+ * Parses a "geo:" URI result, which specifies a location on the surface of
+ * the Earth as well as an optional altitude above the surface. See
+ * <a href="http://tools.ietf.org/html/draft-mayrhofer-geo-uri-00">
+ * http://tools.ietf.org/html/draft-mayrhofer-geo-uri-00</a>.
+ */
+pub struct GeoResultParser;
+
+impl ResultParser for GeoResultParser {
+    fn parse(&self, text: &str, _format: &BarcodeFormat) -> Option<ParsedResult> {
+        if !text.get(..4)?.eq_ignore_ascii_case("geo:") {
+            return None;
+        }
+        let (coordinates, query) = match text[4..].find('?') {
+            Some(query_start) => (
+                &text[4..4 + query_start],
+                Some(text[5 + query_start..].to_string()),
+            ),
+            None => (&text[4..], None),
+        };
+        let parts: Vec<&str> = coordinates.split(',').collect();
+        if parts.len() < 2
+            || parts.len() > 3
+            || parts.iter().any(|part| {
+                part.is_empty()
+                    || !part
+                        .chars()
+                        .all(|c| c == '-' || c == '.' || c.is_ascii_digit())
+            })
+        {
+            return None;
+        }
+        let latitude = parts[0].parse::<f64>().ok()?;
+        if !(-90.0..=90.0).contains(&latitude) {
+            return None;
+        }
+        let longitude = parts[1].parse::<f64>().ok()?;
+        if !(-180.0..=180.0).contains(&longitude) {
+            return None;
+        }
+        let altitude = match parts.get(2) {
+            Some(altitude) => altitude.parse::<f64>().ok()?,
+            None => 0.0,
+        };
+        if altitude < 0.0 {
+            return None;
+        }
+        Some(ParsedResult::Geo(GeoParsedResult::new(
+            latitude, longitude, altitude, query,
+        )))
+    }
+}
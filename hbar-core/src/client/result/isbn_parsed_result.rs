@@ -0,0 +1,21 @@
+/**
+ * An ISBN, the number of a book, as carried by EAN-13 "Bookland" barcodes.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct IsbnParsedResult {
+    isbn: String,
+}
+
+impl IsbnParsedResult {
+    pub fn new(isbn: String) -> IsbnParsedResult {
+        IsbnParsedResult { isbn }
+    }
+
+    pub fn get_isbn(&self) -> &str {
+        &self.isbn
+    }
+
+    pub fn get_display_result(&self) -> String {
+        self.isbn.clone()
+    }
+}
@@ -0,0 +1,27 @@
+use crate::BarcodeFormat;
+
+use super::result_parser::ResultParser;
+use super::{IsbnParsedResult, ParsedResult};
+
+/**
+ * Parses strings of digits that represent a ISBN.
+ */
+pub struct IsbnResultParser;
+
+impl ResultParser for IsbnResultParser {
+    /**
+     * See <a href="http://www.bisg.org/isbn-13/for.dummies.html">ISBN-13 For Dummies</a>
+     */
+    fn parse(&self, text: &str, format: &BarcodeFormat) -> Option<ParsedResult> {
+        if *format != BarcodeFormat::Ean13 {
+            return None;
+        }
+        if text.len() != 13 {
+            return None;
+        }
+        if !text.starts_with("978") && !text.starts_with("979") {
+            return None;
+        }
+        Some(ParsedResult::Isbn(IsbnParsedResult::new(text.to_string())))
+    }
+}
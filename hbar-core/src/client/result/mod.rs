@@ -0,0 +1,67 @@
+mod address_book_do_co_mo_result_parser;
+mod address_book_parsed_result;
+mod bizcard_result_parser;
+mod bookmark_do_co_mo_result_parser;
+mod calendar_parsed_result;
+mod email_address_parsed_result;
+mod email_address_result_parser;
+mod email_do_co_mo_result_parser;
+mod emvco_result_parser;
+mod geo_parsed_result;
+mod geo_result_parser;
+mod isbn_parsed_result;
+mod isbn_result_parser;
+mod parsed_result;
+mod product_parsed_result;
+mod product_result_parser;
+mod result_parser;
+mod sms_mms_result_parser;
+mod sms_parsed_result;
+mod smsto_mmsto_result_parser;
+mod smtp_result_parser;
+mod tel_parsed_result;
+mod tel_result_parser;
+mod text_parsed_result;
+mod uri_parsed_result;
+mod uri_result_parser;
+mod urlto_result_parser;
+mod vcard_result_parser;
+mod vevent_result_parser;
+mod vin_parsed_result;
+mod vin_result_parser;
+mod wifi_parsed_result;
+mod wifi_result_parser;
+
+pub use address_book_do_co_mo_result_parser::AddressBookDoCoMoResultParser;
+pub use address_book_parsed_result::AddressBookParsedResult;
+pub use bizcard_result_parser::BizcardResultParser;
+pub use bookmark_do_co_mo_result_parser::BookmarkDoCoMoResultParser;
+pub use calendar_parsed_result::CalendarParsedResult;
+pub use email_address_parsed_result::EmailAddressParsedResult;
+pub use email_address_result_parser::EmailAddressResultParser;
+pub use email_do_co_mo_result_parser::EmailDoCoMoResultParser;
+pub use emvco_result_parser::EmvcoResultParser;
+pub use geo_parsed_result::GeoParsedResult;
+pub use geo_result_parser::GeoResultParser;
+pub use isbn_parsed_result::IsbnParsedResult;
+pub use isbn_result_parser::IsbnResultParser;
+pub use parsed_result::ParsedResult;
+pub use product_parsed_result::ProductParsedResult;
+pub use product_result_parser::ProductResultParser;
+pub use result_parser::ResultParser;
+pub use sms_mms_result_parser::SmsMmsResultParser;
+pub use sms_parsed_result::SmsParsedResult;
+pub use smsto_mmsto_result_parser::SmstoMmstoResultParser;
+pub use smtp_result_parser::SmtpResultParser;
+pub use tel_parsed_result::TelParsedResult;
+pub use tel_result_parser::TelResultParser;
+pub use text_parsed_result::TextParsedResult;
+pub use uri_parsed_result::UriParsedResult;
+pub use uri_result_parser::UriResultParser;
+pub use urlto_result_parser::UrltoResultParser;
+pub use vcard_result_parser::VCardResultParser;
+pub use vevent_result_parser::VEventResultParser;
+pub use vin_parsed_result::VinParsedResult;
+pub use vin_result_parser::VinResultParser;
+pub use wifi_parsed_result::WifiParsedResult;
+pub use wifi_result_parser::WifiResultParser;
@@ -0,0 +1,368 @@
+use crate::client::emvco::EmvcoPayload;
+use crate::BarcodeFormat;
+#[cfg(feature = "decode")]
+use crate::Results;
+
+use super::result_parser::{get_massaged_text, ResultParser};
+use super::{
+    AddressBookDoCoMoResultParser, BizcardResultParser, BookmarkDoCoMoResultParser,
+    EmailAddressResultParser, EmailDoCoMoResultParser, EmvcoResultParser, GeoResultParser,
+    IsbnResultParser, ProductResultParser, SmsMmsResultParser, SmstoMmstoResultParser,
+    SmtpResultParser, TelResultParser, UriResultParser, UrltoResultParser, VCardResultParser,
+    VEventResultParser, VinResultParser, WifiResultParser,
+};
+use super::{
+    AddressBookParsedResult, CalendarParsedResult, EmailAddressParsedResult, GeoParsedResult,
+    IsbnParsedResult, ProductParsedResult, SmsParsedResult, TelParsedResult, TextParsedResult,
+    UriParsedResult, VinParsedResult, WifiParsedResult,
+};
+
+use std::fmt;
+
+// The parsers, in the order they are tried. The more specific ones come first, and text which
+// none of them understand is plain text.
+const PARSERS: [&(dyn ResultParser + Sync); 19] = [
+    &EmvcoResultParser,
+    &BookmarkDoCoMoResultParser,
+    &AddressBookDoCoMoResultParser,
+    &EmailDoCoMoResultParser,
+    &VCardResultParser,
+    &BizcardResultParser,
+    &VEventResultParser,
+    &EmailAddressResultParser,
+    &SmtpResultParser,
+    &TelResultParser,
+    &SmsMmsResultParser,
+    &SmstoMmstoResultParser,
+    &GeoResultParser,
+    &WifiResultParser,
+    &UrltoResultParser,
+    &UriResultParser,
+    &IsbnResultParser,
+    &ProductResultParser,
+    &VinResultParser,
+];
+
+/**
+ * <p>The result of decoding a barcode, as more than a String -- as some type of structured data,
+ * like a URL or an e-mail address.</p>
+ *
+ * <p>{@link #parse_result} turns a decoded {@link Results} into the most appropriate kind.</p>
+ */
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParsedResult {
+    AddressBook(AddressBookParsedResult),
+    EmailAddress(EmailAddressParsedResult),
+    Product(ProductParsedResult),
+    Uri(UriParsedResult),
+    Text(TextParsedResult),
+    Geo(GeoParsedResult),
+    Tel(TelParsedResult),
+    Sms(SmsParsedResult),
+    Calendar(CalendarParsedResult),
+    Wifi(WifiParsedResult),
+    Isbn(IsbnParsedResult),
+    Vin(VinParsedResult),
+    Emvco(EmvcoPayload),
+}
+
+impl ParsedResult {
+    /**
+     * Parses the text of a decoded barcode into the most appropriate kind of result.
+     */
+    #[cfg(feature = "decode")]
+    pub fn parse_result(result: &Results) -> ParsedResult {
+        ParsedResult::parse_text(result.get_text(), result.get_barcode_format())
+    }
+
+    /**
+     * Parses barcode text into the most appropriate kind of result, or {@link TextParsedResult}
+     * if nothing more specific fits.
+     *
+     * @param text the text of the barcode
+     * @param format the format it was decoded as, which products, ISBNs and VINs depend on
+     */
+    pub fn parse_text(text: &str, format: &BarcodeFormat) -> ParsedResult {
+        let text = get_massaged_text(text);
+        PARSERS
+            .iter()
+            .find_map(|parser| parser.parse(text, format))
+            .unwrap_or_else(|| ParsedResult::Text(TextParsedResult::new(text.to_string(), None)))
+    }
+
+    /**
+     * @return a human-readable representation of the result, one field per line
+     */
+    pub fn get_display_result(&self) -> String {
+        match self {
+            ParsedResult::AddressBook(result) => result.get_display_result(),
+            ParsedResult::EmailAddress(result) => result.get_display_result(),
+            ParsedResult::Product(result) => result.get_display_result(),
+            ParsedResult::Uri(result) => result.get_display_result(),
+            ParsedResult::Text(result) => result.get_display_result(),
+            ParsedResult::Geo(result) => result.get_display_result(),
+            ParsedResult::Tel(result) => result.get_display_result(),
+            ParsedResult::Sms(result) => result.get_display_result(),
+            ParsedResult::Calendar(result) => result.get_display_result(),
+            ParsedResult::Wifi(result) => result.get_display_result(),
+            ParsedResult::Isbn(result) => result.get_display_result(),
+            ParsedResult::Vin(result) => result.get_display_result(),
+            ParsedResult::Emvco(result) => result.get_display_result(),
+        }
+    }
+
+    pub(crate) fn maybe_append(value: Option<&str>, result: &mut String) {
+        if let Some(value) = value {
+            if !value.is_empty() {
+                // Don't add a newline before the first value
+                if !result.is_empty() {
+                    result.push('\n');
+                }
+                result.push_str(value);
+            }
+        }
+    }
+
+    pub(crate) fn maybe_append_all(values: &[String], result: &mut String) {
+        for value in values {
+            ParsedResult::maybe_append(Some(value), result);
+        }
+    }
+}
+
+impl fmt::Display for ParsedResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.get_display_result())
+    }
+}
+
+#[cfg(test)]
+mod parsed_result_tests {
+    use super::ParsedResult;
+    use crate::BarcodeFormat;
+
+    fn parse(text: &str) -> ParsedResult {
+        ParsedResult::parse_text(text, &BarcodeFormat::QRCode)
+    }
+
+    #[test]
+    fn parses_uris_and_plain_text() {
+        match parse("google.com/search?q=x") {
+            ParsedResult::Uri(uri) => assert_eq!(uri.get_uri(), "http://google.com/search?q=x"),
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse("MEBKM:TITLE:Google;URL:https://www.google.com;;") {
+            ParsedResult::Uri(uri) => {
+                assert_eq!(uri.get_uri(), "https://www.google.com");
+                assert_eq!(uri.get_title(), Some("Google"));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse("\u{feff}This is just text") {
+            ParsedResult::Text(text) => assert_eq!(text.get_text(), "This is just text"),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_wifi() {
+        match parse(r"WIFI:T:WPA;S:my\;net;P:pa\:ss;H:true;;") {
+            ParsedResult::Wifi(wifi) => {
+                assert_eq!(wifi.get_network_encryption(), "WPA");
+                assert_eq!(wifi.get_ssid(), "my;net");
+                assert_eq!(wifi.get_password(), Some("pa:ss"));
+                assert!(wifi.is_hidden());
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_contacts() {
+        let vcard = "BEGIN:VCARD\r\nN:Owen;Sean\r\nTEL;TYPE=CELL:+12125551212\r\n\
+                     EMAIL:srowen@example.org\r\nADR;HOME:;;123 Main St;Anytown;CA;95014\r\n\
+                     BDAY:1976-05-20\r\nGEO:37.1;-122.2\r\nNOTE:first\\nsec\r\n ond\r\nEND:VCARD";
+        match parse(vcard) {
+            ParsedResult::AddressBook(contact) => {
+                assert_eq!(contact.get_names(), ["Sean Owen"]);
+                assert_eq!(contact.get_phone_numbers(), ["+12125551212"]);
+                assert_eq!(contact.get_phone_types(), [Some("CELL".to_string())]);
+                assert_eq!(contact.get_emails(), ["srowen@example.org"]);
+                assert_eq!(contact.get_addresses(), ["123 Main St\nAnytown\nCA\n95014"]);
+                assert_eq!(contact.get_address_types(), [Some("HOME".to_string())]);
+                assert_eq!(contact.get_birthday(), Some("1976-05-20"));
+                assert_eq!(contact.get_geo(), ["37.1", "-122.2"]);
+                assert_eq!(contact.get_note(), Some("first\nsecond"));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        let quoted_printable = "BEGIN:VCARD\nFN;CHARSET=UTF-8;ENCODING=QUOTED-PRINTABLE:\
+                                =E6=97=A5=E6=9C=AC\nEND:VCARD";
+        match parse(quoted_printable) {
+            ParsedResult::AddressBook(contact) => assert_eq!(contact.get_names(), ["日本"]),
+            other => panic!("unexpected {:?}", other),
+        }
+        // A broken escape before a multibyte character is dropped, not split
+        match parse("BEGIN:VCARD\nFN;ENCODING=QUOTED-PRINTABLE:=E\u{e9}\nEND:VCARD") {
+            ParsedResult::AddressBook(contact) => assert_eq!(contact.get_names(), ["\u{e9}"]),
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse("MECARD:N:Owen,Sean;TEL:+12125551212;TEL:+12125551213;BDAY:19760520;;") {
+            ParsedResult::AddressBook(contact) => {
+                assert_eq!(contact.get_names(), ["Sean Owen"]);
+                assert_eq!(
+                    contact.get_phone_numbers(),
+                    ["+12125551212", "+12125551213"]
+                );
+                assert_eq!(contact.get_birthday(), Some("19760520"));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse("BIZCARD:N:Sean;X:Owen;C:Google;A:123 Main St;M:+12345678900;;") {
+            ParsedResult::AddressBook(contact) => {
+                assert_eq!(contact.get_names(), ["Sean Owen"]);
+                assert_eq!(contact.get_org(), Some("Google"));
+                assert_eq!(contact.get_addresses(), ["123 Main St"]);
+                assert_eq!(contact.get_phone_numbers(), ["+12345678900"]);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_geo_and_tel() {
+        match parse("geo:1,2.5,3?q=Zurich") {
+            ParsedResult::Geo(geo) => {
+                assert_eq!(geo.get_latitude(), 1.0);
+                assert_eq!(geo.get_longitude(), 2.5);
+                assert_eq!(geo.get_altitude(), 3.0);
+                assert_eq!(geo.get_query(), Some("q=Zurich"));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(matches!(parse("geo:91,0"), ParsedResult::Uri(_)));
+        match parse("tel:+15551212") {
+            ParsedResult::Tel(tel) => {
+                assert_eq!(tel.get_number(), "+15551212");
+                assert_eq!(tel.get_tel_uri(), "tel:+15551212");
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_messages() {
+        match parse("mailto:srowen@example.org?subject=Hi%20there&body=Stuff&cc=a@example.org") {
+            ParsedResult::EmailAddress(email) => {
+                assert_eq!(email.get_tos(), ["srowen@example.org"]);
+                assert_eq!(email.get_ccs(), ["a@example.org"]);
+                assert_eq!(email.get_subject(), Some("Hi there"));
+                assert_eq!(email.get_body(), Some("Stuff"));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse("MATMSG:TO:srowen@example.org;SUB:Stuff;BODY:This is some text;;") {
+            ParsedResult::EmailAddress(email) => {
+                assert_eq!(email.get_tos(), ["srowen@example.org"]);
+                assert_eq!(email.get_subject(), Some("Stuff"));
+                assert_eq!(email.get_body(), Some("This is some text"));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse("smtp:srowen@example.org:foo:bar") {
+            ParsedResult::EmailAddress(email) => {
+                assert_eq!(email.get_subject(), Some("foo"));
+                assert_eq!(email.get_body(), Some("bar"));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse("sms:+15551212,+15551213;via=x?subject=Hi&body=There") {
+            ParsedResult::Sms(sms) => {
+                assert_eq!(sms.get_numbers(), ["+15551212", "+15551213"]);
+                assert_eq!(sms.get_vias(), [None, Some("x".to_string())]);
+                assert_eq!(sms.get_subject(), Some("Hi"));
+                assert_eq!(sms.get_body(), Some("There"));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse("MMSTO:+15551212:Hello") {
+            ParsedResult::Sms(sms) => {
+                assert_eq!(sms.get_numbers(), ["+15551212"]);
+                assert_eq!(sms.get_body(), Some("Hello"));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_calendar_events() {
+        let event = "BEGIN:VEVENT\r\nSUMMARY:foo\r\nDTSTART:20080504T123456Z\r\n\
+                     DTEND:20080505T234555Z\r\nLOCATION:Miami\r\n\
+                     ORGANIZER:mailto:bob@example.org\r\nATTENDEE:MAILTO:al@example.org\r\n\
+                     GEO:-12.345;-45.678\r\nEND:VEVENT";
+        match parse(event) {
+            ParsedResult::Calendar(calendar) => {
+                assert_eq!(calendar.get_summary(), Some("foo"));
+                assert_eq!(calendar.get_start_timestamp(), 1_209_904_496_000);
+                assert!(!calendar.is_start_all_day());
+                assert_eq!(calendar.get_end_timestamp(), 1_210_031_155_000);
+                assert_eq!(calendar.get_location(), Some("Miami"));
+                assert_eq!(calendar.get_organizer(), Some("bob@example.org"));
+                assert_eq!(calendar.get_attendees(), ["al@example.org"]);
+                assert_eq!(calendar.get_latitude(), -12.345);
+                assert_eq!(calendar.get_longitude(), -45.678);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse("BEGIN:VEVENT\nDTSTART:20080504\nDURATION:P1DT2H\nEND:VEVENT") {
+            ParsedResult::Calendar(calendar) => {
+                assert!(calendar.is_start_all_day());
+                assert_eq!(calendar.get_start_timestamp(), 1_209_859_200_000);
+                assert_eq!(
+                    calendar.get_end_timestamp(),
+                    1_209_859_200_000 + 26 * 60 * 60 * 1000
+                );
+                assert!(calendar.get_latitude().is_nan());
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse("BEGIN:VEVENT\nDTSTART:20080504\nDURATION:P99999999999999999W\nEND:VEVENT") {
+            ParsedResult::Calendar(calendar) => assert_eq!(calendar.get_end_timestamp(), -1),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(matches!(
+            parse("BEGIN:VEVENT\nDTSTART:2008\nEND:VEVENT"),
+            ParsedResult::Text(_)
+        ));
+    }
+
+    #[test]
+    fn parses_products_by_format() {
+        match ParsedResult::parse_text("01234565", &BarcodeFormat::UpcE) {
+            ParsedResult::Product(product) => {
+                assert_eq!(product.get_product_id(), "01234565");
+                assert_eq!(product.get_normalized_product_id(), "012345000065");
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match ParsedResult::parse_text("9780201310054", &BarcodeFormat::Ean13) {
+            ParsedResult::Isbn(isbn) => assert_eq!(isbn.get_isbn(), "9780201310054"),
+            other => panic!("unexpected {:?}", other),
+        }
+        match ParsedResult::parse_text("1M8GDM9AXKP042788", &BarcodeFormat::Code39) {
+            ParsedResult::Vin(vin) => {
+                assert_eq!(vin.get_world_manufacturer_id(), "1M8");
+                assert_eq!(vin.get_vehicle_descriptor_section(), "GDM9AX");
+                assert_eq!(vin.get_vehicle_identifier_section(), "KP042788");
+                assert_eq!(vin.get_country_code(), Some("US"));
+                assert_eq!(vin.get_model_year(), 1989);
+                assert_eq!(vin.get_plant_code(), 'P');
+                assert_eq!(vin.get_sequential_number(), "042788");
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        // The same digits in a QR Code are just text
+        assert!(matches!(parse("9780201310054"), ParsedResult::Text(_)));
+    }
+}
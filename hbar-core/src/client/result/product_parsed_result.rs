@@ -0,0 +1,32 @@
+/**
+ * A product, identified by its UPC or EAN.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProductParsedResult {
+    product_id: String,
+    normalized_product_id: String,
+}
+
+impl ProductParsedResult {
+    pub fn new(product_id: String, normalized_product_id: String) -> ProductParsedResult {
+        ProductParsedResult {
+            product_id,
+            normalized_product_id,
+        }
+    }
+
+    pub fn get_product_id(&self) -> &str {
+        &self.product_id
+    }
+
+    /**
+     * @return the product ID, with UPC-E expanded to UPC-A
+     */
+    pub fn get_normalized_product_id(&self) -> &str {
+        &self.normalized_product_id
+    }
+
+    pub fn get_display_result(&self) -> String {
+        self.product_id.clone()
+    }
+}
@@ -0,0 +1,79 @@
+use crate::BarcodeFormat;
+
+use super::result_parser::{is_string_of_digits, ResultParser};
+use super::{ParsedResult, ProductParsedResult};
+
+/**
+ * Parses strings of digits that represent a UPC.
+ */
+pub struct ProductResultParser;
+
+impl ResultParser for ProductResultParser {
+    // Treat all UPC and EAN variants as UPCs, in the sense that they are all product barcodes.
+    fn parse(&self, text: &str, format: &BarcodeFormat) -> Option<ParsedResult> {
+        if !matches!(
+            format,
+            BarcodeFormat::UpcA | BarcodeFormat::UpcE | BarcodeFormat::Ean8 | BarcodeFormat::Ean13
+        ) {
+            return None;
+        }
+        if !is_string_of_digits(Some(text), text.len()) {
+            return None;
+        }
+        // Not actually checking the checksum again here
+
+        let normalized_product_id = if *format == BarcodeFormat::UpcE && text.len() == 8 {
+            ProductResultParser::convert_upce_to_upca(text)
+        } else {
+            text.to_string()
+        };
+
+        Some(ParsedResult::Product(ProductParsedResult::new(
+            text.to_string(),
+            normalized_product_id,
+        )))
+    }
+}
+
+impl ProductResultParser {
+    /**
+     * Expands a UPC-E value back into its full, equivalent UPC-A code value.
+     *
+     * @param upce UPC-E code as string of digits
+     * @return equivalent UPC-A code as string of digits
+     */
+    pub fn convert_upce_to_upca(upce: &str) -> String {
+        let upce_chars = &upce[1..7];
+        let mut result = String::with_capacity(12);
+        result.push_str(&upce[..1]);
+        let last_char = upce_chars.as_bytes()[5];
+        match last_char {
+            b'0' | b'1' | b'2' => {
+                result.push_str(&upce_chars[..2]);
+                result.push(last_char as char);
+                result.push_str("0000");
+                result.push_str(&upce_chars[2..5]);
+            }
+            b'3' => {
+                result.push_str(&upce_chars[..3]);
+                result.push_str("00000");
+                result.push_str(&upce_chars[3..5]);
+            }
+            b'4' => {
+                result.push_str(&upce_chars[..4]);
+                result.push_str("00000");
+                result.push_str(&upce_chars[4..5]);
+            }
+            _ => {
+                result.push_str(&upce_chars[..5]);
+                result.push_str("0000");
+                result.push(last_char as char);
+            }
+        }
+        // Only append check digit in conversion if supplied
+        if upce.len() >= 8 {
+            result.push_str(&upce[7..8]);
+        }
+        result
+    }
+}
@@ -0,0 +1,202 @@
+use crate::BarcodeFormat;
+
+use super::ParsedResult;
+
+use std::collections::HashMap;
+
+/**
+ * <p>Turns the raw text of a barcode into one kind of structured data, like a URL or an
+ * e-mail address. {@link ParsedResult#parse_result} tries each parser in turn and picks the
+ * most appropriate type of structured representation.</p>
+ *
+ * <p>Thanks to Jeff Griffin for proposing rewrite of these classes that relies less
+ * on exception-based mechanisms during parsing.</p>
+ */
+pub trait ResultParser {
+    /**
+     * Attempts to parse the raw text of a barcode into a more useful form.
+     *
+     * @param text raw text of the barcode, with any leading byte order mark removed
+     * @param format the format the barcode was decoded as
+     * @return the parsed result, or None if the text is not of the type this parser handles
+     */
+    fn parse(&self, text: &str, format: &BarcodeFormat) -> Option<ParsedResult>;
+}
+
+/**
+ * Strips the byte order mark some encoders put in front of the text.
+ */
+pub(crate) fn get_massaged_text(text: &str) -> &str {
+    text.strip_prefix('\u{feff}').unwrap_or(text)
+}
+
+pub(crate) fn unescape_backslash(escaped: &str) -> String {
+    if !escaped.contains('\\') {
+        return escaped.to_string();
+    }
+    let mut unescaped = String::with_capacity(escaped.len());
+    let mut next_is_escaped = false;
+    for c in escaped.chars() {
+        if next_is_escaped || c != '\\' {
+            unescaped.push(c);
+            next_is_escaped = false;
+        } else {
+            next_is_escaped = true;
+        }
+    }
+    unescaped
+}
+
+pub(crate) fn parse_hex_digit(c: char) -> Option<u8> {
+    c.to_digit(16).map(|digit| digit as u8)
+}
+
+pub(crate) fn is_string_of_digits(value: Option<&str>, length: usize) -> bool {
+    match value {
+        Some(value) => length > 0 && value.len() == length && is_digits(value),
+        None => false,
+    }
+}
+
+pub(crate) fn is_substring_of_digits(value: &str, offset: usize, length: usize) -> bool {
+    length > 0 && value.get(offset..offset + length).is_some_and(is_digits)
+}
+
+fn is_digits(value: &str) -> bool {
+    value.bytes().all(|b| b.is_ascii_digit())
+}
+
+/**
+ * Parses the query of a URI, the part after '?', into its decoded names and values.
+ *
+ * @return None if there is no query, or it is not properly encoded
+ */
+pub(crate) fn parse_name_value_pairs(uri: &str) -> Option<HashMap<String, String>> {
+    let param_start = uri.find('?')?;
+    let mut result = HashMap::with_capacity(3);
+    for key_value in uri[param_start + 1..].split('&') {
+        if let Some(equals) = key_value.find('=') {
+            let key = &key_value[..equals];
+            let value = url_decode(&key_value[equals + 1..])?;
+            result.insert(key.to_string(), value);
+        }
+    }
+    Some(result)
+}
+
+/**
+ * Decodes "application/x-www-form-urlencoded" text, as UTF-8.
+ *
+ * @return None if a percent escape is malformed
+ */
+pub(crate) fn url_decode(encoded: &str) -> Option<String> {
+    if !encoded.contains(['%', '+']) {
+        return Some(encoded.to_string());
+    }
+    let mut bytes = Vec::with_capacity(encoded.len());
+    let mut chars = encoded.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '+' => bytes.push(b' '),
+            '%' => {
+                let high = parse_hex_digit(chars.next()?)?;
+                let low = parse_hex_digit(chars.next()?)?;
+                bytes.push((high << 4) | low);
+            }
+            _ => {
+                let mut buffer = [0u8; 4];
+                bytes.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+            }
+        }
+    }
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+fn count_preceding_backslashes(s: &str, pos: usize) -> usize {
+    s.as_bytes()[..pos]
+        .iter()
+        .rev()
+        .take_while(|&&b| b == b'\\')
+        .count()
+}
+
+/**
+ * Finds every value following {@code prefix} up to an unescaped {@code end_char}, or the end of
+ * the text, and unescapes it. Empty values are dropped.
+ */
+pub(crate) fn match_prefixed_field(
+    prefix: &str,
+    raw_text: &str,
+    end_char: char,
+    trim: bool,
+) -> Option<Vec<String>> {
+    let mut matches = Vec::new();
+    let mut i = 0;
+    let max = raw_text.len();
+    while i < max {
+        i = match raw_text[i..].find(prefix) {
+            Some(found) => i + found + prefix.len(),
+            None => break,
+        };
+        // Found the start of a match here
+        let start = i;
+        loop {
+            match raw_text[i..].find(end_char) {
+                None => {
+                    // No terminating end character? uh, done. Set i such that loop terminates
+                    i = max;
+                    break;
+                }
+                Some(found) => {
+                    i += found;
+                    if !count_preceding_backslashes(raw_text, i).is_multiple_of(2) {
+                        // end character was escaped (odd count of preceding backslashes)
+                        i += end_char.len_utf8();
+                        continue;
+                    }
+                    let element = unescape_backslash(&raw_text[start..i]);
+                    let element = if trim {
+                        element.trim().to_string()
+                    } else {
+                        element
+                    };
+                    if !element.is_empty() {
+                        matches.push(element);
+                    }
+                    i += end_char.len_utf8();
+                    break;
+                }
+            }
+        }
+    }
+    if matches.is_empty() {
+        None
+    } else {
+        Some(matches)
+    }
+}
+
+pub(crate) fn match_single_prefixed_field(
+    prefix: &str,
+    raw_text: &str,
+    end_char: char,
+    trim: bool,
+) -> Option<String> {
+    match_prefixed_field(prefix, raw_text, end_char, trim).map(|mut matches| matches.remove(0))
+}
+
+/**
+ * Matches a field of a NTT DoCoMo-style "MECARD:N:Owen,Sean;..." text, whose fields are
+ * ended by semicolons.
+ */
+pub(crate) fn match_do_co_mo_prefixed_field(prefix: &str, raw_text: &str) -> Option<Vec<String>> {
+    match_prefixed_field(prefix, raw_text, ';', true)
+}
+
+pub(crate) fn match_single_do_co_mo_prefixed_field(
+    prefix: &str,
+    raw_text: &str,
+    trim: bool,
+) -> Option<String> {
+    match_single_prefixed_field(prefix, raw_text, ';', trim)
+}
@@ -0,0 +1,71 @@
+use crate::BarcodeFormat;
+
+use super::result_parser::{parse_name_value_pairs, ResultParser};
+use super::{ParsedResult, SmsParsedResult};
+
+/**
+ * <p>Parses an "sms:" URI result, which specifies a number to SMS.
+ * See <a href="http://tools.ietf.org/html/rfc5724"> RFC 5724</a> on this.</p>
+ *
+ * <p>This class supports "via" syntax for numbers, which is not part of the spec.
+ * For example "+12125551212;via=+12124440101" may appear as a number.
+ * It also supports a "subject" query parameter, which is not mentioned in the spec.
+ * These are included since they were mentioned in earlier IETF drafts and might be
+ * used.</p>
+ *
+ * <p>This actually also parses URIs starting with "mms:" and treats them all the same way,
+ * and effectively converts them to an "sms:" URI for purposes of forwarding to the platform.</p>
+ */
+pub struct SmsMmsResultParser;
+
+impl ResultParser for SmsMmsResultParser {
+    fn parse(&self, text: &str, _format: &BarcodeFormat) -> Option<ParsedResult> {
+        if !["sms:", "SMS:", "mms:", "MMS:"]
+            .iter()
+            .any(|prefix| text.starts_with(prefix))
+        {
+            return None;
+        }
+
+        // Check up front if this is a URI syntax string with query arguments
+        let mut subject = None;
+        let mut body = None;
+        let mut query_syntax = false;
+        if let Some(mut name_value_pairs) = parse_name_value_pairs(text) {
+            if !name_value_pairs.is_empty() {
+                subject = name_value_pairs.remove("subject");
+                body = name_value_pairs.remove("body");
+                query_syntax = true;
+            }
+        }
+
+        // Drop sms, query portion
+        let sms_uri_without_query = match text[4..].find('?') {
+            Some(query_start) if query_syntax => &text[4..4 + query_start],
+            _ => &text[4..],
+        };
+
+        let mut numbers = Vec::with_capacity(1);
+        let mut vias = Vec::with_capacity(1);
+        for number_part in sms_uri_without_query.split(',') {
+            match number_part.find(';') {
+                None => {
+                    numbers.push(number_part.to_string());
+                    vias.push(None);
+                }
+                Some(number_end) => {
+                    numbers.push(number_part[..number_end].to_string());
+                    vias.push(
+                        number_part[number_end + 1..]
+                            .strip_prefix("via=")
+                            .map(String::from),
+                    );
+                }
+            }
+        }
+
+        Some(ParsedResult::Sms(SmsParsedResult::new(
+            numbers, vias, subject, body,
+        )))
+    }
+}
@@ -0,0 +1,86 @@
+use super::ParsedResult;
+
+/**
+ * An SMS or MMS message: its recipients, subject and body.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct SmsParsedResult {
+    numbers: Vec<String>,
+    vias: Vec<Option<String>>,
+    subject: Option<String>,
+    body: Option<String>,
+}
+
+impl SmsParsedResult {
+    /**
+     * @param numbers the recipients
+     * @param vias the gateway to send through for each recipient, if any
+     */
+    pub fn new(
+        numbers: Vec<String>,
+        vias: Vec<Option<String>>,
+        subject: Option<String>,
+        body: Option<String>,
+    ) -> SmsParsedResult {
+        SmsParsedResult {
+            numbers,
+            vias,
+            subject,
+            body,
+        }
+    }
+
+    /**
+     * @return the message as an "sms:" URI
+     */
+    pub fn get_sms_uri(&self) -> String {
+        let mut result = String::from("sms:");
+        for (i, number) in self.numbers.iter().enumerate() {
+            if i > 0 {
+                result.push(',');
+            }
+            result.push_str(number);
+            if let Some(Some(via)) = self.vias.get(i) {
+                result.push_str(";via=");
+                result.push_str(via);
+            }
+        }
+        let mut separator = '?';
+        if let Some(body) = &self.body {
+            result.push(separator);
+            result.push_str("body=");
+            result.push_str(body);
+            separator = '&';
+        }
+        if let Some(subject) = &self.subject {
+            result.push(separator);
+            result.push_str("subject=");
+            result.push_str(subject);
+        }
+        result
+    }
+
+    pub fn get_numbers(&self) -> &[String] {
+        &self.numbers
+    }
+
+    pub fn get_vias(&self) -> &[Option<String>] {
+        &self.vias
+    }
+
+    pub fn get_subject(&self) -> Option<&str> {
+        self.subject.as_deref()
+    }
+
+    pub fn get_body(&self) -> Option<&str> {
+        self.body.as_deref()
+    }
+
+    pub fn get_display_result(&self) -> String {
+        let mut result = String::with_capacity(100);
+        ParsedResult::maybe_append_all(&self.numbers, &mut result);
+        ParsedResult::maybe_append(self.subject.as_deref(), &mut result);
+        ParsedResult::maybe_append(self.body.as_deref(), &mut result);
+        result
+    }
+}
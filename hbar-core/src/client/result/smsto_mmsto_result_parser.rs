@@ -0,0 +1,39 @@
+use crate::BarcodeFormat;
+
+use super::result_parser::ResultParser;
+use super::{ParsedResult, SmsParsedResult};
+
+/**
+ * <p>Parses an "smsto:" URI result, whose format is not standardized but appears to be like:
+ * {@code smsto:number(:body)}.</p>
+ *
+ * <p>This actually also parses URIs starting with "smsto:", "mmsto:", "SMSTO:", and
+ * "MMSTO:", and treats them all the same way, and effectively converts them to an "sms:" URI
+ * for purposes of forwarding to the platform.</p>
+ */
+pub struct SmstoMmstoResultParser;
+
+impl ResultParser for SmstoMmstoResultParser {
+    fn parse(&self, text: &str, _format: &BarcodeFormat) -> Option<ParsedResult> {
+        if !["smsto:", "SMSTO:", "mmsto:", "MMSTO:"]
+            .iter()
+            .any(|prefix| text.starts_with(prefix))
+        {
+            return None;
+        }
+        // Thanks to dominik.wild for suggesting this enhancement to support
+        // smsto:number:body URIs
+        let mut number = &text[6..];
+        let mut body = None;
+        if let Some(body_start) = number.find(':') {
+            body = Some(number[body_start + 1..].to_string());
+            number = &number[..body_start];
+        }
+        Some(ParsedResult::Sms(SmsParsedResult::new(
+            vec![number.to_string()],
+            vec![None],
+            None,
+            body,
+        )))
+    }
+}
@@ -0,0 +1,37 @@
+use crate::BarcodeFormat;
+
+use super::result_parser::ResultParser;
+use super::{EmailAddressParsedResult, ParsedResult};
+
+/**
+ * <p>Parses an "smtp:" URI result, whose format is not standardized but appears to be like:
+ * {@code smtp[:subject[:body]]}.</p>
+ */
+pub struct SmtpResultParser;
+
+impl ResultParser for SmtpResultParser {
+    fn parse(&self, text: &str, _format: &BarcodeFormat) -> Option<ParsedResult> {
+        if !text.starts_with("smtp:") && !text.starts_with("SMTP:") {
+            return None;
+        }
+        let mut email_address = &text[5..];
+        let mut subject = None;
+        let mut body = None;
+        if let Some(colon) = email_address.find(':') {
+            let mut rest = &email_address[colon + 1..];
+            email_address = &email_address[..colon];
+            if let Some(colon) = rest.find(':') {
+                body = Some(rest[colon + 1..].to_string());
+                rest = &rest[..colon];
+            }
+            subject = Some(rest.to_string());
+        }
+        Some(ParsedResult::EmailAddress(EmailAddressParsedResult::new(
+            vec![email_address.to_string()],
+            Vec::new(),
+            Vec::new(),
+            subject,
+            body,
+        )))
+    }
+}
@@ -0,0 +1,40 @@
+use super::ParsedResult;
+
+/**
+ * A telephone number.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct TelParsedResult {
+    number: String,
+    tel_uri: String,
+    title: Option<String>,
+}
+
+impl TelParsedResult {
+    pub fn new(number: String, tel_uri: String, title: Option<String>) -> TelParsedResult {
+        TelParsedResult {
+            number,
+            tel_uri,
+            title,
+        }
+    }
+
+    pub fn get_number(&self) -> &str {
+        &self.number
+    }
+
+    pub fn get_tel_uri(&self) -> &str {
+        &self.tel_uri
+    }
+
+    pub fn get_title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    pub fn get_display_result(&self) -> String {
+        let mut result = String::with_capacity(20);
+        ParsedResult::maybe_append(Some(&self.number), &mut result);
+        ParsedResult::maybe_append(self.title.as_deref(), &mut result);
+        result
+    }
+}
@@ -0,0 +1,29 @@
+use crate::BarcodeFormat;
+
+use super::result_parser::ResultParser;
+use super::{ParsedResult, TelParsedResult};
+
+/**
+ * Parses a "tel:" URI result, which specifies a phone number.
+ */
+pub struct TelResultParser;
+
+impl ResultParser for TelResultParser {
+    fn parse(&self, text: &str, _format: &BarcodeFormat) -> Option<ParsedResult> {
+        if !text.starts_with("tel:") && !text.starts_with("TEL:") {
+            return None;
+        }
+        // Normalize "TEL:" to "tel:"
+        let tel_uri = format!("tel:{}", &text[4..]);
+        // Drop tel, query portion
+        let number = match text[4..].find('?') {
+            Some(query_start) => &text[4..4 + query_start],
+            None => &text[4..],
+        };
+        Some(ParsedResult::Tel(TelParsedResult::new(
+            number.to_string(),
+            tel_uri,
+            None,
+        )))
+    }
+}
@@ -0,0 +1,30 @@
+use super::ParsedResult;
+
+/**
+ * A simple result type encapsulating a string that has no further interpretation.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextParsedResult {
+    text: String,
+    language: Option<String>,
+}
+
+impl TextParsedResult {
+    pub fn new(text: String, language: Option<String>) -> TextParsedResult {
+        TextParsedResult { text, language }
+    }
+
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn get_language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    pub fn get_display_result(&self) -> String {
+        let mut result = String::with_capacity(self.text.len());
+        ParsedResult::maybe_append(Some(&self.text), &mut result);
+        result
+    }
+}
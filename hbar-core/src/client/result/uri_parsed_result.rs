@@ -0,0 +1,61 @@
+use super::result_parser::is_substring_of_digits;
+use super::ParsedResult;
+
+/**
+ * A URI, with an optional title.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct UriParsedResult {
+    uri: String,
+    title: Option<String>,
+}
+
+impl UriParsedResult {
+    pub fn new(uri: &str, title: Option<String>) -> UriParsedResult {
+        UriParsedResult {
+            uri: UriParsedResult::massage_uri(uri),
+            title,
+        }
+    }
+
+    pub fn get_uri(&self) -> &str {
+        &self.uri
+    }
+
+    pub fn get_title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    pub fn get_display_result(&self) -> String {
+        let mut result = String::with_capacity(30);
+        ParsedResult::maybe_append(self.title.as_deref(), &mut result);
+        ParsedResult::maybe_append(Some(&self.uri), &mut result);
+        result
+    }
+
+    /**
+     * Transforms a string that represents a URI into something more proper, by adding or
+     * canonicalizing the protocol.
+     */
+    fn massage_uri(uri: &str) -> String {
+        let uri = uri.trim();
+        match uri.find(':') {
+            Some(protocol_end)
+                if !UriParsedResult::is_colon_followed_by_port_number(uri, protocol_end) =>
+            {
+                uri.to_string()
+            }
+            // No protocol, or found a colon, but it looks like it is after the host, so the
+            // protocol is still missing
+            _ => format!("http://{}", uri),
+        }
+    }
+
+    fn is_colon_followed_by_port_number(uri: &str, protocol_end: usize) -> bool {
+        let start = protocol_end + 1;
+        let next_slash = uri[start..]
+            .find('/')
+            .map_or(uri.len(), |slash| start + slash);
+        is_substring_of_digits(uri, start, next_slash - start)
+    }
+}
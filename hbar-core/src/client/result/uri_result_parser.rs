@@ -0,0 +1,127 @@
+use crate::BarcodeFormat;
+
+use super::result_parser::{get_massaged_text, ResultParser};
+use super::{ParsedResult, UriParsedResult};
+
+/**
+ * Tries to parse results that are a URI of some kind.
+ */
+pub struct UriResultParser;
+
+impl ResultParser for UriResultParser {
+    fn parse(&self, text: &str, _format: &BarcodeFormat) -> Option<ParsedResult> {
+        let raw_text = get_massaged_text(text);
+        // We specifically handle the odd "URL" scheme here for simplicity and add "URI" for fun
+        // Assume anything starting this way really means to be a URI
+        if raw_text.starts_with("URL:") || raw_text.starts_with("URI:") {
+            return Some(ParsedResult::Uri(UriParsedResult::new(
+                raw_text[4..].trim(),
+                None,
+            )));
+        }
+        let raw_text = raw_text.trim();
+        if !UriResultParser::is_basically_valid_uri(raw_text)
+            || UriResultParser::is_possibly_malicious_uri(raw_text)
+        {
+            return None;
+        }
+        Some(ParsedResult::Uri(UriParsedResult::new(raw_text, None)))
+    }
+}
+
+impl UriResultParser {
+    /**
+     * @return true if the URI contains suspicious patterns that may suggest it intends to
+     *  mislead the user about its true nature: characters outside those allowed in URIs, or
+     *  user information before the host, as in "http://user@host"
+     */
+    pub fn is_possibly_malicious_uri(uri: &str) -> bool {
+        let allowed = |c: char| c.is_ascii_alphanumeric() || "-._~:/?#[]@!$&'()*+,;=%".contains(c);
+        !uri.chars().all(allowed) || UriResultParser::has_user_in_host(uri)
+    }
+
+    /**
+     * @return true if the text starts with a scheme, like "http:", or a host name, like
+     *  "example.com/"
+     */
+    pub fn is_basically_valid_uri(uri: &str) -> bool {
+        if uri.contains(' ') {
+            // Quick hack check for a common case
+            return false;
+        }
+        UriResultParser::starts_with_scheme(uri) || UriResultParser::starts_with_host(uri)
+    }
+
+    // A letter, then letters, digits, '+', ',', '-' or '.', then a colon.
+    fn starts_with_scheme(uri: &str) -> bool {
+        let bytes = uri.as_bytes();
+        if bytes.is_empty() || !bytes[0].is_ascii_alphabetic() {
+            return false;
+        }
+        let length = bytes[1..]
+            .iter()
+            .take_while(|&&b| b.is_ascii_alphanumeric() || (b'+'..=b'.').contains(&b))
+            .count();
+        length > 0 && bytes.get(1 + length) == Some(&b':')
+    }
+
+    // One to six labels each followed by a dot, a top level domain of two letters or more, an
+    // optional port, and then a slash, question mark or the end.
+    fn starts_with_host(uri: &str) -> bool {
+        let bytes = uri.as_bytes();
+        let is_label = |b: &u8| b.is_ascii_alphanumeric() || *b == b'-';
+        let mut i = 0;
+        for _ in 0..6 {
+            let label = bytes[i..].iter().take_while(|b| is_label(b)).count();
+            if label == 0 || bytes.get(i + label) != Some(&b'.') {
+                break;
+            }
+            i += label + 1;
+            if UriResultParser::is_host_end(&bytes[i..]) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn is_host_end(bytes: &[u8]) -> bool {
+        let letters = bytes.iter().take_while(|b| b.is_ascii_alphabetic()).count();
+        if letters < 2 {
+            return false;
+        }
+        let mut rest = &bytes[letters..];
+        if rest.first() == Some(&b':') {
+            let digits = rest[1..].iter().take_while(|b| b.is_ascii_digit()).count();
+            if !(1..=5).contains(&digits) {
+                return false;
+            }
+            rest = &rest[1 + digits..];
+        }
+        matches!(rest.first(), None | Some(b'/') | Some(b'?'))
+    }
+
+    // A colon, any slashes, then characters other than '/' and '@' up to an '@' which is
+    // followed by more than a slash.
+    fn has_user_in_host(uri: &str) -> bool {
+        let bytes = uri.as_bytes();
+        bytes.iter().enumerate().any(|(colon, &b)| {
+            if b != b':' {
+                return false;
+            }
+            let start = colon
+                + 1
+                + bytes[colon + 1..]
+                    .iter()
+                    .take_while(|&&b| b == b'/')
+                    .count();
+            let user = bytes[start..]
+                .iter()
+                .take_while(|&&b| b != b'/' && b != b'@')
+                .count();
+            let at = start + user;
+            user > 0
+                && bytes.get(at) == Some(&b'@')
+                && bytes.get(at + 1).is_some_and(|&b| b != b'/')
+        })
+    }
+}
@@ -0,0 +1,30 @@
+use crate::BarcodeFormat;
+
+use super::result_parser::ResultParser;
+use super::{ParsedResult, UriParsedResult};
+
+/**
+ * Parses the "URLTO" result format, which is of the form "URLTO:[title]:[url]".
+ * This seems to be used sometimes, but I am not able to find documentation
+ * on its origin or official format?
+ */
+pub struct UrltoResultParser;
+
+impl ResultParser for UrltoResultParser {
+    fn parse(&self, text: &str, _format: &BarcodeFormat) -> Option<ParsedResult> {
+        if !text.starts_with("urlto:") && !text.starts_with("URLTO:") {
+            return None;
+        }
+        // Thanks to Google Goggles for the URLTO format
+        let title_end = 6 + text[6..].find(':')?;
+        let title = if title_end <= 6 {
+            None
+        } else {
+            Some(text[6..title_end].to_string())
+        };
+        Some(ParsedResult::Uri(UriParsedResult::new(
+            &text[title_end + 1..],
+            title,
+        )))
+    }
+}
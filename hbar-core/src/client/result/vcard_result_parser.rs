@@ -0,0 +1,422 @@
+use crate::BarcodeFormat;
+use crate::Charset;
+
+use super::result_parser::{parse_hex_digit, ResultParser};
+use super::{AddressBookParsedResult, ParsedResult};
+
+use std::str::FromStr;
+
+/**
+ * A vCard property: its value, and its parameters as written, like "TYPE=HOME".
+ */
+pub(crate) struct VCardField {
+    pub(crate) value: String,
+    pub(crate) metadata: Vec<String>,
+}
+
+/**
+ * Parses contact information formatted according to the VCard (2.1) format. This is not a
+ * complete implementation but should parse information as commonly encoded in 2D barcodes.
+ */
+pub struct VCardResultParser;
+
+impl ResultParser for VCardResultParser {
+    fn parse(&self, text: &str, _format: &BarcodeFormat) -> Option<ParsedResult> {
+        // Although we should insist on the raw text ending with "END:VCARD", there's no reason
+        // to throw out everything else we parsed just because this was omitted. In fact, Eclair
+        // is doing just that, and we can't parse its contacts without this leniency.
+        if !text.get(..11)?.eq_ignore_ascii_case("BEGIN:VCARD") {
+            return None;
+        }
+        let mut names = VCardResultParser::match_vcard_prefixed_field("FN", text, true, false);
+        if names.is_empty() {
+            // If no display names found, look for regular name fields and format them
+            names = VCardResultParser::match_vcard_prefixed_field("N", text, true, false);
+            VCardResultParser::format_names(&mut names);
+        }
+        let nicknames =
+            VCardResultParser::match_single_vcard_prefixed_field("NICKNAME", text, true, false)
+                .map_or_else(Vec::new, |nicknames| {
+                    nicknames.value.split(',').map(String::from).collect()
+                });
+        let phone_numbers = VCardResultParser::match_vcard_prefixed_field("TEL", text, true, false);
+        let emails = VCardResultParser::match_vcard_prefixed_field("EMAIL", text, true, false);
+        let note = VCardResultParser::match_single_vcard_prefixed_field("NOTE", text, false, false);
+        let addresses = VCardResultParser::match_vcard_prefixed_field("ADR", text, true, true);
+        let org = VCardResultParser::match_single_vcard_prefixed_field("ORG", text, true, true);
+        let birthday =
+            VCardResultParser::match_single_vcard_prefixed_field("BDAY", text, true, false)
+                .filter(|birthday| VCardResultParser::is_like_vcard_date(&birthday.value));
+        let title =
+            VCardResultParser::match_single_vcard_prefixed_field("TITLE", text, true, false);
+        let urls = VCardResultParser::match_vcard_prefixed_field("URL", text, true, false);
+        let instant_messenger =
+            VCardResultParser::match_single_vcard_prefixed_field("IMPP", text, true, false);
+        let geo = VCardResultParser::match_single_vcard_prefixed_field("GEO", text, true, false)
+            .map(|geo| {
+                // vCard 4.0 gives a "geo:" URI, earlier versions just the coordinates
+                let coordinates = match geo.value.get(..4) {
+                    Some(scheme) if scheme.eq_ignore_ascii_case("geo:") => &geo.value[4..],
+                    _ => &geo.value[..],
+                };
+                coordinates
+                    .split([';', ','])
+                    .map(String::from)
+                    .collect::<Vec<_>>()
+            })
+            .filter(|geo| geo.len() == 2)
+            .unwrap_or_default();
+
+        Some(ParsedResult::AddressBook(AddressBookParsedResult::new(
+            VCardResultParser::to_primary_values(&names),
+            nicknames,
+            None,
+            VCardResultParser::to_primary_values(&phone_numbers),
+            VCardResultParser::to_types(&phone_numbers),
+            VCardResultParser::to_primary_values(&emails),
+            VCardResultParser::to_types(&emails),
+            VCardResultParser::to_primary_value(instant_messenger),
+            VCardResultParser::to_primary_value(note),
+            VCardResultParser::to_primary_values(&addresses),
+            VCardResultParser::to_types(&addresses),
+            VCardResultParser::to_primary_value(org),
+            VCardResultParser::to_primary_value(birthday),
+            VCardResultParser::to_primary_value(title),
+            VCardResultParser::to_primary_values(&urls),
+            geo,
+        )))
+    }
+}
+
+impl VCardResultParser {
+    /**
+     * Finds every property with the given name, case insensitively, at the start of a line.
+     * Folded lines are unfolded, quoted-printable values decoded and escapes undone.
+     *
+     * @param trim whether to trim whitespace around the value
+     * @param parse_field_divider whether to turn the semicolons between the components of a
+     *  structured value, like an address, into newlines
+     */
+    pub(crate) fn match_vcard_prefixed_field(
+        prefix: &str,
+        raw_text: &str,
+        trim: bool,
+        parse_field_divider: bool,
+    ) -> Vec<VCardField> {
+        let bytes = raw_text.as_bytes();
+        let mut matches = Vec::new();
+        let mut i = 0;
+        let max = raw_text.len();
+
+        while i < max {
+            let (params, value_start) = match VCardResultParser::find_property(raw_text, i, prefix)
+            {
+                Some(property) => property,
+                None => break,
+            };
+            i = value_start;
+
+            let mut metadata = Vec::new();
+            let mut quoted_printable = false;
+            let mut quoted_printable_charset = None;
+            let mut value_type = None;
+            if let Some(params) = params {
+                for metadatum in params.split(';') {
+                    metadata.push(metadatum.to_string());
+                    if let Some(equals) = metadatum.find('=') {
+                        let key = &metadatum[..equals];
+                        let value = &metadatum[equals + 1..];
+                        if key.eq_ignore_ascii_case("ENCODING")
+                            && value.eq_ignore_ascii_case("QUOTED-PRINTABLE")
+                        {
+                            quoted_printable = true;
+                        } else if key.eq_ignore_ascii_case("CHARSET") {
+                            quoted_printable_charset = Some(value);
+                        } else if key.eq_ignore_ascii_case("VALUE") {
+                            value_type = Some(value);
+                        }
+                    }
+                }
+            }
+
+            // Found the start of a match here
+            let match_start = i;
+            // Really, end in \r\n
+            while let Some(newline) = raw_text[i..].find('\n') {
+                i += newline;
+                if i < max - 1 && (bytes[i + 1] == b' ' || bytes[i + 1] == b'\t') {
+                    // But if followed by tab or space, this is only a continuation
+                    i += 2;
+                } else if quoted_printable
+                    && ((i >= 1 && bytes[i - 1] == b'=') || (i >= 2 && bytes[i - 2] == b'='))
+                {
+                    // If preceded by = in quoted printable, this is a continuation
+                    i += 1;
+                } else {
+                    break;
+                }
+                if i >= max {
+                    break;
+                }
+            }
+            if !raw_text[i.min(max)..].starts_with('\n') {
+                // The value runs to the end of the text
+                i = max;
+            }
+
+            if i > match_start {
+                let mut end = i;
+                if bytes[end - 1] == b'\r' {
+                    // Back up over \r, which really should be there
+                    end -= 1;
+                }
+                let mut element = raw_text[match_start..end].to_string();
+                if trim {
+                    element = element.trim().to_string();
+                }
+                if quoted_printable {
+                    element = VCardResultParser::decode_quoted_printable(
+                        &element,
+                        quoted_printable_charset,
+                    );
+                    if parse_field_divider {
+                        element = VCardResultParser::divide_fields(&element);
+                    }
+                } else {
+                    if parse_field_divider {
+                        element = VCardResultParser::divide_fields(&element);
+                    }
+                    element = element.replace("\r\n ", "").replace("\r\n\t", "");
+                    element = element.replace("\n ", "").replace("\n\t", "");
+                    element = VCardResultParser::unescape(&element);
+                }
+                // Only handle VALUE=uri specially
+                if value_type.is_some_and(|value_type| value_type.eq_ignore_ascii_case("uri")) {
+                    if let Some(colon) = element.find(':') {
+                        element = element[colon + 1..].to_string();
+                    }
+                }
+                matches.push(VCardField {
+                    value: element,
+                    metadata,
+                });
+            }
+            i += 1;
+        }
+        matches
+    }
+
+    pub(crate) fn match_single_vcard_prefixed_field(
+        prefix: &str,
+        raw_text: &str,
+        trim: bool,
+        parse_field_divider: bool,
+    ) -> Option<VCardField> {
+        VCardResultParser::match_vcard_prefixed_field(prefix, raw_text, trim, parse_field_divider)
+            .into_iter()
+            .next()
+    }
+
+    /**
+     * Finds a line from {@code from} on which starts with the property name followed by ';' or
+     * ':'.
+     *
+     * @return the parameters between the name and the colon, if any, and where the value starts
+     */
+    fn find_property<'a>(
+        raw_text: &'a str,
+        from: usize,
+        prefix: &str,
+    ) -> Option<(Option<&'a str>, usize)> {
+        let at_line_start = from == 0 || raw_text.as_bytes()[from - 1] == b'\n';
+        let line_starts = at_line_start.then_some(from).into_iter().chain(
+            raw_text[from..]
+                .match_indices('\n')
+                .map(|(newline, _)| from + newline + 1),
+        );
+        for line_start in line_starts {
+            let after_prefix = line_start + prefix.len();
+            let matches_prefix = raw_text
+                .get(line_start..after_prefix)
+                .is_some_and(|name| name.eq_ignore_ascii_case(prefix));
+            if !matches_prefix {
+                continue;
+            }
+            match raw_text.as_bytes().get(after_prefix) {
+                Some(b':') => return Some((None, after_prefix + 1)),
+                Some(b';') => {
+                    if let Some(colon) = raw_text[after_prefix..].find(':') {
+                        let colon = after_prefix + colon;
+                        return Some((Some(&raw_text[after_prefix + 1..colon]), colon + 1));
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    // Replaces each run of unescaped semicolons with a newline. A backslash escapes only the
+    // character after it, so in "\\;" the semicolon still divides.
+    fn divide_fields(value: &str) -> String {
+        let mut result = String::with_capacity(value.len());
+        let mut escaped = false;
+        let mut divided = false;
+        for c in value.chars() {
+            if c == ';' && !escaped {
+                if !divided {
+                    result.push('\n');
+                }
+                divided = true;
+            } else {
+                result.push(c);
+                divided = false;
+            }
+            escaped = c == '\\' && !escaped;
+        }
+        result.trim().to_string()
+    }
+
+    // Turns "\n" and "\N" into newlines, and "\,", "\;" and "\\" into the escaped character.
+    fn unescape(value: &str) -> String {
+        let mut result = String::with_capacity(value.len());
+        let mut chars = value.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (c, chars.peek()) {
+                ('\\', Some('n')) | ('\\', Some('N')) => {
+                    chars.next();
+                    result.push('\n');
+                }
+                ('\\', Some(&escaped)) if escaped == ',' || escaped == ';' || escaped == '\\' => {
+                    chars.next();
+                    result.push(escaped);
+                }
+                _ => result.push(c),
+            }
+        }
+        result
+    }
+
+    fn decode_quoted_printable(value: &str, charset: Option<&str>) -> String {
+        let bytes = value.as_bytes();
+        let mut result = String::with_capacity(value.len());
+        let mut fragment = Vec::new();
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\r' | b'\n' => {}
+                b'=' => {
+                    if i + 2 < bytes.len() && bytes[i + 1] != b'\r' && bytes[i + 1] != b'\n' {
+                        let first_digit = parse_hex_digit(bytes[i + 1] as char);
+                        let second_digit = parse_hex_digit(bytes[i + 2] as char);
+                        if let (Some(first_digit), Some(second_digit)) = (first_digit, second_digit)
+                        {
+                            fragment.push((first_digit << 4) + second_digit);
+                            i += 2;
+                        } else {
+                            // Ignore it, assume it was incorrectly encoded, but never skip into
+                            // the middle of a multibyte character
+                            i += bytes[i + 1..=i + 2]
+                                .iter()
+                                .take_while(|b| b.is_ascii())
+                                .count();
+                        }
+                    }
+                }
+                _ => {
+                    VCardResultParser::maybe_append_fragment(&mut fragment, charset, &mut result);
+                    let c = value[i..].chars().next().unwrap();
+                    result.push(c);
+                    i += c.len_utf8();
+                    continue;
+                }
+            }
+            i += 1;
+        }
+        VCardResultParser::maybe_append_fragment(&mut fragment, charset, &mut result);
+        result
+    }
+
+    fn maybe_append_fragment(fragment: &mut Vec<u8>, charset: Option<&str>, result: &mut String) {
+        if fragment.is_empty() {
+            return;
+        }
+        let decoded = charset
+            .and_then(|charset| Charset::from_str(charset).ok())
+            .and_then(|charset| charset.decode(fragment).ok());
+        match decoded {
+            Some(decoded) => result.push_str(&decoded),
+            None => result.push_str(&String::from_utf8_lossy(fragment)),
+        }
+        fragment.clear();
+    }
+
+    fn to_primary_value(field: Option<VCardField>) -> Option<String> {
+        field
+            .map(|field| field.value)
+            .filter(|value| !value.is_empty())
+    }
+
+    fn to_primary_values(fields: &[VCardField]) -> Vec<String> {
+        fields
+            .iter()
+            .filter(|field| !field.value.is_empty())
+            .map(|field| field.value.clone())
+            .collect()
+    }
+
+    // The type of each non-empty value: its TYPE parameter, or a parameter without a name.
+    fn to_types(fields: &[VCardField]) -> Vec<Option<String>> {
+        fields
+            .iter()
+            .filter(|field| !field.value.is_empty())
+            .map(|field| {
+                field
+                    .metadata
+                    .iter()
+                    .find_map(|metadatum| match metadatum.find('=') {
+                        None => Some(metadatum.clone()),
+                        Some(equals) if metadatum[..equals].eq_ignore_ascii_case("TYPE") => {
+                            Some(metadatum[equals + 1..].to_string())
+                        }
+                        Some(_) => None,
+                    })
+            })
+            .collect()
+    }
+
+    // Checks for yyyyMMdd, optionally with hyphens.
+    fn is_like_vcard_date(value: &str) -> bool {
+        let digits: String = value.chars().filter(|&c| c != '-').collect();
+        let bytes = value.as_bytes();
+        let hyphens_in_place = match bytes.len() {
+            8 => true,
+            9 => bytes[4] == b'-' || bytes[6] == b'-',
+            10 => bytes[4] == b'-' && bytes[7] == b'-',
+            _ => false,
+        };
+        hyphens_in_place && digits.len() == 8 && digits.bytes().all(|b| b.is_ascii_digit())
+    }
+
+    /**
+     * Formats name fields of the form "Public;John;Q.;Reverend;III" into a form like
+     * "Reverend John Q. Public III".
+     */
+    fn format_names(names: &mut [VCardField]) {
+        for name in names.iter_mut() {
+            let components: Vec<&str> = name.value.splitn(5, ';').collect();
+            let mut new_name = String::with_capacity(100);
+            for &index in &[3, 1, 2, 0, 4] {
+                if let Some(component) = components.get(index) {
+                    if !component.is_empty() {
+                        if !new_name.is_empty() {
+                            new_name.push(' ');
+                        }
+                        new_name.push_str(component);
+                    }
+                }
+            }
+            name.value = new_name.trim().to_string();
+        }
+    }
+}
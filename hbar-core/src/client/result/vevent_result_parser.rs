@@ -0,0 +1,76 @@
+use crate::BarcodeFormat;
+
+use super::result_parser::ResultParser;
+use super::vcard_result_parser::VCardResultParser;
+use super::{CalendarParsedResult, ParsedResult};
+
+/**
+ * Partially implements the iCalendar format's "VEVENT" format for specifying a
+ * calendar event. See RFC 2445. This supports SUMMARY, LOCATION, GEO, DTSTART and DTEND fields.
+ */
+pub struct VEventResultParser;
+
+impl ResultParser for VEventResultParser {
+    fn parse(&self, text: &str, _format: &BarcodeFormat) -> Option<ParsedResult> {
+        if !text.contains("BEGIN:VEVENT") {
+            return None;
+        }
+
+        let summary = VEventResultParser::match_single_vcard_prefixed_field("SUMMARY", text);
+        let start = VEventResultParser::match_single_vcard_prefixed_field("DTSTART", text)?;
+        let end = VEventResultParser::match_single_vcard_prefixed_field("DTEND", text);
+        let duration = VEventResultParser::match_single_vcard_prefixed_field("DURATION", text);
+        let location = VEventResultParser::match_single_vcard_prefixed_field("LOCATION", text);
+        let organizer = VEventResultParser::match_single_vcard_prefixed_field("ORGANIZER", text)
+            .map(VEventResultParser::strip_mailto);
+        let attendees =
+            VCardResultParser::match_vcard_prefixed_field("ATTENDEE", text, true, false)
+                .into_iter()
+                .map(|attendee| VEventResultParser::strip_mailto(attendee.value))
+                .collect();
+        let description =
+            VEventResultParser::match_single_vcard_prefixed_field("DESCRIPTION", text);
+
+        let (latitude, longitude) =
+            match VEventResultParser::match_single_vcard_prefixed_field("GEO", text) {
+                None => (f64::NAN, f64::NAN),
+                Some(geo) => {
+                    let semicolon = geo.find(';')?;
+                    (
+                        geo[..semicolon].parse().ok()?,
+                        geo[semicolon + 1..].parse().ok()?,
+                    )
+                }
+            };
+
+        CalendarParsedResult::new(
+            summary,
+            &start,
+            end.as_deref(),
+            duration.as_deref(),
+            location,
+            organizer,
+            attendees,
+            description,
+            latitude,
+            longitude,
+        )
+        .ok()
+        .map(ParsedResult::Calendar)
+    }
+}
+
+impl VEventResultParser {
+    fn match_single_vcard_prefixed_field(prefix: &str, raw_text: &str) -> Option<String> {
+        VCardResultParser::match_single_vcard_prefixed_field(prefix, raw_text, true, false)
+            .map(|field| field.value)
+    }
+
+    fn strip_mailto(value: String) -> String {
+        if value.starts_with("mailto:") || value.starts_with("MAILTO:") {
+            value[7..].to_string()
+        } else {
+            value
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use super::ParsedResult;
+
+/**
+ * A vehicle identification number (ISO 3779), as found on Code 39 labels, and what can be read
+ * from it.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct VinParsedResult {
+    vin: String,
+    world_manufacturer_id: String,
+    vehicle_descriptor_section: String,
+    vehicle_identifier_section: String,
+    country_code: Option<String>,
+    vehicle_attributes: String,
+    model_year: i32,
+    plant_code: char,
+    sequential_number: String,
+}
+
+impl VinParsedResult {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        vin: String,
+        world_manufacturer_id: String,
+        vehicle_descriptor_section: String,
+        vehicle_identifier_section: String,
+        country_code: Option<String>,
+        vehicle_attributes: String,
+        model_year: i32,
+        plant_code: char,
+        sequential_number: String,
+    ) -> VinParsedResult {
+        VinParsedResult {
+            vin,
+            world_manufacturer_id,
+            vehicle_descriptor_section,
+            vehicle_identifier_section,
+            country_code,
+            vehicle_attributes,
+            model_year,
+            plant_code,
+            sequential_number,
+        }
+    }
+
+    pub fn get_vin(&self) -> &str {
+        &self.vin
+    }
+
+    /**
+     * @return the first three characters, identifying the manufacturer
+     */
+    pub fn get_world_manufacturer_id(&self) -> &str {
+        &self.world_manufacturer_id
+    }
+
+    /**
+     * @return characters 4 to 9, describing the vehicle
+     */
+    pub fn get_vehicle_descriptor_section(&self) -> &str {
+        &self.vehicle_descriptor_section
+    }
+
+    /**
+     * @return the last eight characters, identifying the vehicle
+     */
+    pub fn get_vehicle_identifier_section(&self) -> &str {
+        &self.vehicle_identifier_section
+    }
+
+    /**
+     * @return the country of manufacture, where the manufacturer ID tells it
+     */
+    pub fn get_country_code(&self) -> Option<&str> {
+        self.country_code.as_deref()
+    }
+
+    pub fn get_vehicle_attributes(&self) -> &str {
+        &self.vehicle_attributes
+    }
+
+    pub fn get_model_year(&self) -> i32 {
+        self.model_year
+    }
+
+    pub fn get_plant_code(&self) -> char {
+        self.plant_code
+    }
+
+    pub fn get_sequential_number(&self) -> &str {
+        &self.sequential_number
+    }
+
+    pub fn get_display_result(&self) -> String {
+        let mut result = String::with_capacity(50);
+        ParsedResult::maybe_append(Some(&self.world_manufacturer_id), &mut result);
+        result.push(' ');
+        result.push_str(&self.vehicle_descriptor_section);
+        result.push(' ');
+        result.push_str(&self.vehicle_identifier_section);
+        result.push('\n');
+        if let Some(country_code) = &self.country_code {
+            result.push_str(country_code);
+            result.push(' ');
+        }
+        result.push_str(&self.model_year.to_string());
+        result.push(' ');
+        result.push(self.plant_code);
+        result.push(' ');
+        result.push_str(&self.sequential_number);
+        result.push('\n');
+        result
+    }
+}
@@ -0,0 +1,118 @@
+use crate::BarcodeFormat;
+
+use super::result_parser::ResultParser;
+use super::{ParsedResult, VinParsedResult};
+
+/**
+ * Detects a result that is likely a vehicle identification number.
+ */
+pub struct VinResultParser;
+
+impl ResultParser for VinResultParser {
+    fn parse(&self, text: &str, format: &BarcodeFormat) -> Option<ParsedResult> {
+        if *format != BarcodeFormat::Code39 {
+            return None;
+        }
+        // I, O and Q never appear in a VIN, so they must be noise
+        let raw_text: String = text.chars().filter(|c| !"IOQ".contains(*c)).collect();
+        let raw_text = raw_text.trim();
+        if raw_text.len() != 17
+            || !raw_text
+                .bytes()
+                .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+        {
+            return None;
+        }
+        if !VinResultParser::check_checksum(raw_text)? {
+            return None;
+        }
+        let wmi = &raw_text[..3];
+        Some(ParsedResult::Vin(VinParsedResult::new(
+            raw_text.to_string(),
+            wmi.to_string(),
+            raw_text[3..9].to_string(),
+            raw_text[9..17].to_string(),
+            VinResultParser::country_code(wmi).map(String::from),
+            raw_text[3..8].to_string(),
+            VinResultParser::model_year(raw_text.as_bytes()[9])?,
+            raw_text.as_bytes()[10] as char,
+            raw_text[11..].to_string(),
+        )))
+    }
+}
+
+impl VinResultParser {
+    fn check_checksum(vin: &str) -> Option<bool> {
+        let mut sum = 0;
+        for (i, c) in vin.bytes().enumerate() {
+            sum +=
+                VinResultParser::vin_position_weight(i + 1) * VinResultParser::vin_char_value(c)?;
+        }
+        let check_char = vin.as_bytes()[8];
+        let expected_check_char = VinResultParser::check_char(sum % 11);
+        Some(check_char == expected_check_char)
+    }
+
+    fn vin_char_value(c: u8) -> Option<u32> {
+        match c {
+            b'A'..=b'I' => Some((c - b'A') as u32 + 1),
+            b'J'..=b'R' => Some((c - b'J') as u32 + 1),
+            b'S'..=b'Z' => Some((c - b'S') as u32 + 2),
+            b'0'..=b'9' => Some((c - b'0') as u32),
+            _ => None,
+        }
+    }
+
+    fn vin_position_weight(position: usize) -> u32 {
+        match position {
+            1..=7 => 9 - position as u32,
+            8 => 10,
+            9 => 0,
+            _ => 19 - position as u32,
+        }
+    }
+
+    fn check_char(remainder: u32) -> u8 {
+        if remainder < 10 {
+            b'0' + remainder as u8
+        } else {
+            b'X'
+        }
+    }
+
+    fn model_year(c: u8) -> Option<i32> {
+        match c {
+            b'E'..=b'H' => Some((c - b'E') as i32 + 1984),
+            b'J'..=b'N' => Some((c - b'J') as i32 + 1988),
+            b'P' => Some(1993),
+            b'R'..=b'T' => Some((c - b'R') as i32 + 1994),
+            b'V'..=b'Y' => Some((c - b'V') as i32 + 1997),
+            b'1'..=b'9' => Some((c - b'1') as i32 + 2001),
+            b'A'..=b'D' => Some((c - b'A') as i32 + 2010),
+            _ => None,
+        }
+    }
+
+    fn country_code(wmi: &str) -> Option<&'static str> {
+        let bytes = wmi.as_bytes();
+        let (c1, c2) = (bytes[0], bytes[1]);
+        match c1 {
+            b'1' | b'4' | b'5' => Some("US"),
+            b'2' => Some("CA"),
+            b'3' if (b'A'..=b'W').contains(&c2) => Some("MX"),
+            b'9' if (b'A'..=b'E').contains(&c2) || (b'3'..=b'9').contains(&c2) => Some("BR"),
+            b'J' if (b'A'..=b'T').contains(&c2) => Some("JP"),
+            b'K' if (b'L'..=b'R').contains(&c2) => Some("KO"),
+            b'L' => Some("CN"),
+            b'M' if (b'A'..=b'E').contains(&c2) => Some("IN"),
+            b'S' if (b'A'..=b'M').contains(&c2) => Some("UK"),
+            b'S' if (b'N'..=b'T').contains(&c2) => Some("DE"),
+            b'V' if (b'F'..=b'R').contains(&c2) => Some("FR"),
+            b'V' if (b'S'..=b'W').contains(&c2) => Some("ES"),
+            b'W' => Some("DE"),
+            b'X' if c2 == b'0' || (b'3'..=b'9').contains(&c2) => Some("RU"),
+            b'Z' if (b'A'..=b'R').contains(&c2) => Some("IT"),
+            _ => None,
+        }
+    }
+}
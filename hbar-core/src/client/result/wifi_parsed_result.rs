@@ -0,0 +1,94 @@
+use super::ParsedResult;
+
+/**
+ * The settings of a Wi-Fi network, as in a "WIFI:S:ssid;T:WPA;P:password;;" code.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct WifiParsedResult {
+    ssid: String,
+    network_encryption: String,
+    password: Option<String>,
+    hidden: bool,
+    identity: Option<String>,
+    anonymous_identity: Option<String>,
+    eap_method: Option<String>,
+    phase2_method: Option<String>,
+}
+
+impl WifiParsedResult {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        network_encryption: String,
+        ssid: String,
+        password: Option<String>,
+        hidden: bool,
+        identity: Option<String>,
+        anonymous_identity: Option<String>,
+        eap_method: Option<String>,
+        phase2_method: Option<String>,
+    ) -> WifiParsedResult {
+        WifiParsedResult {
+            ssid,
+            network_encryption,
+            password,
+            hidden,
+            identity,
+            anonymous_identity,
+            eap_method,
+            phase2_method,
+        }
+    }
+
+    pub fn get_ssid(&self) -> &str {
+        &self.ssid
+    }
+
+    /**
+     * @return the authentication type, like "WEP", "WPA" or "nopass"
+     */
+    pub fn get_network_encryption(&self) -> &str {
+        &self.network_encryption
+    }
+
+    pub fn get_password(&self) -> Option<&str> {
+        self.password.as_deref()
+    }
+
+    pub fn is_hidden(&self) -> bool {
+        self.hidden
+    }
+
+    /**
+     * @return the identity for WPA2-Enterprise
+     */
+    pub fn get_identity(&self) -> Option<&str> {
+        self.identity.as_deref()
+    }
+
+    pub fn get_anonymous_identity(&self) -> Option<&str> {
+        self.anonymous_identity.as_deref()
+    }
+
+    /**
+     * @return the EAP method for WPA2-Enterprise, like "PEAP" or "TTLS"
+     */
+    pub fn get_eap_method(&self) -> Option<&str> {
+        self.eap_method.as_deref()
+    }
+
+    /**
+     * @return the phase 2 method for WPA2-Enterprise, like "MSCHAPV2"
+     */
+    pub fn get_phase2_method(&self) -> Option<&str> {
+        self.phase2_method.as_deref()
+    }
+
+    pub fn get_display_result(&self) -> String {
+        let mut result = String::with_capacity(80);
+        ParsedResult::maybe_append(Some(&self.ssid), &mut result);
+        ParsedResult::maybe_append(Some(&self.network_encryption), &mut result);
+        ParsedResult::maybe_append(self.password.as_deref(), &mut result);
+        ParsedResult::maybe_append(Some(&self.hidden.to_string()), &mut result);
+        result
+    }
+}
@@ -0,0 +1,64 @@
+use crate::BarcodeFormat;
+
+use super::result_parser::{match_single_prefixed_field, ResultParser};
+use super::{ParsedResult, WifiParsedResult};
+
+/**
+ * <p>Parses a WIFI configuration string. Strings will be of the form:</p>
+ *
+ * <p>{@code WIFI:T:[network type];S:[network SSID];P:[network password];H:[hidden?];;}</p>
+ *
+ * <p>For WPA2 enterprise (EAP), strings will be of the form:</p>
+ *
+ * <p>{@code WIFI:T:WPA2-EAP;S:[network SSID];H:[hidden?];E:[EAP method];PH2:[Phase 2 method];A:[anonymous identity];I:[username];P:[password];;}</p>
+ *
+ * <p>"EAP method" can e.g. be "TTLS" or "PWD" or one of the other fields in <a href="https://developer.android.com/reference/android/net/wifi/WifiEnterpriseConfig.Eap.html">WifiEnterpriseConfig.Eap</a> and "Phase 2 method" can e.g. be "MSCHAPV2" or any of the other fields in <a href="https://developer.android.com/reference/android/net/wifi/WifiEnterpriseConfig.Phase2.html">WifiEnterpriseConfig.Phase2</a></p>
+ *
+ * <p>The fields can appear in any order. Only "S:" is required.</p>
+ */
+pub struct WifiResultParser;
+
+impl ResultParser for WifiResultParser {
+    fn parse(&self, text: &str, _format: &BarcodeFormat) -> Option<ParsedResult> {
+        let raw_text = text.strip_prefix("WIFI:")?;
+        let field = |prefix: &str| match_single_prefixed_field(prefix, raw_text, ';', false);
+        let ssid = field("S:")?;
+        if ssid.is_empty() {
+            return None;
+        }
+        let pass = field("P:");
+        let network_type = field("T:").unwrap_or_else(|| String::from("nopass"));
+
+        // Unfortunately, in the past, H: was not just used for boolean 'hidden', but 'phase 2
+        // method'. To try to retain backwards compatibility, we set one or the other based on
+        // whether the string is 'true' or 'false':
+        let mut hidden = false;
+        let mut phase2_method = field("PH2:");
+        if let Some(h_value) = field("H:") {
+            // If PH2 was specified separately, or if the value is clearly boolean, interpret it
+            // as 'hidden'
+            if phase2_method.is_some()
+                || h_value.eq_ignore_ascii_case("true")
+                || h_value.eq_ignore_ascii_case("false")
+            {
+                hidden = h_value.eq_ignore_ascii_case("true");
+            } else {
+                phase2_method = Some(h_value);
+            }
+        }
+
+        let identity = field("I:");
+        let anonymous_identity = field("A:");
+        let eap_method = field("E:");
+        Some(ParsedResult::Wifi(WifiParsedResult::new(
+            network_type,
+            ssid,
+            pass,
+            hidden,
+            identity,
+            anonymous_identity,
+            eap_method,
+            phase2_method,
+        )))
+    }
+}
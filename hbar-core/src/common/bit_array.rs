@@ -4,7 +4,9 @@ pub struct BitArray {
     size: i32,
 }
 
-use std::fmt;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
 impl fmt::Display for BitArray {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for i in 0..self.size {
@@ -29,13 +31,15 @@ impl BitArray {
         }
     }
 
+    #[cfg(feature = "decode")]
     pub fn new1(size: u32) -> BitArray {
         BitArray {
             bits: BitArray::make_array(size),
-            size: 0,
+            size: size as i32,
         }
     }
 
+    #[cfg(feature = "decode")]
     fn make_array(size: u32) -> Vec<i32> {
         let size = ((size + 31) / 32) as usize;
         vec![0; size]
@@ -106,6 +110,7 @@ impl BitArray {
      *
      * @param i bit to set
      */
+    #[cfg(feature = "decode")]
     pub fn set(&mut self, i: i32) {
         self.bits[i as usize / 32] |= 1 << (i & 0x1F);
     }
@@ -150,6 +155,7 @@ impl BitArray {
     /**
      * Clears all bits (sets to false).
      */
+    #[cfg(feature = "decode")]
     pub fn clear(&mut self) {
         self.bits = vec![0; self.bits.len()]
     }
@@ -1,5 +1,9 @@
 use crate::{Error, ResultError};
 
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
 #[derive(Debug, Clone)]
 pub struct BitMatrix {
     pub width: i32,
@@ -83,6 +87,28 @@ impl BitMatrix {
         self.bits[offset as usize] |= 1 << (x & 0x1f);
     }
 
+    /**
+     * <p>Sets the given bit to false.</p>
+     *
+     * @param x The horizontal component (i.e. which column)
+     * @param y The vertical component (i.e. which row)
+     */
+    pub fn unset(&mut self, x: u32, y: u32) {
+        let offset = y as i32 * self.row_size + (x as i32 / 32);
+        self.bits[offset as usize] &= !(1 << (x & 0x1f));
+    }
+
+    /**
+     * <p>Flips the given bit.</p>
+     *
+     * @param x The horizontal component (i.e. which column)
+     * @param y The vertical component (i.e. which row)
+     */
+    pub fn flip(&mut self, x: u32, y: u32) {
+        let offset = y as i32 * self.row_size + (x as i32 / 32);
+        self.bits[offset as usize] ^= 1 << (x & 0x1f);
+    }
+
     /**
      * <p>Sets a square region of the bit matrix to true.</p>
      *
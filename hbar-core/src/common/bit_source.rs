@@ -0,0 +1,88 @@
+use crate::{Error, ResultError};
+
+/**
+ * <p>This provides an easy abstraction to read bits at a time from a sequence of bytes, where the
+ * number of bits read is not often a multiple of 8.</p>
+ *
+ * <p>This class is thread-safe but not reentrant -- unless the caller modifies the bytes array
+ * it passed in, in which case all bets are off.</p>
+ */
+pub struct BitSource<'a> {
+    bytes: &'a [u8],
+    byte_offset: usize,
+    bit_offset: usize,
+}
+
+impl<'a> BitSource<'a> {
+    /**
+     * @param bytes bytes from which this will read bits. Bits will be read from the first byte first.
+     * Bits are read within a byte from most-significant to least-significant bit.
+     */
+    pub fn new(bytes: &'a [u8]) -> BitSource<'a> {
+        BitSource {
+            bytes,
+            byte_offset: 0,
+            bit_offset: 0,
+        }
+    }
+
+    /**
+     * @param num_bits number of bits to read
+     * @return int representing the bits read. The bits will appear as the least-significant
+     *         bits of the int
+     * @throws IllegalArgumentException if numBits isn't in [1,32] or more than is available
+     */
+    pub fn read_bits(&mut self, num_bits: usize) -> ResultError<i32> {
+        if !(1..=32).contains(&num_bits) || num_bits > self.available() {
+            return Err(Error::IllegalArgumentException(format!(
+                "Can not read {} bits",
+                num_bits
+            )));
+        }
+
+        let mut num_bits = num_bits;
+        let mut result: u32 = 0;
+
+        // First, read remainder from current byte
+        if self.bit_offset > 0 {
+            let bits_left = 8 - self.bit_offset;
+            let to_read = num_bits.min(bits_left);
+            let bits_to_not_read = bits_left - to_read;
+            let mask = (0xFFu32 >> (8 - to_read)) << bits_to_not_read;
+            result = (self.bytes[self.byte_offset] as u32 & mask) >> bits_to_not_read;
+            num_bits -= to_read;
+            self.bit_offset += to_read;
+            if self.bit_offset == 8 {
+                self.bit_offset = 0;
+                self.byte_offset += 1;
+            }
+        }
+
+        // Next read whole bytes
+        if num_bits > 0 {
+            while num_bits >= 8 {
+                result = (result << 8) | self.bytes[self.byte_offset] as u32;
+                self.byte_offset += 1;
+                num_bits -= 8;
+            }
+
+            // Finally read a partial byte
+            if num_bits > 0 {
+                let bits_to_not_read = 8 - num_bits;
+                let mask = (0xFFu32 >> bits_to_not_read) << bits_to_not_read;
+                result = (result << num_bits)
+                    | ((self.bytes[self.byte_offset] as u32 & mask) >> bits_to_not_read);
+                self.bit_offset += num_bits;
+            }
+        }
+
+        Ok(result as i32)
+    }
+
+    /**
+     * @return number of bits that can be read successfully
+     */
+    pub fn available(&self) -> usize {
+        8 * (self.bytes.len() - self.byte_offset) - self.bit_offset
+    }
+}
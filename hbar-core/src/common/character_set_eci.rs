@@ -0,0 +1,44 @@
+use crate::common::Charset;
+
+/**
+ * Encapsulates a Character Set ECI, according to "Extended Channel Interpretations" 5.3.1.1
+ * of ISO 18004.
+ */
+pub struct CharacterSetECI;
+
+impl CharacterSetECI {
+    /**
+     * @param charset character set
+     * @return the ECI value used to announce this character set, or {@code None} if it has none
+     */
+    #[cfg(feature = "encode")]
+    pub fn get_value(charset: &Charset) -> Option<i32> {
+        match charset {
+            Charset::ASCII => Some(27),
+            Charset::ISO8859_1 => Some(3),
+            Charset::UTF8 => Some(26),
+            Charset::GBK => Some(29),
+            Charset::GB18030 => Some(32),
+            Charset::ShiftJIS => Some(20),
+            Charset::EucJP => None,
+        }
+    }
+
+    /**
+     * @param value character set ECI value
+     * @return {@code Charset} representing ECI of given value, or {@code None} if it is legal but
+     *   unsupported
+     */
+    #[cfg(feature = "decode")]
+    pub fn get_charset_by_value(value: i32) -> Option<Charset> {
+        match value {
+            1 | 3 => Some(Charset::ISO8859_1),
+            20 => Some(Charset::ShiftJIS),
+            26 => Some(Charset::UTF8),
+            27 | 170 => Some(Charset::ASCII),
+            29 => Some(Charset::GBK),
+            32 => Some(Charset::GB18030),
+            _ => None,
+        }
+    }
+}
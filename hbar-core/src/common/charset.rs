@@ -1,29 +1,161 @@
-use encoding::all::{ASCII, GBK, ISO_8859_1, UTF_8};
-use encoding::{EncoderTrap, Encoding};
+#[cfg(feature = "std")]
+use encoding::all::{ASCII, EUC_JP, GB18030, GBK, ISO_8859_1, UTF_8, WINDOWS_31J};
+#[cfg(feature = "std")]
+use encoding::{DecoderTrap, EncoderTrap, Encoding};
+use strum_macros::Display;
 use strum_macros::EnumString;
-use strum_macros::ToString;
 
-use crate::WriterException;
+use crate::{Error, WriterException};
 
-#[derive(Debug, PartialEq, Eq, Hash, EnumString, ToString, Clone)]
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[derive(Debug, PartialEq, Eq, Hash, EnumString, Display, Clone)]
 pub enum Charset {
+    #[strum(to_string = "ASCII", serialize = "US-ASCII")]
     ASCII,
+    #[strum(
+        to_string = "ISO8859_1",
+        serialize = "ISO-8859-1",
+        serialize = "ISO8859-1"
+    )]
     ISO8859_1,
+    #[strum(to_string = "UTF8", serialize = "UTF-8")]
     UTF8,
+    #[strum(to_string = "GBK", serialize = "GB2312")]
     GBK,
+    GB18030,
+    #[strum(to_string = "Shift_JIS", serialize = "ShiftJIS", serialize = "SJIS")]
+    ShiftJIS,
+    #[strum(to_string = "EUC-JP", serialize = "EucJP", serialize = "EUC_JP")]
+    EucJP,
 }
 
+#[cfg(feature = "std")]
 impl Charset {
+    fn get_encoding(&self) -> &'static dyn Encoding {
+        match self {
+            Charset::ASCII => ASCII,
+            Charset::ISO8859_1 => ISO_8859_1,
+            Charset::UTF8 => UTF_8,
+            Charset::GBK => GBK,
+            Charset::GB18030 => GB18030,
+            Charset::ShiftJIS => WINDOWS_31J,
+            Charset::EucJP => EUC_JP,
+        }
+    }
+
     pub fn encode(&self, value: &String) -> Result<Vec<u8>, WriterException> {
-        println!("Charset encode: {:?}, value: {}", self, value);
-        match Some(self) {
-            Some(Charset::ASCII) => Ok(ASCII.encode(value, EncoderTrap::Strict).unwrap()),
-            Some(Charset::ISO8859_1) => Ok(ISO_8859_1.encode(value, EncoderTrap::Strict).unwrap()),
-            Some(Charset::UTF8) => Ok(UTF_8.encode(value, EncoderTrap::Strict).unwrap()),
-            Some(Charset::GBK) => Ok(GBK.encode(value, EncoderTrap::Strict).unwrap()),
-            None => Err(WriterException {
-                reason: String::from("None"),
+        match self.get_encoding().encode(value, EncoderTrap::Strict) {
+            Ok(bytes) => Ok(bytes),
+            Err(err) => Err(WriterException {
+                reason: format!("Can not encode {} as {:?}: {}", value, self, err),
             }),
         }
     }
+
+    /**
+     * Decodes bytes which were encoded in this character set back to a Unicode string.
+     *
+     * @param bytes bytes to decode
+     * @return the decoded string
+     * @throws FormatException if bytes are not a valid sequence in this character set
+     */
+    pub fn decode(&self, bytes: &[u8]) -> Result<String, Error> {
+        match self.get_encoding().decode(bytes, DecoderTrap::Strict) {
+            Ok(value) => Ok(value),
+            Err(err) => Err(Error::FormatException(format!(
+                "Can not decode bytes as {:?}: {}",
+                self, err
+            ))),
+        }
+    }
+}
+
+/**
+ * Without std only the character sets which need no tables are available: UTF-8, and
+ * ISO-8859-1 and ASCII, whose code points are their byte values.
+ */
+#[cfg(not(feature = "std"))]
+impl Charset {
+    fn get_max_code_point(&self) -> Option<u32> {
+        match self {
+            Charset::ASCII => Some(0x7F),
+            Charset::ISO8859_1 => Some(0xFF),
+            _ => None,
+        }
+    }
+
+    pub fn encode(&self, value: &String) -> Result<Vec<u8>, WriterException> {
+        if *self == Charset::UTF8 {
+            return Ok(value.as_bytes().to_vec());
+        }
+        let max = match self.get_max_code_point() {
+            Some(max) => max,
+            None => {
+                return Err(WriterException {
+                    reason: format!("{:?} needs the std feature", self),
+                })
+            }
+        };
+        let mut bytes = Vec::with_capacity(value.len());
+        for c in value.chars() {
+            if c as u32 > max {
+                return Err(WriterException {
+                    reason: format!("Can not encode {} as {:?}: {:?}", value, self, c),
+                });
+            }
+            bytes.push(c as u8);
+        }
+        Ok(bytes)
+    }
+
+    /**
+     * Decodes bytes which were encoded in this character set back to a Unicode string.
+     *
+     * @param bytes bytes to decode
+     * @return the decoded string
+     * @throws FormatException if bytes are not a valid sequence in this character set
+     */
+    pub fn decode(&self, bytes: &[u8]) -> Result<String, Error> {
+        if *self == Charset::UTF8 {
+            return String::from_utf8(bytes.to_vec()).map_err(|err| {
+                Error::FormatException(format!("Can not decode bytes as {:?}: {}", self, err))
+            });
+        }
+        match self.get_max_code_point() {
+            Some(max) if bytes.iter().all(|&b| b as u32 <= max) => {
+                Ok(bytes.iter().map(|&b| b as char).collect())
+            }
+            Some(_) => Err(Error::FormatException(format!(
+                "Can not decode bytes as {:?}",
+                self
+            ))),
+            None => Err(Error::FormatException(format!(
+                "{:?} needs the std feature",
+                self
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod charset_tests {
+    use super::*;
+
+    #[test]
+    fn encode_and_decode() {
+        let value = String::from("Gr\u{fc}\u{df}e");
+        let latin1 = Charset::ISO8859_1.encode(&value).unwrap();
+        assert_eq!([0x47, 0x72, 0xFC, 0xDF, 0x65].to_vec(), latin1);
+        assert_eq!(value, Charset::ISO8859_1.decode(&latin1).unwrap());
+
+        let utf8 = Charset::UTF8.encode(&value).unwrap();
+        assert_eq!(value.as_bytes().to_vec(), utf8);
+        assert_eq!(value, Charset::UTF8.decode(&utf8).unwrap());
+
+        assert!(Charset::ASCII.encode(&value).is_err());
+        assert!(Charset::ASCII.decode(&latin1).is_err());
+    }
 }
@@ -1,9 +1,16 @@
+use crate::BlockCorrections;
+
 pub struct DecoderResult {
     rawBytes: Vec<u8>,
     numBits: i32,
     text: String,
     byteSegments: Vec<Vec<u8>>,
     ecLevel: String,
+    errors_corrected: i32,
+    erasures: i32,
+    block_corrections: Vec<BlockCorrections>,
+    symbol_version: i32,
+    data_mask: i32,
     structuredAppendParity: i32,
     structuredAppendSequenceNumber: i32,
     symbologyModifier: i32,
@@ -72,6 +79,11 @@ impl DecoderResult {
             text: text,
             byteSegments: byteSegments,
             ecLevel: ecLevel,
+            errors_corrected: 0,
+            erasures: 0,
+            block_corrections: Vec::new(),
+            symbol_version: -1,
+            data_mask: -1,
             structuredAppendParity: saParity,
             structuredAppendSequenceNumber: saSequence,
             symbologyModifier: symbologyModifier,
@@ -122,12 +134,61 @@ impl DecoderResult {
         &self.ecLevel
     }
 
-    // /**
-    //  * @return number of errors corrected, or {@code null} if not applicable
-    //  */
-    // pub fn getErrorsCorrected(&self) -> i32{
-    //     self.error
-    // }
+    /**
+     * @return number of errors corrected
+     */
+    pub fn get_errors_corrected(&self) -> i32 {
+        self.errors_corrected
+    }
+
+    pub fn set_errors_corrected(&mut self, errors_corrected: i32) {
+        self.errors_corrected = errors_corrected;
+    }
+
+    /**
+     * @return number of erasures corrected
+     */
+    pub fn get_erasures(&self) -> i32 {
+        self.erasures
+    }
+
+    pub fn set_erasures(&mut self, erasures: i32) {
+        self.erasures = erasures;
+    }
+
+    /**
+     * @return what error correction did in each block, in order; empty if not applicable
+     */
+    pub fn get_block_corrections(&self) -> &[BlockCorrections] {
+        &self.block_corrections
+    }
+
+    pub fn set_block_corrections(&mut self, block_corrections: Vec<BlockCorrections>) {
+        self.block_corrections = block_corrections;
+    }
+
+    /**
+     * @return version of the symbol, or -1 if not applicable
+     */
+    pub fn get_symbol_version(&self) -> i32 {
+        self.symbol_version
+    }
+
+    pub fn set_symbol_version(&mut self, symbol_version: i32) {
+        self.symbol_version = symbol_version;
+    }
+
+    /**
+     * @return data mask pattern of the symbol, or -1 if not applicable
+     */
+    pub fn get_data_mask(&self) -> i32 {
+        self.data_mask
+    }
+
+    pub fn set_data_mask(&mut self, data_mask: i32) {
+        self.data_mask = data_mask;
+    }
+
     pub fn hasStructuredAppend(&self) -> bool {
         self.structuredAppendParity >= 0 && self.structuredAppendSequenceNumber >= 0
     }
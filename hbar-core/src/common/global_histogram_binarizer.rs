@@ -1,8 +1,6 @@
 use crate::common::{BitArray, BitMatrix};
 use crate::{Binarizer, Error, LuminanceSource, ResultError};
 
-use std::cell::RefCell;
-
 /**
  * This Binarizer implementation uses the old ZXing global histogram approach. It is suitable
  * for low-end mobile devices which don't have enough CPU or memory to use a local thresholding
@@ -11,10 +9,11 @@ use std::cell::RefCell;
  *
  * Faster mobile devices and all desktop applications should probably use HybridBinarizer instead.
  *
+ * The histogram is scratch space local to each call, so rows of one image may be binarized
+ * from several threads at once.
+ *
  */
 pub struct GlobalHistogramBinarizer<S> {
-    luminances: RefCell<Vec<u8>>,
-    buckets: RefCell<Vec<i32>>,
     source: S,
 }
 
@@ -24,27 +23,11 @@ impl<S> GlobalHistogramBinarizer<S> {
     const LUMINANCE_BUCKETS: i32 = 1 << GlobalHistogramBinarizer::<S>::LUMINANCE_BITS;
 
     pub fn new(source: S) -> GlobalHistogramBinarizer<S> {
-        GlobalHistogramBinarizer {
-            source: source,
-            luminances: RefCell::new(vec![0]),
-            buckets: RefCell::new(vec![
-                0;
-                GlobalHistogramBinarizer::<S>::LUMINANCE_BUCKETS
-                    as usize
-            ]),
-        }
+        GlobalHistogramBinarizer { source }
     }
 
-    fn init_arrays(&self, luminance_size: i32) {
-        let luminance_size = luminance_size as usize;
-        if self.luminances.borrow().len() == luminance_size {
-            for x in 0..self.luminances.borrow().len() {
-                self.luminances.borrow_mut()[x] = 0;
-            }
-        }
-        for x in 0..GlobalHistogramBinarizer::<S>::LUMINANCE_BUCKETS {
-            self.buckets.borrow_mut()[x as usize] = 0;
-        }
+    fn new_buckets() -> Vec<i32> {
+        vec![0; GlobalHistogramBinarizer::<S>::LUMINANCE_BUCKETS as usize]
     }
 
     fn estimateBlackPoint(buckets: &Vec<i32>) -> ResultError<i32> {
@@ -67,7 +50,7 @@ impl<S> GlobalHistogramBinarizer<S> {
         let mut secondPeak = 0;
         let mut secondPeakScore = 0;
         for x in 0..numBuckets {
-            let distanceToBiggest = (x - firstPeak) as i32;
+            let distanceToBiggest = x as i32 - firstPeak as i32;
             // Encourage more distant second peaks by multiplying by square of distance.
             let score = buckets[x] * distanceToBiggest * distanceToBiggest;
             if score > secondPeakScore {
@@ -123,16 +106,14 @@ where
             row.clear()
         }
 
-        self.init_arrays(width as i32);
-        let localLuminances = source.get_row(y as i32, &(*self.luminances.borrow()))?;
+        let mut buckets = GlobalHistogramBinarizer::<S>::new_buckets();
+        let localLuminances = source.get_row(y as i32, &Vec::new())?;
         for x in 0..width {
             let x = (localLuminances[x as usize] & 0xFF)
                 >> GlobalHistogramBinarizer::<S>::LUMINANCE_SHIFT;
-            self.buckets.borrow_mut()[x as usize] += 1;
+            buckets[x as usize] += 1;
         }
-        let blackPoint =
-            GlobalHistogramBinarizer::<S>::estimateBlackPoint(&(*self.buckets.borrow()))?;
-        println!("blackPoint: {}", blackPoint);
+        let blackPoint = GlobalHistogramBinarizer::<S>::estimateBlackPoint(&buckets)?;
         if width < 3 {
             // Special case for very small images
             for x in 0..width {
@@ -146,7 +127,7 @@ where
             for x in 1..(width - 1) {
                 let right = localLuminances[x as usize + 1] & 0xff;
                 // A simple -1 4 -1 box filter with a weight of 2.
-                if (((center * 4) - left - right) as i32) / 2 < blackPoint {
+                if ((center as i32 * 4) - left as i32 - right as i32) / 2 < blackPoint {
                     row.set(x as i32)
                 }
                 left = center;
@@ -157,7 +138,7 @@ where
     }
 
     // Does not sharpen the data, as this call is intended to only be used by 2D Readers.
-    fn get_black_matrix(&mut self) -> ResultError<BitMatrix> {
+    fn get_black_matrix(&self) -> ResultError<BitMatrix> {
         let source = self.get_luminance_source()?;
         let width = source.get_width() as i32;
         let height = source.get_height() as i32;
@@ -165,19 +146,17 @@ where
 
         // Quickly calculates the histogram by sampling four rows from the image. This proved to be
         // more robust on the blackbox tests than sampling a diagonal as we used to do.
-        self.init_arrays(width);
+        let mut buckets = GlobalHistogramBinarizer::<S>::new_buckets();
         for y in 1..5 {
             let row = height * y / 5;
-            let localLuminances = source.get_row(row, &(*self.luminances.borrow()))?;
+            let localLuminances = source.get_row(row, &Vec::new())?;
             let right = (width * 4) / 5;
             for x in width / 5..right {
                 let pixel = localLuminances[x as usize] & 0xFF;
-                self.buckets.borrow_mut()
-                    [(pixel >> GlobalHistogramBinarizer::<S>::LUMINANCE_SHIFT) as usize] += 1;
+                buckets[(pixel >> GlobalHistogramBinarizer::<S>::LUMINANCE_SHIFT) as usize] += 1;
             }
         }
-        let blackPoint =
-            GlobalHistogramBinarizer::<S>::estimateBlackPoint(&(*self.buckets.borrow()))?;
+        let blackPoint = GlobalHistogramBinarizer::<S>::estimateBlackPoint(&buckets)?;
 
         // We delay reading the entire image luminance until the black point estimation succeeds.
         // Although we end up reading four rows twice, it is consistent with our motto of
@@ -1,53 +1,113 @@
 use crate::common::GlobalHistogramBinarizer;
 use crate::common::{BitArray, BitMatrix};
-use crate::{Binarizer, LuminanceSource, ResultError};
+use crate::{Binarizer, Error, LuminanceSource, ResultError};
 
-// use std::cell::RefCell;
+/**
+ * Tuning for {@link HybridBinarizer}. The defaults are the values ZXing has always used.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HybridBinarizerConfig {
+    /**
+     * Blocks are {@code 1 << block_size_power} pixels square. Larger blocks follow the lighting
+     * less closely but cope better with large modules. Must be in [1, 8]; the default is 3.
+     */
+    pub block_size_power: u32,
+    /**
+     * Blocks whose luminances span no more than this are taken to be all light or all dark,
+     * rather than thresholded at their average. The default is 24.
+     */
+    pub min_dynamic_range: u32,
+}
 
+impl Default for HybridBinarizerConfig {
+    fn default() -> Self {
+        HybridBinarizerConfig {
+            block_size_power: 3,
+            min_dynamic_range: 24,
+        }
+    }
+}
+
+/**
+ * This class implements a local thresholding algorithm, which while slower than the
+ * GlobalHistogramBinarizer, is fairly efficient for what it does. It is designed for
+ * high frequency images of barcodes with black data on white backgrounds. For this application,
+ * it does a much better job than a global blackpoint with severe shadows and gradients.
+ * However it tends to produce artifacts on lower frequency images and is therefore not
+ * a good general purpose binarizer for uses outside ZXing.
+ *
+ * The block size and the minimum dynamic range of a block can be tuned with a
+ * {@link HybridBinarizerConfig}. Images smaller than five blocks in either direction fall back
+ * to the GlobalHistogramBinarizer.
+ */
 pub struct HybridBinarizer<S> {
-    matrix: Option<BitMatrix>,
     binary: GlobalHistogramBinarizer<S>,
+    config: HybridBinarizerConfig,
 }
 
 impl<S> HybridBinarizer<S> {
-    // This class uses 5x5 blocks to compute local luminance, where each block is 8x8 pixels.
-    // So this is the smallest dimension in each axis we can accept.
-    const BLOCK_SIZE_POWER: i32 = 3;
-    const BLOCK_SIZE: u32 = 1 << HybridBinarizer::<S>::BLOCK_SIZE_POWER; // ...0100...00
-    const BLOCK_SIZE_MASK: u32 = HybridBinarizer::<S>::BLOCK_SIZE - 1; // ...0011...11
-    const MINIMUM_DIMENSION: u32 = HybridBinarizer::<S>::BLOCK_SIZE * 5;
-    const MIN_DYNAMIC_RANGE: u32 = 24;
-
     pub fn new(source: S) -> HybridBinarizer<S> {
-        let binary = GlobalHistogramBinarizer::new(source);
         HybridBinarizer {
-            binary: binary,
-            matrix: None,
+            binary: GlobalHistogramBinarizer::new(source),
+            config: HybridBinarizerConfig::default(),
         }
     }
 
+    /**
+     * @throws IllegalArgumentException if the block size is out of range
+     */
+    pub fn with_config(
+        source: S,
+        config: HybridBinarizerConfig,
+    ) -> ResultError<HybridBinarizer<S>> {
+        if !(1..=8).contains(&config.block_size_power) {
+            return Err(Error::IllegalArgumentException(format!(
+                "Block size power must be in [1, 8], but got: {}",
+                config.block_size_power
+            )));
+        }
+        Ok(HybridBinarizer {
+            binary: GlobalHistogramBinarizer::new(source),
+            config,
+        })
+    }
+
+    pub fn get_config(&self) -> &HybridBinarizerConfig {
+        &self.config
+    }
+
+    fn block_size(&self) -> u32 {
+        1 << self.config.block_size_power
+    }
+
+    // The 5x5 grid of blocks used to average black points is the smallest image we can accept.
+    fn minimum_dimension(&self) -> u32 {
+        self.block_size() * 5
+    }
+
     /**
      * Calculates a single black point for each block of pixels and saves it away.
      * See the following thread for a discussion of this algorithm:
      *  http://groups.google.com/group/zxing/browse_thread/thread/d06efa2c35a7ddc0
      */
     fn calculateBlackPoints(
+        &self,
         luminances: &Vec<u8>,
         subWidth: i32,
         subHeight: i32,
         width: i32,
         height: i32,
     ) -> Vec<Vec<i32>> {
-        let maxYOffset = height - HybridBinarizer::<S>::BLOCK_SIZE as i32;
-        let maxXOffset = width - HybridBinarizer::<S>::BLOCK_SIZE as i32;
+        let maxYOffset = height - self.block_size() as i32;
+        let maxXOffset = width - self.block_size() as i32;
         let mut blackPoints = vec![vec![0; subWidth as usize]; subHeight as usize];
         for y in 0..subHeight {
-            let mut yoffset = y << HybridBinarizer::<S>::BLOCK_SIZE_POWER;
+            let mut yoffset = y << self.config.block_size_power as i32;
             if yoffset > maxYOffset {
                 yoffset = maxYOffset;
             }
             for x in 0..subWidth {
-                let mut xoffset = x << HybridBinarizer::<S>::BLOCK_SIZE_POWER;
+                let mut xoffset = x << self.config.block_size_power as i32;
                 if xoffset > maxXOffset {
                     xoffset = maxXOffset;
                 }
@@ -55,8 +115,9 @@ impl<S> HybridBinarizer<S> {
                 let mut min = 0xFF;
                 let mut max = 0;
                 let mut offset = yoffset * width + xoffset;
-                for mut yy in 0..HybridBinarizer::<S>::BLOCK_SIZE {
-                    for xx in 0..HybridBinarizer::<S>::BLOCK_SIZE {
+                let mut yy = 0;
+                while yy < self.block_size() {
+                    for xx in 0..self.block_size() {
                         let pixel = luminances[offset as usize + xx as usize] & 0xFF;
                         sum += pixel as i32;
                         // still looking for good contrast
@@ -68,22 +129,26 @@ impl<S> HybridBinarizer<S> {
                         }
                     }
                     // short-circuit min/max tests once dynamic range is met
-                    if max - min > HybridBinarizer::<S>::MIN_DYNAMIC_RANGE as u8 {
+                    if (max - min) as u32 > self.config.min_dynamic_range {
                         // finish the rest of the rows quickly
+                        yy += 1;
                         offset += width;
-                        for _yy in yy..HybridBinarizer::<S>::BLOCK_SIZE {
-                            for _xx in 0..HybridBinarizer::<S>::BLOCK_SIZE {
-                                sum += (luminances[offset as usize + _xx as usize] & 0xFF) as i32;
+                        while yy < self.block_size() {
+                            for xx in 0..self.block_size() {
+                                sum += (luminances[offset as usize + xx as usize] & 0xFF) as i32;
                             }
-                            yy = _yy
+                            yy += 1;
+                            offset += width;
                         }
+                        break;
                     }
+                    yy += 1;
                     offset += width
                 }
 
                 // The default estimate is the average of the values in the block.
-                let mut average = sum >> (HybridBinarizer::<S>::BLOCK_SIZE_POWER * 2);
-                if max - min <= HybridBinarizer::<S>::MIN_DYNAMIC_RANGE as u8 {
+                let mut average = sum >> (self.config.block_size_power as i32 * 2);
+                if (max - min) as u32 <= self.config.min_dynamic_range {
                     // If variation within the block is low, assume this is a block with only light or only
                     // dark pixels. In that case we do not want to use the average, as it would divide this
                     // low contrast area into black and white pixels, essentially creating data out of noise.
@@ -122,7 +187,9 @@ impl<S> HybridBinarizer<S> {
      * of the blocks around it. Also handles the corner cases (fractional blocks are computed based
      * on the last pixels in the row/column which are also used in the previous block).
      */
+    #[allow(clippy::too_many_arguments)]
     fn calculateThresholdForBlock(
+        &self,
         luminances: &Vec<u8>,
         subWidth: i32,
         subHeight: i32,
@@ -131,16 +198,16 @@ impl<S> HybridBinarizer<S> {
         blackPoints: &Vec<Vec<i32>>,
         matrix: &mut BitMatrix,
     ) {
-        let maxYOffset = height - HybridBinarizer::<S>::BLOCK_SIZE as i32;
-        let maxXOffset = width - HybridBinarizer::<S>::BLOCK_SIZE as i32;
+        let maxYOffset = height - self.block_size() as i32;
+        let maxXOffset = width - self.block_size() as i32;
         for y in 0..subHeight {
-            let mut yoffset = y << HybridBinarizer::<S>::BLOCK_SIZE_POWER;
+            let mut yoffset = y << self.config.block_size_power as i32;
             if yoffset > maxYOffset {
                 yoffset = maxYOffset;
             }
             let top = HybridBinarizer::<S>::cap(y, subHeight - 3);
             for x in 0..subWidth {
-                let mut xoffset = x << HybridBinarizer::<S>::BLOCK_SIZE_POWER;
+                let mut xoffset = x << self.config.block_size_power as i32;
                 if xoffset > maxXOffset {
                     xoffset = maxXOffset;
                 }
@@ -155,9 +222,7 @@ impl<S> HybridBinarizer<S> {
                         + blackRow[left as usize + 2]
                 }
                 let average = sum / 25;
-                HybridBinarizer::<S>::thresholdBlock(
-                    luminances, xoffset, yoffset, average, width, matrix,
-                );
+                self.thresholdBlock(luminances, xoffset, yoffset, average, width, matrix);
             }
         }
     }
@@ -174,6 +239,7 @@ impl<S> HybridBinarizer<S> {
      * Applies a single threshold to a block of pixels.
      */
     fn thresholdBlock(
+        &self,
         luminances: &Vec<u8>,
         xoffset: i32,
         yoffset: i32,
@@ -182,8 +248,8 @@ impl<S> HybridBinarizer<S> {
         matrix: &mut BitMatrix,
     ) {
         let mut offset = yoffset * stride + xoffset;
-        for y in 9..HybridBinarizer::<S>::BLOCK_SIZE {
-            for x in 0..HybridBinarizer::<S>::BLOCK_SIZE {
+        for y in 0..self.block_size() {
+            for x in 0..self.block_size() {
                 // Comparison needs to be <= so that black == 0 pixels are black even if the threshold is 0.
                 if ((luminances[offset as usize + x as usize] & 0xFF) as i32) <= threshold {
                     matrix.set(xoffset as u32 + x, yoffset as u32 + y);
@@ -203,30 +269,24 @@ where
     }
 
     /**
-     * Calculates the final BitMatrix once for all requests. This could be called once from the
-     * constructor instead, but there are some advantages to doing it lazily, such as making
-     * profiling easier, and not doing heavy lifting when callers don't expect it.
+     * Calculates the final BitMatrix. {@link BinaryBitmap} keeps the result, so this runs once per
+     * image no matter how many readers ask for the matrix.
      */
-    fn get_black_matrix(&mut self) -> ResultError<BitMatrix> {
-        if let Some(matrix) = &self.matrix {
-            return Ok((*matrix).clone());
-        }
+    fn get_black_matrix(&self) -> ResultError<BitMatrix> {
         let source = self.get_luminance_source()?;
         let width = source.get_width();
         let height = source.get_height();
-        if width >= HybridBinarizer::<S>::MINIMUM_DIMENSION
-            && height >= HybridBinarizer::<S>::MINIMUM_DIMENSION
-        {
+        if width >= self.minimum_dimension() && height >= self.minimum_dimension() {
             let luminances = source.get_matrix()?;
-            let mut subWidth = width >> HybridBinarizer::<S>::BLOCK_SIZE_POWER;
-            if (width & HybridBinarizer::<S>::BLOCK_SIZE_MASK) != 0 {
+            let mut subWidth = width >> self.config.block_size_power as i32;
+            if (width & (self.block_size() - 1)) != 0 {
                 subWidth += 1
             }
-            let mut subHeight = height >> HybridBinarizer::<S>::BLOCK_SIZE_POWER;
-            if (height & HybridBinarizer::<S>::BLOCK_SIZE_MASK) != 0 {
+            let mut subHeight = height >> self.config.block_size_power as i32;
+            if (height & (self.block_size() - 1)) != 0 {
                 subHeight += 1;
             }
-            let blackPoints = HybridBinarizer::<S>::calculateBlackPoints(
+            let blackPoints = self.calculateBlackPoints(
                 &luminances,
                 subWidth as i32,
                 subHeight as i32,
@@ -234,7 +294,7 @@ where
                 height as i32,
             );
             let mut newMatrix = BitMatrix::new2(width as i32, height as i32)?;
-            HybridBinarizer::<S>::calculateThresholdForBlock(
+            self.calculateThresholdForBlock(
                 &luminances,
                 subWidth as i32,
                 subHeight as i32,
@@ -243,17 +303,18 @@ where
                 &blackPoints,
                 &mut newMatrix,
             );
-            self.matrix = Some(newMatrix);
+            Ok(newMatrix)
         } else {
             // If the image is too small, fall back to the global histogram approach.
-            self.matrix = Some(self.binary.get_black_matrix()?);
+            self.binary.get_black_matrix()
         }
-
-        Ok(self.matrix.as_ref().unwrap().clone())
     }
 
     fn create_binarizer(&self, source: S) -> Self {
-        HybridBinarizer::new(source)
+        HybridBinarizer {
+            binary: GlobalHistogramBinarizer::new(source),
+            config: self.config,
+        }
     }
 
     fn get_luminance_source(&self) -> ResultError<&S> {
@@ -0,0 +1,359 @@
+use crate::common::{BitArray, BitMatrix};
+use crate::{Binarizer, Error, LuminanceSource, ResultError};
+
+/**
+ * How {@link LocalThresholdBinarizer} derives a pixel's threshold T from the mean m and the
+ * standard deviation s of the luminances in the window around it.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocalThresholdMethod {
+    /**
+     * T = m * (1 + k * (s / R - 1)). Backgrounds, where s is small, end up well below their mean,
+     * which keeps noise and stains out; a good default for paper.
+     */
+    Sauvola,
+    /**
+     * T = m + k * s. Follows faint ink more closely than Sauvola, at the cost of more noise.
+     */
+    Niblack,
+}
+
+/**
+ * Tuning for {@link LocalThresholdBinarizer}.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LocalThresholdConfig {
+    pub method: LocalThresholdMethod,
+    /**
+     * Side of the square window around each pixel, in pixels. Must be odd and at least 3; it
+     * should span a few modules of the barcode.
+     */
+    pub window_size: u32,
+    /**
+     * Weight of the standard deviation. Smaller values of k help Sauvola with faded prints.
+     */
+    pub k: f64,
+    /**
+     * R, the dynamic range of the standard deviation, used by Sauvola. Must be positive.
+     */
+    pub dynamic_range: f64,
+    /**
+     * Windows whose standard deviation is below this hold no edge. Their pixels are black only
+     * if the window is darker than R / 2, so flat backgrounds do not turn into noise.
+     */
+    pub min_standard_deviation: f64,
+}
+
+impl LocalThresholdConfig {
+    pub fn sauvola() -> Self {
+        LocalThresholdConfig {
+            method: LocalThresholdMethod::Sauvola,
+            window_size: 31,
+            k: 0.2,
+            dynamic_range: 128.0,
+            min_standard_deviation: 8.0,
+        }
+    }
+
+    pub fn niblack() -> Self {
+        LocalThresholdConfig {
+            method: LocalThresholdMethod::Niblack,
+            k: -0.2,
+            ..LocalThresholdConfig::sauvola()
+        }
+    }
+}
+
+impl Default for LocalThresholdConfig {
+    fn default() -> Self {
+        LocalThresholdConfig::sauvola()
+    }
+}
+
+/**
+ * Sums and sums of squares of the luminances above and to the left of each pixel, so that the
+ * mean and standard deviation of any window take constant time.
+ */
+struct IntegralImage {
+    stride: usize,
+    sums: Vec<u64>,
+    squares: Vec<u64>,
+}
+
+impl IntegralImage {
+    fn new(luminances: &[u8], width: usize, height: usize) -> IntegralImage {
+        let stride = width + 1;
+        let mut sums = vec![0; stride * (height + 1)];
+        let mut squares = vec![0; stride * (height + 1)];
+        for y in 0..height {
+            let mut row_sum = 0;
+            let mut row_squares = 0;
+            for x in 0..width {
+                let luminance = luminances[y * width + x] as u64;
+                row_sum += luminance;
+                row_squares += luminance * luminance;
+                let offset = (y + 1) * stride + x + 1;
+                sums[offset] = sums[offset - stride] + row_sum;
+                squares[offset] = squares[offset - stride] + row_squares;
+            }
+        }
+        IntegralImage {
+            stride,
+            sums,
+            squares,
+        }
+    }
+
+    fn window(
+        values: &[u64],
+        stride: usize,
+        left: usize,
+        top: usize,
+        right: usize,
+        bottom: usize,
+    ) -> u64 {
+        values[bottom * stride + right] + values[top * stride + left]
+            - values[top * stride + right]
+            - values[bottom * stride + left]
+    }
+
+    /**
+     * @return the mean and standard deviation of [left, right) x [top, bottom)
+     */
+    fn statistics(&self, left: usize, top: usize, right: usize, bottom: usize) -> (f64, f64) {
+        let count = ((right - left) * (bottom - top)) as f64;
+        let sum = IntegralImage::window(&self.sums, self.stride, left, top, right, bottom);
+        let squares = IntegralImage::window(&self.squares, self.stride, left, top, right, bottom);
+        let mean = sum as f64 / count;
+        let variance = (squares as f64 / count - mean * mean).max(0.0);
+        (mean, variance.sqrt())
+    }
+}
+
+/**
+ * This Binarizer thresholds each pixel against the luminances of the window around it, with
+ * Sauvola's or Niblack's method. The window statistics come from integral images, so the cost
+ * does not depend on the window size.
+ *
+ * It follows uneven lighting, glare and fading more closely than HybridBinarizer, whose
+ * thresholds are per block, but is slower. A black row is computed from the rows within half
+ * a window of it, and agrees with the same row of the black matrix.
+ */
+pub struct LocalThresholdBinarizer<S> {
+    source: S,
+    config: LocalThresholdConfig,
+}
+
+impl<S> LocalThresholdBinarizer<S> {
+    /**
+     * Creates a binarizer using Sauvola's method with the default settings.
+     */
+    pub fn new(source: S) -> LocalThresholdBinarizer<S> {
+        LocalThresholdBinarizer {
+            source,
+            config: LocalThresholdConfig::default(),
+        }
+    }
+
+    /**
+     * @throws IllegalArgumentException if the window size is even or less than 3, or the dynamic
+     *  range is not positive
+     */
+    pub fn with_config(
+        source: S,
+        config: LocalThresholdConfig,
+    ) -> ResultError<LocalThresholdBinarizer<S>> {
+        if config.window_size < 3 || config.window_size & 1 == 0 {
+            return Err(Error::IllegalArgumentException(format!(
+                "Window size must be odd and at least 3, but got: {}",
+                config.window_size
+            )));
+        }
+        if config.dynamic_range.is_nan() || config.dynamic_range <= 0.0 {
+            return Err(Error::IllegalArgumentException(format!(
+                "Dynamic range must be positive, but got: {}",
+                config.dynamic_range
+            )));
+        }
+        Ok(LocalThresholdBinarizer { source, config })
+    }
+
+    pub fn get_config(&self) -> &LocalThresholdConfig {
+        &self.config
+    }
+
+    fn is_black(&self, luminance: u8, mean: f64, standard_deviation: f64) -> bool {
+        let config = &self.config;
+        if standard_deviation < config.min_standard_deviation {
+            return mean < config.dynamic_range / 2.0;
+        }
+        let threshold = match config.method {
+            LocalThresholdMethod::Sauvola => {
+                mean * (1.0 + config.k * (standard_deviation / config.dynamic_range - 1.0))
+            }
+            LocalThresholdMethod::Niblack => mean + config.k * standard_deviation,
+        };
+        luminance as f64 <= threshold
+    }
+
+    /**
+     * Calls {@code set} with the x of every black pixel in row {@code y} of the given
+     * luminances, which are {@code width} wide.
+     */
+    fn threshold_row(
+        &self,
+        integral: &IntegralImage,
+        luminances: &[u8],
+        width: usize,
+        height: usize,
+        y: usize,
+        mut set: impl FnMut(usize),
+    ) {
+        let half = self.config.window_size as usize / 2;
+        let top = y.saturating_sub(half);
+        let bottom = (y + half + 1).min(height);
+        for x in 0..width {
+            let left = x.saturating_sub(half);
+            let right = (x + half + 1).min(width);
+            let (mean, standard_deviation) = integral.statistics(left, top, right, bottom);
+            if self.is_black(luminances[y * width + x], mean, standard_deviation) {
+                set(x);
+            }
+        }
+    }
+}
+
+impl<S> Binarizer<S> for LocalThresholdBinarizer<S>
+where
+    S: LuminanceSource,
+{
+    fn get_black_row(&self, y: u32, row: &BitArray) -> ResultError<BitArray> {
+        let source = self.get_luminance_source()?;
+        let width = source.get_width() as usize;
+        let height = source.get_height() as usize;
+        let mut row = row.clone();
+        if (row.get_size() as usize) < width {
+            row = BitArray::new1(width as u32);
+        } else {
+            row.clear()
+        }
+
+        // Only the rows the windows of this row reach are needed.
+        let half = self.config.window_size as usize / 2;
+        let top = (y as usize).saturating_sub(half);
+        let bottom = (y as usize + half + 1).min(height);
+        let mut band = Vec::with_capacity(width * (bottom - top));
+        for band_y in top..bottom {
+            band.extend_from_slice(&source.get_row(band_y as i32, &Vec::new())?[..width]);
+        }
+        let integral = IntegralImage::new(&band, width, bottom - top);
+        self.threshold_row(
+            &integral,
+            &band,
+            width,
+            bottom - top,
+            y as usize - top,
+            |x| row.set(x as i32),
+        );
+        Ok(row)
+    }
+
+    fn get_black_matrix(&self) -> ResultError<BitMatrix> {
+        let source = self.get_luminance_source()?;
+        let width = source.get_width() as usize;
+        let height = source.get_height() as usize;
+        let luminances = source.get_matrix()?;
+        let integral = IntegralImage::new(&luminances, width, height);
+
+        let mut matrix = BitMatrix::new2(width as i32, height as i32)?;
+        for y in 0..height {
+            self.threshold_row(&integral, &luminances, width, height, y, |x| {
+                matrix.set(x as u32, y as u32)
+            });
+        }
+        Ok(matrix)
+    }
+
+    fn create_binarizer(&self, source: S) -> Self {
+        LocalThresholdBinarizer {
+            source,
+            config: self.config,
+        }
+    }
+
+    fn get_luminance_source(&self) -> ResultError<&S> {
+        Ok(&self.source)
+    }
+
+    fn get_width(&self) -> u32 {
+        self.source.get_width()
+    }
+
+    fn get_height(&self) -> u32 {
+        self.source.get_height()
+    }
+}
+
+#[cfg(test)]
+mod local_threshold_binarizer_tests {
+    use super::*;
+    use crate::GrayLuminanceSource;
+
+    // Vertical stripes of 4 pixel modules, lit from the left: the ink on the right is lighter
+    // than the paper on the left, so no single threshold separates them.
+    fn shaded_stripes() -> GrayLuminanceSource<'static> {
+        let (width, height) = (64, 16);
+        let luminances = (0..width * height)
+            .map(|offset| {
+                let x = offset % width;
+                let light = 60 + 2 * x as u32;
+                if (x / 4) % 2 == 0 {
+                    (light * 2 / 5) as u8
+                } else {
+                    light.min(255) as u8
+                }
+            })
+            .collect();
+        GrayLuminanceSource::from_vec(luminances, width as u32, height as u32).unwrap()
+    }
+
+    #[test]
+    fn follows_uneven_lighting() {
+        for config in [
+            LocalThresholdConfig::sauvola(),
+            LocalThresholdConfig::niblack(),
+        ]
+        .iter()
+        {
+            let config = LocalThresholdConfig {
+                window_size: 9,
+                ..*config
+            };
+            let binarizer = LocalThresholdBinarizer::with_config(shaded_stripes(), config).unwrap();
+            let matrix = binarizer.get_black_matrix().unwrap();
+            for y in [0, 8, 15].iter() {
+                let row = binarizer.get_black_row(*y, &BitArray::new()).unwrap();
+                for x in 0..64 {
+                    assert_eq!(
+                        (x / 4) % 2 == 0,
+                        matrix.get(x, *y),
+                        "{:?} ({}, {})",
+                        config.method,
+                        x,
+                        y
+                    );
+                    assert_eq!(matrix.get(x, *y), row.get(x as i32));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_bad_config() {
+        let config = LocalThresholdConfig {
+            window_size: 8,
+            ..LocalThresholdConfig::default()
+        };
+        assert!(LocalThresholdBinarizer::with_config(shaded_stripes(), config).is_err());
+    }
+}
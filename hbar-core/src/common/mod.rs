@@ -1,19 +1,59 @@
+#[cfg(any(feature = "decode", all(feature = "qrcode", feature = "encode")))]
 mod bit_array;
 mod bit_matrix;
+#[cfg(all(feature = "qrcode", feature = "decode"))]
+mod bit_source;
+#[cfg(all(feature = "qrcode", any(feature = "encode", feature = "decode")))]
+mod character_set_eci;
 mod charset;
+#[cfg(all(feature = "qrcode", feature = "decode"))]
 mod decoder_result;
+#[cfg(feature = "decode")]
 mod detector;
+#[cfg(feature = "decode")]
 mod global_histogram_binarizer;
+#[cfg(feature = "decode")]
 mod hybrid_binarizer;
+#[cfg(feature = "decode")]
+mod local_threshold_binarizer;
+#[cfg(feature = "decode")]
+mod otsu_binarizer;
+#[cfg(any(feature = "encode", feature = "decode"))]
 mod reedsolomon;
+#[cfg(all(feature = "qrcode", feature = "decode"))]
+mod string_utils;
+#[cfg(feature = "decode")]
 mod system_times;
 
+#[cfg(any(feature = "decode", all(feature = "qrcode", feature = "encode")))]
 pub use bit_array::BitArray;
 pub use bit_matrix::BitMatrix;
+#[cfg(all(feature = "qrcode", feature = "decode"))]
+pub use bit_source::BitSource;
+#[cfg(all(feature = "qrcode", any(feature = "encode", feature = "decode")))]
+pub use character_set_eci::CharacterSetECI;
 pub use charset::Charset;
+#[cfg(all(feature = "qrcode", feature = "decode"))]
 pub use decoder_result::DecoderResult;
+#[cfg(feature = "decode")]
 pub use detector::MathUtils;
+#[cfg(feature = "decode")]
 pub use global_histogram_binarizer::GlobalHistogramBinarizer;
-pub use hybrid_binarizer::HybridBinarizer;
-pub use reedsolomon::{GenericGF, GenericGFEnum, ReedSolomonDecoder, ReedSolomonEncoder};
+#[cfg(feature = "decode")]
+pub use hybrid_binarizer::{HybridBinarizer, HybridBinarizerConfig};
+#[cfg(feature = "decode")]
+pub use local_threshold_binarizer::{
+    LocalThresholdBinarizer, LocalThresholdConfig, LocalThresholdMethod,
+};
+#[cfg(feature = "decode")]
+pub use otsu_binarizer::OtsuBinarizer;
+#[cfg(feature = "encode")]
+pub use reedsolomon::ReedSolomonEncoder;
+#[cfg(feature = "decode")]
+pub use reedsolomon::{Corrections, ReedSolomonDecoder};
+#[cfg(any(feature = "encode", feature = "decode"))]
+pub use reedsolomon::{GenericGF, GenericGFEnum};
+#[cfg(all(feature = "qrcode", feature = "decode"))]
+pub use string_utils::StringUtils;
+#[cfg(feature = "decode")]
 pub use system_times::SystemTimes;
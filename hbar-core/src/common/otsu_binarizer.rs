@@ -0,0 +1,180 @@
+use crate::common::{BitArray, BitMatrix};
+use crate::{Binarizer, Error, LuminanceSource, ResultError};
+
+/**
+ * This Binarizer picks one global threshold with Otsu's method: of all 256 luminances it takes
+ * the one which best separates the histogram into a dark and a light class, that is the one
+ * maximising the variance between the two classes.
+ *
+ * Unlike the GlobalHistogramBinarizer it looks at every pixel, and it copes better with faded
+ * prints whose dark and light peaks are close together. Like any global threshold it cannot
+ * handle uneven lighting; use HybridBinarizer or LocalThresholdBinarizer for that.
+ *
+ * Rows are thresholded on their own histogram, the matrix on the histogram of the whole image.
+ */
+pub struct OtsuBinarizer<S> {
+    source: S,
+}
+
+impl<S> OtsuBinarizer<S> {
+    // Below this distance between the means of the two classes there is no barcode to find.
+    const MIN_CONTRAST: f64 = 24.0;
+
+    pub fn new(source: S) -> OtsuBinarizer<S> {
+        OtsuBinarizer { source }
+    }
+
+    /**
+     * Computes Otsu's threshold for a histogram of luminances. Luminances less than or equal to
+     * the threshold are black.
+     *
+     * @throws NotFoundException if the histogram has too little contrast
+     */
+    pub(crate) fn threshold(histogram: &[u32; 256]) -> ResultError<u8> {
+        let total: f64 = histogram.iter().map(|&count| count as f64).sum();
+        let sum: f64 = histogram
+            .iter()
+            .enumerate()
+            .map(|(luminance, &count)| luminance as f64 * count as f64)
+            .sum();
+
+        let mut dark_count = 0.0;
+        let mut dark_sum = 0.0;
+        let mut best_variance = 0.0;
+        let mut best = None;
+        for (luminance, &count) in histogram.iter().enumerate() {
+            dark_count += count as f64;
+            if dark_count == 0.0 {
+                continue;
+            }
+            let light_count = total - dark_count;
+            if light_count == 0.0 {
+                break;
+            }
+            dark_sum += luminance as f64 * count as f64;
+            let dark_mean = dark_sum / dark_count;
+            let light_mean = (sum - dark_sum) / light_count;
+            let variance = dark_count * light_count * (light_mean - dark_mean).powi(2);
+            if variance > best_variance {
+                best_variance = variance;
+                best = Some((luminance as u8, light_mean - dark_mean));
+            }
+        }
+
+        match best {
+            Some((threshold, contrast)) if contrast > OtsuBinarizer::<S>::MIN_CONTRAST => {
+                Ok(threshold)
+            }
+            _ => Err(Error::NotFoundException(String::from(
+                "Too little contrast in the image",
+            ))),
+        }
+    }
+
+    pub(crate) fn histogram(luminances: &[u8]) -> [u32; 256] {
+        let mut histogram = [0; 256];
+        for &luminance in luminances {
+            histogram[luminance as usize] += 1;
+        }
+        histogram
+    }
+}
+
+impl<S> Binarizer<S> for OtsuBinarizer<S>
+where
+    S: LuminanceSource,
+{
+    fn get_black_row(&self, y: u32, row: &BitArray) -> ResultError<BitArray> {
+        let source = self.get_luminance_source()?;
+        let width = source.get_width();
+        let mut row = row.clone();
+        if (row.get_size() as u32) < width {
+            row = BitArray::new1(width);
+        } else {
+            row.clear()
+        }
+
+        let luminances = source.get_row(y as i32, &Vec::new())?;
+        let luminances = &luminances[..width as usize];
+        let threshold = OtsuBinarizer::<S>::threshold(&OtsuBinarizer::<S>::histogram(luminances))?;
+        for (x, &luminance) in luminances.iter().enumerate() {
+            if luminance <= threshold {
+                row.set(x as i32);
+            }
+        }
+        Ok(row)
+    }
+
+    fn get_black_matrix(&self) -> ResultError<BitMatrix> {
+        let source = self.get_luminance_source()?;
+        let width = source.get_width();
+        let height = source.get_height();
+        let luminances = source.get_matrix()?;
+        let luminances = &luminances[..(width * height) as usize];
+        let threshold = OtsuBinarizer::<S>::threshold(&OtsuBinarizer::<S>::histogram(luminances))?;
+
+        let mut matrix = BitMatrix::new2(width as i32, height as i32)?;
+        for (offset, &luminance) in luminances.iter().enumerate() {
+            if luminance <= threshold {
+                matrix.set(offset as u32 % width, offset as u32 / width);
+            }
+        }
+        Ok(matrix)
+    }
+
+    fn create_binarizer(&self, source: S) -> Self {
+        OtsuBinarizer::new(source)
+    }
+
+    fn get_luminance_source(&self) -> ResultError<&S> {
+        Ok(&self.source)
+    }
+
+    fn get_width(&self) -> u32 {
+        self.source.get_width()
+    }
+
+    fn get_height(&self) -> u32 {
+        self.source.get_height()
+    }
+}
+
+#[cfg(test)]
+mod otsu_binarizer_tests {
+    use super::*;
+    use crate::GrayLuminanceSource;
+
+    #[test]
+    fn separates_close_peaks() {
+        // A faded print: ink at 150-160 on paper at 200-210, far from the middle of the range.
+        let luminances: Vec<u8> = (0..64)
+            .map(|i| if i % 2 == 0 { 150 + (i % 11) } else { 200 + (i % 11) } as u8)
+            .collect();
+        let source = GrayLuminanceSource::from_vec(luminances, 8, 8).unwrap();
+        let binarizer = OtsuBinarizer::new(source);
+        let threshold = OtsuBinarizer::<GrayLuminanceSource>::threshold(&OtsuBinarizer::<
+            GrayLuminanceSource,
+        >::histogram(
+            &binarizer.source.get_matrix().unwrap(),
+        ))
+        .unwrap();
+        assert!((160..200).contains(&threshold));
+
+        let matrix = binarizer.get_black_matrix().unwrap();
+        let row = binarizer.get_black_row(3, &BitArray::new()).unwrap();
+        for x in 0..8 {
+            assert_eq!(x % 2 == 0, matrix.get(x, 3));
+            assert_eq!(x % 2 == 0, row.get(x as i32));
+        }
+    }
+
+    #[test]
+    fn rejects_flat_images() {
+        let source = GrayLuminanceSource::from_vec(vec![128; 64], 8, 8).unwrap();
+        let binarizer = OtsuBinarizer::new(source);
+        assert!(matches!(
+            binarizer.get_black_matrix(),
+            Err(Error::NotFoundException(_))
+        ));
+    }
+}
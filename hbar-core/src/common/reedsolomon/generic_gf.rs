@@ -1,8 +1,15 @@
 use crate::common::reedsolomon::GenericGFPoly;
 use crate::Error;
 
-use std::rc::Rc;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
 
+use once_cell::race::OnceBox;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GenericGFEnum {
     AztecData12,
     AztecData10,
@@ -15,16 +22,46 @@ pub enum GenericGFEnum {
 }
 
 impl GenericGFEnum {
-    pub fn get(&self) -> GenericGF {
+    /**
+     * Returns the field, whose tables are built the first time it is asked for and shared by
+     * every caller from then on.
+     */
+    pub fn get(&self) -> Arc<GenericGF> {
+        static FIELDS: [OnceBox<Arc<GenericGF>>; GenericGFEnum::DISTINCT_FIELDS] = [
+            OnceBox::new(),
+            OnceBox::new(),
+            OnceBox::new(),
+            OnceBox::new(),
+            OnceBox::new(),
+            OnceBox::new(),
+        ];
+        let field = FIELDS[self.index()].get_or_init(|| {
+            Box::new(Arc::new(match self.index() {
+                0 => GenericGF::new(0x1069, 4096, 1), // x^12 + x^6 + x^5 + x^3 + 1
+                1 => GenericGF::new(0x409, 1024, 1),  // x^10 + x^3 + 1
+                2 => GenericGF::new(0x43, 64, 1),     // x^6 + x + 1
+                3 => GenericGF::new(0x13, 16, 1),     // x^4 + x + 1
+                4 => GenericGF::new(0x011D, 256, 0),  // x^8 + x^4 + x^3 + x^2 + 1
+                _ => GenericGF::new(0x012D, 256, 1),  // x^8 + x^5 + x^3 + x^2 + 1
+            }))
+        });
+        Arc::clone(field)
+    }
+
+    pub(crate) const DISTINCT_FIELDS: usize = 6;
+
+    /**
+     * @return the position of this field among the distinct ones, the aliases AztecData8 and
+     *  MaxicodeField64 sharing that of the field they stand for
+     */
+    pub(crate) fn index(&self) -> usize {
         match self {
-            GenericGFEnum::AztecData12 => GenericGF::new(0x1069, 4096, 1), // x^12 + x^6 + x^5 + x^3 + 1
-            GenericGFEnum::AztecData10 => GenericGF::new(0x409, 1024, 1),  // x^10 + x^3 + 1
-            GenericGFEnum::AztecData6 => GenericGF::new(0x43, 64, 1),      // x^6 + x + 1
-            GenericGFEnum::AztecParam => GenericGF::new(0x13, 16, 1),      // x^4 + x + 1
-            GenericGFEnum::QrCodeField256 => GenericGF::new(0x011D, 256, 0), // x^8 + x^4 + x^3 + x^2 + 1
-            GenericGFEnum::DataMatrixField256 => GenericGF::new(0x012D, 256, 1), // x^8 + x^5 + x^3 + x^2 + 1
-            GenericGFEnum::AztecData8 => GenericGFEnum::DataMatrixField256.get(),
-            GenericGFEnum::MaxicodeField64 => GenericGFEnum::AztecData6.get(),
+            GenericGFEnum::AztecData12 => 0,
+            GenericGFEnum::AztecData10 => 1,
+            GenericGFEnum::AztecData6 | GenericGFEnum::MaxicodeField64 => 2,
+            GenericGFEnum::AztecParam => 3,
+            GenericGFEnum::QrCodeField256 => 4,
+            GenericGFEnum::DataMatrixField256 | GenericGFEnum::AztecData8 => 5,
         }
     }
 }
@@ -82,18 +119,22 @@ impl GenericGF {
         }
     }
 
-    pub fn get_zero(&self) -> Result<GenericGFPoly, Error> {
-        GenericGFPoly::new(Rc::new(self.clone()), vec![0])
+    pub fn get_zero(self: &Arc<Self>) -> Result<GenericGFPoly, Error> {
+        GenericGFPoly::new(Arc::clone(self), vec![0])
     }
 
-    pub fn get_one(&self) -> Result<GenericGFPoly, Error> {
-        GenericGFPoly::new(Rc::new(self.clone()), vec![1])
+    pub fn get_one(self: &Arc<Self>) -> Result<GenericGFPoly, Error> {
+        GenericGFPoly::new(Arc::clone(self), vec![1])
     }
 
     /**
      * @return the monomial representing coefficient * x^degree
      */
-    pub fn build_monomial(&self, degree: i32, coefficient: i32) -> Result<GenericGFPoly, Error> {
+    pub fn build_monomial(
+        self: &Arc<Self>,
+        degree: i32,
+        coefficient: i32,
+    ) -> Result<GenericGFPoly, Error> {
         if degree < 0 {
             return Err(Error::IllegalArgumentException(format!(
                 "build monomial error degree: {}",
@@ -108,7 +149,7 @@ impl GenericGF {
         let mut coefficients = vec![0; degree as usize + 1];
         coefficients[0] = coefficient;
 
-        GenericGFPoly::new(Rc::new(self.clone()), coefficients)
+        GenericGFPoly::new(Arc::clone(self), coefficients)
     }
 
     /**
@@ -192,4 +233,65 @@ impl GenericGF {
     pub fn get_generator_base(&self) -> i32 {
         self.generator_base
     }
+
+    /**
+     * Splits bytes into as few blocks as fit, each with its error-correction bytes, in one
+     * codeword of this field. As in QR Code the blocks differ in length by at most one, the
+     * longer ones last.
+     *
+     * @param data_len how many data bytes there are
+     * @param ec_len how many error-correction bytes each block gets
+     * @return the number of data bytes in each block
+     * @throws IllegalArgumentException if the field has more than 256 elements, or there are
+     *  no data bytes or no room for them
+     */
+    #[cfg(any(feature = "encode", feature = "decode"))]
+    pub(crate) fn byte_blocks(&self, data_len: usize, ec_len: usize) -> Result<Vec<usize>, Error> {
+        let size = self.size as usize;
+        if size > 256 {
+            return Err(Error::IllegalArgumentException(format!(
+                "Bytes need a field of at most 256 elements, not {}",
+                size
+            )));
+        }
+        if data_len == 0 || ec_len == 0 || ec_len + 1 >= size {
+            return Err(Error::IllegalArgumentException(format!(
+                "Cannot split {} data bytes into blocks with {} error-correction bytes",
+                data_len, ec_len
+            )));
+        }
+        let max_block_len = size - 1 - ec_len;
+        let num_blocks = data_len.div_ceil(max_block_len);
+        let short_block_len = data_len / num_blocks;
+        let num_long_blocks = data_len % num_blocks;
+        Ok((0..num_blocks)
+            .map(|i| short_block_len + (i >= num_blocks - num_long_blocks) as usize)
+            .collect())
+    }
+
+    /**
+     * The inverse of {@link #byte_blocks}: recovers the blocks from how many bytes were
+     * received in all, data and error-correction bytes together.
+     *
+     * @throws IllegalArgumentException if no split of the data bytes adds up to the total
+     */
+    #[cfg(feature = "decode")]
+    pub(crate) fn received_byte_blocks(
+        &self,
+        received_len: usize,
+        ec_len: usize,
+    ) -> Result<Vec<usize>, Error> {
+        // n blocks take more bytes than n - 1 full codewords and at most n, so only one n fits
+        let num_blocks = received_len.div_ceil(self.size as usize - 1);
+        if num_blocks * ec_len < received_len {
+            let blocks = self.byte_blocks(received_len - num_blocks * ec_len, ec_len)?;
+            if blocks.len() == num_blocks {
+                return Ok(blocks);
+            }
+        }
+        Err(Error::IllegalArgumentException(format!(
+            "{} bytes do not split into blocks with {} error-correction bytes",
+            received_len, ec_len
+        )))
+    }
 }
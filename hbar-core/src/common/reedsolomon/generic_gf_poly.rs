@@ -1,11 +1,15 @@
 use crate::common::reedsolomon::GenericGF;
 use crate::Error;
 
-use std::rc::Rc;
+use alloc::format;
+use alloc::string::ToString;
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
 
 #[derive(Clone, Debug, Eq, PartialEq)] // we implement the Copy trait
 pub struct GenericGFPoly {
-    field: Rc<GenericGF>,
+    field: Arc<GenericGF>,
     coefficients: Vec<i32>,
 }
 
@@ -19,7 +23,7 @@ impl GenericGFPoly {
      * or if leading coefficient is 0 and this is not a
      * constant polynomial (that is, it is not the monomial "0")
      */
-    pub fn new(field: Rc<GenericGF>, coefficients: Vec<i32>) -> Result<GenericGFPoly, Error> {
+    pub fn new(field: Arc<GenericGF>, coefficients: Vec<i32>) -> Result<GenericGFPoly, Error> {
         if coefficients.len() == 0 {
             return Err(Error::IllegalArgumentException(format!(
                 "coefficients length is zero"
@@ -104,7 +108,7 @@ impl GenericGFPoly {
         }
         let mut result = self.coefficients[0];
         let size = self.coefficients.len();
-        for i in 0..size {
+        for i in 1..size {
             result =
                 GenericGF::add_or_subtract(self.field.multiply(a, result)?, self.coefficients[i])
         }
@@ -1,9 +1,13 @@
 mod generic_gf;
 mod generic_gf_poly;
+#[cfg(feature = "decode")]
 mod reed_solomon_decoder;
+#[cfg(feature = "encode")]
 mod reed_solomon_encoder;
 
 pub use generic_gf::{GenericGF, GenericGFEnum};
 pub use generic_gf_poly::GenericGFPoly;
-pub use reed_solomon_decoder::ReedSolomonDecoder;
+#[cfg(feature = "decode")]
+pub use reed_solomon_decoder::{Corrections, ReedSolomonDecoder};
+#[cfg(feature = "encode")]
 pub use reed_solomon_encoder::ReedSolomonEncoder;
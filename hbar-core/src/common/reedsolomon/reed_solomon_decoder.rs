@@ -2,14 +2,41 @@ use crate::common::reedsolomon::{GenericGF, GenericGFPoly};
 use crate::{Error, ResultError};
 
 use std::borrow::{Borrow, BorrowMut};
-use std::rc::Rc;
+use std::sync::Arc;
+
+/**
+ * How many codewords a call to {@link ReedSolomonDecoder#decode_with_erasures} corrected.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Corrections {
+    errors: usize,
+    erasures: usize,
+}
+
+impl Corrections {
+    /**
+     * @return number of errors found and corrected, at positions that were not known in advance
+     */
+    pub fn get_errors(&self) -> usize {
+        self.errors
+    }
+
+    /**
+     * @return number of erasures filled in; each used up one error-correction codeword, even
+     *  when the erased codeword turned out to be right. 0 if the codewords were already
+     *  consistent and nothing was corrected
+     */
+    pub fn get_erasures(&self) -> usize {
+        self.erasures
+    }
+}
 
 pub struct ReedSolomonDecoder {
-    field: Rc<GenericGF>,
+    field: Arc<GenericGF>,
 }
 
 impl ReedSolomonDecoder {
-    pub fn new(field: Rc<GenericGF>) -> Self {
+    pub fn new(field: Arc<GenericGF>) -> Self {
         ReedSolomonDecoder { field }
     }
 
@@ -23,41 +50,157 @@ impl ReedSolomonDecoder {
      * @throws ReedSolomonException if decoding fails for any reason
      */
     pub fn decode(&self, received: &mut Vec<i32>, twoS: i32) -> ResultError<()> {
-        let poly = GenericGFPoly::new(Rc::clone(&self.field), received.to_vec())?;
+        self.decode_with_erasures(received, twoS, &[])?;
+        Ok(())
+    }
 
-        let mut syndromeCoefficients = vec![0; twoS as usize];
-        let syndromeCoefficients_len = syndromeCoefficients.len();
-        let mut noError = true;
-        for i in 0..twoS {
+    /**
+     * <p>Like {@link #decode(Vec, i32)}, but told which codewords are known to be unreadable.
+     * An erasure costs one error-correction codeword where an error at an unknown position
+     * costs two, so {@code 2 * errors + erasures <= two_s} can be corrected.</p>
+     *
+     * <p>The erasures are removed from the syndromes first (Forney syndromes), and the
+     * Euclidean algorithm then only has to find the remaining errors.</p>
+     *
+     * <p>Only the QR Code decoder marks erasures so far. Data Matrix has no decoder in this
+     * tree yet; once it is ported it should mark codewords the same way, see
+     * {@link qrcode::decoder::Decoder#decode_with_erasures}.</p>
+     *
+     * @param received data and error-correction codewords
+     * @param two_s number of error-correction codewords available
+     * @param erasures indices into {@code received} of the codewords known to be unreadable
+     * @return how many errors and erasures were corrected
+     * @throws IllegalArgumentException if an erasure is out of range or given twice
+     * @throws ReedSolomonException if decoding fails for any reason
+     */
+    pub fn decode_with_erasures(
+        &self,
+        received: &mut Vec<i32>,
+        two_s: i32,
+        erasures: &[usize],
+    ) -> ResultError<Corrections> {
+        for (i, &erasure) in erasures.iter().enumerate() {
+            if erasure >= received.len() || erasures[..i].contains(&erasure) {
+                return Err(Error::IllegalArgumentException(format!(
+                    "Bad erasure position {} among {} codewords",
+                    erasure,
+                    received.len()
+                )));
+            }
+        }
+        let num_erasures = erasures.len() as i32;
+        if num_erasures > two_s {
+            return Err(Error::ReedSolomonException(format!(
+                "{} erasures but only {} error-correction codewords",
+                num_erasures, two_s
+            )));
+        }
+
+        let poly = GenericGFPoly::new(Arc::clone(&self.field), received.to_vec())?;
+
+        let mut syndrome_coefficients = vec![0; two_s as usize];
+        let syndrome_coefficients_len = syndrome_coefficients.len();
+        let mut no_error = true;
+        for i in 0..two_s {
             let eval = poly.evaluate_at(self.field.exp(i + self.field.get_generator_base())?)?;
-            syndromeCoefficients[syndromeCoefficients_len - 1 - i as usize] = eval;
+            syndrome_coefficients[syndrome_coefficients_len - 1 - i as usize] = eval;
             if eval != 0 {
-                noError = false;
+                no_error = false;
             }
         }
-        if noError {
-            return Ok(());
+        if no_error {
+            // Nothing needed filling in, so no error-correction codewords were used up
+            return Ok(Corrections::default());
         }
 
-        let syndrome = GenericGFPoly::new(Rc::clone(&self.field), syndromeCoefficients)?;
-        let sigmaOmega =
-            self.runEuclideanAlgorithm(self.field.build_monomial(twoS, 1)?, syndrome, twoS)?;
+        // The erasure locator has a root at the inverse of each erasure's location
+        let mut erasure_locator = self.field.get_one()?;
+        for &erasure in erasures {
+            let location = self.field.exp(received.len() as i32 - 1 - erasure as i32)?;
+            erasure_locator = erasure_locator.multiply_by_other(&GenericGFPoly::new(
+                Arc::clone(&self.field),
+                vec![location, 1],
+            )?)?;
+        }
+
+        // Forney syndromes: the syndromes with the erasures taken out, modulo x^two_s
+        let syndrome = GenericGFPoly::new(Arc::clone(&self.field), syndrome_coefficients)?
+            .multiply_by_other(&erasure_locator)?;
+        let mut forney_coefficients = vec![0; two_s as usize];
+        for i in 0..two_s.min(syndrome.get_degree() + 1) {
+            forney_coefficients[(two_s - 1 - i) as usize] = syndrome.get_coefficient(i)?;
+        }
+        let forney_syndrome = GenericGFPoly::new(Arc::clone(&self.field), forney_coefficients)?;
 
-        let sigma = &sigmaOmega[0];
-        let omega = &sigmaOmega[1];
-        let errorLocations = self.findErrorLocations(sigma)?;
-        let errorMagnitudes = self.findErrorMagnitudes(omega, &errorLocations)?;
-        for i in 0..errorLocations.len() {
-            let position = received.len() as i32 - 1 - self.field.log(errorLocations[i])?;
+        let sigma_omega = self.runEuclideanAlgorithm(
+            self.field.build_monomial(two_s, 1)?,
+            forney_syndrome,
+            two_s + num_erasures,
+        )?;
+
+        let sigma = &sigma_omega[0];
+        let omega = &sigma_omega[1];
+        let num_errors = sigma.get_degree();
+        if 2 * num_errors + num_erasures > two_s {
+            return Err(Error::ReedSolomonException(format!(
+                "{} errors and {} erasures are more than {} error-correction codewords can correct",
+                num_errors, num_erasures, two_s
+            )));
+        }
+
+        // Errors and erasures are then located and corrected together
+        let errata_locator = sigma.multiply_by_other(&erasure_locator)?;
+        let error_locations = self.findErrorLocations(&errata_locator)?;
+        let error_magnitudes = self.findErrorMagnitudes(omega, &error_locations)?;
+        for i in 0..error_locations.len() {
+            let position = received.len() as i32 - 1 - self.field.log(error_locations[i])?;
             if position < 0 {
                 return Err(Error::ReedSolomonException(String::from(
                     "Bad error location",
                 )));
             }
             received[position as usize] =
-                GenericGF::add_or_subtract(received[position as usize], errorMagnitudes[i]);
+                GenericGF::add_or_subtract(received[position as usize], error_magnitudes[i]);
         }
-        Ok(())
+        Ok(Corrections {
+            errors: num_errors as usize,
+            erasures: erasures.len(),
+        })
+    }
+
+    /**
+     * Decodes bytes, in a field of at most 256 elements such as QrCodeField256, laid out in
+     * interleaved blocks as {@link ReedSolomonEncoder#encode_u8} writes them.
+     *
+     * @param received the data bytes followed by the error-correction bytes, both interleaved
+     * @param ec_len how many error-correction bytes each block has
+     * @return the corrected data bytes
+     * @throws IllegalArgumentException if the field has more than 256 elements, or received
+     *  does not split into blocks with ec_len error-correction bytes
+     * @throws ReedSolomonException if decoding any of the blocks fails for any reason
+     */
+    pub fn decode_u8(&self, received: &[u8], ec_len: usize) -> ResultError<Vec<u8>> {
+        let blocks = self.field.received_byte_blocks(received.len(), ec_len)?;
+        let mut codewords: Vec<Vec<i32>> = blocks
+            .iter()
+            .map(|&block_len| Vec::with_capacity(block_len + ec_len))
+            .collect();
+        let mut bytes = received.iter().map(|&byte| byte as i32);
+        let max_block_len = blocks[blocks.len() - 1];
+        for i in 0..max_block_len + ec_len {
+            for (codeword, &block_len) in codewords.iter_mut().zip(&blocks) {
+                if i < block_len || i >= max_block_len {
+                    codeword.extend(bytes.next());
+                }
+            }
+        }
+
+        let mut result = Vec::with_capacity(received.len() - blocks.len() * ec_len);
+        for (codeword, &block_len) in codewords.iter_mut().zip(&blocks) {
+            self.decode(codeword, ec_len as i32)?;
+            result.extend(codeword[..block_len].iter().map(|&symbol| symbol as u8));
+        }
+        Ok(result)
     }
 
     fn runEuclideanAlgorithm(
@@ -193,3 +336,155 @@ impl ReedSolomonDecoder {
         Ok(result)
     }
 }
+
+#[cfg(all(test, feature = "encode"))]
+mod reed_solomon_tests {
+    use super::*;
+    use crate::common::{GenericGFEnum, ReedSolomonEncoder};
+
+    #[test]
+    fn shares_fields_and_encoders() {
+        let field = GenericGFEnum::QrCodeField256.get();
+        assert!(Arc::ptr_eq(&field, &GenericGFEnum::QrCodeField256.get()));
+        assert!(Arc::ptr_eq(
+            &GenericGFEnum::AztecData8.get(),
+            &GenericGFEnum::DataMatrixField256.get()
+        ));
+        let encoder = ReedSolomonEncoder::for_field(GenericGFEnum::QrCodeField256).unwrap();
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                std::thread::spawn(move || {
+                    let encoder =
+                        ReedSolomonEncoder::for_field(GenericGFEnum::QrCodeField256).unwrap();
+                    encoder.encode_u8(&[i; 20], 10 + i as usize).unwrap()
+                })
+            })
+            .collect();
+        for (i, handle) in handles.into_iter().enumerate() {
+            assert_eq!(
+                encoder.encode_u8(&[i as u8; 20], 10 + i).unwrap(),
+                handle.join().unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn round_trip_bytes() {
+        let data: Vec<u8> = (0..200).map(|i| (i * 7 + 3) as u8).collect();
+        let encoder = ReedSolomonEncoder::for_field(GenericGFEnum::DataMatrixField256).unwrap();
+        let mut codeword = encoder.encode_u8(&data, 40).unwrap();
+        assert_eq!(240, codeword.len());
+        assert_eq!(&data[..], &codeword[..200]);
+
+        for position in (0..240).step_by(12) {
+            codeword[position] ^= 0x5A;
+        }
+        let decoder = ReedSolomonDecoder::new(GenericGFEnum::DataMatrixField256.get());
+        assert_eq!(data, decoder.decode_u8(&codeword, 40).unwrap());
+
+        assert!(encoder.encode_u8(&data, 255).is_err());
+        assert!(encoder.encode_u8(&[], 10).is_err());
+        let wide = ReedSolomonEncoder::for_field(GenericGFEnum::AztecData10).unwrap();
+        assert!(wide.encode_u8(&data[..10], 4).is_err());
+    }
+
+    #[test]
+    fn round_trip_interleaved_blocks() {
+        let encoder = ReedSolomonEncoder::for_field(GenericGFEnum::QrCodeField256).unwrap();
+        let decoder = ReedSolomonDecoder::new(GenericGFEnum::QrCodeField256.get());
+        // 234 data bytes fit in a block beside 20 error-correction bytes, so 1001 need five
+        // blocks, four of 200 and one of 201.
+        let data: Vec<u8> = (0..1001).map(|i| (i * 31 + 7) as u8).collect();
+        let mut codewords = encoder.encode_u8(&data, 20).unwrap();
+        assert_eq!(1001 + 5 * 20, codewords.len());
+        let first_bytes = [data[0], data[200], data[400], data[600], data[800]];
+        assert_eq!(&first_bytes, &codewords[..5]);
+        assert_eq!(data[1000], codewords[1000]);
+
+        // A burst of 50 bytes is spread over the blocks, ten bad bytes in each.
+        for byte in &mut codewords[300..350] {
+            *byte ^= 0xA7;
+        }
+        assert_eq!(data, decoder.decode_u8(&codewords, 20).unwrap());
+        for byte in &mut codewords[300..360] {
+            *byte ^= 0x3C;
+        }
+        assert!(decoder.decode_u8(&codewords, 20).is_err());
+        assert!(decoder.decode_u8(&codewords[..20], 20).is_err());
+    }
+
+    #[test]
+    fn corrects_errors_and_erasures() {
+        for field in [
+            GenericGFEnum::QrCodeField256,
+            GenericGFEnum::DataMatrixField256,
+        ] {
+            let data: Vec<u8> = (0..30).map(|i| (i * 13 + 1) as u8).collect();
+            let encoder = ReedSolomonEncoder::for_field(field).unwrap();
+            let codeword: Vec<i32> = encoder
+                .encode_u8(&data, 10)
+                .unwrap()
+                .iter()
+                .map(|&b| b as i32)
+                .collect();
+            let decoder = ReedSolomonDecoder::new(field.get());
+
+            // Ten erasures use up all ten error-correction codewords; one of them is intact.
+            let erasures: Vec<usize> = (0..40).step_by(4).collect();
+            let mut received = codeword.clone();
+            for &position in &erasures[1..] {
+                received[position] ^= 0x33;
+            }
+            let corrections = decoder
+                .decode_with_erasures(&mut received, 10, &erasures)
+                .unwrap();
+            assert_eq!(codeword, received);
+            assert_eq!(
+                (0, 10),
+                (corrections.get_errors(), corrections.get_erasures())
+            );
+
+            // Four erasures leave room for three errors at unknown positions.
+            let mut received = codeword.clone();
+            for position in [1, 5, 9, 13, 20, 27, 38] {
+                received[position] ^= 0xC5;
+            }
+            let corrections = decoder
+                .decode_with_erasures(&mut received, 10, &[1, 5, 9, 13])
+                .unwrap();
+            assert_eq!(codeword, received);
+            assert_eq!(
+                (3, 4),
+                (corrections.get_errors(), corrections.get_erasures())
+            );
+
+            // Without the erasures the same damage is too much.
+            let mut received = codeword.clone();
+            for position in [1, 5, 9, 13, 20, 27, 38] {
+                received[position] ^= 0xC5;
+            }
+            assert!(decoder.decode(&mut received, 10).is_err() || received != codeword);
+
+            // Erasures that turn out to be intact need no correction at all.
+            let mut received = codeword.clone();
+            let corrections = decoder
+                .decode_with_erasures(&mut received, 10, &[1, 5, 9, 13])
+                .unwrap();
+            assert_eq!(codeword, received);
+            assert_eq!(
+                (0, 0),
+                (corrections.get_errors(), corrections.get_erasures())
+            );
+
+            assert!(decoder
+                .decode_with_erasures(&mut received, 10, &[3, 3])
+                .is_err());
+            assert!(decoder
+                .decode_with_erasures(&mut received, 10, &[40])
+                .is_err());
+            assert!(decoder
+                .decode_with_erasures(&mut received, 10, &(0..11).collect::<Vec<_>>())
+                .is_err());
+        }
+    }
+}
@@ -1,61 +1,83 @@
-use crate::common::reedsolomon::{GenericGF, GenericGFPoly};
+use crate::common::reedsolomon::{GenericGF, GenericGFEnum, GenericGFPoly};
 use crate::Error;
 
-use std::rc::Rc;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::ToString;
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
 
+use once_cell::race::OnceBox;
+
+/**
+ * <p>Implements Reed-Solomon encoding, as the name implies.</p>
+ *
+ * The generator polynomials are built the first time each degree is needed and kept, and
+ * encoding takes {@code &self}, so one encoder can be shared between threads; see
+ * {@link #for_field} for the shared encoder of each of the predefined fields.
+ */
 pub struct ReedSolomonEncoder {
-    field: Rc<GenericGF>,
-    cached_generators: Vec<GenericGFPoly>,
+    field: Arc<GenericGF>,
+    // The generator of degree d, for d check symbols, in slot d. There are fewer check symbols
+    // than the size of the field.
+    cached_generators: Vec<OnceBox<GenericGFPoly>>,
 }
 
 impl ReedSolomonEncoder {
-    pub fn new(field: Rc<GenericGF>) -> Result<ReedSolomonEncoder, Error> {
-        let mut cached_generators = Vec::new();
-        cached_generators.push(GenericGFPoly::new(Rc::clone(&field), vec![1])?);
+    pub fn new(field: Arc<GenericGF>) -> Result<ReedSolomonEncoder, Error> {
+        let cached_generators = (0..field.get_size()).map(|_| OnceBox::new()).collect();
         Ok(ReedSolomonEncoder {
-            field: Rc::clone(&field),
-            cached_generators: cached_generators,
+            field,
+            cached_generators,
         })
     }
 
-    pub fn build_generator(&mut self, degree: i32) -> Result<GenericGFPoly, Error> {
-        if degree > self.cached_generators.len() as i32 {
-            let mut last_generator = self.cached_generators.get(self.cached_generators.len() - 1);
-            let mut last_generator = match last_generator {
-                Some(generator) => generator.clone(),
-                None => {
-                    return Err(Error::IllegalArgumentException(
-                        "Build generator error".to_string(),
-                    ))
-                }
-            };
-            for d in self.cached_generators.len()..(degree + 1) as usize {
+    /**
+     * @return the encoder for the given field, built the first time it is asked for and shared
+     *  by every caller from then on
+     */
+    pub fn for_field(field: GenericGFEnum) -> Result<&'static ReedSolomonEncoder, Error> {
+        static ENCODERS: [OnceBox<ReedSolomonEncoder>; GenericGFEnum::DISTINCT_FIELDS] = [
+            OnceBox::new(),
+            OnceBox::new(),
+            OnceBox::new(),
+            OnceBox::new(),
+            OnceBox::new(),
+            OnceBox::new(),
+        ];
+        ENCODERS[field.index()]
+            .get_or_try_init(|| ReedSolomonEncoder::new(field.get()).map(Box::new))
+    }
+
+    pub fn build_generator(&self, degree: i32) -> Result<&GenericGFPoly, Error> {
+        if degree < 0 || degree as usize >= self.cached_generators.len() {
+            return Err(Error::IllegalArgumentException(format!(
+                "Build generator error degree: {}",
+                degree
+            )));
+        }
+        let mut generator = self.cached_generators[0].get_or_try_init(|| {
+            GenericGFPoly::new(Arc::clone(&self.field), vec![1]).map(Box::new)
+        })?;
+        for d in 1..=degree as usize {
+            let last_generator = generator;
+            generator = self.cached_generators[d].get_or_try_init(|| {
                 let other = GenericGFPoly::new(
-                    self.field.clone(),
+                    Arc::clone(&self.field),
                     vec![
                         1,
                         self.field
                             .exp(d as i32 - 1 + self.field.get_generator_base())?,
                     ],
                 )?;
-                let next_generator = last_generator.multiply_by_other(&other)?;
-                self.cached_generators.push(next_generator.clone());
-                last_generator = next_generator;
-            }
+                last_generator.multiply_by_other(&other).map(Box::new)
+            })?;
         }
-        let last_generator = self.cached_generators.get(degree as usize);
-        let last_generator = match last_generator {
-            Some(generator) => generator.clone(),
-            None => {
-                return Err(Error::IllegalArgumentException(
-                    "Build generator error".to_string(),
-                ))
-            }
-        };
-        Ok(last_generator)
+        Ok(generator)
     }
 
-    pub fn encode(&mut self, to_encode: &mut Vec<i32>, ec_bytes: i32) -> Result<(), Error> {
+    pub fn encode(&self, to_encode: &mut Vec<i32>, ec_bytes: i32) -> Result<(), Error> {
         if ec_bytes <= 0 {
             return Err(Error::IllegalArgumentException(format!(
                 "No error correction bytes ec_bytes is: {}",
@@ -73,7 +95,7 @@ impl ReedSolomonEncoder {
         let info_coefficients = (&to_encode[0..data_bytes]).to_vec();
         let mut info = GenericGFPoly::new(self.field.clone(), info_coefficients)?;
         info = info.multiply_by_monomial(ec_bytes, 1)?;
-        let remainder = &info.divide(&generator)?[1];
+        let remainder = &info.divide(generator)?[1];
         let coefficients = remainder.get_coefficients();
         let num_zero_coefficients = ec_bytes - coefficients.len() as i32;
         if num_zero_coefficients < 0 {
@@ -93,4 +115,50 @@ impl ReedSolomonEncoder {
         }
         Ok(())
     }
+
+    /**
+     * Encodes bytes, in a field of at most 256 elements such as QrCodeField256. Data too long
+     * for one codeword is split into blocks of nearly equal length, each with its own
+     * error-correction bytes, and as in QR Code the blocks are interleaved so that a burst of
+     * damage is spread across them. A single block comes out as its data bytes followed by its
+     * error-correction bytes.
+     *
+     * @param data the data bytes
+     * @param ec_len how many error-correction bytes to add to each block
+     * @return the data bytes of all blocks, interleaved, followed by their error-correction
+     *  bytes, interleaved
+     * @throws IllegalArgumentException if the field has more than 256 elements, or there are no
+     *  data bytes or no room for them beside ec_len error-correction bytes
+     */
+    pub fn encode_u8(&self, data: &[u8], ec_len: usize) -> Result<Vec<u8>, Error> {
+        let blocks = self.field.byte_blocks(data.len(), ec_len)?;
+        let mut codewords = Vec::with_capacity(blocks.len());
+        let mut offset = 0;
+        for &block_len in &blocks {
+            let mut to_encode: Vec<i32> = data[offset..offset + block_len]
+                .iter()
+                .map(|&byte| byte as i32)
+                .collect();
+            to_encode.resize(block_len + ec_len, 0);
+            self.encode(&mut to_encode, ec_len as i32)?;
+            codewords.push(to_encode);
+            offset += block_len;
+        }
+
+        let mut result = Vec::with_capacity(data.len() + blocks.len() * ec_len);
+        let max_block_len = blocks[blocks.len() - 1];
+        for i in 0..max_block_len {
+            for (codeword, &block_len) in codewords.iter().zip(&blocks) {
+                if i < block_len {
+                    result.push(codeword[i] as u8);
+                }
+            }
+        }
+        for i in 0..ec_len {
+            for (codeword, &block_len) in codewords.iter().zip(&blocks) {
+                result.push(codeword[block_len + i] as u8);
+            }
+        }
+        Ok(result)
+    }
 }
@@ -0,0 +1,276 @@
+use crate::common::Charset;
+use crate::{DecodeHintType, DecodeHintValue};
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/**
+ * Common string-related functions.
+ */
+pub struct StringUtils;
+
+impl StringUtils {
+    const ASSUME_SHIFT_JIS: bool = false;
+
+    /**
+     * @param bytes bytes encoding a string, whose encoding should be guessed
+     * @param hints decode hints if applicable
+     * @return Charset of guessed encoding; at the moment will only guess one of:
+     *  {@link Charset#ShiftJIS}, {@link Charset#GB18030}, {@link Charset#UTF8},
+     *  {@link Charset#ISO8859_1}.
+     *  If a {@link DecodeHintType#CharacterSet} hint naming a supported character set
+     *  is present, that is returned instead.
+     */
+    pub fn guess_charset(
+        bytes: &[u8],
+        hints: &HashMap<DecodeHintType, DecodeHintValue>,
+    ) -> Charset {
+        if let Some(DecodeHintValue::STRING(name)) = hints.get(&DecodeHintType::CharacterSet) {
+            if let Ok(charset) = Charset::from_str(name) {
+                return charset;
+            }
+        }
+
+        // For now, merely tries to distinguish ISO-8859-1, UTF-8, Shift_JIS and GB18030,
+        // which should be by far the most common encodings.
+        let length = bytes.len();
+        let mut can_be_iso88591 = true;
+        let mut can_be_shift_jis = true;
+        let mut can_be_utf8 = true;
+        let mut can_be_gb18030 = true;
+        let mut utf8_bytes_left = 0;
+        let mut utf2_bytes_chars = 0;
+        let mut utf3_bytes_chars = 0;
+        let mut utf4_bytes_chars = 0;
+        let mut sjis_bytes_left = 0;
+        let mut sjis_katakana_chars = 0;
+        let mut sjis_cur_katakana_word_length = 0;
+        let mut sjis_cur_double_bytes_word_length = 0;
+        let mut sjis_max_katakana_word_length = 0;
+        let mut sjis_max_double_bytes_word_length = 0;
+        let mut iso_high_other = 0;
+        let mut gb_lead_byte = 0;
+        let mut gb_four_bytes_left = 0;
+        let mut gb2312_chars = 0;
+        let mut gb_other_chars = 0;
+
+        let utf8bom = length > 3 && bytes[0] == 0xEF && bytes[1] == 0xBB && bytes[2] == 0xBF;
+
+        for &value in bytes {
+            if !(can_be_iso88591 || can_be_shift_jis || can_be_utf8 || can_be_gb18030) {
+                break;
+            }
+
+            // UTF-8 stuff
+            if can_be_utf8 {
+                if utf8_bytes_left > 0 {
+                    if (value & 0x80) == 0 {
+                        can_be_utf8 = false;
+                    } else {
+                        utf8_bytes_left -= 1;
+                    }
+                } else if (value & 0x80) != 0 {
+                    if (value & 0x40) == 0 {
+                        can_be_utf8 = false;
+                    } else {
+                        utf8_bytes_left += 1;
+                        if (value & 0x20) == 0 {
+                            utf2_bytes_chars += 1;
+                        } else {
+                            utf8_bytes_left += 1;
+                            if (value & 0x10) == 0 {
+                                utf3_bytes_chars += 1;
+                            } else {
+                                utf8_bytes_left += 1;
+                                if (value & 0x08) == 0 {
+                                    utf4_bytes_chars += 1;
+                                } else {
+                                    can_be_utf8 = false;
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            // ISO-8859-1 stuff
+            if can_be_iso88591 {
+                if value > 0x7F && value < 0xA0 {
+                    can_be_iso88591 = false;
+                } else if value > 0x9F && (value < 0xC0 || value == 0xD7 || value == 0xF7) {
+                    iso_high_other += 1;
+                }
+            }
+
+            // Shift_JIS stuff
+            if can_be_shift_jis {
+                if sjis_bytes_left > 0 {
+                    if value < 0x40 || value == 0x7F || value > 0xFC {
+                        can_be_shift_jis = false;
+                    } else {
+                        sjis_bytes_left -= 1;
+                    }
+                } else if value == 0x80 || value == 0xA0 || value > 0xEF {
+                    can_be_shift_jis = false;
+                } else if value > 0xA0 && value < 0xE0 {
+                    sjis_katakana_chars += 1;
+                    sjis_cur_double_bytes_word_length = 0;
+                    sjis_cur_katakana_word_length += 1;
+                    if sjis_cur_katakana_word_length > sjis_max_katakana_word_length {
+                        sjis_max_katakana_word_length = sjis_cur_katakana_word_length;
+                    }
+                } else if value > 0x7F {
+                    sjis_bytes_left += 1;
+                    sjis_cur_katakana_word_length = 0;
+                    sjis_cur_double_bytes_word_length += 1;
+                    if sjis_cur_double_bytes_word_length > sjis_max_double_bytes_word_length {
+                        sjis_max_double_bytes_word_length = sjis_cur_double_bytes_word_length;
+                    }
+                } else {
+                    sjis_cur_katakana_word_length = 0;
+                    sjis_cur_double_bytes_word_length = 0;
+                }
+            }
+
+            // GB18030 stuff: single bytes below 0x80, two-byte sequences led by 0x81-0xFE,
+            // and four-byte sequences of the form [81-FE][30-39][81-FE][30-39]
+            if can_be_gb18030 {
+                if gb_four_bytes_left == 2 {
+                    if (0x81..=0xFE).contains(&value) {
+                        gb_four_bytes_left = 1;
+                    } else {
+                        can_be_gb18030 = false;
+                    }
+                } else if gb_four_bytes_left == 1 {
+                    if (0x30..=0x39).contains(&value) {
+                        gb_four_bytes_left = 0;
+                        gb_other_chars += 1;
+                    } else {
+                        can_be_gb18030 = false;
+                    }
+                } else if gb_lead_byte > 0 {
+                    if (0x30..=0x39).contains(&value) {
+                        gb_four_bytes_left = 2;
+                    } else if (0x40..=0x7E).contains(&value) || (0x80..=0xFE).contains(&value) {
+                        // The GB2312 hanzi area: lead 0xB0-0xF7, trail 0xA1-0xFE
+                        if (0xB0..=0xF7).contains(&gb_lead_byte) && value >= 0xA1 {
+                            gb2312_chars += 1;
+                        } else {
+                            gb_other_chars += 1;
+                        }
+                    } else {
+                        can_be_gb18030 = false;
+                    }
+                    gb_lead_byte = 0;
+                } else if value == 0x80 || value == 0xFF {
+                    can_be_gb18030 = false;
+                } else if value > 0x80 {
+                    gb_lead_byte = value;
+                }
+            }
+        }
+
+        if can_be_utf8 && utf8_bytes_left > 0 {
+            can_be_utf8 = false;
+        }
+        if can_be_shift_jis && sjis_bytes_left > 0 {
+            can_be_shift_jis = false;
+        }
+        if can_be_gb18030 && (gb_lead_byte > 0 || gb_four_bytes_left > 0) {
+            can_be_gb18030 = false;
+        }
+
+        // Easy -- if there is BOM or at least 1 valid not-single byte character (and no evidence it can't be UTF-8), done
+        if can_be_utf8 && (utf8bom || utf2_bytes_chars + utf3_bytes_chars + utf4_bytes_chars > 0) {
+            return Charset::UTF8;
+        }
+        // Simplified Chinese text is made of GB2312 hanzi, which Shift_JIS would otherwise
+        // read as runs of half-width katakana. Require at least two of them, and nothing
+        // outside that area, to keep accented Latin-1 pairs from matching.
+        if can_be_gb18030 && gb_other_chars == 0 && gb2312_chars >= 2 {
+            return Charset::GB18030;
+        }
+        // Easy -- if assuming Shift_JIS or >= 3 valid consecutive not-ascii characters (and no evidence it can't be), done
+        if can_be_shift_jis
+            && (StringUtils::ASSUME_SHIFT_JIS
+                || sjis_max_katakana_word_length >= 3
+                || sjis_max_double_bytes_word_length >= 3)
+        {
+            return Charset::ShiftJIS;
+        }
+        // Distinguishing Shift_JIS and ISO-8859-1 can be a little tough for short words. The crude heuristic is:
+        // - If we saw
+        //   - only two consecutive katakana chars in the whole text, or
+        //   - at least 10% of bytes that could be "upper" not-alphanumeric Latin1,
+        // - then we conclude Shift_JIS, else ISO-8859-1
+        if can_be_iso88591 && can_be_shift_jis {
+            return if (sjis_max_katakana_word_length == 2 && sjis_katakana_chars == 2)
+                || iso_high_other * 10 >= length
+            {
+                Charset::ShiftJIS
+            } else {
+                Charset::ISO8859_1
+            };
+        }
+
+        // Otherwise, try in order ISO-8859-1, Shift JIS, GB18030, UTF-8 and fall back to UTF-8
+        if can_be_iso88591 {
+            return Charset::ISO8859_1;
+        }
+        if can_be_shift_jis {
+            return Charset::ShiftJIS;
+        }
+        if can_be_gb18030 {
+            return Charset::GB18030;
+        }
+        Charset::UTF8
+    }
+}
+
+#[cfg(test)]
+mod string_utils_tests {
+    use super::*;
+
+    fn guess(bytes: &[u8]) -> Charset {
+        StringUtils::guess_charset(bytes, &HashMap::new())
+    }
+
+    #[test]
+    fn short_iso88591() {
+        assert_eq!(Charset::ISO8859_1, guess(&[0x61, 0x62, 0x63]));
+        assert_eq!(Charset::ISO8859_1, guess(&[0x47, 0x72, 0xF6, 0xDF, 0x65]));
+    }
+
+    #[test]
+    fn utf8() {
+        assert_eq!(Charset::UTF8, guess("中文 and 日本語".as_bytes()));
+        assert_eq!(Charset::UTF8, guess("Größe".as_bytes()));
+    }
+
+    #[test]
+    fn shift_jis() {
+        let bytes = Charset::ShiftJIS
+            .encode(&String::from("日本語のラベル"))
+            .unwrap();
+        assert_eq!(Charset::ShiftJIS, guess(&bytes));
+    }
+
+    #[test]
+    fn gb18030() {
+        let bytes = Charset::GB18030.encode(&String::from("中文标签")).unwrap();
+        assert_eq!(Charset::GB18030, guess(&bytes));
+    }
+
+    #[test]
+    fn character_set_hint() {
+        let mut hints = HashMap::new();
+        hints.insert(
+            DecodeHintType::CharacterSet,
+            DecodeHintValue::STRING(String::from("Shift_JIS")),
+        );
+        assert_eq!(
+            Charset::ShiftJIS,
+            StringUtils::guess_charset(b"abc", &hints)
+        );
+    }
+}
@@ -1,9 +1,10 @@
 use crate::barcode_format::BarcodeFormat;
 use crate::common::BitMatrix;
-use crate::encode_hint_type::EncodeHintType;
+use crate::encode_options::EncodeOptions;
 use crate::writer::Writer;
 use crate::ResultError;
-use std::collections::HashMap;
+
+use alloc::string::String;
 
 pub struct DataMatrixWriter;
 
@@ -14,24 +15,13 @@ impl DataMatrixWriter {
 }
 
 impl Writer for DataMatrixWriter {
-    fn encode(
-        &self,
-        contents: &String,
-        format: &BarcodeFormat,
-        width: i32,
-        height: i32,
-    ) -> ResultError<BitMatrix> {
-        let hints: HashMap<EncodeHintType, &String> = HashMap::new();
-        self.encode_hints(contents, format, width, height, hints)
-    }
-
-    fn encode_hints(
+    fn encode_options(
         &self,
         contents: &String,
         format: &BarcodeFormat,
         width: i32,
         height: i32,
-        hints: HashMap<EncodeHintType, &String>,
+        options: &EncodeOptions,
     ) -> ResultError<BitMatrix> {
         Ok(BitMatrix::new2(0, 0)?)
     }
@@ -0,0 +1,3 @@
+mod symbol_shape_hint;
+
+pub use symbol_shape_hint::SymbolShapeHint;
@@ -0,0 +1,16 @@
+use strum_macros::Display;
+use strum_macros::EnumString;
+
+/**
+ * Enumeration for DataMatrix symbol shape hint. It can be used to force square or rectangular
+ * symbols.
+ */
+#[derive(Debug, PartialEq, Eq, Hash, EnumString, Display, Clone, Copy)]
+pub enum SymbolShapeHint {
+    #[strum(serialize = "ForceNone", serialize = "FORCE_NONE")]
+    ForceNone,
+    #[strum(serialize = "ForceSquare", serialize = "FORCE_SQUARE")]
+    ForceSquare,
+    #[strum(serialize = "ForceRectangle", serialize = "FORCE_RECTANGLE")]
+    ForceRectangle,
+}
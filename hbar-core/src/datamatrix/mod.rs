@@ -1,3 +1,8 @@
+// Only the writer is ported so far. The decoder, when it is, should also take the unreliable
+// modules and correct their codewords as erasures, as qrcode::decoder::Decoder does.
+#[cfg(feature = "datamatrix")]
 pub mod datamatrix_writer;
+pub mod encoder;
 
+#[cfg(feature = "datamatrix")]
 pub use datamatrix_writer::DataMatrixWriter;
@@ -1,13 +1,17 @@
 use crate::BarcodeFormat;
+use crate::ResultPointCallback;
+use strum_macros::Display;
 use strum_macros::EnumString;
-use strum_macros::ToString;
+
+use std::sync::Arc;
+
 /**
  * Encapsulates a type of hint that a caller may pass to a barcode reader to help it
  * more quickly or accurately decode it. It is up to implementations to decide what,
  * if anything, to do with the information that is supplied.
  */
 
-#[derive(Debug, PartialEq, Eq, Hash, EnumString, ToString, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, EnumString, Display, Clone)]
 pub enum DecodeHintType {
     /**
      * Unspecified, application-specific hint. Maps to an unspecified {@link Object}.
@@ -82,13 +86,67 @@ pub enum DecodeHintType {
      * second time with an inverted image. Doesn't matter what it maps to; use {@link Boolean#TRUE}.
      */
     AlsoInverted,
+
+    /**
+     * If true, and no barcode is found, binarizes the image again with OtsuBinarizer,
+     * LocalThresholdBinarizer using Sauvola's and then Niblack's method, and
+     * GlobalHistogramBinarizer, in that order, and tries all configured decoders on each.
+     * Doesn't matter what it maps to; use {@link Boolean#TRUE}.
+     */
+    TryAlternativeBinarizers,
+
+    /**
+     * The angles, in degrees counterclockwise, by which to rotate the image when
+     * {@link #TryHarder} is set and nothing is found upright; all configured decoders are tried
+     * on each rotation in turn. Meant for 1D codes at odd angles. Maps to an {@code int[]}.
+     * Each angle rotates and binarizes a copy of the image, so the image is only rotated when
+     * this is set.
+     */
+    TryHarderAngles,
+
+    /**
+     * Tries the image scaled down first, coarsest scale first, and then at full resolution.
+     * Large photos binarize much faster, and often decode better, at a lower resolution.
+     * Each scale halves the previous one while its smaller side stays at least 256 pixels.
+     * Doesn't matter what it maps to; use {@link Boolean#TRUE}.
+     */
+    MultiScale,
+
+    /**
+     * The largest number of pixels to binarize. Larger images are scaled down by the smallest
+     * whole factor which fits, and never decoded at full resolution. Maps to an {@code int}.
+     */
+    MaxPixels,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+/**
+ * The value a {@link DecodeHintType} maps to. The get_ methods panic when the value is of
+ * another type; the try_get_ methods return None instead.
+ */
+#[derive(Debug, Clone)]
 pub enum DecodeHintValue {
     BOOL(bool),
+    I32(i32),
     VecI32(Vec<i32>),
     VecBarcodeFormat(Vec<BarcodeFormat>),
+    STRING(String),
+    ResultPointCallback(Arc<dyn ResultPointCallback>),
+}
+
+impl PartialEq for DecodeHintValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (DecodeHintValue::BOOL(a), DecodeHintValue::BOOL(b)) => a == b,
+            (DecodeHintValue::I32(a), DecodeHintValue::I32(b)) => a == b,
+            (DecodeHintValue::VecI32(a), DecodeHintValue::VecI32(b)) => a == b,
+            (DecodeHintValue::VecBarcodeFormat(a), DecodeHintValue::VecBarcodeFormat(b)) => a == b,
+            (DecodeHintValue::STRING(a), DecodeHintValue::STRING(b)) => a == b,
+            (DecodeHintValue::ResultPointCallback(a), DecodeHintValue::ResultPointCallback(b)) => {
+                Arc::ptr_eq(a, b)
+            }
+            _ => false,
+        }
+    }
 }
 
 impl DecodeHintValue {
@@ -99,6 +157,27 @@ impl DecodeHintValue {
         }
     }
 
+    pub fn try_get_bool(&self) -> Option<bool> {
+        match self {
+            DecodeHintValue::BOOL(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn get_i32(&self) -> i32 {
+        match self {
+            DecodeHintValue::I32(v) => *v,
+            _ => panic!(),
+        }
+    }
+
+    pub fn try_get_i32(&self) -> Option<i32> {
+        match self {
+            DecodeHintValue::I32(v) => Some(*v),
+            _ => None,
+        }
+    }
+
     pub fn get_vec_i32(&self) -> &Vec<i32> {
         match self {
             DecodeHintValue::VecI32(v) => v,
@@ -106,10 +185,52 @@ impl DecodeHintValue {
         }
     }
 
+    pub fn try_get_vec_i32(&self) -> Option<&Vec<i32>> {
+        match self {
+            DecodeHintValue::VecI32(v) => Some(v),
+            _ => None,
+        }
+    }
+
     pub fn get_vec_barcode_format(&self) -> &Vec<BarcodeFormat> {
         match self {
             DecodeHintValue::VecBarcodeFormat(v) => v,
             _ => panic!(),
         }
     }
+
+    pub fn try_get_vec_barcode_format(&self) -> Option<&Vec<BarcodeFormat>> {
+        match self {
+            DecodeHintValue::VecBarcodeFormat(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn get_string(&self) -> &String {
+        match self {
+            DecodeHintValue::STRING(v) => v,
+            _ => panic!(),
+        }
+    }
+
+    pub fn try_get_string(&self) -> Option<&String> {
+        match self {
+            DecodeHintValue::STRING(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn get_result_point_callback(&self) -> &Arc<dyn ResultPointCallback> {
+        match self {
+            DecodeHintValue::ResultPointCallback(v) => v,
+            _ => panic!(),
+        }
+    }
+
+    pub fn try_get_result_point_callback(&self) -> Option<&Arc<dyn ResultPointCallback>> {
+        match self {
+            DecodeHintValue::ResultPointCallback(v) => Some(v),
+            _ => None,
+        }
+    }
 }
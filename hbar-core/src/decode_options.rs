@@ -0,0 +1,419 @@
+use crate::common::Charset;
+use crate::{BarcodeFormat, DecodeHintType, DecodeHintValue};
+use crate::{Error, ResultError};
+use crate::{ResultPoint, ResultPointCallback};
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/**
+ * Typed decoding options. Every {@link DecodeHintType} has a matching setter taking a value of
+ * the right type, so a mismatch between a hint and its value is a compile error.
+ *
+ * <pre>
+ * let options = DecodeOptions::new()
+ *     .try_harder(true)
+ *     .possible_formats(vec![BarcodeFormat::QRCode])
+ *     .character_set(Charset::ShiftJIS);
+ * </pre>
+ */
+#[derive(Debug, Clone, Default)]
+pub struct DecodeOptions {
+    other: Option<String>,
+    pure_barcode: bool,
+    possible_formats: Option<Vec<BarcodeFormat>>,
+    try_harder: bool,
+    character_set: Option<Charset>,
+    allowed_lengths: Option<Vec<i32>>,
+    assume_code39_check_digit: bool,
+    assume_gs1: bool,
+    return_codabar_start_end: bool,
+    result_point_callback: Option<Arc<dyn ResultPointCallback>>,
+    allowed_ean_extensions: Option<Vec<i32>>,
+    also_inverted: bool,
+    try_alternative_binarizers: bool,
+    try_harder_angles: Option<Vec<i32>>,
+    multi_scale: bool,
+    max_pixels: Option<i32>,
+}
+
+impl DecodeOptions {
+    pub fn new() -> Self {
+        DecodeOptions::default()
+    }
+
+    pub fn other(mut self, other: String) -> Self {
+        self.other = Some(other);
+        self
+    }
+
+    pub fn pure_barcode(mut self, pure_barcode: bool) -> Self {
+        self.pure_barcode = pure_barcode;
+        self
+    }
+
+    pub fn possible_formats(mut self, formats: Vec<BarcodeFormat>) -> Self {
+        self.possible_formats = Some(formats);
+        self
+    }
+
+    pub fn try_harder(mut self, try_harder: bool) -> Self {
+        self.try_harder = try_harder;
+        self
+    }
+
+    pub fn character_set(mut self, charset: Charset) -> Self {
+        self.character_set = Some(charset);
+        self
+    }
+
+    pub fn allowed_lengths(mut self, lengths: Vec<i32>) -> Self {
+        self.allowed_lengths = Some(lengths);
+        self
+    }
+
+    pub fn assume_code39_check_digit(mut self, assume: bool) -> Self {
+        self.assume_code39_check_digit = assume;
+        self
+    }
+
+    pub fn assume_gs1(mut self, assume: bool) -> Self {
+        self.assume_gs1 = assume;
+        self
+    }
+
+    pub fn return_codabar_start_end(mut self, return_start_end: bool) -> Self {
+        self.return_codabar_start_end = return_start_end;
+        self
+    }
+
+    pub fn result_point_callback(mut self, callback: Arc<dyn ResultPointCallback>) -> Self {
+        self.result_point_callback = Some(callback);
+        self
+    }
+
+    pub fn allowed_ean_extensions(mut self, extensions: Vec<i32>) -> Self {
+        self.allowed_ean_extensions = Some(extensions);
+        self
+    }
+
+    pub fn also_inverted(mut self, also_inverted: bool) -> Self {
+        self.also_inverted = also_inverted;
+        self
+    }
+
+    pub fn try_alternative_binarizers(mut self, try_alternatives: bool) -> Self {
+        self.try_alternative_binarizers = try_alternatives;
+        self
+    }
+
+    pub fn try_harder_angles(mut self, degrees: Vec<i32>) -> Self {
+        self.try_harder_angles = Some(degrees);
+        self
+    }
+
+    pub fn multi_scale(mut self, multi_scale: bool) -> Self {
+        self.multi_scale = multi_scale;
+        self
+    }
+
+    pub fn max_pixels(mut self, max_pixels: i32) -> Self {
+        self.max_pixels = Some(max_pixels);
+        self
+    }
+
+    pub fn get_other(&self) -> Option<&String> {
+        self.other.as_ref()
+    }
+
+    pub fn is_pure_barcode(&self) -> bool {
+        self.pure_barcode
+    }
+
+    pub fn get_possible_formats(&self) -> Option<&Vec<BarcodeFormat>> {
+        self.possible_formats.as_ref()
+    }
+
+    pub fn is_try_harder(&self) -> bool {
+        self.try_harder
+    }
+
+    pub fn get_character_set(&self) -> Option<&Charset> {
+        self.character_set.as_ref()
+    }
+
+    pub fn get_allowed_lengths(&self) -> Option<&Vec<i32>> {
+        self.allowed_lengths.as_ref()
+    }
+
+    pub fn is_assume_code39_check_digit(&self) -> bool {
+        self.assume_code39_check_digit
+    }
+
+    pub fn is_assume_gs1(&self) -> bool {
+        self.assume_gs1
+    }
+
+    pub fn is_return_codabar_start_end(&self) -> bool {
+        self.return_codabar_start_end
+    }
+
+    pub fn get_result_point_callback(&self) -> Option<&Arc<dyn ResultPointCallback>> {
+        self.result_point_callback.as_ref()
+    }
+
+    pub fn get_allowed_ean_extensions(&self) -> Option<&Vec<i32>> {
+        self.allowed_ean_extensions.as_ref()
+    }
+
+    pub fn is_also_inverted(&self) -> bool {
+        self.also_inverted
+    }
+
+    pub fn is_try_alternative_binarizers(&self) -> bool {
+        self.try_alternative_binarizers
+    }
+
+    pub fn get_try_harder_angles(&self) -> Option<&Vec<i32>> {
+        self.try_harder_angles.as_ref()
+    }
+
+    pub fn is_multi_scale(&self) -> bool {
+        self.multi_scale
+    }
+
+    pub fn get_max_pixels(&self) -> Option<i32> {
+        self.max_pixels
+    }
+
+    /**
+     * Notifies the result point callback, if any, that a possible result point was found.
+     */
+    pub fn found_possible_result_point(&self, point: &ResultPoint) {
+        if let Some(callback) = &self.result_point_callback {
+            callback.found_possible_result_point(point);
+        }
+    }
+
+    /**
+     * Converts these options to the hint map understood by {@link Reader#decode_hints}.
+     * Flags which are off are left out, as readers only check for their presence.
+     */
+    pub fn to_hints(&self) -> HashMap<DecodeHintType, DecodeHintValue> {
+        let mut hints = HashMap::new();
+        let flags = [
+            (DecodeHintType::PureBarcode, self.pure_barcode),
+            (DecodeHintType::TryHarder, self.try_harder),
+            (
+                DecodeHintType::AssumeCode39CheckDigit,
+                self.assume_code39_check_digit,
+            ),
+            (DecodeHintType::AssumeGs1, self.assume_gs1),
+            (
+                DecodeHintType::ReturnCodabarStartEnd,
+                self.return_codabar_start_end,
+            ),
+            (DecodeHintType::AlsoInverted, self.also_inverted),
+            (
+                DecodeHintType::TryAlternativeBinarizers,
+                self.try_alternative_binarizers,
+            ),
+            (DecodeHintType::MultiScale, self.multi_scale),
+        ];
+        for (hint, flag) in flags {
+            if flag {
+                hints.insert(hint, DecodeHintValue::BOOL(true));
+            }
+        }
+        if let Some(other) = &self.other {
+            hints.insert(
+                DecodeHintType::OTHER,
+                DecodeHintValue::STRING(other.clone()),
+            );
+        }
+        if let Some(formats) = &self.possible_formats {
+            hints.insert(
+                DecodeHintType::PossibleFormats,
+                DecodeHintValue::VecBarcodeFormat(formats.clone()),
+            );
+        }
+        if let Some(charset) = &self.character_set {
+            hints.insert(
+                DecodeHintType::CharacterSet,
+                DecodeHintValue::STRING(charset.to_string()),
+            );
+        }
+        if let Some(lengths) = &self.allowed_lengths {
+            hints.insert(
+                DecodeHintType::AllowedLengths,
+                DecodeHintValue::VecI32(lengths.clone()),
+            );
+        }
+        if let Some(callback) = &self.result_point_callback {
+            hints.insert(
+                DecodeHintType::NeedResultPointCallback,
+                DecodeHintValue::ResultPointCallback(Arc::clone(callback)),
+            );
+        }
+        if let Some(extensions) = &self.allowed_ean_extensions {
+            hints.insert(
+                DecodeHintType::AllowedEanExtensions,
+                DecodeHintValue::VecI32(extensions.clone()),
+            );
+        }
+        if let Some(degrees) = &self.try_harder_angles {
+            hints.insert(
+                DecodeHintType::TryHarderAngles,
+                DecodeHintValue::VecI32(degrees.clone()),
+            );
+        }
+        if let Some(max_pixels) = self.max_pixels {
+            hints.insert(DecodeHintType::MaxPixels, DecodeHintValue::I32(max_pixels));
+        }
+        hints
+    }
+}
+
+fn mismatch(hint: &DecodeHintType, value: &DecodeHintValue) -> Error {
+    Error::IllegalArgumentException(format!("Invalid value for {:?}: {:?}", hint, value))
+}
+
+impl TryFrom<&HashMap<DecodeHintType, DecodeHintValue>> for DecodeOptions {
+    type Error = Error;
+
+    /**
+     * Reads a hint map. Flag hints are on whenever present, whatever they map to; any other
+     * hint mapped to a value of the wrong type gives an IllegalArgumentException.
+     */
+    fn try_from(hints: &HashMap<DecodeHintType, DecodeHintValue>) -> ResultError<Self> {
+        let mut options = DecodeOptions::new();
+        for (hint, value) in hints.iter() {
+            options = match hint {
+                DecodeHintType::OTHER => match value {
+                    DecodeHintValue::STRING(other) => options.other(other.clone()),
+                    _ => return Err(mismatch(hint, value)),
+                },
+                DecodeHintType::PureBarcode => options.pure_barcode(true),
+                DecodeHintType::PossibleFormats => match value {
+                    DecodeHintValue::VecBarcodeFormat(formats) => {
+                        options.possible_formats(formats.clone())
+                    }
+                    _ => return Err(mismatch(hint, value)),
+                },
+                DecodeHintType::TryHarder => options.try_harder(true),
+                DecodeHintType::CharacterSet => match value {
+                    DecodeHintValue::STRING(name) => match Charset::from_str(name) {
+                        Ok(charset) => options.character_set(charset),
+                        Err(_) => return Err(mismatch(hint, value)),
+                    },
+                    _ => return Err(mismatch(hint, value)),
+                },
+                DecodeHintType::AllowedLengths => match value {
+                    DecodeHintValue::VecI32(lengths) => options.allowed_lengths(lengths.clone()),
+                    _ => return Err(mismatch(hint, value)),
+                },
+                DecodeHintType::AssumeCode39CheckDigit => options.assume_code39_check_digit(true),
+                DecodeHintType::AssumeGs1 => options.assume_gs1(true),
+                DecodeHintType::ReturnCodabarStartEnd => options.return_codabar_start_end(true),
+                DecodeHintType::NeedResultPointCallback => match value {
+                    DecodeHintValue::ResultPointCallback(callback) => {
+                        options.result_point_callback(Arc::clone(callback))
+                    }
+                    _ => return Err(mismatch(hint, value)),
+                },
+                DecodeHintType::AllowedEanExtensions => match value {
+                    DecodeHintValue::VecI32(extensions) => {
+                        options.allowed_ean_extensions(extensions.clone())
+                    }
+                    _ => return Err(mismatch(hint, value)),
+                },
+                DecodeHintType::AlsoInverted => options.also_inverted(true),
+                DecodeHintType::TryAlternativeBinarizers => {
+                    options.try_alternative_binarizers(true)
+                }
+                DecodeHintType::TryHarderAngles => match value {
+                    DecodeHintValue::VecI32(degrees) => options.try_harder_angles(degrees.clone()),
+                    _ => return Err(mismatch(hint, value)),
+                },
+                DecodeHintType::MultiScale => options.multi_scale(true),
+                DecodeHintType::MaxPixels => match value {
+                    DecodeHintValue::I32(max_pixels) => options.max_pixels(*max_pixels),
+                    _ => return Err(mismatch(hint, value)),
+                },
+            };
+        }
+        Ok(options)
+    }
+}
+
+impl From<&DecodeOptions> for HashMap<DecodeHintType, DecodeHintValue> {
+    fn from(options: &DecodeOptions) -> Self {
+        options.to_hints()
+    }
+}
+
+#[cfg(test)]
+mod decode_options_tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn round_trip_hints() {
+        let options = DecodeOptions::new()
+            .try_harder(true)
+            .possible_formats(vec![BarcodeFormat::QRCode])
+            .character_set(Charset::ShiftJIS)
+            .allowed_lengths(vec![8, 13])
+            .try_alternative_binarizers(true)
+            .max_pixels(1 << 20);
+        let hints = options.to_hints();
+        assert_eq!(
+            Some(&DecodeHintValue::STRING(String::from("Shift_JIS"))),
+            hints.get(&DecodeHintType::CharacterSet)
+        );
+        assert!(!hints.contains_key(&DecodeHintType::PureBarcode));
+
+        let parsed = DecodeOptions::try_from(&hints).unwrap();
+        assert!(parsed.is_try_harder());
+        assert!(!parsed.is_pure_barcode());
+        assert_eq!(
+            Some(&vec![BarcodeFormat::QRCode]),
+            parsed.get_possible_formats()
+        );
+        assert_eq!(Some(&Charset::ShiftJIS), parsed.get_character_set());
+        assert_eq!(Some(&vec![8, 13]), parsed.get_allowed_lengths());
+        assert!(parsed.is_try_alternative_binarizers());
+        assert_eq!(Some(1 << 20), parsed.get_max_pixels());
+    }
+
+    #[test]
+    fn mismatched_value() {
+        let mut hints = HashMap::new();
+        hints.insert(DecodeHintType::PossibleFormats, DecodeHintValue::BOOL(true));
+        assert!(matches!(
+            DecodeOptions::try_from(&hints),
+            Err(Error::IllegalArgumentException(_))
+        ));
+        assert_eq!(
+            None,
+            DecodeHintValue::BOOL(true).try_get_vec_barcode_format()
+        );
+        assert!(DecodeHintValue::BOOL(true).get_bool());
+    }
+
+    #[test]
+    fn result_point_callback() {
+        let found = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&found);
+        let options =
+            DecodeOptions::new().result_point_callback(Arc::new(move |_: &ResultPoint| {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }));
+        let parsed = DecodeOptions::try_from(&options.to_hints()).unwrap();
+        parsed.found_possible_result_point(&ResultPoint::new(1.0, 2.0));
+        assert_eq!(1, found.load(Ordering::SeqCst));
+    }
+}
@@ -1,7 +1,7 @@
+use strum_macros::Display;
 use strum_macros::EnumString;
-use strum_macros::ToString;
 
-#[derive(Debug, PartialEq, Eq, Hash, EnumString, ToString)]
+#[derive(Debug, PartialEq, Eq, Hash, EnumString, Display)]
 pub enum EncodeHintType {
     /**
      * Specifies what degree of error correction to use, for example in QR Codes.
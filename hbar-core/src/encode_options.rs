@@ -0,0 +1,383 @@
+use crate::common::Charset;
+use crate::datamatrix::encoder::SymbolShapeHint;
+use crate::encode_hint_type::EncodeHintType;
+use crate::pdf417::encoder::{Compaction, Dimensions};
+use crate::qrcode::decoder::ErrorCorrectionLevel;
+use crate::{Error, ResultError};
+
+use alloc::format;
+#[cfg(feature = "std")]
+use alloc::string::{String, ToString};
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::convert::TryFrom;
+#[cfg(feature = "std")]
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+/**
+ * Degree of error correction to use. The meaning depends on the format.
+ */
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ErrorCorrection {
+    /** QR Code error correction level */
+    Level(ErrorCorrectionLevel),
+    /**
+     * For Aztec, the minimal percentage of error correction words (0 to 100).
+     * For PDF417, the error correction level (0 to 8).
+     */
+    Value(i32),
+}
+
+/**
+ * Typed encoding options. Every {@link EncodeHintType} has a matching setter; values are checked
+ * by {@link #validate()}, which writers call before any encoding work starts.
+ *
+ * <pre>
+ * let options = EncodeOptions::new()
+ *     .error_correction(ErrorCorrectionLevel::H)
+ *     .margin(2);
+ * </pre>
+ */
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct EncodeOptions {
+    error_correction: Option<ErrorCorrection>,
+    character_set: Option<Charset>,
+    data_matrix_shape: Option<SymbolShapeHint>,
+    margin: Option<i32>,
+    pdf417_compact: Option<bool>,
+    pdf417_compaction: Option<Compaction>,
+    pdf417_dimensions: Option<Dimensions>,
+    aztec_layers: Option<i32>,
+    qr_version: Option<i32>,
+    qr_mask_pattern: Option<i32>,
+    gs1_format: Option<bool>,
+    force_code_set: Option<char>,
+}
+
+impl EncodeOptions {
+    pub fn new() -> Self {
+        EncodeOptions::default()
+    }
+
+    /**
+     * Sets the QR Code error correction level.
+     */
+    pub fn error_correction(mut self, ec_level: ErrorCorrectionLevel) -> Self {
+        self.error_correction = Some(ErrorCorrection::Level(ec_level));
+        self
+    }
+
+    /**
+     * Sets a numeric error correction value, for formats such as Aztec and PDF417.
+     */
+    pub fn error_correction_value(mut self, value: i32) -> Self {
+        self.error_correction = Some(ErrorCorrection::Value(value));
+        self
+    }
+
+    pub fn character_set(mut self, charset: Charset) -> Self {
+        self.character_set = Some(charset);
+        self
+    }
+
+    pub fn data_matrix_shape(mut self, shape: SymbolShapeHint) -> Self {
+        self.data_matrix_shape = Some(shape);
+        self
+    }
+
+    pub fn margin(mut self, margin: i32) -> Self {
+        self.margin = Some(margin);
+        self
+    }
+
+    pub fn pdf417_compact(mut self, compact: bool) -> Self {
+        self.pdf417_compact = Some(compact);
+        self
+    }
+
+    pub fn pdf417_compaction(mut self, compaction: Compaction) -> Self {
+        self.pdf417_compaction = Some(compaction);
+        self
+    }
+
+    pub fn pdf417_dimensions(mut self, dimensions: Dimensions) -> Self {
+        self.pdf417_dimensions = Some(dimensions);
+        self
+    }
+
+    pub fn aztec_layers(mut self, layers: i32) -> Self {
+        self.aztec_layers = Some(layers);
+        self
+    }
+
+    pub fn qr_version(mut self, version: i32) -> Self {
+        self.qr_version = Some(version);
+        self
+    }
+
+    pub fn qr_mask_pattern(mut self, mask_pattern: i32) -> Self {
+        self.qr_mask_pattern = Some(mask_pattern);
+        self
+    }
+
+    pub fn gs1_format(mut self, gs1_format: bool) -> Self {
+        self.gs1_format = Some(gs1_format);
+        self
+    }
+
+    pub fn force_code_set(mut self, code_set: char) -> Self {
+        self.force_code_set = Some(code_set);
+        self
+    }
+
+    pub fn get_error_correction(&self) -> Option<&ErrorCorrection> {
+        self.error_correction.as_ref()
+    }
+
+    pub fn get_character_set(&self) -> Option<&Charset> {
+        self.character_set.as_ref()
+    }
+
+    pub fn get_data_matrix_shape(&self) -> Option<SymbolShapeHint> {
+        self.data_matrix_shape
+    }
+
+    pub fn get_margin(&self) -> Option<i32> {
+        self.margin
+    }
+
+    pub fn get_pdf417_compact(&self) -> Option<bool> {
+        self.pdf417_compact
+    }
+
+    pub fn get_pdf417_compaction(&self) -> Option<Compaction> {
+        self.pdf417_compaction
+    }
+
+    pub fn get_pdf417_dimensions(&self) -> Option<&Dimensions> {
+        self.pdf417_dimensions.as_ref()
+    }
+
+    pub fn get_aztec_layers(&self) -> Option<i32> {
+        self.aztec_layers
+    }
+
+    pub fn get_qr_version(&self) -> Option<i32> {
+        self.qr_version
+    }
+
+    pub fn get_qr_mask_pattern(&self) -> Option<i32> {
+        self.qr_mask_pattern
+    }
+
+    pub fn is_gs1_format(&self) -> bool {
+        self.gs1_format.unwrap_or(false)
+    }
+
+    pub fn get_force_code_set(&self) -> Option<char> {
+        self.force_code_set
+    }
+
+    /**
+     * Checks every option that has been set against its allowed range.
+     *
+     * @throws IllegalArgumentException naming the first invalid option
+     */
+    pub fn validate(&self) -> ResultError<()> {
+        if let Some(ErrorCorrection::Value(value)) = self.error_correction {
+            if !(0..=100).contains(&value) {
+                return Err(invalid(&EncodeHintType::ErrorCorrection, value));
+            }
+        }
+        if let Some(margin) = self.margin {
+            if margin < 0 {
+                return Err(invalid(&EncodeHintType::MARGIN, margin));
+            }
+        }
+        if let Some(layers) = self.aztec_layers {
+            if !(-4..=32).contains(&layers) {
+                return Err(invalid(&EncodeHintType::AztecLayers, layers));
+            }
+        }
+        if let Some(version) = self.qr_version {
+            if !(1..=40).contains(&version) {
+                return Err(invalid(&EncodeHintType::QRVersion, version));
+            }
+        }
+        if let Some(mask_pattern) = self.qr_mask_pattern {
+            if !(0..8).contains(&mask_pattern) {
+                return Err(invalid(&EncodeHintType::QRMaskPattern, mask_pattern));
+            }
+        }
+        if let Some(code_set) = self.force_code_set {
+            if !('A'..='C').contains(&code_set) {
+                return Err(invalid(&EncodeHintType::ForceCodeSet, code_set));
+            }
+        }
+        Ok(())
+    }
+
+    /**
+     * Converts these options back to the string form used by the hint map.
+     */
+    #[cfg(feature = "std")]
+    pub fn to_hints(&self) -> HashMap<EncodeHintType, String> {
+        let mut hints = HashMap::new();
+        if let Some(error_correction) = &self.error_correction {
+            let value = match error_correction {
+                ErrorCorrection::Level(ec_level) => format!("{:?}", ec_level),
+                ErrorCorrection::Value(value) => value.to_string(),
+            };
+            hints.insert(EncodeHintType::ErrorCorrection, value);
+        }
+        if let Some(charset) = &self.character_set {
+            hints.insert(EncodeHintType::CharacterSet, charset.to_string());
+        }
+        if let Some(shape) = self.data_matrix_shape {
+            hints.insert(EncodeHintType::DataMatrixShape, shape.to_string());
+        }
+        if let Some(margin) = self.margin {
+            hints.insert(EncodeHintType::MARGIN, margin.to_string());
+        }
+        if let Some(compact) = self.pdf417_compact {
+            hints.insert(EncodeHintType::Pdf417Compact, compact.to_string());
+        }
+        if let Some(compaction) = self.pdf417_compaction {
+            hints.insert(EncodeHintType::Pdf417Compaction, compaction.to_string());
+        }
+        if let Some(dimensions) = &self.pdf417_dimensions {
+            hints.insert(
+                EncodeHintType::Pdf417Dimensions,
+                format!(
+                    "{},{},{},{}",
+                    dimensions.get_min_cols(),
+                    dimensions.get_max_cols(),
+                    dimensions.get_min_rows(),
+                    dimensions.get_max_rows()
+                ),
+            );
+        }
+        if let Some(layers) = self.aztec_layers {
+            hints.insert(EncodeHintType::AztecLayers, layers.to_string());
+        }
+        if let Some(version) = self.qr_version {
+            hints.insert(EncodeHintType::QRVersion, version.to_string());
+        }
+        if let Some(mask_pattern) = self.qr_mask_pattern {
+            hints.insert(EncodeHintType::QRMaskPattern, mask_pattern.to_string());
+        }
+        if let Some(gs1_format) = self.gs1_format {
+            hints.insert(EncodeHintType::Gs1Format, gs1_format.to_string());
+        }
+        if let Some(code_set) = self.force_code_set {
+            hints.insert(EncodeHintType::ForceCodeSet, code_set.to_string());
+        }
+        hints
+    }
+}
+
+fn invalid<T: core::fmt::Display>(hint: &EncodeHintType, value: T) -> Error {
+    Error::IllegalArgumentException(format!("Invalid value for {:?}: {}", hint, value))
+}
+
+#[cfg(feature = "std")]
+fn parse<T: FromStr>(hint: &EncodeHintType, value: &str) -> ResultError<T> {
+    value.trim().parse::<T>().map_err(|_| invalid(hint, value))
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<&HashMap<EncodeHintType, &String>> for EncodeOptions {
+    type Error = Error;
+
+    /**
+     * Parses the string values of a hint map. Unparseable or out of range values give
+     * an IllegalArgumentException.
+     */
+    fn try_from(hints: &HashMap<EncodeHintType, &String>) -> ResultError<Self> {
+        let mut options = EncodeOptions::new();
+        for (hint, value) in hints.iter() {
+            let value = value.as_str();
+            options = match hint {
+                EncodeHintType::ErrorCorrection => match ErrorCorrectionLevel::from(value) {
+                    Ok(ec_level) => options.error_correction(ec_level),
+                    Err(_) => options.error_correction_value(parse(hint, value)?),
+                },
+                EncodeHintType::CharacterSet => options.character_set(parse(hint, value)?),
+                EncodeHintType::DataMatrixShape => options.data_matrix_shape(parse(hint, value)?),
+                EncodeHintType::MARGIN => options.margin(parse(hint, value)?),
+                EncodeHintType::Pdf417Compact => options.pdf417_compact(parse(hint, value)?),
+                EncodeHintType::Pdf417Compaction => options.pdf417_compaction(parse(hint, value)?),
+                EncodeHintType::Pdf417Dimensions => {
+                    let values = value
+                        .split(',')
+                        .map(|v| parse::<i32>(hint, v))
+                        .collect::<ResultError<Vec<i32>>>()?;
+                    if values.len() != 4 {
+                        return Err(invalid(hint, value));
+                    }
+                    options.pdf417_dimensions(Dimensions::new(
+                        values[0], values[1], values[2], values[3],
+                    )?)
+                }
+                EncodeHintType::AztecLayers => options.aztec_layers(parse(hint, value)?),
+                EncodeHintType::QRVersion => options.qr_version(parse(hint, value)?),
+                EncodeHintType::QRMaskPattern => options.qr_mask_pattern(parse(hint, value)?),
+                EncodeHintType::Gs1Format => options.gs1_format(parse(hint, value)?),
+                EncodeHintType::ForceCodeSet => options.force_code_set(parse(hint, value)?),
+            };
+        }
+        options.validate()?;
+        Ok(options)
+    }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<HashMap<EncodeHintType, &String>> for EncodeOptions {
+    type Error = Error;
+
+    fn try_from(hints: HashMap<EncodeHintType, &String>) -> ResultError<Self> {
+        EncodeOptions::try_from(&hints)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod encode_options_tests {
+    use super::*;
+
+    #[test]
+    fn from_hints() {
+        let ec_level = String::from("H");
+        let margin = String::from("2");
+        let charset = String::from("Shift_JIS");
+        let mut hints: HashMap<EncodeHintType, &String> = HashMap::new();
+        hints.insert(EncodeHintType::ErrorCorrection, &ec_level);
+        hints.insert(EncodeHintType::MARGIN, &margin);
+        hints.insert(EncodeHintType::CharacterSet, &charset);
+        let options = EncodeOptions::try_from(&hints).unwrap();
+        assert_eq!(
+            EncodeOptions::new()
+                .error_correction(ErrorCorrectionLevel::H)
+                .margin(2)
+                .character_set(Charset::ShiftJIS),
+            options
+        );
+        assert_eq!("H", options.to_hints()[&EncodeHintType::ErrorCorrection]);
+    }
+
+    #[test]
+    fn invalid_values() {
+        let typo = String::from("2O");
+        let mut hints: HashMap<EncodeHintType, &String> = HashMap::new();
+        hints.insert(EncodeHintType::MARGIN, &typo);
+        assert!(matches!(
+            EncodeOptions::try_from(&hints),
+            Err(Error::IllegalArgumentException(_))
+        ));
+        assert!(EncodeOptions::new().qr_mask_pattern(8).validate().is_err());
+        assert!(EncodeOptions::new().qr_version(41).validate().is_err());
+        assert!(EncodeOptions::new().aztec_layers(-5).validate().is_err());
+    }
+}
@@ -1,3 +1,5 @@
+use alloc::string::String;
+
 #[derive(Debug)]
 pub enum Error {
     IllegalArgumentException(String),
@@ -11,5 +13,6 @@ pub enum Error {
     ReedSolomonException(String),
     IllegalStateException(String),
     FormatException(String),
+    ChecksumException(String),
 }
 pub type ResultError<T> = Result<T, Error>;
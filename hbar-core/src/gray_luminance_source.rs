@@ -0,0 +1,283 @@
+use crate::InvertedLuminanceSource;
+use crate::LuminanceSource;
+use crate::{Error, ResultError};
+
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+enum GrayData<'a> {
+    Borrowed(&'a [u8]),
+    Shared(Arc<[u8]>),
+}
+
+impl<'a> GrayData<'a> {
+    fn as_slice(&self) -> &[u8] {
+        match self {
+            GrayData::Borrowed(data) => data,
+            GrayData::Shared(data) => data,
+        }
+    }
+}
+
+/**
+ * A {@link LuminanceSource} over 8-bit grayscale pixels, one byte per pixel, with rows
+ * {@code stride} bytes apart. The pixels may be borrowed from the caller, for example a
+ * camera frame or a row-padded image buffer, so no copy is made to decode them.
+ *
+ * Cropping shares the pixels. As the {@link LuminanceSource} trait hands out owned sources,
+ * {@link #crop} copies the cropped region once when the pixels are borrowed; use
+ * {@link #view} to crop a borrowed buffer without copying.
+ */
+#[derive(Debug, Clone)]
+pub struct GrayLuminanceSource<'a> {
+    data: GrayData<'a>,
+    stride: u32,
+    left: u32,
+    top: u32,
+    width: u32,
+    height: u32,
+}
+
+impl<'a> GrayLuminanceSource<'a> {
+    /**
+     * @param data the pixels, row-major, one byte per pixel
+     * @param width image width in pixels
+     * @param height image height in pixels
+     * @param stride distance in bytes between the starts of two rows, at least {@code width}
+     * @throws IllegalArgumentException if the buffer is too small for the given geometry
+     */
+    pub fn new(
+        data: &'a [u8],
+        width: u32,
+        height: u32,
+        stride: u32,
+    ) -> ResultError<GrayLuminanceSource<'a>> {
+        GrayLuminanceSource::check(data.len(), width, height, stride)?;
+        Ok(GrayLuminanceSource {
+            data: GrayData::Borrowed(data),
+            stride,
+            left: 0,
+            top: 0,
+            width,
+            height,
+        })
+    }
+
+    fn check(len: usize, width: u32, height: u32, stride: u32) -> ResultError<()> {
+        if width == 0 || height == 0 {
+            return Err(Error::IllegalArgumentException(String::from(
+                "Both dimensions must be greater than 0",
+            )));
+        }
+        if stride < width {
+            return Err(Error::IllegalArgumentException(format!(
+                "Stride {} is smaller than the width {}",
+                stride, width
+            )));
+        }
+        let needed = stride as usize * (height as usize - 1) + width as usize;
+        if len < needed {
+            return Err(Error::IllegalArgumentException(format!(
+                "Buffer of {} bytes is too small for a {}x{} image with stride {}",
+                len, width, height, stride
+            )));
+        }
+        Ok(())
+    }
+
+    /**
+     * Returns a view of part of this source which shares its pixels, including borrowed ones.
+     *
+     * @throws IllegalArgumentException if the rectangle does not fit inside this source
+     */
+    pub fn view(
+        &self,
+        left: u32,
+        top: u32,
+        width: u32,
+        height: u32,
+    ) -> ResultError<GrayLuminanceSource<'a>> {
+        if width == 0
+            || height == 0
+            || left as u64 + width as u64 > self.width as u64
+            || top as u64 + height as u64 > self.height as u64
+        {
+            return Err(Error::IllegalArgumentException(String::from(
+                "Crop rectangle does not fit within image data.",
+            )));
+        }
+        Ok(GrayLuminanceSource {
+            data: self.data.clone(),
+            stride: self.stride,
+            left: self.left + left,
+            top: self.top + top,
+            width,
+            height,
+        })
+    }
+
+    /**
+     * Returns an equivalent source which owns its pixels. Only copies when they are borrowed.
+     */
+    pub fn into_owned(self) -> GrayLuminanceSource<'static> {
+        match self.data {
+            GrayData::Shared(data) => GrayLuminanceSource {
+                data: GrayData::Shared(data),
+                stride: self.stride,
+                left: self.left,
+                top: self.top,
+                width: self.width,
+                height: self.height,
+            },
+            GrayData::Borrowed(_) => {
+                let matrix = self.matrix();
+                GrayLuminanceSource::from_luminances(matrix, self.width, self.height)
+            }
+        }
+    }
+
+    fn row(&self, y: u32) -> &[u8] {
+        let offset = (self.top + y) as usize * self.stride as usize + self.left as usize;
+        &self.data.as_slice()[offset..offset + self.width as usize]
+    }
+
+    fn matrix(&self) -> Vec<u8> {
+        let mut matrix = Vec::with_capacity(self.width as usize * self.height as usize);
+        for y in 0..self.height {
+            matrix.extend_from_slice(self.row(y));
+        }
+        matrix
+    }
+}
+
+impl GrayLuminanceSource<'static> {
+    /**
+     * Takes ownership of tightly packed pixels, {@code width * height} bytes.
+     */
+    pub fn from_vec(
+        data: Vec<u8>,
+        width: u32,
+        height: u32,
+    ) -> ResultError<GrayLuminanceSource<'static>> {
+        GrayLuminanceSource::check(data.len(), width, height, width)?;
+        Ok(GrayLuminanceSource::from_luminances(data, width, height))
+    }
+
+    pub(crate) fn from_luminances(
+        luminances: Vec<u8>,
+        width: u32,
+        height: u32,
+    ) -> GrayLuminanceSource<'static> {
+        GrayLuminanceSource {
+            data: GrayData::Shared(Arc::from(luminances)),
+            stride: width,
+            left: 0,
+            top: 0,
+            width,
+            height,
+        }
+    }
+}
+
+impl<'a> LuminanceSource for GrayLuminanceSource<'a> {
+    fn get_row(&self, y: i32, _row: &Vec<u8>) -> ResultError<Vec<u8>> {
+        if y < 0 || y as u32 >= self.height {
+            return Err(Error::IllegalArgumentException(format!(
+                "Requested row is outside the image: {}",
+                y
+            )));
+        }
+        Ok(self.row(y as u32).to_vec())
+    }
+
+    fn get_matrix(&self) -> ResultError<Vec<u8>> {
+        Ok(self.matrix())
+    }
+
+    fn get_width(&self) -> u32 {
+        self.width
+    }
+
+    fn get_height(&self) -> u32 {
+        self.height
+    }
+
+    fn is_crop_supported(&self) -> bool {
+        true
+    }
+
+    fn crop(
+        &self,
+        left: u32,
+        top: u32,
+        width: u32,
+        height: u32,
+    ) -> ResultError<Arc<dyn LuminanceSource>> {
+        Ok(Arc::new(self.view(left, top, width, height)?.into_owned()))
+    }
+
+    fn is_rotate_supported(&self) -> bool {
+        true
+    }
+
+    fn invert(&self) -> ResultError<Arc<dyn LuminanceSource>> {
+        Ok(Arc::new(InvertedLuminanceSource::new(Arc::new(
+            self.clone().into_owned(),
+        ))))
+    }
+
+    fn rotate_counter_clockwise(&self) -> ResultError<Arc<dyn LuminanceSource>> {
+        let width = self.width as usize;
+        let height = self.height as usize;
+        let mut rotated = vec![0; width * height];
+        for y in 0..height {
+            let row = self.row(y as u32);
+            for (x, &luminance) in row.iter().enumerate() {
+                rotated[(width - 1 - x) * height + y] = luminance;
+            }
+        }
+        Ok(Arc::new(GrayLuminanceSource::from_luminances(
+            rotated,
+            self.height,
+            self.width,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod gray_luminance_source_tests {
+    use super::*;
+
+    // 3x2 image in a buffer with a stride of 4
+    const DATA: [u8; 8] = [1, 2, 3, 0, 4, 5, 6, 0];
+
+    #[test]
+    fn stride_and_crop() {
+        let source = GrayLuminanceSource::new(&DATA, 3, 2, 4).unwrap();
+        assert_eq!(vec![1, 2, 3, 4, 5, 6], source.get_matrix().unwrap());
+        assert_eq!(vec![4, 5, 6], source.get_row(1, &Vec::new()).unwrap());
+
+        let view = source.view(1, 0, 2, 2).unwrap();
+        assert_eq!(vec![2, 3, 5, 6], view.get_matrix().unwrap());
+        let cropped = view.crop(1, 1, 1, 1).unwrap();
+        assert_eq!(vec![6], cropped.get_matrix().unwrap());
+
+        assert!(source.view(2, 0, 2, 1).is_err());
+        assert!(GrayLuminanceSource::new(&DATA, 3, 3, 4).is_err());
+    }
+
+    #[test]
+    fn rotate_and_invert() {
+        let source = GrayLuminanceSource::new(&DATA, 3, 2, 4).unwrap();
+        let rotated = source.rotate_counter_clockwise().unwrap();
+        assert_eq!(2, rotated.get_width());
+        assert_eq!(3, rotated.get_height());
+        assert_eq!(vec![3, 6, 2, 5, 1, 4], rotated.get_matrix().unwrap());
+
+        let inverted = source.invert().unwrap();
+        assert_eq!(
+            vec![251, 250, 249],
+            inverted.get_row(1, &Vec::new()).unwrap()
+        );
+    }
+}
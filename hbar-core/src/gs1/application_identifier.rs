@@ -0,0 +1,402 @@
+use crate::{Error, ResultError};
+
+use alloc::format;
+
+/**
+ * The kinds of characters a component of a GS1 element string may hold.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gs1Content {
+    /** Digits */
+    Numeric,
+    /** The 82 characters of GS1 AI encodable character set 82 */
+    Cset82,
+    /** The 39 characters of GS1 AI encodable character set 39 */
+    Cset39,
+    /** A date, YYMMDD, where a day of 00 means the end of the month */
+    Date,
+    /** A date and time, YYMMDDhhmm */
+    DateTime,
+}
+
+/**
+ * One component of the data of an application identifier: its content, its least and greatest
+ * length, and whether its last digit is a GS1 check digit.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gs1Component {
+    content: Gs1Content,
+    min: usize,
+    max: usize,
+    check_digit: bool,
+}
+
+impl Gs1Component {
+    const fn new(content: Gs1Content, min: usize, max: usize, check_digit: bool) -> Self {
+        Gs1Component {
+            content,
+            min,
+            max,
+            check_digit,
+        }
+    }
+
+    pub fn get_content(&self) -> Gs1Content {
+        self.content
+    }
+
+    pub fn get_min(&self) -> usize {
+        self.min
+    }
+
+    pub fn get_max(&self) -> usize {
+        self.max
+    }
+
+    pub fn has_check_digit(&self) -> bool {
+        self.check_digit
+    }
+}
+
+const fn n(length: usize) -> Gs1Component {
+    Gs1Component::new(Gs1Content::Numeric, length, length, false)
+}
+
+const fn n_var(max: usize) -> Gs1Component {
+    Gs1Component::new(Gs1Content::Numeric, 1, max, false)
+}
+
+const fn n_check(length: usize) -> Gs1Component {
+    Gs1Component::new(Gs1Content::Numeric, length, length, true)
+}
+
+const fn x(max: usize) -> Gs1Component {
+    Gs1Component::new(Gs1Content::Cset82, 1, max, false)
+}
+
+const DATE: Gs1Component = Gs1Component::new(Gs1Content::Date, 6, 6, false);
+
+/**
+ * An application identifier from the GS1 General Specifications: the digits that say what the
+ * data following them means, and the format of that data. The decimal point indicator of
+ * AIs like 310n counts as part of the AI, so these are looked up by their first three digits.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApplicationIdentifier {
+    prefix: &'static str,
+    length: usize,
+    data_title: &'static str,
+    components: &'static [Gs1Component],
+}
+
+macro_rules! ai {
+    ($prefix:expr, $length:expr, $data_title:expr, [$($component:expr),+]) => {
+        ApplicationIdentifier {
+            prefix: $prefix,
+            length: $length,
+            data_title: $data_title,
+            components: &[$($component),+],
+        }
+    };
+}
+
+// The two digit prefixes of the element strings whose length is predefined, which need no
+// separator after them, after figure 7.8.5-2 of the General Specifications.
+const PREDEFINED_LENGTH_PREFIXES: [&str; 22] = [
+    "00", "01", "02", "03", "04", "11", "12", "13", "14", "15", "16", "17", "18", "19", "20", "31",
+    "32", "33", "34", "35", "36", "41",
+];
+
+const APPLICATION_IDENTIFIERS: [ApplicationIdentifier; 96] = [
+    ai!("00", 2, "SSCC", [n_check(18)]),
+    ai!("01", 2, "GTIN", [n_check(14)]),
+    ai!("02", 2, "CONTENT", [n_check(14)]),
+    ai!("10", 2, "BATCH/LOT", [x(20)]),
+    ai!("11", 2, "PROD DATE", [DATE]),
+    ai!("12", 2, "DUE DATE", [DATE]),
+    ai!("13", 2, "PACK DATE", [DATE]),
+    ai!("15", 2, "BEST BEFORE or BEST BY", [DATE]),
+    ai!("16", 2, "SELL BY", [DATE]),
+    ai!("17", 2, "USE BY or EXPIRY", [DATE]),
+    ai!("20", 2, "VARIANT", [n(2)]),
+    ai!("21", 2, "SERIAL", [x(20)]),
+    ai!("22", 2, "CPV", [x(20)]),
+    ai!("235", 3, "TPX", [x(28)]),
+    ai!("240", 3, "ADDITIONAL ID", [x(30)]),
+    ai!("241", 3, "CUST. PART No.", [x(30)]),
+    ai!("242", 3, "MTO VARIANT", [n_var(6)]),
+    ai!("243", 3, "PCN", [x(20)]),
+    ai!("250", 3, "SECONDARY SERIAL", [x(30)]),
+    ai!("251", 3, "REF. TO SOURCE", [x(30)]),
+    ai!(
+        "253",
+        3,
+        "GDTI",
+        [
+            n_check(13),
+            Gs1Component::new(Gs1Content::Cset82, 0, 17, false)
+        ]
+    ),
+    ai!("254", 3, "GLN EXTENSION COMPONENT", [x(20)]),
+    ai!(
+        "255",
+        3,
+        "GCN",
+        [
+            n_check(13),
+            Gs1Component::new(Gs1Content::Numeric, 0, 12, false)
+        ]
+    ),
+    ai!("30", 2, "VAR. COUNT", [n_var(8)]),
+    ai!("310", 4, "NET WEIGHT (kg)", [n(6)]),
+    ai!("311", 4, "LENGTH (m)", [n(6)]),
+    ai!("312", 4, "WIDTH (m)", [n(6)]),
+    ai!("313", 4, "HEIGHT (m)", [n(6)]),
+    ai!("314", 4, "AREA (m²)", [n(6)]),
+    ai!("315", 4, "NET VOLUME (l)", [n(6)]),
+    ai!("316", 4, "NET VOLUME (m³)", [n(6)]),
+    ai!("320", 4, "NET WEIGHT (lb)", [n(6)]),
+    ai!("321", 4, "LENGTH (i)", [n(6)]),
+    ai!("322", 4, "LENGTH (f)", [n(6)]),
+    ai!("323", 4, "LENGTH (y)", [n(6)]),
+    ai!("324", 4, "WIDTH (i)", [n(6)]),
+    ai!("325", 4, "WIDTH (f)", [n(6)]),
+    ai!("326", 4, "WIDTH (y)", [n(6)]),
+    ai!("327", 4, "HEIGHT (i)", [n(6)]),
+    ai!("328", 4, "HEIGHT (f)", [n(6)]),
+    ai!("329", 4, "HEIGHT (y)", [n(6)]),
+    ai!("330", 4, "GROSS WEIGHT (kg)", [n(6)]),
+    ai!("331", 4, "LENGTH (m), log", [n(6)]),
+    ai!("332", 4, "WIDTH (m), log", [n(6)]),
+    ai!("333", 4, "HEIGHT (m), log", [n(6)]),
+    ai!("334", 4, "AREA (m²), log", [n(6)]),
+    ai!("335", 4, "VOLUME (l), log", [n(6)]),
+    ai!("336", 4, "VOLUME (m³), log", [n(6)]),
+    ai!("340", 4, "GROSS WEIGHT (lb)", [n(6)]),
+    ai!("350", 4, "AREA (i²)", [n(6)]),
+    ai!("351", 4, "AREA (f²)", [n(6)]),
+    ai!("352", 4, "AREA (y²)", [n(6)]),
+    ai!("356", 4, "NET WEIGHT (t)", [n(6)]),
+    ai!("357", 4, "NET VOLUME (oz)", [n(6)]),
+    ai!("360", 4, "NET VOLUME (q)", [n(6)]),
+    ai!("361", 4, "NET VOLUME (g)", [n(6)]),
+    ai!("37", 2, "COUNT", [n_var(8)]),
+    ai!("390", 4, "AMOUNT", [n_var(15)]),
+    ai!("391", 4, "AMOUNT", [n(3), n_var(15)]),
+    ai!("392", 4, "PRICE", [n_var(15)]),
+    ai!("393", 4, "PRICE", [n(3), n_var(15)]),
+    ai!("400", 3, "ORDER NUMBER", [x(30)]),
+    ai!("401", 3, "GINC", [x(30)]),
+    ai!("402", 3, "GSIN", [n_check(17)]),
+    ai!("403", 3, "ROUTE", [x(30)]),
+    ai!("410", 3, "SHIP TO LOC", [n_check(13)]),
+    ai!("411", 3, "BILL TO", [n_check(13)]),
+    ai!("412", 3, "PURCHASE FROM", [n_check(13)]),
+    ai!("413", 3, "SHIP FOR LOC", [n_check(13)]),
+    ai!("414", 3, "LOC No.", [n_check(13)]),
+    ai!("415", 3, "PAY TO", [n_check(13)]),
+    ai!("416", 3, "PROD/SERV LOC", [n_check(13)]),
+    ai!("417", 3, "PARTY", [n_check(13)]),
+    ai!("420", 3, "SHIP TO POST", [x(20)]),
+    ai!("421", 3, "SHIP TO POST", [n(3), x(9)]),
+    ai!("422", 3, "ORIGIN", [n(3)]),
+    ai!(
+        "7003",
+        4,
+        "EXPIRY TIME",
+        [Gs1Component::new(Gs1Content::DateTime, 10, 10, false)]
+    ),
+    ai!(
+        "8003",
+        4,
+        "GRAI",
+        [
+            n(1),
+            n_check(13),
+            Gs1Component::new(Gs1Content::Cset82, 0, 16, false)
+        ]
+    ),
+    ai!("8004", 4, "GIAI", [x(30)]),
+    ai!("8006", 4, "ITIP", [n_check(14), n(2), n(2)]),
+    ai!(
+        "8010",
+        4,
+        "CPID",
+        [Gs1Component::new(Gs1Content::Cset39, 1, 30, false)]
+    ),
+    ai!("8017", 4, "GSRN - PROVIDER", [n_check(18)]),
+    ai!("8018", 4, "GSRN - RECIPIENT", [n_check(18)]),
+    ai!("8019", 4, "SRIN", [n_var(10)]),
+    ai!("8020", 4, "REF No.", [x(25)]),
+    ai!("8200", 4, "PRODUCT URL", [x(70)]),
+    ai!("90", 2, "INTERNAL", [x(30)]),
+    ai!("91", 2, "INTERNAL", [x(90)]),
+    ai!("92", 2, "INTERNAL", [x(90)]),
+    ai!("93", 2, "INTERNAL", [x(90)]),
+    ai!("94", 2, "INTERNAL", [x(90)]),
+    ai!("95", 2, "INTERNAL", [x(90)]),
+    ai!("96", 2, "INTERNAL", [x(90)]),
+    ai!("97", 2, "INTERNAL", [x(90)]),
+    ai!("98", 2, "INTERNAL", [x(90)]),
+    ai!("99", 2, "INTERNAL", [x(90)]),
+];
+
+impl ApplicationIdentifier {
+    /**
+     * Finds the application identifier which {@code data} starts with.
+     *
+     * @param data an element string, or just its AI
+     * @return the AI, or None if the data does not start with a known one
+     */
+    pub fn find(data: &str) -> Option<&'static ApplicationIdentifier> {
+        APPLICATION_IDENTIFIERS.iter().find(|ai| {
+            data.starts_with(ai.prefix)
+                && data
+                    .get(..ai.length)
+                    .is_some_and(|digits| digits.bytes().all(|b| b.is_ascii_digit()))
+        })
+    }
+
+    /**
+     * @return the digits common to every AI of this kind, like "310" for AIs 3100 to 3109
+     */
+    pub fn get_prefix(&self) -> &'static str {
+        self.prefix
+    }
+
+    /**
+     * @return the number of digits of the AI
+     */
+    pub fn get_length(&self) -> usize {
+        self.length
+    }
+
+    pub fn get_data_title(&self) -> &'static str {
+        self.data_title
+    }
+
+    pub fn get_components(&self) -> &'static [Gs1Component] {
+        self.components
+    }
+
+    /**
+     * @return whether the element string has a predefined length, and so needs no separator
+     *  after it even when it is not last
+     */
+    pub fn is_predefined_length(&self) -> bool {
+        PREDEFINED_LENGTH_PREFIXES.contains(&&self.prefix[..2])
+    }
+
+    /**
+     * @return the greatest length of the data following the AI
+     */
+    pub fn get_max_data_length(&self) -> usize {
+        self.components.iter().map(|component| component.max).sum()
+    }
+
+    /**
+     * Checks the data following the AI against the format of each component.
+     *
+     * @throws FormatException if the data is too short or long, holds characters outside the
+     *  allowed set, or has a bad date
+     * @throws ChecksumException if a check digit is wrong
+     */
+    pub fn validate(&self, ai: &str, data: &str) -> ResultError<()> {
+        let mut rest = data;
+        for (i, component) in self.components.iter().enumerate() {
+            let is_last = i == self.components.len() - 1;
+            let length = if is_last {
+                rest.len()
+            } else {
+                component.max.min(rest.len())
+            };
+            if length < component.min || length > component.max || !rest.is_char_boundary(length) {
+                return Err(Error::FormatException(format!(
+                    "AI ({}) takes {} to {} characters in component {}: {}",
+                    ai,
+                    component.min,
+                    component.max,
+                    i + 1,
+                    data
+                )));
+            }
+            let value = &rest[..length];
+            if !ApplicationIdentifier::is_valid_content(component.content, value) {
+                return Err(Error::FormatException(format!(
+                    "AI ({}) has invalid {:?} content: {}",
+                    ai, component.content, data
+                )));
+            }
+            if component.check_digit && !is_valid_check_digit(value) {
+                return Err(Error::ChecksumException(format!(
+                    "AI ({}) has a wrong check digit: {}",
+                    ai, data
+                )));
+            }
+            rest = &rest[length..];
+        }
+        Ok(())
+    }
+
+    fn is_valid_content(content: Gs1Content, value: &str) -> bool {
+        match content {
+            Gs1Content::Numeric => value.bytes().all(|b| b.is_ascii_digit()),
+            Gs1Content::Cset82 => value
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b"!\"%&'()*+,-./:;<=>?_".contains(&b)),
+            Gs1Content::Cset39 => value
+                .bytes()
+                .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || b"#-/".contains(&b)),
+            Gs1Content::Date => is_valid_date(value),
+            Gs1Content::DateTime => {
+                value.len() == 10
+                    && is_valid_date(&value[..6])
+                    && value[6..].bytes().all(|b| b.is_ascii_digit())
+                    && &value[6..8] < "24"
+                    && &value[8..] < "60"
+            }
+        }
+    }
+}
+
+/**
+ * Computes the GS1 check digit of the given digits: the weights 3 and 1 alternate from the
+ * right, and the check digit brings the weighted sum up to a multiple of 10.
+ *
+ * @param digits the digits without their check digit
+ * @return the check digit, or None if there is something other than digits
+ */
+pub fn check_digit(digits: &str) -> Option<u8> {
+    let mut sum = 0u32;
+    for (i, b) in digits.bytes().rev().enumerate() {
+        if !b.is_ascii_digit() {
+            return None;
+        }
+        let weight = if i % 2 == 0 { 3 } else { 1 };
+        sum += weight * (b - b'0') as u32;
+    }
+    Some(((10 - sum % 10) % 10) as u8)
+}
+
+fn is_valid_check_digit(value: &str) -> bool {
+    let (digits, check) = value.split_at(value.len() - 1);
+    check_digit(digits) == Some(check.as_bytes()[0] - b'0')
+}
+
+fn is_valid_date(value: &str) -> bool {
+    if value.len() != 6 || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return false;
+    }
+    let number = |range: core::ops::Range<usize>| value[range].parse::<u32>().unwrap();
+    let (year, month, day) = (number(0..2), number(2..4), number(4..6));
+    let days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        // The century is unknown, but 00 is 2000, a leap year
+        2 if year % 4 == 0 => 29,
+        2 => 28,
+        _ => return false,
+    };
+    // A day of 00 stands for the last day of the month
+    day <= days
+}
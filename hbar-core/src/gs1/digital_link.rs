@@ -0,0 +1,61 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/**
+ * The AIs which may be the primary key of a GS1 Digital Link, in the order one is picked when
+ * element strings hold several.
+ */
+pub(crate) const PRIMARY_KEYS: [&str; 13] = [
+    "01", "00", "414", "417", "8003", "8004", "8006", "8017", "8018", "253", "255", "401", "402",
+];
+
+/**
+ * @return the AIs which may follow a primary key in the path of a GS1 Digital Link, in their
+ *  required order
+ */
+pub(crate) fn get_qualifiers(primary_key: &str) -> &'static [&'static str] {
+    match primary_key {
+        "01" | "8006" => &["22", "10", "21"],
+        "414" => &["254"],
+        "8017" | "8018" => &["8019"],
+        _ => &[],
+    }
+}
+
+/**
+ * Percent-encodes every byte of the UTF-8 value except the unreserved characters of RFC 3986.
+ */
+pub(crate) fn percent_encode(value: &str) -> String {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+    let mut encoded = String::with_capacity(value.len());
+    for b in value.bytes() {
+        if b.is_ascii_alphanumeric() || b"-._~".contains(&b) {
+            encoded.push(b as char);
+        } else {
+            encoded.push('%');
+            encoded.push(HEX[(b >> 4) as usize] as char);
+            encoded.push(HEX[(b & 0xf) as usize] as char);
+        }
+    }
+    encoded
+}
+
+/**
+ * Decodes percent escapes, as UTF-8. Unlike form decoding, '+' is kept.
+ *
+ * @return None if an escape is malformed or the bytes are not UTF-8
+ */
+pub(crate) fn percent_decode(encoded: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(encoded.len());
+    let mut iter = encoded.bytes();
+    while let Some(b) = iter.next() {
+        if b == b'%' {
+            let high = (iter.next()? as char).to_digit(16)?;
+            let low = (iter.next()? as char).to_digit(16)?;
+            bytes.push((high << 4 | low) as u8);
+        } else {
+            bytes.push(b);
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
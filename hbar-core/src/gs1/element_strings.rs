@@ -0,0 +1,400 @@
+use crate::{Error, ResultError};
+
+use super::digital_link::{get_qualifiers, percent_decode, percent_encode, PRIMARY_KEYS};
+use super::ApplicationIdentifier;
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+/**
+ * The group separator, which stands for FNC1 after a variable length element string.
+ */
+pub const GROUP_SEPARATOR: char = '\u{1d}';
+
+/**
+ * GS1 data: the values of a set of application identifiers, each checked against the built-in
+ * AI table. It is read from and written to the concatenated element strings carried by GS1
+ * barcodes, like "0109501101020917\u{1d}10ABC123", or GS1 Digital Link URIs.
+ */
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ElementStrings {
+    elements: BTreeMap<String, String>,
+}
+
+impl ElementStrings {
+    pub fn new() -> Self {
+        ElementStrings {
+            elements: BTreeMap::new(),
+        }
+    }
+
+    /**
+     * Parses concatenated element strings. Element strings of variable length end at a group
+     * separator, or the end of the text. A leading symbology identifier, like "]Q3", or group
+     * separator is skipped.
+     *
+     * @throws FormatException if the text is not ASCII, an AI is unknown or repeated, or its
+     *  data is malformed
+     * @throws ChecksumException if a check digit is wrong
+     */
+    pub fn parse(text: &str) -> ResultError<ElementStrings> {
+        // Every GS1 character set is a subset of ASCII, which also keeps the slicing below on
+        // character boundaries
+        if !text.is_ascii() {
+            return Err(Error::FormatException(format!(
+                "GS1 element strings must be ASCII: {}",
+                text
+            )));
+        }
+        let mut rest = text;
+        if rest.starts_with(']') {
+            rest = rest.get(3..).unwrap_or_default();
+        }
+        rest = rest.strip_prefix(GROUP_SEPARATOR).unwrap_or(rest);
+        if rest.is_empty() {
+            return Err(Error::FormatException(String::from(
+                "No GS1 element strings",
+            )));
+        }
+        let mut element_strings = ElementStrings::new();
+        while !rest.is_empty() {
+            let ai = match ApplicationIdentifier::find(rest) {
+                Some(ai) => ai,
+                None => {
+                    return Err(Error::FormatException(format!(
+                        "Unknown application identifier: {}",
+                        rest
+                    )))
+                }
+            };
+            let (digits, data) = rest.split_at(ai.get_length());
+            let end = if ai.is_predefined_length() {
+                ai.get_max_data_length().min(data.len())
+            } else {
+                data.find(GROUP_SEPARATOR).unwrap_or(data.len())
+            };
+            element_strings.insert_new(digits, &data[..end])?;
+            rest = &data[end..];
+            rest = rest.strip_prefix(GROUP_SEPARATOR).unwrap_or(rest);
+        }
+        Ok(element_strings)
+    }
+
+    /**
+     * Sets the value of an application identifier, replacing any it had.
+     *
+     * @param ai the digits of the AI, like "01" or "3103"
+     * @param value the data of the element string
+     * @throws FormatException if the AI is unknown, or the value is malformed
+     * @throws ChecksumException if a check digit is wrong
+     */
+    pub fn insert(&mut self, ai: &str, value: &str) -> ResultError<()> {
+        match ApplicationIdentifier::find(ai) {
+            Some(found) if found.get_length() == ai.len() => {
+                found.validate(ai, value)?;
+                self.elements.insert(ai.to_string(), value.to_string());
+                Ok(())
+            }
+            _ => Err(Error::FormatException(format!(
+                "Unknown application identifier: {}",
+                ai
+            ))),
+        }
+    }
+
+    fn insert_new(&mut self, ai: &str, value: &str) -> ResultError<()> {
+        if self.elements.contains_key(ai) {
+            return Err(Error::FormatException(format!(
+                "Application identifier ({}) is repeated",
+                ai
+            )));
+        }
+        self.insert(ai, value)
+    }
+
+    /**
+     * @return the value of the given AI, or None if it is not present
+     */
+    pub fn get(&self, ai: &str) -> Option<&str> {
+        self.elements.get(ai).map(String::as_str)
+    }
+
+    /**
+     * @return the values by AI, in the order of the AIs
+     */
+    pub fn get_elements(&self) -> &BTreeMap<String, String> {
+        &self.elements
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /**
+     * Concatenates the element strings for encoding, with FNC1 in the first position left to
+     * the encoder. Element strings of predefined length go first, so that as few group
+     * separators as possible are needed.
+     */
+    pub fn to_element_string(&self) -> String {
+        let (predefined, variable): (Vec<_>, Vec<_>) = self.elements.iter().partition(|(ai, _)| {
+            ApplicationIdentifier::find(ai).is_some_and(ApplicationIdentifier::is_predefined_length)
+        });
+        let mut text = String::new();
+        for (ai, value) in predefined {
+            text.push_str(ai);
+            text.push_str(value);
+        }
+        for (i, (ai, value)) in variable.iter().enumerate() {
+            if i > 0 {
+                text.push(GROUP_SEPARATOR);
+            }
+            text.push_str(ai);
+            text.push_str(value);
+        }
+        text
+    }
+
+    /**
+     * Reads a GS1 Digital Link URI, like "https://id.gs1.org/01/09520123456788/10/ABC1?17=201225".
+     * The path ends with a primary key, such as a GTIN, and its key qualifiers; other AIs are
+     * query parameters. Query parameters which are not AIs, like "linkType", are ignored.
+     * GTIN-8, GTIN-12 and GTIN-13 are padded to 14 digits.
+     *
+     * @throws FormatException if the URI has no primary key, a path AI does not qualify it, an
+     *  AI is repeated or a value is malformed
+     * @throws ChecksumException if a check digit is wrong
+     */
+    pub fn from_digital_link(uri: &str) -> ResultError<ElementStrings> {
+        let uri = uri.split('#').next().unwrap_or_default();
+        let (path, query) = uri.split_once('?').unwrap_or((uri, ""));
+        let path = match path.find("://") {
+            Some(scheme_end) => {
+                let authority = &path[scheme_end + 3..];
+                authority.find('/').map_or("", |slash| &authority[slash..])
+            }
+            None => {
+                return Err(Error::FormatException(format!(
+                    "Not an absolute URI: {}",
+                    uri
+                )))
+            }
+        };
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+        // The AIs are found from the end of the path, as it may start with segments of its own
+        let mut start = segments.len();
+        while start >= 2 && ElementStrings::is_ai(segments[start - 2]) {
+            start -= 2;
+        }
+        let pairs: Vec<(&str, &str)> = segments[start..]
+            .chunks(2)
+            .map(|pair| (pair[0], pair[1]))
+            .collect();
+        let primary_key = match pairs.first() {
+            Some((ai, _)) if PRIMARY_KEYS.contains(ai) => *ai,
+            _ => {
+                return Err(Error::FormatException(format!(
+                    "No GS1 primary key in the path: {}",
+                    path
+                )))
+            }
+        };
+        let qualifiers = get_qualifiers(primary_key);
+        let mut last_qualifier = None;
+        for (ai, _) in &pairs[1..] {
+            let position = qualifiers.iter().position(|qualifier| qualifier == ai);
+            if position.is_none() || position <= last_qualifier {
+                return Err(Error::FormatException(format!(
+                    "AI ({}) is not a key qualifier of ({}) in this place",
+                    ai, primary_key
+                )));
+            }
+            last_qualifier = position;
+        }
+
+        let mut element_strings = ElementStrings::new();
+        for (ai, value) in pairs {
+            let mut value = ElementStrings::decode_component(value)?;
+            if ai == "01" && matches!(value.len(), 8 | 12 | 13) {
+                value = format!("{:0>14}", value);
+            }
+            element_strings.insert_new(ai, &value)?;
+        }
+        for parameter in query.split('&') {
+            if let Some((ai, value)) = parameter.split_once('=') {
+                if ElementStrings::is_ai(ai) {
+                    element_strings.insert_new(ai, &ElementStrings::decode_component(value)?)?;
+                }
+            }
+        }
+        Ok(element_strings)
+    }
+
+    /**
+     * Writes a GS1 Digital Link URI: the primary key and its key qualifiers as the path, and
+     * the other AIs as query parameters.
+     *
+     * @param base the scheme and domain, and any path of its own, like "https://id.gs1.org"
+     * @throws IllegalArgumentException if there is no primary key
+     */
+    pub fn to_digital_link(&self, base: &str) -> ResultError<String> {
+        let primary_key = match PRIMARY_KEYS
+            .iter()
+            .find(|ai| self.elements.contains_key(**ai))
+        {
+            Some(primary_key) => *primary_key,
+            None => {
+                return Err(Error::IllegalArgumentException(String::from(
+                    "A GS1 Digital Link needs a primary key, like a GTIN or SSCC",
+                )))
+            }
+        };
+        let mut uri = String::from(base.trim_end_matches('/'));
+        let mut path_ais = Vec::new();
+        for ai in core::iter::once(&primary_key).chain(get_qualifiers(primary_key)) {
+            if let Some(value) = self.elements.get(*ai) {
+                uri.push_str(&format!("/{}/{}", ai, percent_encode(value)));
+                path_ais.push(*ai);
+            }
+        }
+        let mut separator = '?';
+        for (ai, value) in &self.elements {
+            if !path_ais.contains(&ai.as_str()) {
+                uri.push_str(&format!("{}{}={}", separator, ai, percent_encode(value)));
+                separator = '&';
+            }
+        }
+        Ok(uri)
+    }
+
+    fn is_ai(segment: &str) -> bool {
+        ApplicationIdentifier::find(segment).is_some_and(|ai| ai.get_length() == segment.len())
+    }
+
+    fn decode_component(value: &str) -> ResultError<String> {
+        percent_decode(value)
+            .ok_or_else(|| Error::FormatException(format!("Malformed percent encoding: {}", value)))
+    }
+}
+
+/**
+ * Writes the element strings human readably, each AI in parentheses, like
+ * "(01)09501101020917(10)ABC123".
+ */
+impl fmt::Display for ElementStrings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (ai, value) in &self.elements {
+            write!(f, "({}){}", ai, value)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod element_strings_tests {
+    use super::ElementStrings;
+    use crate::gs1::{check_digit, ApplicationIdentifier};
+    use crate::Error;
+
+    use alloc::string::ToString;
+
+    #[test]
+    fn parses_element_strings() {
+        let parsed =
+            ElementStrings::parse("]Q3010952012345678810ABC123\u{1d}17201225\u{1d}3103000195")
+                .unwrap();
+        assert_eq!(parsed.len(), 4);
+        assert_eq!(parsed.get("01"), Some("09520123456788"));
+        assert_eq!(parsed.get("10"), Some("ABC123"));
+        assert_eq!(parsed.get("17"), Some("201225"));
+        assert_eq!(parsed.get("3103"), Some("000195"));
+        assert_eq!(
+            parsed.to_string(),
+            "(01)09520123456788(10)ABC123(17)201225(3103)000195"
+        );
+        // Predefined lengths first, so no separator is needed
+        assert_eq!(
+            parsed.to_element_string(),
+            "010952012345678817201225310300019510ABC123"
+        );
+        let parsed = ElementStrings::parse("10ABC123\u{1d}21XYZ").unwrap();
+        assert_eq!(parsed.to_element_string(), "10ABC123\u{1d}21XYZ");
+        assert_eq!(
+            ElementStrings::parse(&parsed.to_element_string()).unwrap(),
+            parsed
+        );
+        assert_eq!(
+            ApplicationIdentifier::find("3103000195")
+                .unwrap()
+                .get_data_title(),
+            "NET WEIGHT (kg)"
+        );
+        assert_eq!(check_digit("0952012345678"), Some(8));
+    }
+
+    #[test]
+    fn rejects_invalid_element_strings() {
+        let invalid = |text: &str| ElementStrings::parse(text).unwrap_err();
+        assert!(matches!(
+            invalid("0109520123456789"),
+            Error::ChecksumException(_)
+        ));
+        assert!(matches!(
+            invalid("010952012345678"),
+            Error::FormatException(_)
+        ));
+        assert!(matches!(invalid("17201232"), Error::FormatException(_)));
+        assert!(matches!(invalid("17201300"), Error::FormatException(_)));
+        assert!(matches!(
+            invalid("10ABC\u{1d}10DEF"),
+            Error::FormatException(_)
+        ));
+        assert!(matches!(invalid("10ABC DEF"), Error::FormatException(_)));
+        assert!(matches!(invalid("23ABC"), Error::FormatException(_)));
+        assert!(matches!(invalid(""), Error::FormatException(_)));
+        assert!(matches!(
+            invalid("019501234567890\u{e9}3"),
+            Error::FormatException(_)
+        ));
+        assert!(matches!(
+            invalid("1199139\u{e9}9"),
+            Error::FormatException(_)
+        ));
+        assert!(ElementStrings::parse("17200200").is_ok());
+    }
+
+    #[test]
+    fn converts_digital_links() {
+        let parsed = ElementStrings::from_digital_link(
+            "https://example.com/products/01/9520123456788/10/ABC%2F1/21/12345?17=201225&linkType=gs1:pip",
+        )
+        .unwrap();
+        assert_eq!(parsed.get("01"), Some("09520123456788"));
+        assert_eq!(parsed.get("10"), Some("ABC/1"));
+        assert_eq!(parsed.get("21"), Some("12345"));
+        assert_eq!(parsed.get("17"), Some("201225"));
+        assert_eq!(parsed.len(), 4);
+        assert_eq!(
+            parsed.to_digital_link("https://id.gs1.org/").unwrap(),
+            "https://id.gs1.org/01/09520123456788/10/ABC%2F1/21/12345?17=201225"
+        );
+
+        // Qualifiers must follow their primary key, in order
+        assert!(ElementStrings::from_digital_link(
+            "https://id.gs1.org/01/09520123456788/21/1/10/A"
+        )
+        .is_err());
+        assert!(ElementStrings::from_digital_link("https://id.gs1.org/10/ABC").is_err());
+        assert!(ElementStrings::parse("10ABC")
+            .unwrap()
+            .to_digital_link("https://id.gs1.org")
+            .is_err());
+    }
+}
@@ -0,0 +1,6 @@
+mod application_identifier;
+mod digital_link;
+mod element_strings;
+
+pub use application_identifier::{check_digit, ApplicationIdentifier, Gs1Component, Gs1Content};
+pub use element_strings::{ElementStrings, GROUP_SEPARATOR};
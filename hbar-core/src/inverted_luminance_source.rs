@@ -3,14 +3,14 @@ use std::borrow::Borrow;
 use crate::Error;
 use crate::LuminanceSource;
 
-use std::rc::Rc;
+use std::sync::Arc;
 
 pub struct InvertedLuminanceSource {
-    delegate: Rc<dyn LuminanceSource>,
+    delegate: Arc<dyn LuminanceSource>,
 }
 
 impl InvertedLuminanceSource {
-    pub fn new(delegate: Rc<dyn LuminanceSource>) -> InvertedLuminanceSource {
+    pub fn new(delegate: Arc<dyn LuminanceSource>) -> InvertedLuminanceSource {
         InvertedLuminanceSource { delegate: delegate }
     }
 }
@@ -54,8 +54,8 @@ impl LuminanceSource for InvertedLuminanceSource {
         top: u32,
         width: u32,
         height: u32,
-    ) -> Result<Rc<dyn LuminanceSource>, Error> {
-        Ok(Rc::new(InvertedLuminanceSource::new(
+    ) -> Result<Arc<dyn LuminanceSource>, Error> {
+        Ok(Arc::new(InvertedLuminanceSource::new(
             self.delegate.crop(left, top, width, height)?,
         )))
     }
@@ -64,18 +64,18 @@ impl LuminanceSource for InvertedLuminanceSource {
         self.delegate.is_rotate_supported()
     }
 
-    fn invert(&self) -> Result<Rc<dyn LuminanceSource>, Error> {
-        Ok(Rc::clone(&self.delegate))
+    fn invert(&self) -> Result<Arc<dyn LuminanceSource>, Error> {
+        Ok(Arc::clone(&self.delegate))
     }
 
-    fn rotate_counter_clockwise(&self) -> Result<Rc<dyn LuminanceSource>, Error> {
-        Ok(Rc::new(InvertedLuminanceSource::new(
+    fn rotate_counter_clockwise(&self) -> Result<Arc<dyn LuminanceSource>, Error> {
+        Ok(Arc::new(InvertedLuminanceSource::new(
             self.delegate.rotate_counter_clockwise()?,
         )))
     }
 
-    fn rotate_counter_clockwise45(&self) -> Result<Rc<dyn LuminanceSource>, Error> {
-        Ok(Rc::new(InvertedLuminanceSource::new(
+    fn rotate_counter_clockwise45(&self) -> Result<Arc<dyn LuminanceSource>, Error> {
+        Ok(Arc::new(InvertedLuminanceSource::new(
             self.delegate.rotate_counter_clockwise45()?,
         )))
     }
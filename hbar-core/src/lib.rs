@@ -1,42 +1,140 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 mod barcode_format;
+#[cfg(feature = "decode")]
 mod binarizer;
+#[cfg(feature = "decode")]
 mod binary_bitmap;
+#[cfg(feature = "image")]
 mod buffered_image;
+#[cfg(all(feature = "image", feature = "decode"))]
+mod buffered_image_luminance_source;
+#[cfg(feature = "std")]
+pub mod client;
 mod common;
+#[cfg(feature = "encode")]
 mod datamatrix;
+#[cfg(feature = "decode")]
 mod decode_hint_type;
+#[cfg(feature = "decode")]
+mod decode_options;
+#[cfg(feature = "encode")]
 mod encode_hint_type;
+#[cfg(feature = "encode")]
+mod encode_options;
 mod error;
+#[cfg(feature = "decode")]
+mod gray_luminance_source;
+pub mod gs1;
+#[cfg(feature = "decode")]
 mod inverted_luminance_source;
+#[cfg(feature = "decode")]
 mod luminance_source;
+#[cfg(feature = "decode")]
 mod multi_format_reader;
+#[cfg(all(feature = "encode", feature = "std"))]
 mod multi_format_writer;
+#[cfg(feature = "encode")]
+mod pdf417;
+#[cfg(feature = "decode")]
+mod planar_yuv_luminance_source;
 mod qrcode;
+#[cfg(feature = "decode")]
 mod reader;
+#[cfg(feature = "decode")]
 mod result;
+#[cfg(feature = "decode")]
 mod result_metadata_type;
+#[cfg(feature = "decode")]
 mod result_point;
+#[cfg(feature = "decode")]
+mod result_point_callback;
+#[cfg(feature = "decode")]
+mod rgb_luminance_source;
+#[cfg(feature = "decode")]
+mod rotated_luminance_source;
+#[cfg(feature = "decode")]
+mod scaled_luminance_source;
 pub mod types;
+#[cfg(feature = "decode")]
+pub mod verification;
+#[cfg(feature = "encode")]
 mod writer;
 mod writer_exception;
 
 pub use crate::barcode_format::BarcodeFormat;
+#[cfg(feature = "decode")]
 pub use crate::binarizer::Binarizer;
+#[cfg(feature = "decode")]
 pub use crate::binary_bitmap::BinaryBitmap;
+#[cfg(feature = "image")]
 pub use crate::buffered_image::BufferedImage;
-pub use crate::common::HybridBinarizer;
+#[cfg(all(feature = "image", feature = "decode"))]
+pub use crate::buffered_image_luminance_source::BufferedImageLuminanceSource;
+pub use crate::common::BitMatrix;
+pub use crate::common::Charset;
+#[cfg(feature = "encode")]
+pub use crate::common::ReedSolomonEncoder;
+#[cfg(feature = "decode")]
+pub use crate::common::{Corrections, ReedSolomonDecoder};
+#[cfg(any(feature = "encode", feature = "decode"))]
+pub use crate::common::{GenericGF, GenericGFEnum};
+#[cfg(feature = "decode")]
+pub use crate::common::{
+    GlobalHistogramBinarizer, HybridBinarizer, HybridBinarizerConfig, LocalThresholdBinarizer,
+    LocalThresholdConfig, LocalThresholdMethod, OtsuBinarizer,
+};
+#[cfg(feature = "encode")]
+pub use crate::datamatrix::encoder::SymbolShapeHint;
+#[cfg(feature = "decode")]
 pub use crate::decode_hint_type::{DecodeHintType, DecodeHintValue};
+#[cfg(feature = "decode")]
+pub use crate::decode_options::DecodeOptions;
+#[cfg(feature = "encode")]
 pub use crate::encode_hint_type::EncodeHintType;
+#[cfg(feature = "encode")]
+pub use crate::encode_options::{EncodeOptions, ErrorCorrection};
 pub use crate::error::{Error, ResultError};
+#[cfg(feature = "decode")]
+pub use crate::gray_luminance_source::GrayLuminanceSource;
+#[cfg(feature = "decode")]
 pub use crate::inverted_luminance_source::InvertedLuminanceSource;
+#[cfg(feature = "decode")]
 pub use crate::luminance_source::LuminanceSource;
+#[cfg(feature = "decode")]
 pub use crate::multi_format_reader::MultiFormatReader;
+#[cfg(all(feature = "encode", feature = "std"))]
 pub use crate::multi_format_writer::MultiFormatWriter;
+#[cfg(feature = "encode")]
+pub use crate::pdf417::encoder::{Compaction, Dimensions};
+#[cfg(feature = "decode")]
+pub use crate::planar_yuv_luminance_source::PlanarYUVLuminanceSource;
+pub use crate::qrcode::decoder::ErrorCorrectionLevel;
+#[cfg(all(feature = "qrcode", feature = "encode"))]
+pub use crate::qrcode::encoder::{ByteMatrix, Encoder as QRCodeEncoder, QRCode};
+#[cfg(all(feature = "qrcode", feature = "decode"))]
 pub use crate::qrcode::QRCodeReader;
+#[cfg(all(feature = "qrcode", feature = "encode"))]
+pub use crate::qrcode::QRCodeWriter;
+#[cfg(feature = "decode")]
 pub use crate::reader::Reader;
+#[cfg(feature = "decode")]
 pub use crate::result::Results;
-pub use crate::result_metadata_type::{ResultMetadataType, ResultMetadataValue};
+#[cfg(feature = "decode")]
+pub use crate::result_metadata_type::{BlockCorrections, ResultMetadataType, ResultMetadataValue};
+#[cfg(feature = "decode")]
 pub use crate::result_point::ResultPoint;
+#[cfg(feature = "decode")]
+pub use crate::result_point_callback::ResultPointCallback;
+#[cfg(feature = "decode")]
+pub use crate::rgb_luminance_source::RGBLuminanceSource;
+#[cfg(feature = "decode")]
+pub use crate::rotated_luminance_source::RotatedLuminanceSource;
+#[cfg(feature = "decode")]
+pub use crate::scaled_luminance_source::ScaledLuminanceSource;
+#[cfg(feature = "encode")]
 pub use crate::writer::Writer;
 pub use crate::writer_exception::WriterException;
 
@@ -1,9 +1,17 @@
 use crate::Error;
 
 use std::fmt;
-use std::rc::Rc;
+use std::sync::Arc;
 
-pub trait LuminanceSource {
+/**
+ * The purpose of this class hierarchy is to abstract different bitmap implementations across
+ * platforms into a standard interface for requesting greyscale luminance values. The interface
+ * only provides immutable methods; therefore crop and rotation create copies. This is to ensure
+ * that one Reader does not modify the original luminance source and leave it in an unknown state
+ * for other Readers in the chain. Sources must be {@code Send + Sync} so that one image can be
+ * handed to readers running on several threads.
+ */
+pub trait LuminanceSource: Send + Sync {
     /**
      * Fetches one row of luminance data from the underlying platform's bitmap. Values range from
      * 0 (black) to 255 (white). Because Java does not have an unsigned byte type, callers will have
@@ -61,7 +69,7 @@ pub trait LuminanceSource {
         _top: u32,
         _width: u32,
         _height: u32,
-    ) -> Result<Rc<dyn LuminanceSource>, Error> {
+    ) -> Result<Arc<dyn LuminanceSource>, Error> {
         Err(Error::UnsupportedOperationException(String::from(
             "This luminance source does not support cropping.",
         )))
@@ -78,7 +86,7 @@ pub trait LuminanceSource {
      * @return a wrapper of this {@code LuminanceSource} which inverts the luminances it returns -- black becomes
      *  white and vice versa, and each value becomes (255-value).
      */
-    fn invert(&self) -> Result<Rc<dyn LuminanceSource>, Error>;
+    fn invert(&self) -> Result<Arc<dyn LuminanceSource>, Error>;
 
     /**
      * Returns a new object with rotated image data by 90 degrees counterclockwise.
@@ -86,7 +94,7 @@ pub trait LuminanceSource {
      *
      * @return A rotated version of this object.
      */
-    fn rotate_counter_clockwise(&self) -> Result<Rc<dyn LuminanceSource>, Error> {
+    fn rotate_counter_clockwise(&self) -> Result<Arc<dyn LuminanceSource>, Error> {
         Err(Error::UnsupportedOperationException(String::from(
             "This luminance source does not support rotation by 90 degrees.",
         )))
@@ -98,13 +106,65 @@ pub trait LuminanceSource {
      *
      * @return A rotated version of this object.
      */
-    fn rotate_counter_clockwise45(&self) -> Result<Rc<dyn LuminanceSource>, Error> {
+    fn rotate_counter_clockwise45(&self) -> Result<Arc<dyn LuminanceSource>, Error> {
         Err(Error::UnsupportedOperationException(String::from(
             "This luminance source does not support rotation by 45 degrees.",
         )))
     }
 }
 
+/**
+ * A borrowed source is a source too, so a binarizer can work on an image owned elsewhere, for
+ * example to binarize the image of a {@link BinaryBitmap} a second way.
+ */
+impl<T: LuminanceSource + ?Sized> LuminanceSource for &T {
+    fn get_row(&self, y: i32, row: &Vec<u8>) -> Result<Vec<u8>, Error> {
+        (**self).get_row(y, row)
+    }
+
+    fn get_matrix(&self) -> Result<Vec<u8>, Error> {
+        (**self).get_matrix()
+    }
+
+    fn get_width(&self) -> u32 {
+        (**self).get_width()
+    }
+
+    fn get_height(&self) -> u32 {
+        (**self).get_height()
+    }
+
+    fn is_crop_supported(&self) -> bool {
+        (**self).is_crop_supported()
+    }
+
+    fn crop(
+        &self,
+        left: u32,
+        top: u32,
+        width: u32,
+        height: u32,
+    ) -> Result<Arc<dyn LuminanceSource>, Error> {
+        (**self).crop(left, top, width, height)
+    }
+
+    fn is_rotate_supported(&self) -> bool {
+        (**self).is_rotate_supported()
+    }
+
+    fn invert(&self) -> Result<Arc<dyn LuminanceSource>, Error> {
+        (**self).invert()
+    }
+
+    fn rotate_counter_clockwise(&self) -> Result<Arc<dyn LuminanceSource>, Error> {
+        (**self).rotate_counter_clockwise()
+    }
+
+    fn rotate_counter_clockwise45(&self) -> Result<Arc<dyn LuminanceSource>, Error> {
+        (**self).rotate_counter_clockwise45()
+    }
+}
+
 impl fmt::Display for dyn LuminanceSource {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let width = self.get_width() as usize;
@@ -1,11 +1,18 @@
+#[cfg(feature = "qrcode")]
 use crate::QRCodeReader;
 use crate::Reader;
 use crate::Results;
+use crate::RotatedLuminanceSource;
+use crate::ScaledLuminanceSource;
 use crate::{BarcodeFormat, DecodeHintType, DecodeHintValue};
 use crate::{Binarizer, BinaryBitmap, LuminanceSource};
 use crate::{Error, ResultError};
+use crate::{
+    GlobalHistogramBinarizer, HybridBinarizer, LocalThresholdBinarizer, LocalThresholdConfig,
+    OtsuBinarizer,
+};
+use crate::{ResultMetadataType, ResultMetadataValue};
 
-use std::cell::RefCell;
 use std::collections::HashMap;
 
 /**
@@ -13,10 +20,14 @@ use std::collections::HashMap;
  * By default it attempts to decode all barcode formats that the library supports. Optionally, you
  * can provide a hints object to request different behavior, for example only decoding QR codes.
  *
+ * Decoding never mutates the reader: {@link #decode} and {@link #decode_hints} build the readers
+ * they need for each call, and {@link #decode_with_state} only reads the set configured by
+ * {@link #set_hints}. A single instance can therefore be shared between worker threads.
+ *
  */
 pub struct MultiFormatReader<B, S> {
-    hints: RefCell<HashMap<DecodeHintType, DecodeHintValue>>,
-    readers: RefCell<Vec<Box<dyn Reader<B, S>>>>,
+    hints: HashMap<DecodeHintType, DecodeHintValue>,
+    readers: Vec<Box<dyn Reader<B, S>>>,
 }
 
 impl<B, S> Reader<B, S> for MultiFormatReader<B, S>
@@ -24,25 +35,40 @@ where
     B: Binarizer<S>,
     S: LuminanceSource,
 {
+    /**
+     * This version of decode honors the intent of Reader.decode(BinaryBitmap) in that it
+     * passes null as a hint to the decoders. However, that makes it inefficient to call repeatedly.
+     * Use setHints() followed by decodeWithState() for continuous scan applications.
+     *
+     * @param image The pixel data to decode
+     * @return The contents of the image
+     * @throws NotFoundException Any errors which occurred
+     */
     fn decode(&self, image: &BinaryBitmap<B, S>) -> ResultError<Results> {
-        let hint: HashMap<DecodeHintType, DecodeHintValue> = HashMap::new();
-        self.set_hints(hint);
-
-        self.decode_internal(image)
+        self.decode_hints(image, &HashMap::new())
     }
 
+    /**
+     * Decode an image using the hints provided. Does not honor existing state.
+     *
+     * @param image The pixel data to decode
+     * @param hints The hints to use, clearing the previous state.
+     * @return The contents of the image
+     * @throws NotFoundException Any errors which occurred
+     */
     fn decode_hints(
         &self,
         image: &BinaryBitmap<B, S>,
         hints: &HashMap<DecodeHintType, DecodeHintValue>,
     ) -> ResultError<Results> {
-        self.set_hints(hints.clone());
-
-        self.decode_internal(image)
+        let readers = MultiFormatReader::build_readers(hints);
+        MultiFormatReader::decode_internal(&readers, image, hints)
     }
 
     fn reset(&self) {
-        todo!()
+        for reader in self.readers.iter() {
+            reader.reset();
+        }
     }
 }
 
@@ -51,13 +77,29 @@ where
     B: Binarizer<S>,
     S: LuminanceSource,
 {
+    // The smaller side of the coarsest image MultiScale tries.
+    const MIN_SCALED_DIMENSION: u64 = 256;
+
     pub fn new() -> ResultError<MultiFormatReader<B, S>> {
+        let hints = HashMap::new();
         Ok(MultiFormatReader {
-            hints: RefCell::new(HashMap::new()),
-            readers: RefCell::new(Vec::new()),
+            readers: MultiFormatReader::build_readers(&hints),
+            hints,
         })
     }
 
+    /**
+     * Decode an image using the state set up by calling setHints() previously. Continuous scan
+     * clients will get a <b>large</b> speed increase by using this instead of decode().
+     *
+     * @param image The pixel data to decode
+     * @return The contents of the image
+     * @throws NotFoundException Any errors which occurred
+     */
+    pub fn decode_with_state(&self, image: &BinaryBitmap<B, S>) -> ResultError<Results> {
+        MultiFormatReader::decode_internal(&self.readers, image, &self.hints)
+    }
+
     /**
      * This method adds state to the MultiFormatReader. By setting the hints once, subsequent calls
      * to decodeWithState(image) can reuse the same set of readers without reallocating memory. This
@@ -65,18 +107,27 @@ where
      *
      * @param hints The set of hints to use for subsequent calls to decode(image)
      */
-    pub fn set_hints(&self, hints: HashMap<DecodeHintType, DecodeHintValue>) {
-        self.hints.borrow_mut().clear();
-        for (k, v) in hints.clone() {
-            self.hints.borrow_mut().insert(k, v);
-        }
+    pub fn set_hints(&mut self, hints: HashMap<DecodeHintType, DecodeHintValue>) {
+        self.readers = MultiFormatReader::build_readers(&hints);
+        self.hints = hints;
+    }
 
+    /**
+     * Builds the readers for the requested formats, or for every symbology enabled with cargo
+     * features when no formats are requested or none of them is enabled.
+     */
+    #[allow(unused_mut, unused_variables)]
+    fn build_readers(
+        hints: &HashMap<DecodeHintType, DecodeHintValue>,
+    ) -> Vec<Box<dyn Reader<B, S>>> {
         let try_harder = hints.contains_key(&DecodeHintType::TryHarder);
-        let formats = hints.get(&DecodeHintType::PossibleFormats);
+        let formats = hints
+            .get(&DecodeHintType::PossibleFormats)
+            .and_then(|formats| formats.try_get_vec_barcode_format());
 
         let mut readers: Vec<Box<dyn Reader<B, S>>> = Vec::new();
         if let Some(formats) = formats {
-            let formats = formats.get_vec_barcode_format();
+            #[cfg(feature = "oned")]
             let add_one_dreader = formats.contains(&BarcodeFormat::UpcA)
                 || formats.contains(&BarcodeFormat::UpcE)
                 || formats.contains(&BarcodeFormat::Ean13)
@@ -89,22 +140,26 @@ where
                 || formats.contains(&BarcodeFormat::RSS14)
                 || formats.contains(&BarcodeFormat::RssExpanded);
             // Put 1D readers upfront in "normal" mode
+            #[cfg(feature = "oned")]
             if add_one_dreader && !try_harder {
-                // readers.push(value)
                 // todo!()
             }
+            #[cfg(feature = "qrcode")]
             if formats.contains(&BarcodeFormat::QRCode) {
                 readers.push(Box::new(QRCodeReader::new()))
             }
 
+            #[cfg(feature = "datamatrix")]
             if formats.contains(&BarcodeFormat::DataMatrix) {
                 // todo!()
             }
 
+            #[cfg(feature = "aztec")]
             if formats.contains(&BarcodeFormat::Aztec) {
                 // todo!()
             }
 
+            #[cfg(feature = "pdf417")]
             if formats.contains(&BarcodeFormat::PDF417) {
                 // todo!()
             }
@@ -113,36 +168,530 @@ where
                 // todo!()
             }
             // At end in "try harder" mode
+            #[cfg(feature = "oned")]
             if add_one_dreader && try_harder {
                 // todo!()
             }
         }
         if readers.is_empty() {
+            #[cfg(feature = "oned")]
             if !try_harder {
                 // todo!()
             }
+            #[cfg(feature = "qrcode")]
             readers.push(Box::new(QRCodeReader::new()));
 
+            #[cfg(feature = "oned")]
             if try_harder {
-                todo!()
+                // todo!()
             }
         }
-        self.readers.borrow_mut().clear();
-        for reader in readers {
-            self.readers.borrow_mut().push(reader)
+        readers
+    }
+
+    fn decode_internal(
+        readers: &[Box<dyn Reader<B, S>>],
+        image: &BinaryBitmap<B, S>,
+        hints: &HashMap<DecodeHintType, DecodeHintValue>,
+    ) -> ResultError<Results> {
+        let factors = MultiFormatReader::<B, S>::scale_factors(image, hints)?;
+        if factors.is_empty() {
+            return MultiFormatReader::decode_full(readers, image, hints);
+        }
+
+        // The scaled images are decoded as any other, without scaling them again.
+        let mut scaled_hints = hints.clone();
+        scaled_hints.remove(&DecodeHintType::MultiScale);
+        scaled_hints.remove(&DecodeHintType::MaxPixels);
+        let scaled_readers = MultiFormatReader::<
+            HybridBinarizer<ScaledLuminanceSource>,
+            ScaledLuminanceSource,
+        >::build_readers(&scaled_hints);
+        let mut result = Err(Error::NotFoundException(String::from("Cannot decode...")));
+        for factor in factors {
+            result = if factor == 1 {
+                MultiFormatReader::decode_full(readers, image, &scaled_hints)
+            } else {
+                let scaled = ScaledLuminanceSource::new(image.get_luminance_source()?, factor)?;
+                let scaled_image = BinaryBitmap::new(HybridBinarizer::new(scaled.clone()));
+                MultiFormatReader::decode_full(&scaled_readers, &scaled_image, &scaled_hints).map(
+                    |mut result| {
+                        result.transform_result_points(|point| scaled.to_source_point(point));
+                        if let Some(ResultMetadataValue::FLOAT(module_size)) = result
+                            .get_result_metadata()
+                            .get(&ResultMetadataType::ModuleSize)
+                        {
+                            let module_size = module_size * factor as f32;
+                            result.put_metadata(
+                                ResultMetadataType::ModuleSize,
+                                ResultMetadataValue::FLOAT(module_size),
+                            );
+                        }
+                        result
+                    },
+                )
+            };
+            if result.is_ok() {
+                break;
+            }
+        }
+        result
+    }
+
+    /**
+     * Works out, from the MultiScale and MaxPixels hints, by which factors to scale the image
+     * down before decoding it, coarsest first. A factor of 1 is the image itself; no factors at
+     * all means neither hint is set.
+     */
+    fn scale_factors(
+        image: &BinaryBitmap<B, S>,
+        hints: &HashMap<DecodeHintType, DecodeHintValue>,
+    ) -> ResultError<Vec<u32>> {
+        let max_pixels = match hints.get(&DecodeHintType::MaxPixels) {
+            Some(value) => match value.try_get_i32() {
+                Some(max_pixels) if max_pixels > 0 => Some(max_pixels as u64),
+                _ => {
+                    return Err(Error::IllegalArgumentException(format!(
+                        "Invalid value for {:?}: {:?}",
+                        DecodeHintType::MaxPixels,
+                        value
+                    )))
+                }
+            },
+            None => None,
+        };
+        let multi_scale = hints.contains_key(&DecodeHintType::MultiScale);
+        if max_pixels.is_none() && !multi_scale {
+            return Ok(Vec::new());
+        }
+
+        let width = image.get_width() as u64;
+        let height = image.get_height() as u64;
+        let scaled_pixels = |factor: u64| width.div_ceil(factor) * height.div_ceil(factor);
+        let mut finest = 1;
+        if let Some(max_pixels) = max_pixels {
+            while scaled_pixels(finest) > max_pixels {
+                finest += 1;
+            }
+        }
+        let mut factors = vec![finest as u32];
+        if multi_scale {
+            let mut factor = finest * 2;
+            while width.min(height) / factor >= MultiFormatReader::<B, S>::MIN_SCALED_DIMENSION {
+                factors.push(factor as u32);
+                factor *= 2;
+            }
+        }
+        factors.reverse();
+        Ok(factors)
+    }
+
+    fn decode_full(
+        readers: &[Box<dyn Reader<B, S>>],
+        image: &BinaryBitmap<B, S>,
+        hints: &HashMap<DecodeHintType, DecodeHintValue>,
+    ) -> ResultError<Results> {
+        let mut result = MultiFormatReader::decode_readers(readers, image, hints);
+        if result.is_err() && hints.contains_key(&DecodeHintType::AlsoInverted) {
+            // Light on dark; the geometry stays the same, so the result points need no mapping
+            let inverted = image.get_luminance_source()?.invert()?;
+            result = MultiFormatReader::<B, S>::decode_binarizer(
+                HybridBinarizer::new(inverted.as_ref()),
+                hints,
+            );
+        }
+        if result.is_err() && hints.contains_key(&DecodeHintType::TryAlternativeBinarizers) {
+            let source = image.get_luminance_source()?;
+            result = MultiFormatReader::<B, S>::decode_alternative_binarizers(source, hints);
+        }
+        if result.is_err()
+            && hints.contains_key(&DecodeHintType::TryHarder)
+            && hints.contains_key(&DecodeHintType::TryHarderAngles)
+        {
+            let source = image.get_luminance_source()?;
+            result = MultiFormatReader::<B, S>::decode_rotations(source, hints);
+        }
+        result
+    }
+
+    fn decode_alternative_binarizers(
+        source: &S,
+        hints: &HashMap<DecodeHintType, DecodeHintValue>,
+    ) -> ResultError<Results> {
+        MultiFormatReader::<B, S>::decode_binarizer(OtsuBinarizer::new(source), hints)
+            .or_else(|_| {
+                MultiFormatReader::<B, S>::decode_binarizer(
+                    LocalThresholdBinarizer::new(source),
+                    hints,
+                )
+            })
+            .or_else(|_| {
+                let niblack =
+                    LocalThresholdBinarizer::with_config(source, LocalThresholdConfig::niblack())?;
+                MultiFormatReader::<B, S>::decode_binarizer(niblack, hints)
+            })
+            .or_else(|_| {
+                MultiFormatReader::<B, S>::decode_binarizer(
+                    GlobalHistogramBinarizer::new(source),
+                    hints,
+                )
+            })
+    }
+
+    /**
+     * Rotates the image by each of the angles of the TryHarderAngles hint, and tries the readers for the hints on it. The result points of a rotated image are mapped
+     * back to the original image.
+     */
+    fn decode_rotations(
+        source: &S,
+        hints: &HashMap<DecodeHintType, DecodeHintValue>,
+    ) -> ResultError<Results> {
+        let angles = match hints.get(&DecodeHintType::TryHarderAngles) {
+            Some(DecodeHintValue::VecI32(angles)) => angles,
+            value => {
+                return Err(Error::IllegalArgumentException(format!(
+                    "Invalid value for {:?}: {:?}",
+                    DecodeHintType::TryHarderAngles,
+                    value
+                )))
+            }
+        };
+        let readers = MultiFormatReader::<
+            HybridBinarizer<RotatedLuminanceSource>,
+            RotatedLuminanceSource,
+        >::build_readers(hints);
+        for &degrees in angles {
+            let rotated = RotatedLuminanceSource::new(source, degrees as f32)?;
+            let image = BinaryBitmap::new(HybridBinarizer::new(rotated.clone()));
+            if let Ok(mut result) = MultiFormatReader::decode_readers(&readers, &image, hints) {
+                result.transform_result_points(|point| rotated.to_source_point(point));
+                // Turning the image counterclockwise set the barcode upright, so it was turned
+                // clockwise
+                result.put_metadata(
+                    ResultMetadataType::ORIENTATION,
+                    ResultMetadataValue::INTEGER(degrees.rem_euclid(360)),
+                );
+                return Ok(result);
+            }
         }
+        Err(Error::NotFoundException(String::from("Cannot decode...")))
+    }
+
+    /**
+     * Binarizes the image again with the given binarizer, and tries the readers for the hints
+     * on it.
+     */
+    fn decode_binarizer<T: Binarizer<S2>, S2: LuminanceSource>(
+        binarizer: T,
+        hints: &HashMap<DecodeHintType, DecodeHintValue>,
+    ) -> ResultError<Results> {
+        let readers = MultiFormatReader::<T, S2>::build_readers(hints);
+        MultiFormatReader::decode_readers(&readers, &BinaryBitmap::new(binarizer), hints)
     }
 
-    fn decode_internal(&self, image: &BinaryBitmap<B, S>) -> ResultError<Results> {
-        if self.readers.borrow().is_empty() {
+    fn decode_readers(
+        readers: &[Box<dyn Reader<B, S>>],
+        image: &BinaryBitmap<B, S>,
+        hints: &HashMap<DecodeHintType, DecodeHintValue>,
+    ) -> ResultError<Results> {
+        if readers.is_empty() {
             return Err(Error::NotFoundException(String::from(
                 "Not found any readers!",
             )));
         }
-        for reader in self.readers.borrow().iter() {
-            let result = reader.decode_hints(image, &(*self.hints.borrow()))?;
-            return Ok(result);
+        for reader in readers.iter() {
+            if let Ok(result) = reader.decode_hints(image, hints) {
+                return Ok(result);
+            }
         }
         Err(Error::NotFoundException(String::from("Cannot decode...")))
     }
 }
+
+#[cfg(all(test, feature = "qrcode"))]
+mod multi_format_reader_tests {
+    use super::*;
+    use crate::common::{GenericGF, HybridBinarizer, ReedSolomonDecoder};
+    use crate::qrcode::Decoder;
+    use crate::InvertedLuminanceSource;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn shareable_across_threads() {
+        type Source = InvertedLuminanceSource;
+        assert_send_sync::<MultiFormatReader<HybridBinarizer<Source>, Source>>();
+        assert_send_sync::<QRCodeReader>();
+        assert_send_sync::<Decoder>();
+        assert_send_sync::<ReedSolomonDecoder>();
+        assert_send_sync::<GenericGF>();
+        assert_send_sync::<Box<dyn LuminanceSource>>();
+    }
+
+    // A QR code with square modules of the given size and a 4 module quiet zone.
+    #[cfg(feature = "encode")]
+    fn qr_image(content: &str, module_size: i32) -> crate::GrayLuminanceSource<'static> {
+        qr_image_options(content, module_size, &crate::EncodeOptions::new())
+    }
+
+    #[cfg(feature = "encode")]
+    fn qr_image_options(
+        content: &str,
+        module_size: i32,
+        options: &crate::EncodeOptions,
+    ) -> crate::GrayLuminanceSource<'static> {
+        use crate::qrcode::encoder::Encoder;
+        use crate::ErrorCorrectionLevel;
+
+        let code = Encoder::new()
+            .encode_options(&String::from(content), ErrorCorrectionLevel::M, options)
+            .unwrap();
+        let matrix = code.get_matrix();
+        let dimension = (matrix.get_width() + 8) * module_size;
+        let luminances = (0..dimension * dimension)
+            .map(|offset| {
+                let x = (offset % dimension) / module_size - 4;
+                let y = (offset / dimension) / module_size - 4;
+                let inside =
+                    (0..matrix.get_width()).contains(&x) && (0..matrix.get_height()).contains(&y);
+                if inside && matrix.get(x, y) == 1 {
+                    0
+                } else {
+                    0xFF
+                }
+            })
+            .collect();
+        crate::GrayLuminanceSource::from_vec(luminances, dimension as u32, dimension as u32)
+            .unwrap()
+    }
+
+    #[cfg(feature = "encode")]
+    #[test]
+    fn sweeps_angles_when_trying_harder() {
+        let tilted = RotatedLuminanceSource::new(&qr_image("tilted", 6), -30.0).unwrap();
+        let image = BinaryBitmap::new(HybridBinarizer::new(tilted.clone()));
+        let reader = MultiFormatReader::new().unwrap();
+
+        assert!(reader.decode(&image).is_err());
+        let mut hints = HashMap::new();
+        hints.insert(DecodeHintType::TryHarder, DecodeHintValue::BOOL(true));
+        // Without the angles trying harder doesn't rotate the image
+        assert!(reader.decode_hints(&image, &hints).is_err());
+        hints.insert(
+            DecodeHintType::TryHarderAngles,
+            DecodeHintValue::VecI32(vec![15, 30, 45]),
+        );
+        let result = reader.decode_hints(&image, &hints).unwrap();
+        assert_eq!("tilted", result.get_text());
+        assert_eq!(Some(30), result.get_orientation());
+        // The points come back in the tilted image; turned upright again they are the finder
+        // pattern centres, seven and a half modules of 6 pixels in from the edges of the image.
+        let points: Vec<(f32, f32)> = result
+            .get_result_points()
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|point| tilted.to_source_point(point))
+            .map(|point| (point.get_x(), point.get_y()))
+            .collect();
+        let expected = [(45.0, 129.0), (45.0, 45.0), (129.0, 45.0)];
+        assert_eq!(expected.len(), points.len());
+        for (point, expected) in points.iter().zip(&expected) {
+            assert!(
+                (point.0 - expected.0).abs() < 3.0 && (point.1 - expected.1).abs() < 3.0,
+                "{:?}",
+                points
+            );
+        }
+        hints.insert(
+            DecodeHintType::TryHarderAngles,
+            DecodeHintValue::VecI32(vec![45, 60]),
+        );
+        assert!(reader.decode_hints(&image, &hints).is_err());
+    }
+
+    #[cfg(feature = "encode")]
+    #[test]
+    fn also_inverted() {
+        let light = qr_image("inverted", 4);
+        let dark = light
+            .get_matrix()
+            .unwrap()
+            .iter()
+            .map(|&l| 0xFF - l)
+            .collect();
+        let dark =
+            crate::GrayLuminanceSource::from_vec(dark, light.get_width(), light.get_height())
+                .unwrap();
+        let image = BinaryBitmap::new(HybridBinarizer::new(dark));
+        let reader = MultiFormatReader::new().unwrap();
+
+        assert!(reader.decode(&image).is_err());
+        let mut hints = HashMap::new();
+        hints.insert(DecodeHintType::AlsoInverted, DecodeHintValue::BOOL(true));
+        let result = reader.decode_hints(&image, &hints).unwrap();
+        assert_eq!("inverted", result.get_text());
+        assert_eq!(3, result.get_result_points().as_ref().unwrap().len());
+    }
+
+    #[cfg(feature = "encode")]
+    #[test]
+    fn calls_result_point_callback() {
+        use crate::{DecodeOptions, ResultPoint};
+        use std::sync::{Arc, Mutex};
+
+        let found = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&found);
+        let options =
+            DecodeOptions::new().result_point_callback(Arc::new(move |point: &ResultPoint| {
+                sink.lock().unwrap().push(point.clone());
+            }));
+        let image = BinaryBitmap::new(HybridBinarizer::new(qr_image("points", 4)));
+        let result = MultiFormatReader::new()
+            .unwrap()
+            .decode_options(&image, &options)
+            .unwrap();
+        assert_eq!(
+            result.get_result_points().as_ref().unwrap(),
+            &*found.lock().unwrap()
+        );
+    }
+
+    #[cfg(feature = "encode")]
+    #[test]
+    fn reports_decode_quality() {
+        // Invert one whole data module: it is read confidently, but wrong.
+        let source = qr_image("quality", 4);
+        let width = source.get_width() as usize;
+        let mut luminances = source.get_matrix().unwrap();
+        for y in (4 + 12) * 4..(4 + 13) * 4 {
+            for x in (4 + 12) * 4..(4 + 13) * 4 {
+                luminances[y * width + x] ^= 0xFF;
+            }
+        }
+        let damaged =
+            crate::GrayLuminanceSource::from_vec(luminances, width as u32, width as u32).unwrap();
+        let result = MultiFormatReader::new()
+            .unwrap()
+            .decode(&BinaryBitmap::new(HybridBinarizer::new(damaged)))
+            .unwrap();
+        assert_eq!("quality", result.get_text());
+
+        let metadata = result.get_result_metadata();
+        let get = |t: ResultMetadataType| metadata.get(&t).cloned().unwrap();
+        assert_eq!(
+            ResultMetadataValue::INTEGER(1),
+            get(ResultMetadataType::ErrorsCorrected)
+        );
+        assert_eq!(
+            ResultMetadataValue::INTEGER(0),
+            get(ResultMetadataType::ErasuresCorrected)
+        );
+        assert_eq!(
+            ResultMetadataValue::INTEGER(1),
+            get(ResultMetadataType::SymbolVersion)
+        );
+        assert_eq!(
+            ResultMetadataValue::FLOAT(4.0),
+            get(ResultMetadataType::ModuleSize)
+        );
+        // Version 1-M has a single block with 10 error-correction codewords
+        assert_eq!(
+            ResultMetadataValue::BLOCKS(vec![crate::BlockCorrections::new(10, 1, 0)]),
+            get(ResultMetadataType::ErrorCorrectionBlocks)
+        );
+        assert_eq!(
+            ResultMetadataValue::FLOAT(0.2),
+            get(ResultMetadataType::ErrorCorrectionBudget)
+        );
+        match get(ResultMetadataType::DataMask) {
+            ResultMetadataValue::INTEGER(mask) => assert!((0..8).contains(&mask)),
+            value => panic!("{:?}", value),
+        }
+    }
+
+    #[cfg(feature = "encode")]
+    #[test]
+    fn reports_typed_metadata() {
+        use crate::ErrorCorrectionLevel;
+
+        let image = BinaryBitmap::new(HybridBinarizer::new(qr_image("bytes", 4)));
+        let result = MultiFormatReader::new().unwrap().decode(&image).unwrap();
+        assert_eq!(
+            Some(&ErrorCorrectionLevel::M),
+            result.get_error_correction_level()
+        );
+        assert_eq!(Some(&[b"bytes".to_vec()][..]), result.get_byte_segments());
+        assert_eq!(Some("]Q1"), result.get_symbology_identifier());
+        assert_eq!(None, result.get_orientation());
+        assert_eq!(None, result.get_structured_append_sequence());
+        assert_eq!(None, result.get_gs1_element_strings());
+        assert_eq!(
+            Some(0),
+            result
+                .get_metadata(&ResultMetadataType::ErrorsCorrected)
+                .and_then(ResultMetadataValue::get_integer)
+        );
+
+        let options = crate::EncodeOptions::new().gs1_format(true);
+        let image = BinaryBitmap::new(HybridBinarizer::new(qr_image_options(
+            "0109501101020917\u{1d}10ABC123\u{1d}21XYZ",
+            4,
+            &options,
+        )));
+        let result = MultiFormatReader::new().unwrap().decode(&image).unwrap();
+        assert_eq!(Some("]Q3"), result.get_symbology_identifier());
+        assert_eq!(
+            Some(
+                &[
+                    "0109501101020917".to_string(),
+                    "10ABC123".to_string(),
+                    "21XYZ".to_string()
+                ][..]
+            ),
+            result.get_gs1_element_strings()
+        );
+    }
+
+    #[test]
+    fn scales_coarse_first_within_the_pixel_cap() {
+        let source =
+            crate::GrayLuminanceSource::from_vec(vec![0xFF; 2048 * 1024], 2048, 1024).unwrap();
+        let image = BinaryBitmap::new(HybridBinarizer::new(source));
+        let factors = |hints: Vec<(DecodeHintType, DecodeHintValue)>| {
+            MultiFormatReader::scale_factors(&image, &hints.into_iter().collect())
+        };
+
+        let multi_scale = (DecodeHintType::MultiScale, DecodeHintValue::BOOL(true));
+        let capped = (DecodeHintType::MaxPixels, DecodeHintValue::I32(600_000));
+        assert!(factors(vec![]).unwrap().is_empty());
+        assert_eq!(vec![4, 2, 1], factors(vec![multi_scale.clone()]).unwrap());
+        assert_eq!(vec![2], factors(vec![capped.clone()]).unwrap());
+        assert_eq!(vec![4, 2], factors(vec![multi_scale, capped]).unwrap());
+        assert!(factors(vec![(DecodeHintType::MaxPixels, DecodeHintValue::I32(0))]).is_err());
+    }
+
+    #[cfg(feature = "encode")]
+    #[test]
+    fn decodes_within_the_pixel_cap() {
+        let source = qr_image("scaled", 24);
+        assert_eq!(696, source.get_width());
+        let image = BinaryBitmap::new(HybridBinarizer::new(source));
+        let mut hints = HashMap::new();
+        hints.insert(DecodeHintType::MaxPixels, DecodeHintValue::I32(100_000));
+        hints.insert(DecodeHintType::MultiScale, DecodeHintValue::BOOL(true));
+        let reader = MultiFormatReader::new().unwrap();
+        assert_eq!(
+            vec![3],
+            MultiFormatReader::scale_factors(&image, &hints).unwrap()
+        );
+        let result = reader.decode_hints(&image, &hints).unwrap();
+        assert_eq!("scaled", result.get_text());
+        assert_eq!(
+            Some(&ResultMetadataValue::FLOAT(24.0)),
+            result
+                .get_result_metadata()
+                .get(&ResultMetadataType::ModuleSize)
+        );
+    }
+}
@@ -1,24 +1,30 @@
 use crate::barcode_format::BarcodeFormat;
 use crate::common::BitMatrix;
+#[cfg(feature = "datamatrix")]
 use crate::datamatrix::DataMatrixWriter;
-use crate::encode_hint_type::EncodeHintType;
+use crate::encode_options::EncodeOptions;
+#[cfg(feature = "qrcode")]
 use crate::qrcode::QRCodeWriter;
 use crate::writer::Writer;
-use crate::ResultError;
+use crate::{Error, ResultError};
 
 use std::collections::HashMap;
 
-use image;
-
 pub struct MultiFormatWriter {
     encoders: HashMap<BarcodeFormat, Box<dyn Writer>>,
 }
 
 impl MultiFormatWriter {
+    /**
+     * The writers for the symbologies enabled with cargo features.
+     */
+    #[allow(unused_mut)]
     pub fn get_encoders() -> HashMap<BarcodeFormat, Box<dyn Writer>> {
         let mut maps: HashMap<BarcodeFormat, Box<dyn Writer>> = HashMap::new();
 
+        #[cfg(feature = "qrcode")]
         maps.insert(BarcodeFormat::QRCode, Box::new(QRCodeWriter::new()));
+        #[cfg(feature = "datamatrix")]
         maps.insert(BarcodeFormat::DataMatrix, Box::new(DataMatrixWriter::new()));
         return maps;
     }
@@ -28,6 +34,7 @@ impl MultiFormatWriter {
         }
     }
 
+    #[cfg(feature = "image")]
     pub fn write_to_path(&self, bit_matrix: &BitMatrix, path: &String) {
         println!(
             "write_to_path width: {}, height: {}, row_size: {}",
@@ -57,29 +64,23 @@ impl MultiFormatWriter {
 }
 
 impl Writer for MultiFormatWriter {
-    fn encode(
-        &self,
-        contents: &String,
-        format: &BarcodeFormat,
-        width: i32,
-        height: i32,
-    ) -> ResultError<BitMatrix> {
-        let hints: HashMap<EncodeHintType, &String> = HashMap::new();
-        self.encode_hints(contents, format, width, height, hints)
-    }
-
-    fn encode_hints(
+    fn encode_options(
         &self,
         contents: &String,
         format: &BarcodeFormat,
         width: i32,
         height: i32,
-        hints: HashMap<EncodeHintType, &String>,
+        options: &EncodeOptions,
     ) -> ResultError<BitMatrix> {
-        println!("encode begining...");
-        let encoder = self.encoders.get(format).unwrap();
-        let resut = encoder.encode_hints(contents, format, width, height, hints);
-        println!("encode end!");
-        resut
+        let encoder = match self.encoders.get(format) {
+            Some(encoder) => encoder,
+            None => {
+                return Err(Error::IllegalArgumentException(format!(
+                    "No encoder available for format {:?}",
+                    format
+                )))
+            }
+        };
+        encoder.encode_options(contents, format, width, height, options)
     }
 }
@@ -0,0 +1,14 @@
+use strum_macros::Display;
+use strum_macros::EnumString;
+
+/**
+ * Represents possible PDF417 barcode compaction types.
+ */
+#[derive(Debug, PartialEq, Eq, Hash, EnumString, Display, Clone, Copy)]
+#[strum(ascii_case_insensitive)]
+pub enum Compaction {
+    Auto,
+    Text,
+    Byte,
+    Numeric,
+}
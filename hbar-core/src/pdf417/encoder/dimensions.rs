@@ -0,0 +1,50 @@
+use crate::{Error, ResultError};
+
+use alloc::format;
+
+/**
+ * Data object to specify the minimum and maximum number of rows and columns for a PDF417 barcode.
+ */
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Dimensions {
+    min_cols: i32,
+    max_cols: i32,
+    min_rows: i32,
+    max_rows: i32,
+}
+
+impl Dimensions {
+    /**
+     * @throws IllegalArgumentException if a minimum is below 1 or above its maximum
+     */
+    pub fn new(min_cols: i32, max_cols: i32, min_rows: i32, max_rows: i32) -> ResultError<Self> {
+        if min_cols < 1 || min_rows < 1 || min_cols > max_cols || min_rows > max_rows {
+            return Err(Error::IllegalArgumentException(format!(
+                "Invalid PDF417 dimensions: cols {}..{}, rows {}..{}",
+                min_cols, max_cols, min_rows, max_rows
+            )));
+        }
+        Ok(Dimensions {
+            min_cols,
+            max_cols,
+            min_rows,
+            max_rows,
+        })
+    }
+
+    pub fn get_min_cols(&self) -> i32 {
+        self.min_cols
+    }
+
+    pub fn get_max_cols(&self) -> i32 {
+        self.max_cols
+    }
+
+    pub fn get_min_rows(&self) -> i32 {
+        self.min_rows
+    }
+
+    pub fn get_max_rows(&self) -> i32 {
+        self.max_rows
+    }
+}
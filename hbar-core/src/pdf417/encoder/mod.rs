@@ -0,0 +1,5 @@
+mod compaction;
+mod dimensions;
+
+pub use compaction::Compaction;
+pub use dimensions::Dimensions;
@@ -0,0 +1,207 @@
+use crate::GrayLuminanceSource;
+use crate::InvertedLuminanceSource;
+use crate::LuminanceSource;
+use crate::{Error, ResultError};
+
+use std::sync::Arc;
+
+/**
+ * This object extends LuminanceSource around an array of YUV data returned from the camera driver,
+ * with the option to crop to a rectangle within the full data. This can be used to exclude
+ * superfluous pixels around the perimeter and speed up decoding.
+ *
+ * It works for any pixel format where the Y channel is planar and appears first, including
+ * YCbCr_420_SP (NV21, NV12) and YCbCr_420_P (I420, YV12). Only the Y plane is read; it is
+ * copied once when the source is built, and crops share that copy.
+ */
+#[derive(Debug, Clone)]
+pub struct PlanarYUVLuminanceSource {
+    luminances: GrayLuminanceSource<'static>,
+}
+
+impl PlanarYUVLuminanceSource {
+    const THUMBNAIL_SCALE_FACTOR: u32 = 2;
+
+    /**
+     * @param yuv_data the camera frame, Y plane first
+     * @param data_width width of the frame
+     * @param data_height height of the frame
+     * @param left left edge of the rectangle to decode
+     * @param top top edge of the rectangle to decode
+     * @param width width of the rectangle to decode
+     * @param height height of the rectangle to decode
+     * @param reverse_horizontal whether to mirror the rectangle, for front-facing cameras
+     * @throws IllegalArgumentException if the rectangle does not fit within the frame
+     */
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        yuv_data: &[u8],
+        data_width: u32,
+        data_height: u32,
+        left: u32,
+        top: u32,
+        width: u32,
+        height: u32,
+        reverse_horizontal: bool,
+    ) -> ResultError<PlanarYUVLuminanceSource> {
+        if left as u64 + width as u64 > data_width as u64
+            || top as u64 + height as u64 > data_height as u64
+        {
+            return Err(Error::IllegalArgumentException(String::from(
+                "Crop rectangle does not fit within image data.",
+            )));
+        }
+        let plane_size = data_width as usize * data_height as usize;
+        if yuv_data.len() < plane_size {
+            return Err(Error::IllegalArgumentException(format!(
+                "Buffer of {} bytes is too small for a {}x{} Y plane",
+                yuv_data.len(),
+                data_width,
+                data_height
+            )));
+        }
+        let mut y_plane = yuv_data[..plane_size].to_vec();
+        if reverse_horizontal {
+            for y in top..top + height {
+                let offset = (y * data_width + left) as usize;
+                y_plane[offset..offset + width as usize].reverse();
+            }
+        }
+        let plane = GrayLuminanceSource::from_vec(y_plane, data_width, data_height)?;
+        Ok(PlanarYUVLuminanceSource {
+            luminances: plane.view(left, top, width, height)?,
+        })
+    }
+
+    /**
+     * Reads a whole camera frame, without cropping or mirroring.
+     */
+    pub fn from_frame(
+        yuv_data: &[u8],
+        width: u32,
+        height: u32,
+    ) -> ResultError<PlanarYUVLuminanceSource> {
+        PlanarYUVLuminanceSource::new(yuv_data, width, height, 0, 0, width, height, false)
+    }
+
+    /**
+     * Renders the cropped greyscale bitmap at half size, for display while scanning.
+     *
+     * @return packed 0xAARRGGBB pixels, {@link #get_thumbnail_width} by
+     *  {@link #get_thumbnail_height}
+     */
+    pub fn render_thumbnail(&self) -> Vec<u32> {
+        let width = self.get_thumbnail_width();
+        let height = self.get_thumbnail_height();
+        let mut pixels = Vec::with_capacity(width as usize * height as usize);
+        for y in 0..height {
+            let row = self
+                .luminances
+                .get_row(
+                    (y * PlanarYUVLuminanceSource::THUMBNAIL_SCALE_FACTOR) as i32,
+                    &Vec::new(),
+                )
+                .unwrap_or_default();
+            for x in 0..width {
+                let grey =
+                    row[(x * PlanarYUVLuminanceSource::THUMBNAIL_SCALE_FACTOR) as usize] as u32;
+                pixels.push(0xFF000000 | (grey * 0x00010101));
+            }
+        }
+        pixels
+    }
+
+    /**
+     * @return width of image from {@link #render_thumbnail()}
+     */
+    pub fn get_thumbnail_width(&self) -> u32 {
+        self.get_width() / PlanarYUVLuminanceSource::THUMBNAIL_SCALE_FACTOR
+    }
+
+    /**
+     * @return height of image from {@link #render_thumbnail()}
+     */
+    pub fn get_thumbnail_height(&self) -> u32 {
+        self.get_height() / PlanarYUVLuminanceSource::THUMBNAIL_SCALE_FACTOR
+    }
+}
+
+impl LuminanceSource for PlanarYUVLuminanceSource {
+    fn get_row(&self, y: i32, row: &Vec<u8>) -> ResultError<Vec<u8>> {
+        self.luminances.get_row(y, row)
+    }
+
+    fn get_matrix(&self) -> ResultError<Vec<u8>> {
+        self.luminances.get_matrix()
+    }
+
+    fn get_width(&self) -> u32 {
+        self.luminances.get_width()
+    }
+
+    fn get_height(&self) -> u32 {
+        self.luminances.get_height()
+    }
+
+    fn is_crop_supported(&self) -> bool {
+        true
+    }
+
+    fn crop(
+        &self,
+        left: u32,
+        top: u32,
+        width: u32,
+        height: u32,
+    ) -> ResultError<Arc<dyn LuminanceSource>> {
+        Ok(Arc::new(PlanarYUVLuminanceSource {
+            luminances: self.luminances.view(left, top, width, height)?,
+        }))
+    }
+
+    fn is_rotate_supported(&self) -> bool {
+        true
+    }
+
+    fn invert(&self) -> ResultError<Arc<dyn LuminanceSource>> {
+        Ok(Arc::new(InvertedLuminanceSource::new(Arc::new(
+            self.clone(),
+        ))))
+    }
+
+    fn rotate_counter_clockwise(&self) -> ResultError<Arc<dyn LuminanceSource>> {
+        self.luminances.rotate_counter_clockwise()
+    }
+}
+
+#[cfg(test)]
+mod planar_yuv_luminance_source_tests {
+    use super::*;
+
+    // 4x2 Y plane followed by the 2x1 interleaved chroma of an NV21 frame
+    const NV21: [u8; 12] = [10, 11, 12, 13, 20, 21, 22, 23, 128, 128, 128, 128];
+
+    #[test]
+    fn crop_and_mirror() {
+        let source = PlanarYUVLuminanceSource::from_frame(&NV21, 4, 2).unwrap();
+        assert_eq!(NV21[..8].to_vec(), source.get_matrix().unwrap());
+
+        let cropped = PlanarYUVLuminanceSource::new(&NV21, 4, 2, 1, 0, 3, 2, true).unwrap();
+        assert_eq!(vec![13, 12, 11, 23, 22, 21], cropped.get_matrix().unwrap());
+        assert_eq!(
+            vec![22, 21],
+            cropped.crop(1, 1, 2, 1).unwrap().get_matrix().unwrap()
+        );
+
+        assert!(PlanarYUVLuminanceSource::new(&NV21, 4, 2, 2, 0, 3, 2, false).is_err());
+        assert!(PlanarYUVLuminanceSource::from_frame(&NV21, 4, 4).is_err());
+    }
+
+    #[test]
+    fn thumbnail() {
+        let source = PlanarYUVLuminanceSource::from_frame(&NV21, 4, 2).unwrap();
+        assert_eq!(2, source.get_thumbnail_width());
+        assert_eq!(1, source.get_thumbnail_height());
+        assert_eq!(vec![0xFF0A0A0A, 0xFF0C0C0C], source.render_thumbnail());
+    }
+}
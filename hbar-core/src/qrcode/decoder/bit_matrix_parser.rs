@@ -1,7 +1,7 @@
 use crate::common::BitMatrix;
 use crate::{Error, ResultError};
 
-use super::FormatInformation;
+use super::{DataMask, FormatInformation};
 use super::{Version, Versions};
 
 pub struct BitMatrixParser {
@@ -10,6 +10,8 @@ pub struct BitMatrixParser {
     parsedVersion: Option<Version>,
     parsedFormatInfo: Option<FormatInformation>,
     mirror: bool,
+    unreliable: Option<BitMatrix>,
+    erasures: Vec<usize>,
 }
 
 impl BitMatrixParser {
@@ -17,7 +19,7 @@ impl BitMatrixParser {
      * @param bitMatrix {@link BitMatrix} to parse
      * @throws FormatException if dimension is not >= 21 and 1 mod 4
      */
-    fn new(bitMatrix: &BitMatrix) -> ResultError<Self> {
+    pub fn new(bitMatrix: &BitMatrix) -> ResultError<Self> {
         let dimension = bitMatrix.getHeight();
         if dimension < 21 || (dimension & 0x03) != 1 {
             return Err(Error::FormatException(String::from("")));
@@ -28,6 +30,8 @@ impl BitMatrixParser {
             parsedVersion: None,
             parsedFormatInfo: None,
             mirror: false,
+            unreliable: None,
+            erasures: Vec::new(),
             versions: Versions::new(),
         })
     }
@@ -39,7 +43,7 @@ impl BitMatrixParser {
      * @throws FormatException if both format information locations cannot be parsed as
      * the valid encoding of format information
      */
-    fn readFormatInformation(&mut self) -> ResultError<FormatInformation> {
+    pub fn readFormatInformation(&mut self) -> ResultError<FormatInformation> {
         if let Some(parsedFormatInfo) = &self.parsedFormatInfo {
             return Ok(parsedFormatInfo.clone());
         }
@@ -84,7 +88,7 @@ impl BitMatrixParser {
      * @throws FormatException if both version information locations cannot be parsed as
      * the valid encoding of version information
      */
-    fn readVersion(&self) -> ResultError<Version> {
+    pub fn readVersion(&mut self) -> ResultError<Version> {
         if let Some(version) = &self.parsedVersion {
             return Ok(version.clone());
         }
@@ -108,7 +112,32 @@ impl BitMatrixParser {
             }
         }
 
-        todo!()
+        if let Some(version) = self.versions.decode_version_information(versionBits) {
+            if version.get_dimension_for_version() == dimension {
+                let version = version.clone();
+                self.parsedVersion = Some(version.clone());
+                return Ok(version);
+            }
+        }
+
+        // Hmm, failed. Try bottom left: 6 wide by 3 tall
+        versionBits = 0;
+        for i in (0..6).rev() {
+            for j in (ijMin..dimension - 8).rev() {
+                versionBits = self.copyBit(i, j, versionBits);
+            }
+        }
+
+        if let Some(version) = self.versions.decode_version_information(versionBits) {
+            if version.get_dimension_for_version() == dimension {
+                let version = version.clone();
+                self.parsedVersion = Some(version.clone());
+                return Ok(version);
+            }
+        }
+        Err(Error::FormatException(String::from(
+            "Can not read version information",
+        )))
     }
 
     fn copyBit(&self, i: i32, j: i32, versionBits: i32) -> i32 {
@@ -123,4 +152,169 @@ impl BitMatrixParser {
             versionBits << 1
         }
     }
+
+    /**
+     * <p>Reads the bits in the {@link BitMatrix} representing the finder pattern in the
+     * correct order in order to reconstruct the codewords bytes contained within the
+     * QR Code.</p>
+     *
+     * @return bytes encoded within the QR Code
+     * @throws FormatException if the exact number of bytes expected is not read
+     */
+    pub fn read_codewords(&mut self) -> ResultError<Vec<u8>> {
+        let format_info = self.readFormatInformation()?;
+        let version = self.readVersion()?;
+
+        // Get the data mask for the format used in this QR Code. This will exclude
+        // some bits from reading as we wind through the bit matrix.
+        let data_mask = DataMask::for_reference(format_info.getDataMask())?;
+        let dimension = self.bitMatrix.getHeight();
+        data_mask.unmask_bit_matrix(&mut self.bitMatrix, dimension);
+
+        let mut result = Vec::with_capacity(version.get_total_codewords() as usize);
+        let mut current_byte = 0;
+        let mut bits_read = 0;
+        self.erasures.clear();
+        for (x, y) in BitMatrixParser::data_modules(&version, dimension)? {
+            // Read a bit
+            bits_read += 1;
+            current_byte <<= 1;
+            if self.bitMatrix.get(x, y) {
+                current_byte |= 1;
+            }
+            if let Some(unreliable) = &self.unreliable {
+                if unreliable.get(x, y) && self.erasures.last() != Some(&result.len()) {
+                    self.erasures.push(result.len());
+                }
+            }
+            // If we've made a whole byte, save it off
+            if bits_read == 8 {
+                result.push(current_byte as u8);
+                bits_read = 0;
+                current_byte = 0;
+            }
+        }
+        if result.len() as i32 != version.get_total_codewords() {
+            return Err(Error::FormatException(format!(
+                "Read {} codewords but expected {}",
+                result.len(),
+                version.get_total_codewords()
+            )));
+        }
+        Ok(result)
+    }
+
+    /**
+     * @return the modules making up each codeword, most significant bit first, in the order
+     *  {@link #read_codewords()} reads the codewords
+     * @throws FormatException if the version cannot be read
+     */
+    pub fn read_codeword_modules(&mut self) -> ResultError<Vec<Vec<(u32, u32)>>> {
+        let version = self.readVersion()?;
+        let dimension = self.bitMatrix.getHeight();
+        Ok(BitMatrixParser::data_modules(&version, dimension)?
+            .chunks_exact(8)
+            .take(version.get_total_codewords() as usize)
+            .map(|modules| modules.to_vec())
+            .collect())
+    }
+
+    // The modules which are not part of the function pattern, in reading order.
+    fn data_modules(version: &Version, dimension: i32) -> ResultError<Vec<(u32, u32)>> {
+        let function_pattern = version.build_function_pattern()?;
+
+        let mut reading_up = true;
+        let mut result = Vec::new();
+        // Read columns in pairs, from right to left
+        let mut j = dimension - 1;
+        while j > 0 {
+            if j == 6 {
+                // Skip whole column with vertical alignment pattern;
+                // saves time and makes the other code proceed more cleanly
+                j -= 1;
+            }
+            // Read alternatingly from bottom to top then top to bottom
+            for count in 0..dimension {
+                let i = if reading_up {
+                    dimension - 1 - count
+                } else {
+                    count
+                };
+                for col in 0..2 {
+                    // Ignore bits covered by the function pattern
+                    if !function_pattern.get((j - col) as u32, i as u32) {
+                        result.push(((j - col) as u32, i as u32));
+                    }
+                }
+            }
+            // switch directions
+            reading_up = !reading_up;
+            j -= 2;
+        }
+        Ok(result)
+    }
+
+    /**
+     * Marks modules whose color could not be told reliably. Each codeword containing one of them
+     * is reported by {@link #get_erasures()} after {@link #read_codewords()}.
+     *
+     * @param unreliable set bits mark the unreliable modules; same size as the matrix being parsed
+     */
+    pub fn set_unreliable_modules(&mut self, unreliable: BitMatrix) {
+        self.unreliable = Some(unreliable);
+    }
+
+    /**
+     * @return indices, into the codewords last read, of those containing an unreliable module
+     */
+    pub fn get_erasures(&self) -> &[usize] {
+        &self.erasures
+    }
+
+    /**
+     * Revert the mask removal done while reading the code words. The bit matrix should revert to its original state.
+     */
+    pub fn remask(&mut self) -> ResultError<()> {
+        if let Some(format_info) = &self.parsedFormatInfo {
+            let data_mask = DataMask::for_reference(format_info.getDataMask())?;
+            let dimension = self.bitMatrix.getHeight();
+            data_mask.unmask_bit_matrix(&mut self.bitMatrix, dimension);
+        }
+        // We have no format information, and have no data mask
+        Ok(())
+    }
+
+    /**
+     * Prepare the parser for a mirrored operation.
+     * This flag has effect only on the {@link #readFormatInformation()} and the
+     * {@link #readVersion()}. Before proceeding with {@link #read_codewords()} the
+     * {@link #mirror()} method should be called.
+     *
+     * @param mirror Whether to read version and format information mirrored.
+     */
+    pub fn set_mirror(&mut self, mirror: bool) {
+        self.parsedVersion = None;
+        self.parsedFormatInfo = None;
+        self.mirror = mirror;
+    }
+
+    /** Mirror the bit matrix in order to attempt a second reading. */
+    pub fn mirror(&mut self) {
+        BitMatrixParser::transpose(&mut self.bitMatrix);
+        if let Some(unreliable) = self.unreliable.as_mut() {
+            BitMatrixParser::transpose(unreliable);
+        }
+    }
+
+    fn transpose(matrix: &mut BitMatrix) {
+        for x in 0..matrix.getWidth() {
+            for y in x + 1..matrix.getHeight() {
+                let (x, y) = (x as u32, y as u32);
+                if matrix.get(x, y) != matrix.get(y, x) {
+                    matrix.flip(y, x);
+                    matrix.flip(x, y);
+                }
+            }
+        }
+    }
 }
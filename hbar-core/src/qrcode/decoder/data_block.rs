@@ -0,0 +1,132 @@
+use crate::qrcode::decoder::{ErrorCorrectionLevel, Version};
+use crate::{Error, ResultError};
+
+/**
+ * <p>Encapsulates a block of data within a QR Code. QR Codes may split their data into
+ * multiple blocks, each of which is a unit of data and error-correction codewords. Each
+ * is represented by an instance of this class.</p>
+ */
+pub struct DataBlock {
+    num_data_codewords: i32,
+    codewords: Vec<u8>,
+}
+
+impl DataBlock {
+    fn new(num_data_codewords: i32, codewords: Vec<u8>) -> DataBlock {
+        DataBlock {
+            num_data_codewords,
+            codewords,
+        }
+    }
+
+    /**
+     * <p>When QR Codes use multiple data blocks, they are actually interleaved.
+     * That is, the first byte of data block 1 to n is written, then the second bytes, and so on. This
+     * method will separate the data into original blocks.</p>
+     *
+     * @param raw_codewords bytes as read directly from the QR Code
+     * @param version version of the QR Code
+     * @param ec_level error-correction level of the QR Code
+     * @return DataBlocks containing original bytes, "de-interleaved" from representation in the
+     *         QR Code
+     */
+    pub fn get_data_blocks(
+        raw_codewords: &[u8],
+        version: &Version,
+        ec_level: &ErrorCorrectionLevel,
+    ) -> ResultError<Vec<DataBlock>> {
+        let ec_codewords_per_block = version
+            .get_ec_blocks_for_level(ec_level)
+            .get_ec_codewords_per_block() as usize;
+        Ok(DataBlock::deinterleave(raw_codewords, version, ec_level)?
+            .into_iter()
+            .map(|codewords| {
+                DataBlock::new((codewords.len() - ec_codewords_per_block) as i32, codewords)
+            })
+            .collect())
+    }
+
+    /**
+     * <p>Separates anything given per codeword, in the order the codewords are read from the
+     * QR Code, into its blocks, the way {@link #get_data_blocks} separates the codewords.</p>
+     *
+     * @param raw one value per codeword, as read directly from the QR Code
+     * @param version version of the QR Code
+     * @param ec_level error-correction level of the QR Code
+     * @return the values of each block, data codewords first
+     */
+    pub fn deinterleave<T: Copy + Default>(
+        raw: &[T],
+        version: &Version,
+        ec_level: &ErrorCorrectionLevel,
+    ) -> ResultError<Vec<Vec<T>>> {
+        if raw.len() as i32 != version.get_total_codewords() {
+            return Err(Error::IllegalArgumentException(format!(
+                "Expected {} codewords but got {}",
+                version.get_total_codewords(),
+                raw.len()
+            )));
+        }
+
+        // Figure out the number and size of data blocks used by this version and
+        // error correction level
+        let ec_blocks = version.get_ec_blocks_for_level(ec_level);
+
+        // Now establish blocks of the appropriate size
+        let mut result: Vec<Vec<T>> = Vec::with_capacity(ec_blocks.get_num_blocks() as usize);
+        for ec_block in ec_blocks.get_ec_blocks() {
+            for _ in 0..ec_block.get_count() {
+                let num_block_codewords =
+                    ec_blocks.get_ec_codewords_per_block() + ec_block.get_data_codewords();
+                result.push(vec![T::default(); num_block_codewords as usize]);
+            }
+        }
+
+        // All blocks have the same amount of data, except that the last n
+        // (where n may be 0) have 1 more byte. Figure out where these start.
+        let shorter_blocks_total_codewords = result[0].len();
+        let mut longer_blocks_start_at = result.len();
+        while longer_blocks_start_at > 0 {
+            let num_codewords = result[longer_blocks_start_at - 1].len();
+            if num_codewords == shorter_blocks_total_codewords {
+                break;
+            }
+            longer_blocks_start_at -= 1;
+        }
+
+        let shorter_blocks_num_data_codewords =
+            shorter_blocks_total_codewords - ec_blocks.get_ec_codewords_per_block() as usize;
+        // The last elements of result may be 1 element longer;
+        // first fill out as many elements as all of them have
+        let mut raw_offset = 0;
+        for i in 0..shorter_blocks_num_data_codewords {
+            for block in result.iter_mut() {
+                block[i] = raw[raw_offset];
+                raw_offset += 1;
+            }
+        }
+        // Fill out the last data block in the longer ones
+        for block in result.iter_mut().skip(longer_blocks_start_at) {
+            block[shorter_blocks_num_data_codewords] = raw[raw_offset];
+            raw_offset += 1;
+        }
+        // Now add in error correction blocks
+        let max = result[0].len();
+        for i in shorter_blocks_num_data_codewords..max {
+            for (j, block) in result.iter_mut().enumerate() {
+                let i_offset = if j < longer_blocks_start_at { i } else { i + 1 };
+                block[i_offset] = raw[raw_offset];
+                raw_offset += 1;
+            }
+        }
+        Ok(result)
+    }
+
+    pub fn get_num_data_codewords(&self) -> i32 {
+        self.num_data_codewords
+    }
+
+    pub fn get_codewords_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.codewords
+    }
+}
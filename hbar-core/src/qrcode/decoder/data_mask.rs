@@ -0,0 +1,108 @@
+use crate::common::BitMatrix;
+use crate::{Error, ResultError};
+
+/**
+ * <p>Encapsulates data masks for the data bits in a QR code, per ISO 18004:2006 6.8. Implementations
+ * of this class can un-mask a raw BitMatrix. For simplicity, they will unmask the entire BitMatrix,
+ * including areas used for finder patterns, timing patterns, etc. These areas should be unused
+ * after the point they are unmasked anyway.</p>
+ *
+ * <p>Note that the diagram in section 6.8.1 is misleading since it indicates that i is column position
+ * and j is row position. In fact, as the text says, i is row position and j is column position.</p>
+ */
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DataMask {
+    /**
+     * 000: mask bits for which (x + y) mod 2 == 0
+     */
+    DataMask000,
+    /**
+     * 001: mask bits for which x mod 2 == 0
+     */
+    DataMask001,
+    /**
+     * 010: mask bits for which y mod 3 == 0
+     */
+    DataMask010,
+    /**
+     * 011: mask bits for which (x + y) mod 3 == 0
+     */
+    DataMask011,
+    /**
+     * 100: mask bits for which (x/2 + y/3) mod 2 == 0
+     */
+    DataMask100,
+    /**
+     * 101: mask bits for which xy mod 2 + xy mod 3 == 0
+     * equivalently, such that xy mod 6 == 0
+     */
+    DataMask101,
+    /**
+     * 110: mask bits for which (xy mod 2 + xy mod 3) mod 2 == 0
+     * equivalently, such that xy mod 6 < 3
+     */
+    DataMask110,
+    /**
+     * 111: mask bits for which ((x+y)mod 2 + xy mod 3) mod 2 == 0
+     * equivalently, such that (x + y + xy mod 3) mod 2 == 0
+     */
+    DataMask111,
+}
+
+impl DataMask {
+    const VALUES: [DataMask; 8] = [
+        DataMask::DataMask000,
+        DataMask::DataMask001,
+        DataMask::DataMask010,
+        DataMask::DataMask011,
+        DataMask::DataMask100,
+        DataMask::DataMask101,
+        DataMask::DataMask110,
+        DataMask::DataMask111,
+    ];
+
+    /**
+     * @param reference a value between 0 and 7 indicating one of the eight possible
+     * data mask patterns a QR Code may use
+     * @return DataMask encapsulating the data mask pattern
+     */
+    pub fn for_reference(reference: i32) -> ResultError<DataMask> {
+        if reference < 0 || reference >= DataMask::VALUES.len() as i32 {
+            return Err(Error::IllegalArgumentException(format!(
+                "Invalid data mask reference: {}",
+                reference
+            )));
+        }
+        Ok(DataMask::VALUES[reference as usize])
+    }
+
+    /**
+     * <p>Implementations of this method reverse the data masking process applied to a QR Code and
+     * make its bits ready to read.</p>
+     *
+     * @param bits representation of QR Code bits
+     * @param dimension dimension of QR Code, represented by bits, being unmasked
+     */
+    pub fn unmask_bit_matrix(&self, bits: &mut BitMatrix, dimension: i32) {
+        for i in 0..dimension {
+            for j in 0..dimension {
+                if self.is_masked(i, j) {
+                    bits.flip(j as u32, i as u32);
+                }
+            }
+        }
+    }
+
+    pub fn is_masked(&self, i: i32, j: i32) -> bool {
+        match self {
+            DataMask::DataMask000 => ((i + j) & 0x01) == 0,
+            DataMask::DataMask001 => (i & 0x01) == 0,
+            DataMask::DataMask010 => j % 3 == 0,
+            DataMask::DataMask011 => (i + j) % 3 == 0,
+            DataMask::DataMask100 => (((i / 2) + (j / 3)) & 0x01) == 0,
+            DataMask::DataMask101 => (i * j) % 6 == 0,
+            DataMask::DataMask110 => ((i * j) % 6) < 3,
+            DataMask::DataMask111 => ((i + j + ((i * j) % 3)) & 0x01) == 0,
+        }
+    }
+}
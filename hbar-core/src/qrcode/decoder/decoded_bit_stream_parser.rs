@@ -0,0 +1,457 @@
+use crate::common::{BitSource, CharacterSetECI, Charset, DecoderResult, StringUtils};
+use crate::qrcode::decoder::{ErrorCorrectionLevel, Mode, Version};
+use crate::{DecodeHintType, DecodeHintValue};
+use crate::{Error, ResultError};
+
+use std::collections::HashMap;
+
+/**
+ * <p>QR Codes can encode text as bits in one of several modes, and can use multiple modes
+ * in one QR Code. This class decodes the bits back into text.</p>
+ *
+ * <p>See ISO 18004:2006, 6.4.3 - 6.4.7</p>
+ */
+pub struct DecodedBitStreamParser;
+
+impl DecodedBitStreamParser {
+    /**
+     * See ISO 18004:2006, 6.4.4 Table 5
+     */
+    const ALPHANUMERIC_CHARS: [char; 45] = [
+        '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H',
+        'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
+        ' ', '$', '%', '*', '+', '-', '.', '/', ':',
+    ];
+    const GB2312_SUBSET: i32 = 1;
+
+    pub fn decode(
+        bytes: &[u8],
+        version: &Version,
+        ec_level: &ErrorCorrectionLevel,
+        hints: &HashMap<DecodeHintType, DecodeHintValue>,
+    ) -> ResultError<DecoderResult> {
+        match DecodedBitStreamParser::decode_internal(bytes, version, ec_level, hints) {
+            // from read_bits() calls
+            Err(Error::IllegalArgumentException(reason)) => Err(Error::FormatException(reason)),
+            other => other,
+        }
+    }
+
+    fn decode_internal(
+        bytes: &[u8],
+        version: &Version,
+        ec_level: &ErrorCorrectionLevel,
+        hints: &HashMap<DecodeHintType, DecodeHintValue>,
+    ) -> ResultError<DecoderResult> {
+        let mut bits = BitSource::new(bytes);
+        let mut result = String::with_capacity(50);
+        let mut byte_segments: Vec<Vec<u8>> = Vec::with_capacity(1);
+        let mut symbol_sequence = -1;
+        let mut parity_data = -1;
+
+        let mut current_character_set_eci: Option<Charset> = None;
+        let mut fc1_in_effect = false;
+        let mut has_fnc1_first = false;
+        let mut has_fnc1_second = false;
+        loop {
+            // While still another segment to read...
+            let mode = if bits.available() < 4 {
+                // OK, assume we're done. Really, a TERMINATOR mode should have been recorded here
+                Mode::get_terminator()
+            } else {
+                // mode is encoded by 4 bits
+                Mode::for_bits(bits.read_bits(4)?)?
+            };
+            match mode {
+                Mode::Terminator(_, _) => break,
+                Mode::Fnc1FirstPosition(_, _) => {
+                    // symbology detection
+                    has_fnc1_first = true;
+                    // We do little with FNC1 except alter the parsed result a bit according to the spec
+                    fc1_in_effect = true;
+                }
+                Mode::Fnc1SecondPosition(_, _) => {
+                    // symbology detection
+                    has_fnc1_second = true;
+                    // We do little with FNC1 except alter the parsed result a bit according to the spec
+                    fc1_in_effect = true;
+                }
+                Mode::StructuredAppend(_, _) => {
+                    if bits.available() < 16 {
+                        return Err(Error::FormatException(String::from(
+                            "Structured append header is truncated",
+                        )));
+                    }
+                    // sequence number and parity is added later to the result metadata
+                    // Read next 8 bits (symbol sequence #) and 8 bits (parity data), then continue
+                    symbol_sequence = bits.read_bits(8)?;
+                    parity_data = bits.read_bits(8)?;
+                }
+                Mode::ECI(_, _) => {
+                    // Count doesn't apply to ECI
+                    let value = DecodedBitStreamParser::parse_eci_value(&mut bits)?;
+                    current_character_set_eci = CharacterSetECI::get_charset_by_value(value);
+                    if current_character_set_eci.is_none() {
+                        return Err(Error::FormatException(format!(
+                            "Unsupported ECI value: {}",
+                            value
+                        )));
+                    }
+                }
+                Mode::Hanzi(_, _) => {
+                    // First handle Hanzi mode which does not start with character count
+                    // Chinese mode contains a sub set indicator right after mode indicator
+                    let subset = bits.read_bits(4)?;
+                    let count_hanzi =
+                        bits.read_bits(mode.get_character_count_bits(version) as usize)?;
+                    if subset == DecodedBitStreamParser::GB2312_SUBSET {
+                        DecodedBitStreamParser::decode_hanzi_segment(
+                            &mut bits,
+                            &mut result,
+                            count_hanzi,
+                        )?;
+                    }
+                }
+                _ => {
+                    // "Normal" QR code modes:
+                    // How many characters will follow, encoded in this mode?
+                    let count = bits.read_bits(mode.get_character_count_bits(version) as usize)?;
+                    match mode {
+                        Mode::Numeric(_, _) => DecodedBitStreamParser::decode_numeric_segment(
+                            &mut bits,
+                            &mut result,
+                            count,
+                        )?,
+                        Mode::Alphanumeric(_, _) => {
+                            DecodedBitStreamParser::decode_alphanumeric_segment(
+                                &mut bits,
+                                &mut result,
+                                count,
+                                fc1_in_effect,
+                            )?
+                        }
+                        Mode::Byte(_, _) => DecodedBitStreamParser::decode_byte_segment(
+                            &mut bits,
+                            &mut result,
+                            count,
+                            &current_character_set_eci,
+                            &mut byte_segments,
+                            hints,
+                        )?,
+                        Mode::Kanji(_, _) => DecodedBitStreamParser::decode_kanji_segment(
+                            &mut bits,
+                            &mut result,
+                            count,
+                        )?,
+                        _ => {
+                            return Err(Error::FormatException(format!(
+                                "Unexpected mode: {:?}",
+                                mode
+                            )))
+                        }
+                    }
+                }
+            }
+        }
+
+        let symbology_modifier = if current_character_set_eci.is_some() {
+            if has_fnc1_first {
+                4
+            } else if has_fnc1_second {
+                6
+            } else {
+                2
+            }
+        } else if has_fnc1_first {
+            3
+        } else if has_fnc1_second {
+            5
+        } else {
+            1
+        };
+
+        Ok(DecoderResult::new3(
+            bytes.to_vec(),
+            result,
+            byte_segments,
+            format!("{:?}", ec_level),
+            symbol_sequence,
+            parity_data,
+            symbology_modifier,
+        ))
+    }
+
+    /**
+     * See specification GBT 18284-2000
+     */
+    fn decode_hanzi_segment(
+        bits: &mut BitSource,
+        result: &mut String,
+        count: i32,
+    ) -> ResultError<()> {
+        // Don't crash trying to read more bits than we have available.
+        if count * 13 > bits.available() as i32 {
+            return Err(Error::FormatException(String::from(
+                "Hanzi segment is truncated",
+            )));
+        }
+
+        // Each character will require 2 bytes. Read the characters as 2-byte pairs
+        // and decode as GB2312 afterwards
+        let mut buffer = Vec::with_capacity(2 * count as usize);
+        for _ in 0..count {
+            // Each 13 bits encodes a 2-byte character
+            let two_bytes = bits.read_bits(13)?;
+            let mut assembled_two_bytes = ((two_bytes / 0x060) << 8) | (two_bytes % 0x060);
+            if assembled_two_bytes < 0x00A00 {
+                // In the 0xA1A1 to 0xAAFE range
+                assembled_two_bytes += 0x0A1A1;
+            } else {
+                // In the 0xB0A1 to 0xFAFE range
+                assembled_two_bytes += 0x0A6A1;
+            }
+            buffer.push(((assembled_two_bytes >> 8) & 0xFF) as u8);
+            buffer.push((assembled_two_bytes & 0xFF) as u8);
+        }
+
+        // GBK is a superset of GB2312
+        result.push_str(&Charset::GBK.decode(&buffer)?);
+        Ok(())
+    }
+
+    fn decode_kanji_segment(
+        bits: &mut BitSource,
+        result: &mut String,
+        count: i32,
+    ) -> ResultError<()> {
+        // Don't crash trying to read more bits than we have available.
+        if count * 13 > bits.available() as i32 {
+            return Err(Error::FormatException(String::from(
+                "Kanji segment is truncated",
+            )));
+        }
+
+        // Each character will require 2 bytes. Read the characters as 2-byte pairs
+        // and decode as Shift_JIS afterwards
+        let mut buffer = Vec::with_capacity(2 * count as usize);
+        for _ in 0..count {
+            // Each 13 bits encodes a 2-byte character
+            let two_bytes = bits.read_bits(13)?;
+            let mut assembled_two_bytes = ((two_bytes / 0x0C0) << 8) | (two_bytes % 0x0C0);
+            if assembled_two_bytes < 0x01F00 {
+                // In the 0x8140 to 0x9FFC range
+                assembled_two_bytes += 0x08140;
+            } else {
+                // In the 0xE040 to 0xEBBF range
+                assembled_two_bytes += 0x0C140;
+            }
+            buffer.push(((assembled_two_bytes >> 8) & 0xFF) as u8);
+            buffer.push((assembled_two_bytes & 0xFF) as u8);
+        }
+
+        result.push_str(&Charset::ShiftJIS.decode(&buffer)?);
+        Ok(())
+    }
+
+    fn decode_byte_segment(
+        bits: &mut BitSource,
+        result: &mut String,
+        count: i32,
+        current_character_set_eci: &Option<Charset>,
+        byte_segments: &mut Vec<Vec<u8>>,
+        hints: &HashMap<DecodeHintType, DecodeHintValue>,
+    ) -> ResultError<()> {
+        // Don't crash trying to read more bits than we have available.
+        if 8 * count > bits.available() as i32 {
+            return Err(Error::FormatException(String::from(
+                "Byte segment is truncated",
+            )));
+        }
+
+        let mut read_bytes = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            read_bytes.push(bits.read_bits(8)? as u8);
+        }
+        let text = match current_character_set_eci {
+            Some(charset) => charset.decode(&read_bytes)?,
+            None => {
+                // The spec isn't clear on this mode; see
+                // section 6.4.5: it does not say which encoding to assuming
+                // upon decoding. I have seen ISO-8859-1 used as well as
+                // Shift_JIS -- without anything like an ECI designator to
+                // give a hint.
+                let encoding = StringUtils::guess_charset(&read_bytes, hints);
+                // A wrong guess or hint must not lose the payload; fall back to ISO-8859-1,
+                // which maps every byte.
+                encoding
+                    .decode(&read_bytes)
+                    .or_else(|_| Charset::ISO8859_1.decode(&read_bytes))?
+            }
+        };
+        result.push_str(&text);
+        byte_segments.push(read_bytes);
+        Ok(())
+    }
+
+    fn to_alpha_numeric_char(value: i32) -> ResultError<char> {
+        if value < 0 || value >= DecodedBitStreamParser::ALPHANUMERIC_CHARS.len() as i32 {
+            return Err(Error::FormatException(format!(
+                "Invalid alphanumeric value: {}",
+                value
+            )));
+        }
+        Ok(DecodedBitStreamParser::ALPHANUMERIC_CHARS[value as usize])
+    }
+
+    fn decode_alphanumeric_segment(
+        bits: &mut BitSource,
+        result: &mut String,
+        count: i32,
+        fc1_in_effect: bool,
+    ) -> ResultError<()> {
+        // Read two characters at a time
+        let mut segment = String::new();
+        let mut count = count;
+        while count > 1 {
+            if bits.available() < 11 {
+                return Err(Error::FormatException(String::from(
+                    "Alphanumeric segment is truncated",
+                )));
+            }
+            let next_two_chars_bits = bits.read_bits(11)?;
+            segment.push(DecodedBitStreamParser::to_alpha_numeric_char(
+                next_two_chars_bits / 45,
+            )?);
+            segment.push(DecodedBitStreamParser::to_alpha_numeric_char(
+                next_two_chars_bits % 45,
+            )?);
+            count -= 2;
+        }
+        if count == 1 {
+            // special case: one character left
+            if bits.available() < 6 {
+                return Err(Error::FormatException(String::from(
+                    "Alphanumeric segment is truncated",
+                )));
+            }
+            segment.push(DecodedBitStreamParser::to_alpha_numeric_char(
+                bits.read_bits(6)?,
+            )?);
+        }
+        // See section 6.4.8.1, 6.4.8.2
+        if fc1_in_effect {
+            // We need to massage the result a bit if in an FNC1 mode:
+            let mut chars = segment.chars().peekable();
+            while let Some(c) = chars.next() {
+                if c == '%' {
+                    if chars.peek() == Some(&'%') {
+                        // %% is rendered as %
+                        chars.next();
+                        result.push('%');
+                    } else {
+                        // In alpha mode, % should be converted to FNC1 separator 0x1D
+                        result.push('\u{1D}');
+                    }
+                } else {
+                    result.push(c);
+                }
+            }
+        } else {
+            result.push_str(&segment);
+        }
+        Ok(())
+    }
+
+    fn decode_numeric_segment(
+        bits: &mut BitSource,
+        result: &mut String,
+        count: i32,
+    ) -> ResultError<()> {
+        // Read three digits at a time
+        let mut count = count;
+        while count >= 3 {
+            // Each 10 bits encodes three digits
+            if bits.available() < 10 {
+                return Err(Error::FormatException(String::from(
+                    "Numeric segment is truncated",
+                )));
+            }
+            let three_digits_bits = bits.read_bits(10)?;
+            if three_digits_bits >= 1000 {
+                return Err(Error::FormatException(format!(
+                    "Invalid three digits value: {}",
+                    three_digits_bits
+                )));
+            }
+            result.push(DecodedBitStreamParser::to_alpha_numeric_char(
+                three_digits_bits / 100,
+            )?);
+            result.push(DecodedBitStreamParser::to_alpha_numeric_char(
+                (three_digits_bits / 10) % 10,
+            )?);
+            result.push(DecodedBitStreamParser::to_alpha_numeric_char(
+                three_digits_bits % 10,
+            )?);
+            count -= 3;
+        }
+        if count == 2 {
+            // Two digits left over to read, encoded in 7 bits
+            if bits.available() < 7 {
+                return Err(Error::FormatException(String::from(
+                    "Numeric segment is truncated",
+                )));
+            }
+            let two_digits_bits = bits.read_bits(7)?;
+            if two_digits_bits >= 100 {
+                return Err(Error::FormatException(format!(
+                    "Invalid two digits value: {}",
+                    two_digits_bits
+                )));
+            }
+            result.push(DecodedBitStreamParser::to_alpha_numeric_char(
+                two_digits_bits / 10,
+            )?);
+            result.push(DecodedBitStreamParser::to_alpha_numeric_char(
+                two_digits_bits % 10,
+            )?);
+        } else if count == 1 {
+            // One digit left over to read
+            if bits.available() < 4 {
+                return Err(Error::FormatException(String::from(
+                    "Numeric segment is truncated",
+                )));
+            }
+            let digit_bits = bits.read_bits(4)?;
+            if digit_bits >= 10 {
+                return Err(Error::FormatException(format!(
+                    "Invalid digit value: {}",
+                    digit_bits
+                )));
+            }
+            result.push(DecodedBitStreamParser::to_alpha_numeric_char(digit_bits)?);
+        }
+        Ok(())
+    }
+
+    fn parse_eci_value(bits: &mut BitSource) -> ResultError<i32> {
+        let first_byte = bits.read_bits(8)?;
+        if (first_byte & 0x80) == 0 {
+            // just one byte
+            return Ok(first_byte & 0x7F);
+        }
+        if (first_byte & 0xC0) == 0x80 {
+            // two bytes
+            let second_byte = bits.read_bits(8)?;
+            return Ok(((first_byte & 0x3F) << 8) | second_byte);
+        }
+        if (first_byte & 0xE0) == 0xC0 {
+            // three bytes
+            let second_third_bytes = bits.read_bits(16)?;
+            return Ok(((first_byte & 0x1F) << 16) | second_third_bytes);
+        }
+        Err(Error::FormatException(format!(
+            "Invalid ECI value: {}",
+            first_byte
+        )))
+    }
+}
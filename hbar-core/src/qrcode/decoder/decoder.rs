@@ -1,11 +1,31 @@
 use crate::common::BitMatrix;
 use crate::common::DecoderResult;
-use crate::common::{GenericGFEnum, ReedSolomonDecoder};
-use crate::ResultError;
+use crate::common::{Corrections, GenericGFEnum, ReedSolomonDecoder};
+use crate::BlockCorrections;
 use crate::{DecodeHintType, DecodeHintValue};
+use crate::{Error, ResultError};
+
+use super::bit_matrix_parser::BitMatrixParser;
+use super::data_block::DataBlock;
+use super::decoded_bit_stream_parser::DecodedBitStreamParser;
 
 use std::collections::HashMap;
-use std::rc::Rc;
+
+/**
+ * The codewords of a decoded QR Code, before and after error correction, for grading it.
+ */
+pub(crate) struct SymbolCodewords {
+    pub(crate) result: DecoderResult,
+    /** The codewords as read from the symbol */
+    pub(crate) read: Vec<u8>,
+    /** The same codewords after error correction */
+    pub(crate) corrected: Vec<u8>,
+    /** The block each codeword belongs to */
+    pub(crate) blocks: Vec<usize>,
+    /** The modules of each codeword, most significant bit first */
+    pub(crate) modules: Vec<Vec<(u32, u32)>>,
+}
+
 /**
  * <p>The main class which implements QR Code decoding -- as opposed to locating and extracting
  * the QR Code from an image.</p>
@@ -18,7 +38,7 @@ pub struct Decoder {
 impl Decoder {
     pub fn new() -> Decoder {
         Decoder {
-            rsDecoder: ReedSolomonDecoder::new(Rc::new(GenericGFEnum::QrCodeField256.get())),
+            rsDecoder: ReedSolomonDecoder::new(GenericGFEnum::QrCodeField256.get()),
         }
     }
 
@@ -59,13 +79,311 @@ impl Decoder {
      * @throws FormatException if the QR Code cannot be decoded
      * @throws ChecksumException if error correction fails
      */
-
     pub fn decode_hints(
         &self,
         bits: &BitMatrix,
         hints: &HashMap<DecodeHintType, DecodeHintValue>,
+    ) -> ResultError<DecoderResult> {
+        self.decode_bits(bits, None, hints)
+    }
+
+    /**
+     * <p>Like {@link #decode_hints(BitMatrix, HashMap)}, for a sampler which can tell which
+     * modules it could not read reliably, for example because of glare or a torn label. Codewords
+     * containing such a module are corrected as erasures, which costs half as much error
+     * correction as an error at an unknown position. Blocks that cannot be corrected that way are
+     * retried without the erasures.</p>
+     *
+     * @param bits booleans representing white/black QR Code modules
+     * @param unreliable set bits mark the modules of {@code bits} which were sampled with low
+     *  confidence
+     * @param hints decoding hints that should be used to influence decoding
+     * @return text and bytes encoded within the QR Code
+     * @throws FormatException if the QR Code cannot be decoded
+     * @throws ChecksumException if error correction fails
+     */
+    pub fn decode_with_erasures(
+        &self,
+        bits: &BitMatrix,
+        unreliable: &BitMatrix,
+        hints: &HashMap<DecodeHintType, DecodeHintValue>,
+    ) -> ResultError<DecoderResult> {
+        if unreliable.getWidth() != bits.getWidth() || unreliable.getHeight() != bits.getHeight() {
+            return Err(Error::IllegalArgumentException(String::from(
+                "Unreliable modules must match the size of the QR Code",
+            )));
+        }
+        self.decode_bits(bits, Some(unreliable), hints)
+    }
+
+    /**
+     * <p>Decodes a QR Code like {@link #decode(BitMatrix)}, without trying it mirrored, and
+     * keeps track of which codewords were corrected and which modules they came from.</p>
+     *
+     * @throws FormatException if the QR Code cannot be decoded
+     * @throws ChecksumException if error correction fails
+     */
+    pub(crate) fn decode_codewords(&self, bits: &BitMatrix) -> ResultError<SymbolCodewords> {
+        let mut parser = BitMatrixParser::new(bits)?;
+        let hints = HashMap::new();
+        let mut codewords = self.decode_parser(&mut parser, &hints)?;
+        codewords.modules = parser.read_codeword_modules()?;
+        Ok(codewords)
+    }
+
+    fn decode_bits(
+        &self,
+        bits: &BitMatrix,
+        unreliable: Option<&BitMatrix>,
+        hints: &HashMap<DecodeHintType, DecodeHintValue>,
     ) -> ResultError<DecoderResult> {
         // Construct a parser and read version, error-correction level
-        todo!()
+        let mut parser = BitMatrixParser::new(bits)?;
+        if let Some(unreliable) = unreliable {
+            parser.set_unreliable_modules(unreliable.clone());
+        }
+        let first_error = match self.decode_parser(&mut parser, hints) {
+            Ok(codewords) => return Ok(codewords.result),
+            Err(err @ Error::FormatException(_)) | Err(err @ Error::ChecksumException(_)) => err,
+            Err(err) => return Err(err),
+        };
+
+        // Revert the bit matrix
+        parser.remask()?;
+
+        // Will be attempting a mirrored reading of the version and format info.
+        parser.set_mirror(true);
+
+        // Preemptively read the version.
+        // Preemptively read the format information.
+        if parser.readVersion().is_err() || parser.readFormatInformation().is_err() {
+            return Err(first_error);
+        }
+
+        /*
+         * Since we're here, this means we have successfully detected some kind
+         * of version and format information when mirrored. This is a good sign,
+         * that the QR code may be mirrored, and we should try once more with a
+         * mirrored content.
+         */
+        // Prepare for a mirrored reading.
+        parser.mirror();
+
+        // If the second attempt fails as well, report the original failure
+        self.decode_parser(&mut parser, hints)
+            .map(|codewords| codewords.result)
+            .map_err(|_| first_error)
+    }
+
+    fn decode_parser(
+        &self,
+        parser: &mut BitMatrixParser,
+        hints: &HashMap<DecodeHintType, DecodeHintValue>,
+    ) -> ResultError<SymbolCodewords> {
+        let version = parser.readVersion()?;
+        let format_info = parser.readFormatInformation()?;
+        let ec_level = format_info.getErrorCorrectionLevel().clone();
+        let data_mask = format_info.getDataMask();
+
+        // Read codewords
+        let codewords = parser.read_codewords()?;
+        // Separate into data blocks
+        let mut data_blocks = DataBlock::get_data_blocks(&codewords, &version, &ec_level)?;
+        // Separating their indices the same way tells where each codeword went
+        let indices: Vec<usize> = (0..codewords.len()).collect();
+        let block_indices = DataBlock::deinterleave(&indices, &version, &ec_level)?;
+
+        // Count total number of data bytes
+        let total_bytes = data_blocks
+            .iter()
+            .map(|block| block.get_num_data_codewords() as usize)
+            .sum();
+        let mut result_bytes = Vec::with_capacity(total_bytes);
+
+        // Error-correct and copy data blocks together into a stream of bytes
+        let mut block_corrections = Vec::with_capacity(data_blocks.len());
+        let mut corrected = codewords.clone();
+        let mut blocks = vec![0; codewords.len()];
+        for (block, indices) in block_indices.iter().enumerate() {
+            let data_block = &mut data_blocks[block];
+            let num_data_codewords = data_block.get_num_data_codewords() as usize;
+            let erasures: Vec<usize> = (0..indices.len())
+                .filter(|&i| parser.get_erasures().contains(&indices[i]))
+                .collect();
+            let codeword_bytes = data_block.get_codewords_mut();
+            let corrections = self.correct_errors(codeword_bytes, num_data_codewords, &erasures)?;
+            block_corrections.push(BlockCorrections::new(
+                (codeword_bytes.len() - num_data_codewords) as i32,
+                corrections.get_errors() as i32,
+                corrections.get_erasures() as i32,
+            ));
+            result_bytes.extend_from_slice(&codeword_bytes[..num_data_codewords]);
+            for (&index, &codeword) in indices.iter().zip(codeword_bytes.iter()) {
+                corrected[index] = codeword;
+                blocks[index] = block;
+            }
+        }
+
+        // Decode the contents of that stream of bytes
+        let mut result = DecodedBitStreamParser::decode(&result_bytes, &version, &ec_level, hints)?;
+        result.set_errors_corrected(block_corrections.iter().map(|b| b.get_errors()).sum());
+        result.set_erasures(block_corrections.iter().map(|b| b.get_erasures()).sum());
+        result.set_block_corrections(block_corrections);
+        result.set_symbol_version(version.get_version_number());
+        result.set_data_mask(data_mask);
+        Ok(SymbolCodewords {
+            result,
+            read: codewords,
+            corrected,
+            blocks,
+            modules: Vec::new(),
+        })
+    }
+
+    /**
+     * <p>Given data and error-correction codewords received, possibly corrupted by errors, attempts to
+     * correct the errors in-place using Reed-Solomon error correction.</p>
+     *
+     * @param codeword_bytes data and error correction codewords
+     * @param num_data_codewords number of codewords that are data bytes
+     * @param erasures indices of codewords known to be unreadable
+     * @return how many errors and erasures were corrected
+     * @throws ChecksumException if error correction fails
+     */
+    fn correct_errors(
+        &self,
+        codeword_bytes: &mut [u8],
+        num_data_codewords: usize,
+        erasures: &[usize],
+    ) -> ResultError<Corrections> {
+        // First read into an array of ints
+        let received: Vec<i32> = codeword_bytes.iter().map(|&b| b as i32).collect();
+        let num_ec_codewords = (codeword_bytes.len() - num_data_codewords) as i32;
+        let mut codewords_ints = received.clone();
+        let mut corrections =
+            self.rsDecoder
+                .decode_with_erasures(&mut codewords_ints, num_ec_codewords, erasures);
+        if let Err(Error::ReedSolomonException(_)) = corrections {
+            if !erasures.is_empty() {
+                // The erasures may have been too many, or wrongly marked; try without them
+                codewords_ints = received;
+                corrections =
+                    self.rsDecoder
+                        .decode_with_erasures(&mut codewords_ints, num_ec_codewords, &[]);
+            }
+        }
+        let corrections = corrections.map_err(|err| match err {
+            Error::ReedSolomonException(msg) => Error::ChecksumException(msg),
+            err => err,
+        })?;
+        // Copy back into array of bytes, error-correction codewords too
+        for (byte, &codeword) in codeword_bytes.iter_mut().zip(codewords_ints.iter()) {
+            *byte = codeword as u8;
+        }
+        Ok(corrections)
+    }
+}
+
+#[cfg(all(test, feature = "encode"))]
+mod decoder_tests {
+    use super::*;
+    use crate::qrcode::decoder::{ErrorCorrectionLevel, Mode};
+    use crate::qrcode::encoder::Encoder;
+
+    use std::sync::Arc;
+
+    fn encode_to_bits(content: &str, ec_level: ErrorCorrectionLevel) -> (Mode, BitMatrix) {
+        let qr_code = Encoder::new()
+            .encode(&String::from(content), ec_level)
+            .unwrap();
+        let matrix = qr_code.get_matrix();
+        let mut bits = BitMatrix::new2(matrix.get_width(), matrix.get_height()).unwrap();
+        for y in 0..matrix.get_height() {
+            for x in 0..matrix.get_width() {
+                if matrix.get(x, y) == 1 {
+                    bits.set(x as u32, y as u32);
+                }
+            }
+        }
+        (qr_code.get_mode().clone(), bits)
+    }
+
+    #[test]
+    fn round_trip_byte() {
+        let (mode, bits) = encode_to_bits("hello, world", ErrorCorrectionLevel::M);
+        assert_eq!(Mode::get_byte(), mode);
+        let result = Decoder::new().decode(&bits).unwrap();
+        assert_eq!("hello, world", result.getText());
+        assert_eq!("M", result.getECLevel());
+    }
+
+    #[test]
+    fn round_trip_kanji() {
+        let content = "日本語の漢字テキスト";
+        let (mode, bits) = encode_to_bits(content, ErrorCorrectionLevel::H);
+        assert_eq!(Mode::get_kanji(), mode);
+        let result = Decoder::new().decode(&bits).unwrap();
+        assert_eq!(content, result.getText());
+    }
+
+    #[test]
+    fn corrects_errors() {
+        let (_, mut bits) = encode_to_bits("0123456789012345", ErrorCorrectionLevel::H);
+        for x in 9..13 {
+            bits.flip(x, 12);
+        }
+        let result = Decoder::new().decode(&bits).unwrap();
+        assert_eq!("0123456789012345", result.getText());
+    }
+
+    #[test]
+    fn corrects_erasures() {
+        let (_, mut bits) = encode_to_bits("ERASE ME", ErrorCorrectionLevel::H);
+        let mut unreliable = BitMatrix::new2(bits.getWidth(), bits.getHeight()).unwrap();
+        unreliable.set_region(13, 9, 8, 12);
+        for y in 9..21 {
+            for x in 13..21 {
+                bits.flip(x, y);
+            }
+        }
+        let hints = HashMap::new();
+        assert!(Decoder::new().decode(&bits).is_err());
+        let result = Decoder::new()
+            .decode_with_erasures(&bits, &unreliable, &hints)
+            .unwrap();
+        assert_eq!("ERASE ME", result.getText());
+        assert_eq!(0, result.get_errors_corrected());
+        assert!(result.get_erasures() > 8, "{}", result.get_erasures());
+
+        // Marking every module falls back to correcting errors only
+        let (_, mut bits) = encode_to_bits("ERASE ME", ErrorCorrectionLevel::H);
+        bits.flip(20, 20);
+        unreliable.set_region(0, 0, bits.getWidth(), bits.getHeight());
+        let result = Decoder::new()
+            .decode_with_erasures(&bits, &unreliable, &hints)
+            .unwrap();
+        assert_eq!("ERASE ME", result.getText());
+        assert_eq!(
+            (1, 0),
+            (result.get_errors_corrected(), result.get_erasures())
+        );
+    }
+
+    #[test]
+    fn shared_between_threads() {
+        let decoder = Arc::new(Decoder::new());
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let decoder = Arc::clone(&decoder);
+                std::thread::spawn(move || {
+                    let content = format!("worker {}", i);
+                    let (_, bits) = encode_to_bits(&content, ErrorCorrectionLevel::Q);
+                    assert_eq!(&content, decoder.decode(&bits).unwrap().getText());
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
     }
 }
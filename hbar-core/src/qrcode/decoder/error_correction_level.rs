@@ -1,11 +1,12 @@
-use strum_macros::{EnumString, EnumVariantNames, ToString};
+use strum_macros::{Display, EnumString, EnumVariantNames};
 // You need to import the trait, to have access to VARIANTS
 use crate::{Error, ResultError};
 use strum::VariantNames;
 
-use std::rc::Rc;
+use alloc::string::String;
+use alloc::string::ToString;
 
-#[derive(Debug, PartialEq, Eq, Hash, EnumString, ToString, EnumVariantNames, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, EnumString, Display, EnumVariantNames, Clone)]
 #[strum(serialize_all = "kebab_case")]
 pub enum ErrorCorrectionLevel {
     /** L = ~7% correction */
@@ -20,10 +21,10 @@ pub enum ErrorCorrectionLevel {
 
 impl ErrorCorrectionLevel {
     const FOR_BITS: [ErrorCorrectionLevel; 4] = [
-        ErrorCorrectionLevel::L,
         ErrorCorrectionLevel::M,
-        ErrorCorrectionLevel::Q,
+        ErrorCorrectionLevel::L,
         ErrorCorrectionLevel::H,
+        ErrorCorrectionLevel::Q,
     ];
 
     pub fn ordinal(&self) -> usize {
@@ -68,11 +69,10 @@ impl ErrorCorrectionLevel {
      * @param bits int containing the two bits encoding a QR Code's error correction level
      * @return ErrorCorrectionLevel representing the encoded error correction level
      */
-    pub fn forBits(bits: i32) -> ResultError<Rc<ErrorCorrectionLevel>> {
+    pub fn forBits(bits: i32) -> ResultError<ErrorCorrectionLevel> {
         if bits < 0 || bits >= ErrorCorrectionLevel::FOR_BITS.len() as i32 {
             return Err(Error::IllegalArgumentException(String::from("")));
         }
-        let ec_level = &ErrorCorrectionLevel::FOR_BITS[bits as usize];
-        Ok(Rc::new(ec_level.clone()))
+        Ok(ErrorCorrectionLevel::FOR_BITS[bits as usize].clone())
     }
 }
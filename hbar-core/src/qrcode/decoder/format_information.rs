@@ -1,7 +1,6 @@
 use super::ErrorCorrectionLevel;
 use crate::{Error, ResultError};
 
-use std::rc::Rc;
 /**
  * <p>Encapsulates a QR Code's format information, including the data mask used and
  * error correction level.</p>
@@ -12,7 +11,7 @@ use std::rc::Rc;
  */
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct FormatInformation {
-    errorCorrectionLevel: Rc<ErrorCorrectionLevel>,
+    errorCorrectionLevel: ErrorCorrectionLevel,
     dataMask: i32,
 }
 
@@ -132,8 +131,8 @@ impl FormatInformation {
         return Ok(None);
     }
 
-    pub fn getErrorCorrectionLevel(&self) -> Rc<ErrorCorrectionLevel> {
-        Rc::clone(&self.errorCorrectionLevel)
+    pub fn getErrorCorrectionLevel(&self) -> &ErrorCorrectionLevel {
+        &self.errorCorrectionLevel
     }
 
     pub fn getDataMask(&self) -> i32 {
@@ -1,12 +1,29 @@
+#[cfg(all(feature = "qrcode", feature = "decode"))]
 mod bit_matrix_parser;
+#[cfg(all(feature = "qrcode", feature = "decode"))]
+mod data_block;
+#[cfg(all(feature = "qrcode", feature = "decode"))]
+mod data_mask;
+#[cfg(all(feature = "qrcode", feature = "decode"))]
+mod decoded_bit_stream_parser;
+#[cfg(all(feature = "qrcode", feature = "decode"))]
 mod decoder;
 mod error_correction_level;
+#[cfg(all(feature = "qrcode", feature = "decode"))]
 mod format_information;
+#[cfg(all(feature = "qrcode", any(feature = "encode", feature = "decode")))]
 mod mode;
+#[cfg(all(feature = "qrcode", any(feature = "encode", feature = "decode")))]
 mod version;
 
+#[cfg(all(feature = "qrcode", feature = "decode"))]
+pub use data_mask::DataMask;
+#[cfg(all(feature = "qrcode", feature = "decode"))]
 pub use decoder::Decoder;
 pub use error_correction_level::ErrorCorrectionLevel;
+#[cfg(all(feature = "qrcode", feature = "decode"))]
 pub use format_information::FormatInformation;
+#[cfg(all(feature = "qrcode", any(feature = "encode", feature = "decode")))]
 pub use mode::Mode;
+#[cfg(all(feature = "qrcode", any(feature = "encode", feature = "decode")))]
 pub use version::{Version, Versions};
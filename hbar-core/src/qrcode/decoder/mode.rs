@@ -1,8 +1,11 @@
 use crate::qrcode::decoder::Version;
+use crate::{Error, ResultError};
+use strum_macros::Display;
 use strum_macros::EnumString;
-use strum_macros::ToString;
 
-#[derive(Debug, PartialEq, Eq, Hash, EnumString, ToString, Clone)]
+use alloc::format;
+
+#[derive(Debug, PartialEq, Eq, Hash, EnumString, Display, Clone)]
 pub enum Mode {
     Terminator([i32; 3], i32),
     Numeric([i32; 3], i32),
@@ -48,6 +51,32 @@ impl Mode {
         Mode::Hanzi([8, 10, 12], 0x0D)
     }
 
+    /**
+     * @param bits four bits encoding a QR Code data mode
+     * @return Mode encoded by these bits
+     * @throws IllegalArgumentException if bits do not correspond to a known mode
+     */
+    pub fn for_bits(bits: i32) -> ResultError<Mode> {
+        match bits {
+            0x0 => Ok(Mode::get_terminator()),
+            0x1 => Ok(Mode::get_numeric()),
+            0x2 => Ok(Mode::get_alphanumeric()),
+            0x3 => Ok(Mode::get_structured_append()),
+            0x4 => Ok(Mode::get_byte()),
+            0x5 => Ok(Mode::get_fnc1_first_position()),
+            0x7 => Ok(Mode::get_eci()),
+            0x8 => Ok(Mode::get_kanji()),
+            0x9 => Ok(Mode::get_fnc1_second_position()),
+            // 0xD is defined in GBT 18284-2000, may not be supported in foreign country
+            0xD => Ok(Mode::get_hanzi()),
+            _ => Err(Error::IllegalArgumentException(format!(
+                "Invalid mode bits: {}",
+                bits
+            ))),
+        }
+    }
+
+    #[cfg(feature = "encode")]
     pub fn get_bits(&self) -> i32 {
         match self {
             Mode::Terminator(_, bits) => *bits,
@@ -1,5 +1,12 @@
+use crate::common::BitMatrix;
 use crate::qrcode::decoder::ErrorCorrectionLevel;
 use crate::{Error, ResultError};
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
 /**
  * <p>Encapsulates the parameters for one error-correction block in one symbol version.
  * This includes the number of data codewords, and the number of times a block with these
@@ -59,6 +66,7 @@ impl ECBlocks {
         return total;
     }
 
+    #[cfg(feature = "encode")]
     pub fn get_total_ec_codewords(&self) -> i32 {
         self.ec_codewords_per_block * self.get_num_blocks()
     }
@@ -168,6 +176,316 @@ fn build_versions() -> Vec<Version> {
                 ECBlocks::new(24, vec![ECB::new(4, 12), ECB::new(4, 13)]),
             ],
         ),
+        Version::new(
+            10,
+            vec![6, 28, 50],
+            vec![
+                ECBlocks::new(18, vec![ECB::new(2, 68), ECB::new(2, 69)]),
+                ECBlocks::new(26, vec![ECB::new(4, 43), ECB::new(1, 44)]),
+                ECBlocks::new(24, vec![ECB::new(6, 19), ECB::new(2, 20)]),
+                ECBlocks::new(28, vec![ECB::new(6, 15), ECB::new(2, 16)]),
+            ],
+        ),
+        Version::new(
+            11,
+            vec![6, 30, 54],
+            vec![
+                ECBlocks::new(20, vec![ECB::new(4, 81)]),
+                ECBlocks::new(30, vec![ECB::new(1, 50), ECB::new(4, 51)]),
+                ECBlocks::new(28, vec![ECB::new(4, 22), ECB::new(4, 23)]),
+                ECBlocks::new(24, vec![ECB::new(3, 12), ECB::new(8, 13)]),
+            ],
+        ),
+        Version::new(
+            12,
+            vec![6, 32, 58],
+            vec![
+                ECBlocks::new(24, vec![ECB::new(2, 92), ECB::new(2, 93)]),
+                ECBlocks::new(22, vec![ECB::new(6, 36), ECB::new(2, 37)]),
+                ECBlocks::new(26, vec![ECB::new(4, 20), ECB::new(6, 21)]),
+                ECBlocks::new(28, vec![ECB::new(7, 14), ECB::new(4, 15)]),
+            ],
+        ),
+        Version::new(
+            13,
+            vec![6, 34, 62],
+            vec![
+                ECBlocks::new(26, vec![ECB::new(4, 107)]),
+                ECBlocks::new(22, vec![ECB::new(8, 37), ECB::new(1, 38)]),
+                ECBlocks::new(24, vec![ECB::new(8, 20), ECB::new(4, 21)]),
+                ECBlocks::new(22, vec![ECB::new(12, 11), ECB::new(4, 12)]),
+            ],
+        ),
+        Version::new(
+            14,
+            vec![6, 26, 46, 66],
+            vec![
+                ECBlocks::new(30, vec![ECB::new(3, 115), ECB::new(1, 116)]),
+                ECBlocks::new(24, vec![ECB::new(4, 40), ECB::new(5, 41)]),
+                ECBlocks::new(20, vec![ECB::new(11, 16), ECB::new(5, 17)]),
+                ECBlocks::new(24, vec![ECB::new(11, 12), ECB::new(5, 13)]),
+            ],
+        ),
+        Version::new(
+            15,
+            vec![6, 26, 48, 70],
+            vec![
+                ECBlocks::new(22, vec![ECB::new(5, 87), ECB::new(1, 88)]),
+                ECBlocks::new(24, vec![ECB::new(5, 41), ECB::new(5, 42)]),
+                ECBlocks::new(30, vec![ECB::new(5, 24), ECB::new(7, 25)]),
+                ECBlocks::new(24, vec![ECB::new(11, 12), ECB::new(7, 13)]),
+            ],
+        ),
+        Version::new(
+            16,
+            vec![6, 26, 50, 74],
+            vec![
+                ECBlocks::new(24, vec![ECB::new(5, 98), ECB::new(1, 99)]),
+                ECBlocks::new(28, vec![ECB::new(7, 45), ECB::new(3, 46)]),
+                ECBlocks::new(24, vec![ECB::new(15, 19), ECB::new(2, 20)]),
+                ECBlocks::new(30, vec![ECB::new(3, 15), ECB::new(13, 16)]),
+            ],
+        ),
+        Version::new(
+            17,
+            vec![6, 30, 54, 78],
+            vec![
+                ECBlocks::new(28, vec![ECB::new(1, 107), ECB::new(5, 108)]),
+                ECBlocks::new(28, vec![ECB::new(10, 46), ECB::new(1, 47)]),
+                ECBlocks::new(28, vec![ECB::new(1, 22), ECB::new(15, 23)]),
+                ECBlocks::new(28, vec![ECB::new(2, 14), ECB::new(17, 15)]),
+            ],
+        ),
+        Version::new(
+            18,
+            vec![6, 30, 56, 82],
+            vec![
+                ECBlocks::new(30, vec![ECB::new(5, 120), ECB::new(1, 121)]),
+                ECBlocks::new(26, vec![ECB::new(9, 43), ECB::new(4, 44)]),
+                ECBlocks::new(28, vec![ECB::new(17, 22), ECB::new(1, 23)]),
+                ECBlocks::new(28, vec![ECB::new(2, 14), ECB::new(19, 15)]),
+            ],
+        ),
+        Version::new(
+            19,
+            vec![6, 30, 58, 86],
+            vec![
+                ECBlocks::new(28, vec![ECB::new(3, 113), ECB::new(4, 114)]),
+                ECBlocks::new(26, vec![ECB::new(3, 44), ECB::new(11, 45)]),
+                ECBlocks::new(26, vec![ECB::new(17, 21), ECB::new(4, 22)]),
+                ECBlocks::new(26, vec![ECB::new(9, 13), ECB::new(16, 14)]),
+            ],
+        ),
+        Version::new(
+            20,
+            vec![6, 34, 62, 90],
+            vec![
+                ECBlocks::new(28, vec![ECB::new(3, 107), ECB::new(5, 108)]),
+                ECBlocks::new(26, vec![ECB::new(3, 41), ECB::new(13, 42)]),
+                ECBlocks::new(30, vec![ECB::new(15, 24), ECB::new(5, 25)]),
+                ECBlocks::new(28, vec![ECB::new(15, 15), ECB::new(10, 16)]),
+            ],
+        ),
+        Version::new(
+            21,
+            vec![6, 28, 50, 72, 94],
+            vec![
+                ECBlocks::new(28, vec![ECB::new(4, 116), ECB::new(4, 117)]),
+                ECBlocks::new(26, vec![ECB::new(17, 42)]),
+                ECBlocks::new(28, vec![ECB::new(17, 22), ECB::new(6, 23)]),
+                ECBlocks::new(30, vec![ECB::new(19, 16), ECB::new(6, 17)]),
+            ],
+        ),
+        Version::new(
+            22,
+            vec![6, 26, 50, 74, 98],
+            vec![
+                ECBlocks::new(28, vec![ECB::new(2, 111), ECB::new(7, 112)]),
+                ECBlocks::new(28, vec![ECB::new(17, 46)]),
+                ECBlocks::new(30, vec![ECB::new(7, 24), ECB::new(16, 25)]),
+                ECBlocks::new(24, vec![ECB::new(34, 13)]),
+            ],
+        ),
+        Version::new(
+            23,
+            vec![6, 30, 54, 78, 102],
+            vec![
+                ECBlocks::new(30, vec![ECB::new(4, 121), ECB::new(5, 122)]),
+                ECBlocks::new(28, vec![ECB::new(4, 47), ECB::new(14, 48)]),
+                ECBlocks::new(30, vec![ECB::new(11, 24), ECB::new(14, 25)]),
+                ECBlocks::new(30, vec![ECB::new(16, 15), ECB::new(14, 16)]),
+            ],
+        ),
+        Version::new(
+            24,
+            vec![6, 28, 54, 80, 106],
+            vec![
+                ECBlocks::new(30, vec![ECB::new(6, 117), ECB::new(4, 118)]),
+                ECBlocks::new(28, vec![ECB::new(6, 45), ECB::new(14, 46)]),
+                ECBlocks::new(30, vec![ECB::new(11, 24), ECB::new(16, 25)]),
+                ECBlocks::new(30, vec![ECB::new(30, 16), ECB::new(2, 17)]),
+            ],
+        ),
+        Version::new(
+            25,
+            vec![6, 32, 58, 84, 110],
+            vec![
+                ECBlocks::new(26, vec![ECB::new(8, 106), ECB::new(4, 107)]),
+                ECBlocks::new(28, vec![ECB::new(8, 47), ECB::new(13, 48)]),
+                ECBlocks::new(30, vec![ECB::new(7, 24), ECB::new(22, 25)]),
+                ECBlocks::new(30, vec![ECB::new(22, 15), ECB::new(13, 16)]),
+            ],
+        ),
+        Version::new(
+            26,
+            vec![6, 30, 58, 86, 114],
+            vec![
+                ECBlocks::new(28, vec![ECB::new(10, 114), ECB::new(2, 115)]),
+                ECBlocks::new(28, vec![ECB::new(19, 46), ECB::new(4, 47)]),
+                ECBlocks::new(28, vec![ECB::new(28, 22), ECB::new(6, 23)]),
+                ECBlocks::new(30, vec![ECB::new(33, 16), ECB::new(4, 17)]),
+            ],
+        ),
+        Version::new(
+            27,
+            vec![6, 34, 62, 90, 118],
+            vec![
+                ECBlocks::new(30, vec![ECB::new(8, 122), ECB::new(4, 123)]),
+                ECBlocks::new(28, vec![ECB::new(22, 45), ECB::new(3, 46)]),
+                ECBlocks::new(30, vec![ECB::new(8, 23), ECB::new(26, 24)]),
+                ECBlocks::new(30, vec![ECB::new(12, 15), ECB::new(28, 16)]),
+            ],
+        ),
+        Version::new(
+            28,
+            vec![6, 26, 50, 74, 98, 122],
+            vec![
+                ECBlocks::new(30, vec![ECB::new(3, 117), ECB::new(10, 118)]),
+                ECBlocks::new(28, vec![ECB::new(3, 45), ECB::new(23, 46)]),
+                ECBlocks::new(30, vec![ECB::new(4, 24), ECB::new(31, 25)]),
+                ECBlocks::new(30, vec![ECB::new(11, 15), ECB::new(31, 16)]),
+            ],
+        ),
+        Version::new(
+            29,
+            vec![6, 30, 54, 78, 102, 126],
+            vec![
+                ECBlocks::new(30, vec![ECB::new(7, 116), ECB::new(7, 117)]),
+                ECBlocks::new(28, vec![ECB::new(21, 45), ECB::new(7, 46)]),
+                ECBlocks::new(30, vec![ECB::new(1, 23), ECB::new(37, 24)]),
+                ECBlocks::new(30, vec![ECB::new(19, 15), ECB::new(26, 16)]),
+            ],
+        ),
+        Version::new(
+            30,
+            vec![6, 26, 52, 78, 104, 130],
+            vec![
+                ECBlocks::new(30, vec![ECB::new(5, 115), ECB::new(10, 116)]),
+                ECBlocks::new(28, vec![ECB::new(19, 47), ECB::new(10, 48)]),
+                ECBlocks::new(30, vec![ECB::new(15, 24), ECB::new(25, 25)]),
+                ECBlocks::new(30, vec![ECB::new(23, 15), ECB::new(25, 16)]),
+            ],
+        ),
+        Version::new(
+            31,
+            vec![6, 30, 56, 82, 108, 134],
+            vec![
+                ECBlocks::new(30, vec![ECB::new(13, 115), ECB::new(3, 116)]),
+                ECBlocks::new(28, vec![ECB::new(2, 46), ECB::new(29, 47)]),
+                ECBlocks::new(30, vec![ECB::new(42, 24), ECB::new(1, 25)]),
+                ECBlocks::new(30, vec![ECB::new(23, 15), ECB::new(28, 16)]),
+            ],
+        ),
+        Version::new(
+            32,
+            vec![6, 34, 60, 86, 112, 138],
+            vec![
+                ECBlocks::new(30, vec![ECB::new(17, 115)]),
+                ECBlocks::new(28, vec![ECB::new(10, 46), ECB::new(23, 47)]),
+                ECBlocks::new(30, vec![ECB::new(10, 24), ECB::new(35, 25)]),
+                ECBlocks::new(30, vec![ECB::new(19, 15), ECB::new(35, 16)]),
+            ],
+        ),
+        Version::new(
+            33,
+            vec![6, 30, 58, 86, 114, 142],
+            vec![
+                ECBlocks::new(30, vec![ECB::new(17, 115), ECB::new(1, 116)]),
+                ECBlocks::new(28, vec![ECB::new(14, 46), ECB::new(21, 47)]),
+                ECBlocks::new(30, vec![ECB::new(29, 24), ECB::new(19, 25)]),
+                ECBlocks::new(30, vec![ECB::new(11, 15), ECB::new(46, 16)]),
+            ],
+        ),
+        Version::new(
+            34,
+            vec![6, 34, 62, 90, 118, 146],
+            vec![
+                ECBlocks::new(30, vec![ECB::new(13, 115), ECB::new(6, 116)]),
+                ECBlocks::new(28, vec![ECB::new(14, 46), ECB::new(23, 47)]),
+                ECBlocks::new(30, vec![ECB::new(44, 24), ECB::new(7, 25)]),
+                ECBlocks::new(30, vec![ECB::new(59, 16), ECB::new(1, 17)]),
+            ],
+        ),
+        Version::new(
+            35,
+            vec![6, 30, 54, 78, 102, 126, 150],
+            vec![
+                ECBlocks::new(30, vec![ECB::new(12, 121), ECB::new(7, 122)]),
+                ECBlocks::new(28, vec![ECB::new(12, 47), ECB::new(26, 48)]),
+                ECBlocks::new(30, vec![ECB::new(39, 24), ECB::new(14, 25)]),
+                ECBlocks::new(30, vec![ECB::new(22, 15), ECB::new(41, 16)]),
+            ],
+        ),
+        Version::new(
+            36,
+            vec![6, 24, 50, 76, 102, 128, 154],
+            vec![
+                ECBlocks::new(30, vec![ECB::new(6, 121), ECB::new(14, 122)]),
+                ECBlocks::new(28, vec![ECB::new(6, 47), ECB::new(34, 48)]),
+                ECBlocks::new(30, vec![ECB::new(46, 24), ECB::new(10, 25)]),
+                ECBlocks::new(30, vec![ECB::new(2, 15), ECB::new(64, 16)]),
+            ],
+        ),
+        Version::new(
+            37,
+            vec![6, 28, 54, 80, 106, 132, 158],
+            vec![
+                ECBlocks::new(30, vec![ECB::new(17, 122), ECB::new(4, 123)]),
+                ECBlocks::new(28, vec![ECB::new(29, 46), ECB::new(14, 47)]),
+                ECBlocks::new(30, vec![ECB::new(49, 24), ECB::new(10, 25)]),
+                ECBlocks::new(30, vec![ECB::new(24, 15), ECB::new(46, 16)]),
+            ],
+        ),
+        Version::new(
+            38,
+            vec![6, 32, 58, 84, 110, 136, 162],
+            vec![
+                ECBlocks::new(30, vec![ECB::new(4, 122), ECB::new(18, 123)]),
+                ECBlocks::new(28, vec![ECB::new(13, 46), ECB::new(32, 47)]),
+                ECBlocks::new(30, vec![ECB::new(48, 24), ECB::new(14, 25)]),
+                ECBlocks::new(30, vec![ECB::new(42, 15), ECB::new(32, 16)]),
+            ],
+        ),
+        Version::new(
+            39,
+            vec![6, 26, 54, 82, 110, 138, 166],
+            vec![
+                ECBlocks::new(30, vec![ECB::new(20, 117), ECB::new(4, 118)]),
+                ECBlocks::new(28, vec![ECB::new(40, 47), ECB::new(7, 48)]),
+                ECBlocks::new(30, vec![ECB::new(43, 24), ECB::new(22, 25)]),
+                ECBlocks::new(30, vec![ECB::new(10, 15), ECB::new(67, 16)]),
+            ],
+        ),
+        Version::new(
+            40,
+            vec![6, 30, 58, 86, 114, 142, 170],
+            vec![
+                ECBlocks::new(30, vec![ECB::new(19, 118), ECB::new(6, 119)]),
+                ECBlocks::new(28, vec![ECB::new(18, 47), ECB::new(31, 48)]),
+                ECBlocks::new(30, vec![ECB::new(34, 24), ECB::new(34, 25)]),
+                ECBlocks::new(30, vec![ECB::new(20, 15), ECB::new(61, 16)]),
+            ],
+        ),
     ]
 }
 
@@ -188,9 +506,59 @@ impl Versions {
         }
         Ok(&self.versions[(version_number - 1) as usize])
     }
+
+    /**
+     * <p>Deduces version information purely from QR Code dimensions.</p>
+     *
+     * @param dimension dimension in modules
+     * @return Version for a QR Code of that dimension
+     * @throws FormatException if dimension is not 1 mod 4
+     */
+    pub fn get_provisional_version_for_dimension(&self, dimension: i32) -> ResultError<&Version> {
+        if dimension % 4 != 1 {
+            return Err(Error::FormatException(format!(
+                "Invalid dimension: {}",
+                dimension
+            )));
+        }
+        match self.get_version_for_number((dimension - 17) / 4) {
+            Ok(version) => Ok(version),
+            Err(_) => Err(Error::FormatException(format!(
+                "No version for dimension: {}",
+                dimension
+            ))),
+        }
+    }
+
+    #[cfg(feature = "decode")]
+    pub fn decode_version_information(&self, version_bits: i32) -> Option<&Version> {
+        let mut best_difference = i32::MAX;
+        let mut best_version = 0;
+        for (i, target_version) in Version::VERSION_DECODE_INFO.iter().enumerate() {
+            // Do the version info bits match exactly? done.
+            if *target_version == version_bits {
+                return self.get_version_for_number(i as i32 + 7).ok();
+            }
+            // Otherwise see if this is the closest to a real version info bit string
+            // we have seen so far
+            let bits_difference = (version_bits ^ target_version).count_ones() as i32;
+            if bits_difference < best_difference {
+                best_version = i as i32 + 7;
+                best_difference = bits_difference;
+            }
+        }
+        // We can tolerate up to 3 bits of error since no two version info codewords will
+        // differ in less than 8 bits.
+        if best_difference <= 3 {
+            return self.get_version_for_number(best_version).ok();
+        }
+        // If we didn't find a close enough match, fail
+        None
+    }
 }
 
 impl Version {
+    #[cfg(feature = "decode")]
     const VERSION_DECODE_INFO: [i32; 34] = [
         0x07C94, 0x085BC, 0x09A99, 0x0A4D3, 0x0BBF6, 0x0C762, 0x0D847, 0x0E60D, 0x0F928, 0x10B78,
         0x1145D, 0x12A17, 0x13532, 0x149A6, 0x15683, 0x168C9, 0x177EC, 0x18EC4, 0x191E1, 0x1AFAB,
@@ -222,6 +590,7 @@ impl Version {
         self.version_number
     }
 
+    #[cfg(feature = "encode")]
     pub fn get_alignment_pattern_centers(&self) -> &Vec<i32> {
         &self.alignment_pattern_centers
     }
@@ -238,15 +607,44 @@ impl Version {
         &self.ec_blocks[ec_level.ordinal()]
     }
 
-    pub fn decodeVersionInformation(versionBits: i32) -> ResultError<Option<Version>> {
-        let bestDifference = i32::MAX;
-        let bestVersion = 0;
-        for i in 0..Version::VERSION_DECODE_INFO.len() {
-            let targetVersion = Version::VERSION_DECODE_INFO[i];
-            // Do the version info bits match exactly? done.
-            if targetVersion == versionBits {}
+    /**
+     * See ISO 18004:2006 Annex E
+     */
+    pub fn build_function_pattern(&self) -> ResultError<BitMatrix> {
+        let dimension = self.get_dimension_for_version();
+        let mut bit_matrix = BitMatrix::new1(dimension)?;
+
+        // Top left finder pattern + separator + format
+        bit_matrix.set_region(0, 0, 9, 9);
+        // Top right finder pattern + separator + format
+        bit_matrix.set_region(dimension - 8, 0, 8, 9);
+        // Bottom left finder pattern + separator + format
+        bit_matrix.set_region(0, dimension - 8, 9, 8);
+
+        // Alignment patterns
+        let max = self.alignment_pattern_centers.len();
+        for x in 0..max {
+            let i = self.alignment_pattern_centers[x] - 2;
+            for y in 0..max {
+                if (x != 0 || (y != 0 && y != max - 1)) && (x != max - 1 || y != 0) {
+                    bit_matrix.set_region(self.alignment_pattern_centers[y] - 2, i, 5, 5);
+                }
+                // else no o alignment patterns near the three finder patterns
+            }
+        }
+
+        // Vertical timing pattern
+        bit_matrix.set_region(6, 9, 1, dimension - 17);
+        // Horizontal timing pattern
+        bit_matrix.set_region(9, 6, dimension - 17, 1);
+
+        if self.version_number > 6 {
+            // Version info, top right
+            bit_matrix.set_region(dimension - 11, 0, 3, 6);
+            // Version info, bottom left
+            bit_matrix.set_region(0, dimension - 11, 6, 3);
         }
 
-        todo!()
+        Ok(bit_matrix)
     }
 }
@@ -1,3 +1,5 @@
+use alloc::vec::Vec;
+
 pub struct BlockPair {
     data_bytes: Vec<i32>,
     error_correction_bytes: Vec<i32>,
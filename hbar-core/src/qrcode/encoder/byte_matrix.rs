@@ -5,7 +5,9 @@ pub struct ByteMatrix {
     height: i32,
 }
 
-use std::fmt;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
 impl fmt::Display for ByteMatrix {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for y in 0..self.height {
@@ -1,20 +1,34 @@
 use crate::common::BitArray;
-use crate::common::Charset;
+use crate::common::{CharacterSetECI, Charset};
 use crate::common::{GenericGFEnum, ReedSolomonEncoder};
+#[cfg(feature = "std")]
 use crate::encode_hint_type::EncodeHintType;
+use crate::encode_options::EncodeOptions;
+use crate::gs1::ElementStrings;
 use crate::qrcode::decoder::{ErrorCorrectionLevel, Mode, Version, Versions};
 use crate::qrcode::encoder::{BlockPair, ByteMatrix, MaskUtil, MatrixUtil, QRCode};
 use crate::Error;
 use crate::WriterException;
 
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::convert::TryFrom;
+#[cfg(feature = "std")]
 use std::collections::HashMap;
-use std::rc::Rc;
-use std::str::FromStr;
 
 pub struct Encoder {
     versions: Versions,
 }
 
+impl Default for Encoder {
+    fn default() -> Self {
+        Encoder::new()
+    }
+}
+
 impl Encoder {
     // The original table is defined in the table 5 of JISX0510:2004 (p.19).
     const ALPHANUMERIC_TABLE: [i32; 96] = [
@@ -39,29 +53,51 @@ impl Encoder {
         content: &String,
         ec_level: ErrorCorrectionLevel,
     ) -> Result<QRCode, WriterException> {
-        let hints: HashMap<EncodeHintType, &String> = HashMap::new();
-        self.encode_hints(content, ec_level, hints)
+        self.encode_options(content, ec_level, &EncodeOptions::new())
     }
 
+    #[cfg(feature = "std")]
     pub fn encode_hints(
         &self,
         content: &String,
         ec_level: ErrorCorrectionLevel,
         hints: HashMap<EncodeHintType, &String>,
     ) -> Result<QRCode, WriterException> {
-        // Determine what character encoding has been specified by the caller, if any
-        let mut encoding = Encoder::DEFAULT_BYTE_MODE_ENCODING;
-        let has_encoding_hint = hints.contains_key(&EncodeHintType::CharacterSet);
-        if has_encoding_hint {
-            let tmp_charset = hints.get(&EncodeHintType::CharacterSet).unwrap();
-            let tmp_encoding = Charset::from_str(tmp_charset).unwrap();
-            encoding = tmp_encoding.clone();
+        let options = EncodeOptions::try_from(&hints).map_err(|err| WriterException {
+            reason: format!("{:?}", err),
+        })?;
+        self.encode_options(content, ec_level, &options)
+    }
+
+    pub fn encode_options(
+        &self,
+        content: &String,
+        ec_level: ErrorCorrectionLevel,
+        options: &EncodeOptions,
+    ) -> Result<QRCode, WriterException> {
+        // GS1 data must be element strings which the AI table accepts
+        if options.is_gs1_format() {
+            ElementStrings::parse(content).map_err(|err| WriterException {
+                reason: format!("Invalid GS1 data: {:?}", err),
+            })?;
         }
 
+        // Determine what character encoding has been specified by the caller, if any
+        let has_encoding_hint = options.get_character_set().is_some();
+        let encoding = options
+            .get_character_set()
+            .cloned()
+            .unwrap_or(Encoder::DEFAULT_BYTE_MODE_ENCODING);
+
         // Pick an encoding mode appropriate for the content. Note that this will not attempt to use
         // multiple modes / segments even if that were more efficient. Twould be nice.
-        let mode = self.choose_mode(content, &encoding).unwrap();
-        println!("mode: {:?}", mode);
+        // Without an explicit character set, content made only of Shift_JIS double-byte
+        // characters goes into the more compact Kanji mode.
+        let mode = if !has_encoding_hint && self.is_only_double_byte_kanji(content) {
+            Mode::get_kanji()
+        } else {
+            self.choose_mode(content, &encoding)?
+        };
 
         // This will store the header information, like mode and
         // length, as well as "header" segments like an ECI segment.
@@ -69,13 +105,15 @@ impl Encoder {
 
         // Append ECI segment if applicable
         if mode == Mode::get_byte() && has_encoding_hint {
-            todo!("Append ECI segment if applicable")
+            if let Some(eci) = CharacterSetECI::get_value(&encoding) {
+                self.append_eci(eci, &mut header_bits);
+            }
         }
 
         // Append the FNC1 mode header for GS1 formatted data if applicable
-        let has_gs1_format_hint = hints.contains_key(&EncodeHintType::Gs1Format);
-        if has_gs1_format_hint {
-            todo!("Append the FNC1 mode header for GS1 formatted data if applicable")
+        if options.is_gs1_format() {
+            // GS1 formatted codes are prefixed with a FNC1 in first position mode header
+            self.append_mode_info(&Mode::get_fnc1_first_position(), &mut header_bits);
         }
 
         // (With ECI in place,) Write the mode marker
@@ -84,50 +122,45 @@ impl Encoder {
         // Collect data within the main segment, separately, to count its size if needed. Don't add it to
         // main payload yet.
         let mut data_bits = BitArray::new();
-        self.append_bytes(content, &mode, &mut data_bits, &encoding);
-
-        println!("header_bits: {}", header_bits);
-        println!("data_bits: {}", data_bits);
+        self.append_bytes(content, &mode, &mut data_bits, &encoding)?;
 
         let version;
-        if hints.contains_key(&EncodeHintType::QRVersion) {
-            todo!("QRVersion")
-        } else {
+        if let Some(version_number) = options.get_qr_version() {
             version = self
-                .recommend_version(&ec_level, &mode, &mut header_bits, &mut data_bits)
-                .unwrap();
+                .versions
+                .get_version_for_number(version_number)
+                .map_err(|err| WriterException {
+                    reason: format!("{:?}", err),
+                })?;
+            let bits_needed =
+                self.calculate_bits_needed(&mode, &mut header_bits, &mut data_bits, version);
+            if !self.will_fit(bits_needed, version, &ec_level) {
+                return Err(WriterException {
+                    reason: String::from("Data too big for requested version"),
+                });
+            }
+        } else {
+            version = self.recommend_version(&ec_level, &mode, &mut header_bits, &mut data_bits)?;
         }
-        println!("version: {:?}", version);
 
         let mut header_and_data_bits = BitArray::new();
         header_and_data_bits.append_bit_array(&mut header_bits);
-        println!("header_bits header_and_data_bits: {}", header_and_data_bits);
         // Find "length" of main segment and write it
         let num_letters;
         if mode == Mode::get_byte() {
             num_letters = data_bits.get_size_in_bytes()
         } else {
-            num_letters = content.len() as i32
+            num_letters = content.chars().count() as i32
         }
         self.append_length_info(num_letters, version, &mode, &mut header_and_data_bits);
-        println!(
-            "append_length_info header_and_data_bits: {}",
-            header_and_data_bits
-        );
         // Put data together into the overall payload
         header_and_data_bits.append_bit_array(&mut data_bits);
-        println!("data_bits header_and_data_bits: {}", header_and_data_bits);
 
         let ec_blocks = version.get_ec_blocks_for_level(&ec_level);
         let num_data_bytes = version.get_total_codewords() - ec_blocks.get_total_ec_codewords();
         // Terminate the bits properly.
         self.terminate_bits(num_data_bytes, &mut header_and_data_bits);
 
-        println!(
-            "terminate_bits header_and_data_bits: {}",
-            header_and_data_bits
-        );
-
         // Interleave data bits with error correction code.
         let mut final_bits = self
             .interleave_with_ec_bytes(
@@ -137,7 +170,6 @@ impl Encoder {
                 ec_blocks.get_num_blocks(),
             )
             .unwrap();
-        println!("final_bits: {}", final_bits);
 
         //  Choose the mask pattern and set to "qrCode".
         let dimension = version.get_dimension_for_version();
@@ -145,9 +177,7 @@ impl Encoder {
 
         // Enable manual selection of the pattern to be used via hint
         let mut mask_pattern = -1;
-        if hints.contains_key(&EncodeHintType::QRMaskPattern) {
-            let hint_mask_pattern = hints.get(&EncodeHintType::QRMaskPattern).unwrap();
-            let hint_mask_pattern = hint_mask_pattern.parse::<i32>().unwrap();
+        if let Some(hint_mask_pattern) = options.get_qr_mask_pattern() {
             if QRCode::is_valid_mask_pattern(hint_mask_pattern) {
                 mask_pattern = hint_mask_pattern
             }
@@ -167,8 +197,6 @@ impl Encoder {
             mask_pattern,
             &mut matrix,
         );
-        println!("mask_pattern: {}", mask_pattern);
-        println!("ec_level: {:?}", ec_level);
         // println!("matrix: {}", matrix);
         let qr_code = QRCode::new(mode, ec_level, version.clone(), mask_pattern, matrix);
 
@@ -180,7 +208,8 @@ impl Encoder {
         content: &String,
         encoding: &Charset,
     ) -> Result<Mode, WriterException> {
-        if self.is_only_double_byte_kanji(content) {
+        if *encoding == Charset::ShiftJIS && self.is_only_double_byte_kanji(content) {
+            // Choose Kanji mode if all input are double-byte characters
             return Ok(Mode::get_kanji());
         }
         let mut has_numeric = false;
@@ -205,19 +234,20 @@ impl Encoder {
     }
 
     pub fn is_only_double_byte_kanji(&self, content: &String) -> bool {
-        let encoding = Charset::UTF8;
-        let bytes = encoding.encode(content).unwrap();
+        let bytes = match Charset::ShiftJIS.encode(content) {
+            Ok(bytes) => bytes,
+            Err(_) => return false,
+        };
         let length = bytes.len();
-        if length % 2 != 0 {
+        if length == 0 || length % 2 != 0 {
             return false;
         }
-        for b in bytes.iter() {
-            let b1 = b & 0xFF;
-            if (b1 < 0x81 || b1 > 0x9F) && (b1 < 0xE0 || b1 > 0xEB) {
+        for b1 in bytes.iter().step_by(2) {
+            if !(0x81..=0x9F).contains(b1) && !(0xE0..=0xEB).contains(b1) {
                 return false;
             }
         }
-        return true;
+        true
     }
 
     fn _get_alphanumeric_code(&self, code: i32) -> i32 {
@@ -231,16 +261,25 @@ impl Encoder {
         bits.append_bits(mode.get_bits() as i32, 4);
     }
 
-    fn append_bytes(&self, content: &String, mode: &Mode, bits: &mut BitArray, encoding: &Charset) {
+    fn append_bytes(
+        &self,
+        content: &String,
+        mode: &Mode,
+        bits: &mut BitArray,
+        encoding: &Charset,
+    ) -> Result<(), WriterException> {
         match mode {
             Mode::Numeric(_, _) => self.append_numeric_bytes(content, bits),
             Mode::Alphanumeric(_, _) => self.append_alphanumeric_bytes(content, bits).unwrap(),
-            Mode::Byte(_, _) => self.append_8bit_bytes(content, bits, encoding),
-            Mode::Kanji(_, _) => self.append_kanji_bytes(content, bits),
+            Mode::Byte(_, _) => self.append_8bit_bytes(content, bits, encoding)?,
+            Mode::Kanji(_, _) => self.append_kanji_bytes(content, bits)?,
             other => {
-                panic!("Invalid mode: {:?}", other);
+                return Err(WriterException {
+                    reason: format!("Invalid mode: {:?}", other),
+                })
             }
         }
+        Ok(())
     }
 
     fn append_numeric_bytes(&self, content: &String, bits: &mut BitArray) {
@@ -306,21 +345,54 @@ impl Encoder {
         Ok(())
     }
 
-    fn append_8bit_bytes(&self, content: &String, bits: &mut BitArray, encoding: &Charset) {
-        let bytes = encoding.encode(content).unwrap();
+    fn append_8bit_bytes(
+        &self,
+        content: &String,
+        bits: &mut BitArray,
+        encoding: &Charset,
+    ) -> Result<(), WriterException> {
+        let bytes = encoding.encode(content)?;
         for byte in bytes {
             bits.append_bits(byte as i32, 8)
         }
+        Ok(())
     }
 
-    fn append_kanji_bytes(&self, content: &String, bits: &mut BitArray) {
-        let bytes = Charset::UTF8.encode(content).unwrap();
-        println!(
-            "append_kanji_bytes bytes: {}, content: {}",
-            bytes.len(),
-            content.len()
-        );
-        todo!()
+    fn append_kanji_bytes(
+        &self,
+        content: &String,
+        bits: &mut BitArray,
+    ) -> Result<(), WriterException> {
+        let bytes = Charset::ShiftJIS.encode(content)?;
+        if bytes.len() % 2 != 0 {
+            return Err(WriterException {
+                reason: String::from("Kanji byte size not even"),
+            });
+        }
+        for pair in bytes.chunks(2) {
+            let code = ((pair[0] as i32) << 8) | pair[1] as i32;
+            let subtracted = if (0x8140..=0x9ffc).contains(&code) {
+                code - 0x8140
+            } else if (0xe040..=0xebbf).contains(&code) {
+                code - 0xc140
+            } else {
+                -1
+            };
+            if subtracted == -1 {
+                return Err(WriterException {
+                    reason: String::from("Invalid byte sequence"),
+                });
+            }
+            let encoded = ((subtracted >> 8) * 0xc0) + (subtracted & 0xff);
+            bits.append_bits(encoded, 13);
+        }
+        Ok(())
+    }
+
+    fn append_eci(&self, eci: i32, bits: &mut BitArray) {
+        bits.append_bits(Mode::get_eci().get_bits(), 4);
+        // This is correct for values up to 127, which is all we need now.
+        bits.append_bits(eci, 8);
     }
 
     /**
@@ -358,9 +430,7 @@ impl Encoder {
         let version = self.versions.get_version_for_number(1).unwrap();
         let provisional_bits_needed =
             self.calculate_bits_needed(mode, header_bits, data_bits, version);
-        let provisional_version = self
-            .choose_version(provisional_bits_needed, ec_level)
-            .unwrap();
+        let provisional_version = self.choose_version(provisional_bits_needed, ec_level)?;
 
         // Use that guess to calculate the right version. I am still not sure this works in 100% of cases.
         let bits_needed =
@@ -383,7 +453,7 @@ impl Encoder {
         num_input_bits: i32,
         ec_level: &ErrorCorrectionLevel,
     ) -> Result<&Version, WriterException> {
-        for version_num in 1..40 {
+        for version_num in 1..=40 {
             let version = self.versions.get_version_for_number(version_num).unwrap();
             if self.will_fit(num_input_bits, version, ec_level) {
                 return Ok(version);
@@ -446,7 +516,6 @@ impl Encoder {
             }
             bits.append_bit(false)
         }
-        println!("terminate_bits11 bits: {}", bits);
         // Append termination bits. See 8.4.8 of JISX0510:2004 (p.24) for details.
         // If the last byte isn't 8-bit aligned, we'll add padding bits.
         let num_bits_in_last_byte = bits.get_size() & 0x07;
@@ -455,7 +524,6 @@ impl Encoder {
                 bits.append_bit(false)
             }
         }
-        println!("terminate_bits22 bits: {}", bits);
         // If we have more space, we'll fill the space with padding patterns defined in 8.4.9 (p.24).
         let num_padding_bytes = num_data_bytes - bits.get_size_in_bytes();
         for i in 0..num_padding_bytes {
@@ -628,21 +696,11 @@ impl Encoder {
             to_encode[i] = data_bytes[i] & 0xFF;
         }
 
-        println!(
-            "generate_ec_bytes to_encode: {:?}, size: {}",
-            to_encode,
-            to_encode.len()
-        );
-        ReedSolomonEncoder::new(Rc::new(GenericGFEnum::QrCodeField256.get()))
+        ReedSolomonEncoder::for_field(GenericGFEnum::QrCodeField256)
             .unwrap()
             .encode(&mut to_encode, num_ec_bytes_in_block)
             .unwrap();
 
-        println!(
-            "generate_ec_bytes encode to_encode: {:?}, size: {}",
-            to_encode,
-            to_encode.len()
-        );
         let mut ec_bytes: Vec<i32> = vec![0; num_ec_bytes_in_block as usize];
         for i in 0..num_ec_bytes_in_block {
             ec_bytes[i as usize] = to_encode[num_data_bytes + i as usize]
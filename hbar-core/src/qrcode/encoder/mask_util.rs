@@ -1,5 +1,7 @@
 use crate::qrcode::encoder::ByteMatrix;
 
+use alloc::vec::Vec;
+
 pub struct MaskUtil;
 
 impl MaskUtil {
@@ -1,8 +1,14 @@
 pub mod decoder;
+#[cfg(all(feature = "qrcode", feature = "encode"))]
 pub mod encoder;
+#[cfg(all(feature = "qrcode", feature = "decode"))]
 mod qrcode_reader;
+#[cfg(all(feature = "qrcode", feature = "encode"))]
 pub mod qrcode_writer;
 
+#[cfg(all(feature = "qrcode", feature = "decode"))]
 pub use decoder::Decoder;
+#[cfg(all(feature = "qrcode", feature = "decode"))]
 pub use qrcode_reader::QRCodeReader;
+#[cfg(all(feature = "qrcode", feature = "encode"))]
 pub use qrcode_writer::QRCodeWriter;
@@ -1,9 +1,11 @@
 use crate::common::BitMatrix;
 use crate::common::DecoderResult;
 use crate::qrcode::Decoder;
+use crate::ErrorCorrectionLevel;
 use crate::Reader;
 use crate::ResultPoint;
 use crate::Results;
+use crate::{BarcodeFormat, ResultMetadataType, ResultMetadataValue};
 use crate::{Binarizer, BinaryBitmap, LuminanceSource};
 use crate::{DecodeHintType, DecodeHintValue};
 use crate::{Error, ResultError};
@@ -30,8 +32,77 @@ impl QRCodeReader {
      * which contains only an unrotated, unskewed, image of a code, with some white border
      * around it. This is a specialized method that works exceptionally fast in this special
      * case.
+     *
+     * Next to the bits it returns the modules whose color is in doubt: those where pixels a
+     * quarter module away from the sampled centre disagree with it, the module size, and the
+     * centres of the finder patterns.
+     */
+    fn extractPureBits(
+        image: &BitMatrix,
+    ) -> ResultError<(BitMatrix, BitMatrix, f32, Vec<ResultPoint>)> {
+        let (left, top, module_size, dimension) = QRCodeReader::pure_geometry(image)?;
+
+        // Now just read off the bits
+        let mut bits = BitMatrix::new2(dimension, dimension)?;
+        let mut unreliable = BitMatrix::new2(dimension, dimension)?;
+        let quarter = (module_size / 4.0f32) as i32;
+        let clamp = |value: i32, max: i32| value.max(0).min(max - 1) as u32;
+        for y in 0..dimension {
+            let iOffset = top + (y as f32 * module_size) as i32;
+            for x in 0..dimension {
+                let j_offset = left + (x as f32 * module_size) as i32;
+                let black = image.get(j_offset as u32, iOffset as u32);
+                if black {
+                    bits.set(x as u32, y as u32);
+                }
+                let agrees = |dx: i32, dy: i32| {
+                    image.get(
+                        clamp(j_offset + dx, image.getWidth()),
+                        clamp(iOffset + dy, image.getHeight()),
+                    ) == black
+                };
+                if !(agrees(-quarter, 0)
+                    && agrees(quarter, 0)
+                    && agrees(0, -quarter)
+                    && agrees(0, quarter))
+                {
+                    unreliable.set(x as u32, y as u32);
+                }
+            }
+        }
+
+        Ok((
+            bits,
+            unreliable,
+            module_size,
+            QRCodeReader::pure_finder_points(left, top, module_size, dimension),
+        ))
+    }
+
+    /**
+     * @return the centres of the bottom left, top left and top right finder patterns of a
+     *  "pure" symbol, in that order, given where {@link #pure_geometry} samples its modules
+     */
+    fn pure_finder_points(
+        left: i32,
+        top: i32,
+        module_size: f32,
+        dimension: i32,
+    ) -> Vec<ResultPoint> {
+        // A finder pattern is 7 modules across, so its centre is that of its fourth module
+        let near = 3.0f32 * module_size;
+        let far = (dimension - 4) as f32 * module_size;
+        let point = |x: f32, y: f32| ResultPoint::new(left as f32 + x, top as f32 + y);
+        vec![point(near, far), point(near, near), point(far, near)]
+    }
+
+    /**
+     * Finds where the modules of a "pure" symbol, see {@link #extractPureBits}, are sampled.
+     *
+     * @return the image position of the centre of the top left module, the module size and the
+     *  number of modules on a side
      */
-    fn extractPureBits(image: &BitMatrix) -> ResultError<BitMatrix> {
+    pub(crate) fn pure_geometry(image: &BitMatrix) -> ResultError<(i32, i32, f32, i32)> {
         let leftTopBlack = match image.getTopLeftOnBit() {
             Some(v) => v,
             None => {
@@ -114,21 +185,47 @@ impl QRCodeReader {
             top -= nudgedTooFarDown;
         }
 
-        // Now just read off the bits
-        let mut bits = BitMatrix::new2(matrixWidth, matrixHeight)?;
-        for y in 0..matrixHeight {
-            let iOffset = top + (y as f32 * moduleSize) as i32;
-            for x in 0..matrixWidth {
-                if image.get(
-                    (left + (x as f32 * moduleSize) as i32) as u32,
-                    iOffset as u32,
-                ) {
-                    bits.set(x as u32, y as u32);
-                }
-            }
-        }
+        Ok((left, top, moduleSize, matrixWidth))
+    }
 
-        Ok(bits)
+    fn put_quality_metadata(
+        result: &mut Results,
+        decoder_result: &DecoderResult,
+        module_size: f32,
+    ) {
+        let blocks = decoder_result.get_block_corrections();
+        let budget = blocks
+            .iter()
+            .map(|block| block.get_budget_used())
+            .fold(0.0f32, f32::max);
+        result.put_metadata(
+            ResultMetadataType::ErrorsCorrected,
+            ResultMetadataValue::INTEGER(decoder_result.get_errors_corrected()),
+        );
+        result.put_metadata(
+            ResultMetadataType::ErasuresCorrected,
+            ResultMetadataValue::INTEGER(decoder_result.get_erasures()),
+        );
+        result.put_metadata(
+            ResultMetadataType::ErrorCorrectionBlocks,
+            ResultMetadataValue::BLOCKS(blocks.to_vec()),
+        );
+        result.put_metadata(
+            ResultMetadataType::ErrorCorrectionBudget,
+            ResultMetadataValue::FLOAT(budget),
+        );
+        result.put_metadata(
+            ResultMetadataType::SymbolVersion,
+            ResultMetadataValue::INTEGER(decoder_result.get_symbol_version()),
+        );
+        result.put_metadata(
+            ResultMetadataType::DataMask,
+            ResultMetadataValue::INTEGER(decoder_result.get_data_mask()),
+        );
+        result.put_metadata(
+            ResultMetadataType::ModuleSize,
+            ResultMetadataValue::FLOAT(module_size),
+        );
     }
 
     fn moduleSize(leftTopBlack: &Vec<i32>, image: &BitMatrix) -> ResultError<f32> {
@@ -182,21 +279,72 @@ where
         image: &BinaryBitmap<B, S>,
         hints: &HashMap<DecodeHintType, DecodeHintValue>,
     ) -> ResultError<Results> {
-        println!("QRCodeReader decode_hints hints: {:?}", hints);
-        let decoderResult: DecoderResult;
-        let mut points: Vec<ResultPoint>;
-        if hints.contains_key(&DecodeHintType::PureBarcode) {
-            println!("QRCodeReader decode_hints PureBarcode");
-            let bits = QRCodeReader::extractPureBits(&image.getBlackMatrix()?);
-            // decoderResult = self.decoder.decode(&bits)
-        } else {
-            todo!()
+        // Only the pure barcode path is available until a finder pattern detector is ported,
+        // so every image is treated as a pure, unrotated symbol.
+        let (bits, unreliable, module_size, points) =
+            QRCodeReader::extractPureBits(image.getBlackMatrix()?)?;
+        if let Some(callback) = hints
+            .get(&DecodeHintType::NeedResultPointCallback)
+            .and_then(DecodeHintValue::try_get_result_point_callback)
+        {
+            for point in &points {
+                callback.found_possible_result_point(point);
+            }
         }
+        let decoder_result: DecoderResult =
+            self.decoder
+                .decode_with_erasures(&bits, &unreliable, hints)?;
 
-        todo!()
+        let mut result = Results::new(
+            decoder_result.getText().clone(),
+            decoder_result.getRawBytes().clone(),
+            Some(points),
+            BarcodeFormat::QRCode,
+        );
+        let byte_segments = decoder_result.getByteSegments();
+        if !byte_segments.is_empty() {
+            result.put_metadata(
+                ResultMetadataType::ByteSegments,
+                ResultMetadataValue::BYTES(byte_segments.clone()),
+            );
+        }
+        result.put_metadata(
+            ResultMetadataType::ErrorCorrectionLevel,
+            ResultMetadataValue::ECLEVEL(ErrorCorrectionLevel::from(decoder_result.getECLevel())?),
+        );
+        if decoder_result.hasStructuredAppend() {
+            result.put_metadata(
+                ResultMetadataType::StructuredAppendSequence,
+                ResultMetadataValue::INTEGER(decoder_result.getStructuredAppendSequenceNumber()),
+            );
+            result.put_metadata(
+                ResultMetadataType::StructuredAppendParity,
+                ResultMetadataValue::INTEGER(decoder_result.getStructuredAppendParity()),
+            );
+        }
+        let symbology_modifier = decoder_result.getSymbologyModifier();
+        result.put_metadata(
+            ResultMetadataType::SymbologyIdentifier,
+            ResultMetadataValue::STRING(format!("]Q{}", symbology_modifier)),
+        );
+        // Modifiers 3 and 4 mean FNC1 in the first position, without and with ECI
+        if symbology_modifier == 3 || symbology_modifier == 4 {
+            let element_strings = decoder_result
+                .getText()
+                .split('\u{1d}')
+                .filter(|element_string| !element_string.is_empty())
+                .map(String::from)
+                .collect();
+            result.put_metadata(
+                ResultMetadataType::Gs1ElementStrings,
+                ResultMetadataValue::GS1(element_strings),
+            );
+        }
+        QRCodeReader::put_quality_metadata(&mut result, &decoder_result, module_size);
+        Ok(result)
     }
 
     fn reset(&self) {
-        todo!()
+        // do nothing
     }
 }
@@ -1,18 +1,24 @@
 use crate::barcode_format::BarcodeFormat;
 use crate::common::BitMatrix;
-use crate::encode_hint_type::EncodeHintType;
+use crate::encode_options::{EncodeOptions, ErrorCorrection};
 use crate::qrcode::decoder::ErrorCorrectionLevel;
 use crate::qrcode::encoder::{Encoder, QRCode};
 use crate::writer::Writer;
 use crate::{Error, ResultError};
 
-use std::collections::HashMap;
-use std::str::FromStr;
+use alloc::format;
+use alloc::string::String;
 
 pub struct QRCodeWriter {
     encoder: Encoder,
 }
 
+impl Default for QRCodeWriter {
+    fn default() -> Self {
+        QRCodeWriter::new()
+    }
+}
+
 impl QRCodeWriter {
     const QUIET_ZONE_SIZE: i32 = 4;
     pub fn new() -> Self {
@@ -23,24 +29,13 @@ impl QRCodeWriter {
 }
 
 impl Writer for QRCodeWriter {
-    fn encode(
+    fn encode_options(
         &self,
         contents: &String,
         format: &BarcodeFormat,
         width: i32,
         height: i32,
-    ) -> ResultError<BitMatrix> {
-        let hints: HashMap<EncodeHintType, &String> = HashMap::new();
-        self.encode_hints(contents, format, width, height, hints)
-    }
-
-    fn encode_hints(
-        &self,
-        contents: &String,
-        format: &BarcodeFormat,
-        width: i32,
-        height: i32,
-        hints: HashMap<EncodeHintType, &String>,
+        options: &EncodeOptions,
     ) -> ResultError<BitMatrix> {
         if contents.is_empty() {
             return Err(Error::IllegalArgumentException(String::from(
@@ -59,29 +54,27 @@ impl Writer for QRCodeWriter {
                 width, height
             )));
         }
+        options.validate()?;
 
-        let error_correction_level;
-        if hints.contains_key(&EncodeHintType::ErrorCorrection) {
-            error_correction_level =
-                ErrorCorrectionLevel::from(hints.get(&EncodeHintType::ErrorCorrection).unwrap())
-                    .unwrap();
-        } else {
-            error_correction_level = ErrorCorrectionLevel::L;
-        }
-
-        let quiet_zone;
-        if hints.contains_key(&EncodeHintType::MARGIN) {
-            let srt_num = hints.get(&EncodeHintType::MARGIN).unwrap();
+        let error_correction_level = match options.get_error_correction() {
+            Some(ErrorCorrection::Level(ec_level)) => ec_level.clone(),
+            Some(ErrorCorrection::Value(value)) => {
+                return Err(Error::IllegalArgumentException(format!(
+                    "QR Code error correction must be one of L, M, Q, H, but got: {}",
+                    value
+                )))
+            }
+            None => ErrorCorrectionLevel::L,
+        };
 
-            quiet_zone = srt_num.parse::<i32>().unwrap();
-        } else {
-            quiet_zone = QRCodeWriter::QUIET_ZONE_SIZE;
-        }
+        let quiet_zone = options
+            .get_margin()
+            .unwrap_or(QRCodeWriter::QUIET_ZONE_SIZE);
 
         let code = self
             .encoder
-            .encode_hints(contents, error_correction_level, hints)
-            .unwrap();
+            .encode_options(contents, error_correction_level, options)
+            .map_err(|err| Error::WriterException(err.reason))?;
         self.render_result(code, width, height, quiet_zone)
     }
 }
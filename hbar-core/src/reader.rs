@@ -1,3 +1,4 @@
+use crate::DecodeOptions;
 use crate::ResultError;
 use crate::Results;
 use crate::{Binarizer, BinaryBitmap, LuminanceSource};
@@ -14,8 +15,11 @@ use std::collections::HashMap;
  * See {@link MultiFormatReader}, which attempts to determine what barcode
  * format is present within the image as well, and then decodes it accordingly.
  *
+ * Readers keep no state between calls beyond their configuration, so one instance can be
+ * shared between threads and used to decode several images at once.
+ *
  */
-pub trait Reader<B, S>
+pub trait Reader<B, S>: Send + Sync
 where
     B: Binarizer<S>,
     S: LuminanceSource,
@@ -51,6 +55,24 @@ where
         hints: &HashMap<DecodeHintType, DecodeHintValue>,
     ) -> ResultError<Results>;
 
+    /**
+     * Locates and decodes a barcode in some format within an image, guided by typed options.
+     *
+     * @param image image of barcode to decode
+     * @param options {@link DecodeOptions} which may help the implementation decode
+     * @return String which the barcode encodes
+     * @throws NotFoundException if no potential barcode is found
+     * @throws ChecksumException if a potential barcode is found but does not pass its checksum
+     * @throws FormatException if a potential barcode is found but format is invalid
+     */
+    fn decode_options(
+        &self,
+        image: &BinaryBitmap<B, S>,
+        options: &DecodeOptions,
+    ) -> ResultError<Results> {
+        self.decode_hints(image, &options.to_hints())
+    }
+
     /**
      * Resets any internal state the implementation has after a decode, to prepare it
      * for reuse.
@@ -1,5 +1,6 @@
 use crate::common::SystemTimes;
 use crate::BarcodeFormat;
+use crate::ErrorCorrectionLevel;
 use crate::ResultPoint;
 use crate::{ResultMetadataType, ResultMetadataValue};
 
@@ -114,6 +115,66 @@ impl Results {
         &self.result_metadata
     }
 
+    /**
+     * @return the metadata of the given type, if the reader reported any
+     */
+    pub fn get_metadata(&self, t: &ResultMetadataType) -> Option<&ResultMetadataValue> {
+        self.result_metadata.get(t)
+    }
+
+    /**
+     * @return degrees the barcode was found rotated clockwise from upright, if it was rotated
+     */
+    pub fn get_orientation(&self) -> Option<i32> {
+        self.get_metadata(&ResultMetadataType::ORIENTATION)
+            .and_then(ResultMetadataValue::get_integer)
+    }
+
+    /**
+     * @return the raw bytes of the byte segments of the barcode, in order
+     */
+    pub fn get_byte_segments(&self) -> Option<&[Vec<u8>]> {
+        self.get_metadata(&ResultMetadataType::ByteSegments)
+            .and_then(ResultMetadataValue::get_bytes)
+    }
+
+    /**
+     * @return the error correction level of a QR Code
+     */
+    pub fn get_error_correction_level(&self) -> Option<&ErrorCorrectionLevel> {
+        self.get_metadata(&ResultMetadataType::ErrorCorrectionLevel)
+            .and_then(ResultMetadataValue::get_ec_level)
+    }
+
+    /**
+     * @return the sequence number of the barcode within a structured append, if it is part of one
+     */
+    pub fn get_structured_append_sequence(&self) -> Option<i32> {
+        self.get_metadata(&ResultMetadataType::StructuredAppendSequence)
+            .and_then(ResultMetadataValue::get_integer)
+    }
+
+    /**
+     * @return the parity of the structured append the barcode is part of, if any
+     */
+    pub fn get_structured_append_parity(&self) -> Option<i32> {
+        self.get_metadata(&ResultMetadataType::StructuredAppendParity)
+            .and_then(ResultMetadataValue::get_integer)
+    }
+
+    pub fn get_symbology_identifier(&self) -> Option<&str> {
+        self.get_metadata(&ResultMetadataType::SymbologyIdentifier)
+            .and_then(ResultMetadataValue::get_string)
+    }
+
+    /**
+     * @return the element strings of GS1 data, see {@link ResultMetadataType#Gs1ElementStrings}
+     */
+    pub fn get_gs1_element_strings(&self) -> Option<&[String]> {
+        self.get_metadata(&ResultMetadataType::Gs1ElementStrings)
+            .and_then(ResultMetadataValue::get_gs1)
+    }
+
     pub fn put_metadata(&mut self, t: ResultMetadataType, value: ResultMetadataValue) {
         self.result_metadata.insert(t, value);
     }
@@ -140,6 +201,18 @@ impl Results {
         }
     }
 
+    /**
+     * Replaces every result point with its image under {@code transform}, for example to map
+     * points found in a rotated or scaled copy of an image back to the image itself.
+     */
+    pub fn transform_result_points<F: Fn(&ResultPoint) -> ResultPoint>(&mut self, transform: F) {
+        if let Some(points) = &mut self.result_points {
+            for point in points.iter_mut() {
+                *point = transform(point);
+            }
+        }
+    }
+
     pub fn get_timestamp(&self) -> u64 {
         self.timestamp
     }
@@ -1,3 +1,5 @@
+use crate::ErrorCorrectionLevel;
+
 /**
  * Represents some type of metadata about the result of the decoding that the decoder
  * wishes to communicate back to the caller.
@@ -31,7 +33,7 @@ pub enum ResultMetadataType {
 
     /**
      * Error correction level used, if applicable. The value type depends on the
-     * format, but is typically a String.
+     * format: an {@link ErrorCorrectionLevel} for QR Codes, typically a String otherwise.
      */
     ErrorCorrectionLevel,
 
@@ -79,8 +81,166 @@ pub enum ResultMetadataType {
      * Note: According to the GS1 specification the identifier may have to replace a leading FNC1/GS character when prepending to the barcode content.
      */
     SymbologyIdentifier,
+
+    /**
+     * Number of errors the error correction found and corrected, as an {@link Integer}.
+     */
+    ErrorsCorrected,
+
+    /**
+     * Number of codewords the reader marked as unreadable and the error correction filled in,
+     * as an {@link Integer}.
+     */
+    ErasuresCorrected,
+
+    /**
+     * What the error correction did in each block of the symbol, in order, as
+     * {@link BlockCorrections}.
+     */
+    ErrorCorrectionBlocks,
+
+    /**
+     * The share of its error-correction capacity used by the block that needed the most, as a
+     * {@link Float} from 0 to 1. Near 1 the symbol is close to unreadable.
+     */
+    ErrorCorrectionBudget,
+
+    /**
+     * The version (size) of the symbol, as an {@link Integer}.
+     */
+    SymbolVersion,
+
+    /**
+     * The data mask pattern of the symbol, as an {@link Integer}.
+     */
+    DataMask,
+
+    /**
+     * The estimated size of one module in the image, in pixels, as a {@link Float}.
+     */
+    ModuleSize,
+
+    /**
+     * If the symbol carries GS1 data, marked by FNC1 in the first position, its element strings
+     * as split at the FNC1 separators (GS, 0x1D), in order. Predefined-length element strings
+     * need no separator, so one entry may hold several of them.
+     */
+    Gs1ElementStrings,
 }
 
+/**
+ * The value of a {@link ResultMetadataType}. Each type documents which variant it maps to.
+ */
+#[derive(Debug, Clone, PartialEq)]
 pub enum ResultMetadataValue {
     STRING(String),
+    INTEGER(i32),
+    FLOAT(f32),
+    BYTES(Vec<Vec<u8>>),
+    ECLEVEL(ErrorCorrectionLevel),
+    GS1(Vec<String>),
+    BLOCKS(Vec<BlockCorrections>),
+}
+
+impl ResultMetadataValue {
+    pub fn get_string(&self) -> Option<&str> {
+        match self {
+            ResultMetadataValue::STRING(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn get_integer(&self) -> Option<i32> {
+        match self {
+            ResultMetadataValue::INTEGER(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn get_float(&self) -> Option<f32> {
+        match self {
+            ResultMetadataValue::FLOAT(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn get_bytes(&self) -> Option<&[Vec<u8>]> {
+        match self {
+            ResultMetadataValue::BYTES(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn get_ec_level(&self) -> Option<&ErrorCorrectionLevel> {
+        match self {
+            ResultMetadataValue::ECLEVEL(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn get_gs1(&self) -> Option<&[String]> {
+        match self {
+            ResultMetadataValue::GS1(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn get_blocks(&self) -> Option<&[BlockCorrections]> {
+        match self {
+            ResultMetadataValue::BLOCKS(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/**
+ * What error correction did in one block of a symbol.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockCorrections {
+    ec_codewords: i32,
+    errors: i32,
+    erasures: i32,
+}
+
+impl BlockCorrections {
+    pub fn new(ec_codewords: i32, errors: i32, erasures: i32) -> BlockCorrections {
+        BlockCorrections {
+            ec_codewords,
+            errors,
+            erasures,
+        }
+    }
+
+    /**
+     * @return number of error-correction codewords in the block
+     */
+    pub fn get_ec_codewords(&self) -> i32 {
+        self.ec_codewords
+    }
+
+    /**
+     * @return number of errors corrected in the block
+     */
+    pub fn get_errors(&self) -> i32 {
+        self.errors
+    }
+
+    /**
+     * @return number of erasures corrected in the block
+     */
+    pub fn get_erasures(&self) -> i32 {
+        self.erasures
+    }
+
+    /**
+     * @return the share of the block's error-correction capacity used, from 0 to 1. An error
+     *  uses two error-correction codewords and an erasure one.
+     */
+    pub fn get_budget_used(&self) -> f32 {
+        if self.ec_codewords == 0 {
+            return 0.0;
+        }
+        (2 * self.errors + self.erasures) as f32 / self.ec_codewords as f32
+    }
 }
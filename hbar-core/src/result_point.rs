@@ -98,4 +98,15 @@ mod result_point_tests {
         assert_ne!(r1, r2);
         assert_eq!(r1, r1.clone());
     }
+
+    #[test]
+    fn order_best_patterns() {
+        let bottom_left = ResultPoint::new(10.0, 90.0);
+        let top_left = ResultPoint::new(10.0, 10.0);
+        let top_right = ResultPoint::new(90.0, 10.0);
+        let mut patterns = vec![&top_right, &bottom_left, &top_left];
+        ResultPoint::order_best_patterns(&mut patterns);
+        assert_eq!(patterns, vec![&bottom_left, &top_left, &top_right]);
+        assert_eq!(80.0, ResultPoint::distance(&top_left, &top_right));
+    }
 }
@@ -0,0 +1,30 @@
+use crate::ResultPoint;
+
+use std::fmt;
+
+/**
+ * Callback which is invoked when a possible result point (significant
+ * point in the barcode image such as a corner) is found.
+ *
+ * Callbacks may be shared by readers running on several threads at once.
+ *
+ * @see DecodeHintType#NeedResultPointCallback
+ */
+pub trait ResultPointCallback: Send + Sync {
+    fn found_possible_result_point(&self, point: &ResultPoint);
+}
+
+impl<F> ResultPointCallback for F
+where
+    F: Fn(&ResultPoint) + Send + Sync,
+{
+    fn found_possible_result_point(&self, point: &ResultPoint) {
+        self(point)
+    }
+}
+
+impl fmt::Debug for dyn ResultPointCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ResultPointCallback")
+    }
+}
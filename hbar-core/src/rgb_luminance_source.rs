@@ -0,0 +1,181 @@
+use crate::GrayLuminanceSource;
+use crate::InvertedLuminanceSource;
+use crate::LuminanceSource;
+use crate::{Error, ResultError};
+
+use std::sync::Arc;
+
+/**
+ * This class is used to help decode images from files which arrive as RGB data from
+ * an ARGB pixel array, or from packed RGB, RGBA or ARGB bytes.
+ *
+ * The luminance of each pixel is computed once, as (R + 2G + B) / 4, when the source is built;
+ * crops share that luminance data and rotation works on a copy of it.
+ */
+#[derive(Debug, Clone)]
+pub struct RGBLuminanceSource {
+    luminances: GrayLuminanceSource<'static>,
+}
+
+impl RGBLuminanceSource {
+    /**
+     * @param width image width
+     * @param height image height
+     * @param pixels packed 0xAARRGGBB pixels, row-major
+     */
+    pub fn new(width: u32, height: u32, pixels: &[u32]) -> ResultError<RGBLuminanceSource> {
+        RGBLuminanceSource::check(pixels.len(), 1, width, height)?;
+        let luminances = pixels
+            .iter()
+            .map(|&pixel| {
+                RGBLuminanceSource::luminance(
+                    ((pixel >> 16) & 0xFF) as u8,
+                    ((pixel >> 8) & 0xFF) as u8,
+                    (pixel & 0xFF) as u8,
+                )
+            })
+            .collect();
+        RGBLuminanceSource::from_luminances(luminances, width, height)
+    }
+
+    /**
+     * @param pixels packed R, G, B bytes, row-major
+     */
+    pub fn from_rgb(width: u32, height: u32, pixels: &[u8]) -> ResultError<RGBLuminanceSource> {
+        RGBLuminanceSource::from_bytes(width, height, pixels, 3, [0, 1, 2])
+    }
+
+    /**
+     * @param pixels packed R, G, B, A bytes, row-major
+     */
+    pub fn from_rgba(width: u32, height: u32, pixels: &[u8]) -> ResultError<RGBLuminanceSource> {
+        RGBLuminanceSource::from_bytes(width, height, pixels, 4, [0, 1, 2])
+    }
+
+    /**
+     * @param pixels packed A, R, G, B bytes, row-major
+     */
+    pub fn from_argb(width: u32, height: u32, pixels: &[u8]) -> ResultError<RGBLuminanceSource> {
+        RGBLuminanceSource::from_bytes(width, height, pixels, 4, [1, 2, 3])
+    }
+
+    fn from_bytes(
+        width: u32,
+        height: u32,
+        pixels: &[u8],
+        bytes_per_pixel: usize,
+        rgb: [usize; 3],
+    ) -> ResultError<RGBLuminanceSource> {
+        RGBLuminanceSource::check(pixels.len(), bytes_per_pixel, width, height)?;
+        let luminances = pixels
+            .chunks_exact(bytes_per_pixel)
+            .take(width as usize * height as usize)
+            .map(|pixel| RGBLuminanceSource::luminance(pixel[rgb[0]], pixel[rgb[1]], pixel[rgb[2]]))
+            .collect();
+        RGBLuminanceSource::from_luminances(luminances, width, height)
+    }
+
+    fn from_luminances(
+        luminances: Vec<u8>,
+        width: u32,
+        height: u32,
+    ) -> ResultError<RGBLuminanceSource> {
+        Ok(RGBLuminanceSource {
+            luminances: GrayLuminanceSource::from_vec(luminances, width, height)?,
+        })
+    }
+
+    fn check(len: usize, bytes_per_pixel: usize, width: u32, height: u32) -> ResultError<()> {
+        let needed = width as usize * height as usize * bytes_per_pixel;
+        if len < needed {
+            return Err(Error::IllegalArgumentException(format!(
+                "Buffer of {} entries is too small for a {}x{} image",
+                len, width, height
+            )));
+        }
+        Ok(())
+    }
+
+    // Calculate green-favouring average cheaply
+    fn luminance(r: u8, g: u8, b: u8) -> u8 {
+        ((r as u32 + 2 * g as u32 + b as u32) / 4) as u8
+    }
+}
+
+impl LuminanceSource for RGBLuminanceSource {
+    fn get_row(&self, y: i32, row: &Vec<u8>) -> ResultError<Vec<u8>> {
+        self.luminances.get_row(y, row)
+    }
+
+    fn get_matrix(&self) -> ResultError<Vec<u8>> {
+        self.luminances.get_matrix()
+    }
+
+    fn get_width(&self) -> u32 {
+        self.luminances.get_width()
+    }
+
+    fn get_height(&self) -> u32 {
+        self.luminances.get_height()
+    }
+
+    fn is_crop_supported(&self) -> bool {
+        true
+    }
+
+    fn crop(
+        &self,
+        left: u32,
+        top: u32,
+        width: u32,
+        height: u32,
+    ) -> ResultError<Arc<dyn LuminanceSource>> {
+        Ok(Arc::new(RGBLuminanceSource {
+            luminances: self.luminances.view(left, top, width, height)?,
+        }))
+    }
+
+    fn is_rotate_supported(&self) -> bool {
+        true
+    }
+
+    fn invert(&self) -> ResultError<Arc<dyn LuminanceSource>> {
+        Ok(Arc::new(InvertedLuminanceSource::new(Arc::new(
+            self.clone(),
+        ))))
+    }
+
+    fn rotate_counter_clockwise(&self) -> ResultError<Arc<dyn LuminanceSource>> {
+        self.luminances.rotate_counter_clockwise()
+    }
+}
+
+#[cfg(test)]
+mod rgb_luminance_source_tests {
+    use super::*;
+
+    #[test]
+    fn packed_formats() {
+        let argb = RGBLuminanceSource::new(2, 1, &[0xFF000000, 0xFF102040]).unwrap();
+        assert_eq!(vec![0x00, 0x24], argb.get_matrix().unwrap());
+
+        let rgb = RGBLuminanceSource::from_rgb(2, 1, &[0, 0, 0, 0x10, 0x20, 0x40]).unwrap();
+        let rgba = RGBLuminanceSource::from_rgba(2, 1, &[0, 0, 0, 9, 0x10, 0x20, 0x40, 9]).unwrap();
+        let argb_bytes =
+            RGBLuminanceSource::from_argb(2, 1, &[9, 0, 0, 0, 9, 0x10, 0x20, 0x40]).unwrap();
+        for source in [rgb, rgba, argb_bytes].iter() {
+            assert_eq!(argb.get_matrix().unwrap(), source.get_matrix().unwrap());
+        }
+        assert!(RGBLuminanceSource::from_rgb(2, 2, &[0; 6]).is_err());
+    }
+
+    #[test]
+    fn crop() {
+        let pixels: Vec<u32> = (0..9).map(|v| v * 0x010101).collect();
+        let source = RGBLuminanceSource::new(3, 3, &pixels).unwrap();
+        let cropped = source.crop(1, 1, 2, 2).unwrap();
+        assert_eq!(vec![4, 5, 7, 8], cropped.get_matrix().unwrap());
+        let rotated = cropped.rotate_counter_clockwise().unwrap();
+        assert_eq!(vec![5, 8, 4, 7], rotated.get_matrix().unwrap());
+    }
+}
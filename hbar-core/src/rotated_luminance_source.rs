@@ -0,0 +1,219 @@
+use crate::GrayLuminanceSource;
+use crate::InvertedLuminanceSource;
+use crate::LuminanceSource;
+use crate::ResultError;
+use crate::ResultPoint;
+
+use std::sync::Arc;
+
+/**
+ * A {@link LuminanceSource} showing another source rotated counterclockwise by any angle about
+ * its centre. The result is the bounding box of the rotated image, with the corners which fall
+ * outside the original left white, and is sampled bilinearly once, when the source is built.
+ *
+ * Points found in the rotated image can be mapped back to the original with
+ * {@link #to_source_point}.
+ */
+#[derive(Debug, Clone)]
+pub struct RotatedLuminanceSource {
+    luminances: GrayLuminanceSource<'static>,
+    degrees: f32,
+    cos: f64,
+    sin: f64,
+    source_center: (f64, f64),
+    center: (f64, f64),
+}
+
+impl RotatedLuminanceSource {
+    /**
+     * @param source the image to rotate
+     * @param degrees the angle to rotate it by, counterclockwise
+     */
+    pub fn new<S: LuminanceSource + ?Sized>(
+        source: &S,
+        degrees: f32,
+    ) -> ResultError<RotatedLuminanceSource> {
+        let source_width = source.get_width() as usize;
+        let source_height = source.get_height() as usize;
+        let matrix = source.get_matrix()?;
+        let radians = (degrees as f64).to_radians();
+        let (sin, cos) = radians.sin_cos();
+
+        // Near-integral sizes are rounding errors, for example at 90 degrees.
+        let bound = |a: f64, b: f64| ((a + b - 1e-6).ceil() as usize).max(1);
+        let width = bound(
+            source_width as f64 * cos.abs(),
+            source_height as f64 * sin.abs(),
+        );
+        let height = bound(
+            source_width as f64 * sin.abs(),
+            source_height as f64 * cos.abs(),
+        );
+
+        let mut rotated = RotatedLuminanceSource {
+            luminances: GrayLuminanceSource::from_luminances(Vec::new(), 0, 0),
+            degrees,
+            cos,
+            sin,
+            source_center: (
+                (source_width - 1) as f64 / 2.0,
+                (source_height - 1) as f64 / 2.0,
+            ),
+            center: ((width - 1) as f64 / 2.0, (height - 1) as f64 / 2.0),
+        };
+        let mut luminances = vec![0xFF; width * height];
+        for y in 0..height {
+            for x in 0..width {
+                let (source_x, source_y) = rotated.to_source(x as f64, y as f64);
+                if let Some(luminance) = RotatedLuminanceSource::sample(
+                    &matrix,
+                    source_width,
+                    source_height,
+                    source_x,
+                    source_y,
+                ) {
+                    luminances[y * width + x] = luminance;
+                }
+            }
+        }
+        rotated.luminances =
+            GrayLuminanceSource::from_luminances(luminances, width as u32, height as u32);
+        Ok(rotated)
+    }
+
+    /**
+     * @return the angle the source was rotated by, counterclockwise, in degrees
+     */
+    pub fn get_degrees(&self) -> f32 {
+        self.degrees
+    }
+
+    /**
+     * Maps a point in this image to the same point in the source image.
+     */
+    pub fn to_source_point(&self, point: &ResultPoint) -> ResultPoint {
+        let (x, y) = self.to_source(point.get_x() as f64, point.get_y() as f64);
+        ResultPoint::new(x as f32, y as f32)
+    }
+
+    fn to_source(&self, x: f64, y: f64) -> (f64, f64) {
+        let u = x - self.center.0;
+        let v = y - self.center.1;
+        (
+            self.source_center.0 + u * self.cos - v * self.sin,
+            self.source_center.1 + u * self.sin + v * self.cos,
+        )
+    }
+
+    // Interpolates between the four pixels around (x, y), or returns None outside the image.
+    fn sample(matrix: &[u8], width: usize, height: usize, x: f64, y: f64) -> Option<u8> {
+        if x < -0.5 || y < -0.5 || x > width as f64 - 0.5 || y > height as f64 - 0.5 {
+            return None;
+        }
+        let x = x.max(0.0).min((width - 1) as f64);
+        let y = y.max(0.0).min((height - 1) as f64);
+        let left = x.floor() as usize;
+        let top = y.floor() as usize;
+        let right = (left + 1).min(width - 1);
+        let bottom = (top + 1).min(height - 1);
+        let dx = x - left as f64;
+        let dy = y - top as f64;
+        let pixel = |x: usize, y: usize| matrix[y * width + x] as f64;
+        let upper = pixel(left, top) * (1.0 - dx) + pixel(right, top) * dx;
+        let lower = pixel(left, bottom) * (1.0 - dx) + pixel(right, bottom) * dx;
+        Some((upper * (1.0 - dy) + lower * dy).round() as u8)
+    }
+}
+
+impl LuminanceSource for RotatedLuminanceSource {
+    fn get_row(&self, y: i32, row: &Vec<u8>) -> ResultError<Vec<u8>> {
+        self.luminances.get_row(y, row)
+    }
+
+    fn get_matrix(&self) -> ResultError<Vec<u8>> {
+        self.luminances.get_matrix()
+    }
+
+    fn get_width(&self) -> u32 {
+        self.luminances.get_width()
+    }
+
+    fn get_height(&self) -> u32 {
+        self.luminances.get_height()
+    }
+
+    fn is_crop_supported(&self) -> bool {
+        true
+    }
+
+    fn crop(
+        &self,
+        left: u32,
+        top: u32,
+        width: u32,
+        height: u32,
+    ) -> ResultError<Arc<dyn LuminanceSource>> {
+        self.luminances.crop(left, top, width, height)
+    }
+
+    fn is_rotate_supported(&self) -> bool {
+        true
+    }
+
+    fn invert(&self) -> ResultError<Arc<dyn LuminanceSource>> {
+        Ok(Arc::new(InvertedLuminanceSource::new(Arc::new(
+            self.clone(),
+        ))))
+    }
+
+    fn rotate_counter_clockwise(&self) -> ResultError<Arc<dyn LuminanceSource>> {
+        self.luminances.rotate_counter_clockwise()
+    }
+
+    fn rotate_counter_clockwise45(&self) -> ResultError<Arc<dyn LuminanceSource>> {
+        Ok(Arc::new(RotatedLuminanceSource::new(
+            &self.luminances,
+            45.0,
+        )?))
+    }
+}
+
+#[cfg(test)]
+mod rotated_luminance_source_tests {
+    use super::*;
+
+    #[test]
+    fn quarter_turn_matches_rotate_counter_clockwise() {
+        let source = GrayLuminanceSource::from_vec((0..12).collect(), 4, 3).unwrap();
+        let rotated = RotatedLuminanceSource::new(&source, 90.0).unwrap();
+        assert_eq!(3, rotated.get_width());
+        assert_eq!(4, rotated.get_height());
+        assert_eq!(
+            source
+                .rotate_counter_clockwise()
+                .unwrap()
+                .get_matrix()
+                .unwrap(),
+            rotated.get_matrix().unwrap()
+        );
+    }
+
+    #[test]
+    fn maps_points_back() {
+        let mut luminances = vec![0xFF; 40 * 20];
+        luminances[5 * 40 + 30] = 0;
+        let source = GrayLuminanceSource::from_vec(luminances, 40, 20).unwrap();
+        let rotated = RotatedLuminanceSource::new(&source, 30.0).unwrap();
+        assert_eq!(45, rotated.get_width());
+        assert_eq!(38, rotated.get_height());
+
+        // The darkest pixel of the rotated image is the black pixel of the source.
+        let matrix = rotated.get_matrix().unwrap();
+        let darkest = (0..matrix.len()).min_by_key(|&i| matrix[i]).unwrap();
+        let width = rotated.get_width() as usize;
+        let point = ResultPoint::new((darkest % width) as f32, (darkest / width) as f32);
+        let mapped = rotated.to_source_point(&point);
+        assert!((mapped.get_x() - 30.0).abs() < 1.0, "{}", mapped);
+        assert!((mapped.get_y() - 5.0).abs() < 1.0, "{}", mapped);
+    }
+}
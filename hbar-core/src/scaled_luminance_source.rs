@@ -0,0 +1,156 @@
+use crate::GrayLuminanceSource;
+use crate::InvertedLuminanceSource;
+use crate::LuminanceSource;
+use crate::ResultPoint;
+use crate::{Error, ResultError};
+
+use std::sync::Arc;
+
+/**
+ * A {@link LuminanceSource} showing another source scaled down by a whole factor. Each pixel is
+ * the average of a {@code factor x factor} block of the source, or of what is left of one at the
+ * right and bottom edges.
+ *
+ * The source is read one row at a time, so a large image is never copied whole. Points found in
+ * the scaled image can be mapped back to the original with {@link #to_source_point}.
+ */
+#[derive(Debug, Clone)]
+pub struct ScaledLuminanceSource {
+    luminances: GrayLuminanceSource<'static>,
+    factor: u32,
+}
+
+impl ScaledLuminanceSource {
+    /**
+     * @param source the image to scale down
+     * @param factor how many source pixels make one pixel of the scaled image, in each direction
+     * @throws IllegalArgumentException if the factor is 0
+     */
+    pub fn new<S: LuminanceSource + ?Sized>(
+        source: &S,
+        factor: u32,
+    ) -> ResultError<ScaledLuminanceSource> {
+        if factor == 0 {
+            return Err(Error::IllegalArgumentException(String::from(
+                "Scale factor must be at least 1",
+            )));
+        }
+        let source_width = source.get_width() as usize;
+        let source_height = source.get_height() as usize;
+        let step = factor as usize;
+        let width = source_width.div_ceil(step);
+        let height = source_height.div_ceil(step);
+
+        let mut luminances = Vec::with_capacity(width * height);
+        let mut sums = vec![0u32; width];
+        let mut row = Vec::new();
+        for top in (0..source_height).step_by(step) {
+            sums.iter_mut().for_each(|sum| *sum = 0);
+            let bottom = (top + step).min(source_height);
+            for y in top..bottom {
+                row = source.get_row(y as i32, &row)?;
+                for (x, &luminance) in row[..source_width].iter().enumerate() {
+                    sums[x / step] += luminance as u32;
+                }
+            }
+            for (x, &sum) in sums.iter().enumerate() {
+                let block_width = (source_width - x * step).min(step);
+                let count = (block_width * (bottom - top)) as u32;
+                luminances.push(((sum + count / 2) / count) as u8);
+            }
+        }
+        Ok(ScaledLuminanceSource {
+            luminances: GrayLuminanceSource::from_luminances(
+                luminances,
+                width as u32,
+                height as u32,
+            ),
+            factor,
+        })
+    }
+
+    pub fn get_factor(&self) -> u32 {
+        self.factor
+    }
+
+    /**
+     * Maps a point in this image to the same point in the source image.
+     */
+    pub fn to_source_point(&self, point: &ResultPoint) -> ResultPoint {
+        let factor = self.factor as f32;
+        ResultPoint::new(
+            (point.get_x() + 0.5) * factor - 0.5,
+            (point.get_y() + 0.5) * factor - 0.5,
+        )
+    }
+}
+
+impl LuminanceSource for ScaledLuminanceSource {
+    fn get_row(&self, y: i32, row: &Vec<u8>) -> ResultError<Vec<u8>> {
+        self.luminances.get_row(y, row)
+    }
+
+    fn get_matrix(&self) -> ResultError<Vec<u8>> {
+        self.luminances.get_matrix()
+    }
+
+    fn get_width(&self) -> u32 {
+        self.luminances.get_width()
+    }
+
+    fn get_height(&self) -> u32 {
+        self.luminances.get_height()
+    }
+
+    fn is_crop_supported(&self) -> bool {
+        true
+    }
+
+    fn crop(
+        &self,
+        left: u32,
+        top: u32,
+        width: u32,
+        height: u32,
+    ) -> ResultError<Arc<dyn LuminanceSource>> {
+        self.luminances.crop(left, top, width, height)
+    }
+
+    fn is_rotate_supported(&self) -> bool {
+        true
+    }
+
+    fn invert(&self) -> ResultError<Arc<dyn LuminanceSource>> {
+        Ok(Arc::new(InvertedLuminanceSource::new(Arc::new(
+            self.clone(),
+        ))))
+    }
+
+    fn rotate_counter_clockwise(&self) -> ResultError<Arc<dyn LuminanceSource>> {
+        self.luminances.rotate_counter_clockwise()
+    }
+}
+
+#[cfg(test)]
+mod scaled_luminance_source_tests {
+    use super::*;
+
+    #[test]
+    fn averages_blocks() {
+        // 5x3, scaled by 2 into 3x2 with partial blocks on the right and at the bottom
+        let source = GrayLuminanceSource::from_vec(
+            vec![0, 2, 10, 20, 7, 4, 6, 30, 40, 9, 100, 200, 50, 50, 255],
+            5,
+            3,
+        )
+        .unwrap();
+        let scaled = ScaledLuminanceSource::new(&source, 2).unwrap();
+        assert_eq!(3, scaled.get_width());
+        assert_eq!(2, scaled.get_height());
+        assert_eq!(vec![3, 25, 8, 150, 50, 255], scaled.get_matrix().unwrap());
+        assert!(ScaledLuminanceSource::new(&source, 0).is_err());
+
+        let point = scaled.to_source_point(&ResultPoint::new(1.0, 0.0));
+        assert_eq!(ResultPoint::new(2.5, 0.5), point);
+    }
+}
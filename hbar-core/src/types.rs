@@ -1,5 +1,6 @@
 use crate::{Error, ResultError};
-use std::convert::TryFrom;
+use alloc::format;
+use core::convert::TryFrom;
 
 pub type Int = i32;
 pub type Char = char;
@@ -0,0 +1,93 @@
+use std::fmt;
+
+/**
+ * A print-quality grade, from A (best) to F (fail), as used by ISO/IEC 15415 and 15416.
+ * Grades are ordered, so the lowest of several is their {@code min}.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Grade {
+    F,
+    D,
+    C,
+    B,
+    A,
+}
+
+impl Grade {
+    /**
+     * @return the grade as a number, from 4 for A down to 0 for F
+     */
+    pub fn get_value(&self) -> i32 {
+        *self as i32
+    }
+
+    /**
+     * Grades a value for which more is better.
+     *
+     * @param bounds the least values earning an A, B, C and D
+     */
+    pub(crate) fn at_least(value: f32, bounds: [f32; 4]) -> Grade {
+        Grade::from_passed(bounds.iter().filter(|&&bound| value >= bound).count())
+    }
+
+    /**
+     * Grades a value for which less is better.
+     *
+     * @param bounds the greatest values earning an A, B, C and D
+     */
+    pub(crate) fn at_most(value: f32, bounds: [f32; 4]) -> Grade {
+        Grade::from_passed(bounds.iter().filter(|&&bound| value <= bound).count())
+    }
+
+    fn from_passed(passed: usize) -> Grade {
+        match passed {
+            0 => Grade::F,
+            1 => Grade::D,
+            2 => Grade::C,
+            3 => Grade::B,
+            _ => Grade::A,
+        }
+    }
+}
+
+impl fmt::Display for Grade {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let letter = match self {
+            Grade::A => "A",
+            Grade::B => "B",
+            Grade::C => "C",
+            Grade::D => "D",
+            Grade::F => "F",
+        };
+        write!(f, "{}", letter)
+    }
+}
+
+/**
+ * A measured quality parameter and the grade it earned.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Measurement {
+    value: f32,
+    grade: Grade,
+}
+
+impl Measurement {
+    pub fn new(value: f32, grade: Grade) -> Measurement {
+        Measurement { value, grade }
+    }
+
+    pub fn get_value(&self) -> f32 {
+        self.value
+    }
+
+    pub fn get_grade(&self) -> Grade {
+        self.grade
+    }
+}
+
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.grade, self.value)
+    }
+}
@@ -0,0 +1,356 @@
+use crate::{Binarizer, BinaryBitmap, LuminanceSource};
+use crate::{Error, ResultError};
+
+use super::{Grade, LinearSymbolQuality, Measurement, ScanProfile};
+
+// Number of scans across the height of a symbol.
+const SCAN_LINES: u32 = 10;
+// Least symbol contrast, in percent, for grades A to D.
+const SYMBOL_CONTRAST: [f32; 4] = [70.0, 55.0, 40.0, 20.0];
+// Least edge contrast, in percent, for a scan to pass.
+const MIN_EDGE_CONTRAST: f32 = 15.0;
+// Least modulation for grades A to D.
+const MODULATION: [f32; 4] = [0.70, 0.60, 0.50, 0.40];
+// Greatest defects for grades A to D.
+const DEFECTS: [f32; 4] = [0.15, 0.20, 0.25, 0.30];
+// Least decodability for grades A to D.
+const DECODABILITY: [f32; 4] = [0.62, 0.50, 0.37, 0.25];
+// Least quiet zone, in modules, either side of a decodable symbol.
+const QUIET_ZONE: f32 = 7.0;
+
+/**
+ * A bar or space of a scan, or a quiet zone, as the pixels [start, end).
+ */
+struct Element {
+    start: usize,
+    end: usize,
+    bar: bool,
+}
+
+/**
+ * <p>Grades the print quality of linear symbols after ISO/IEC 15416, from scan reflectance
+ * profiles taken along 10 rows spread evenly over the middle 80% of the bar height.</p>
+ *
+ * <p>Reflectance is taken to be the luminance, scaled to percent. Each scan is split into bars
+ * and spaces at the global threshold, halfway between its lowest and highest reflectance.</p>
+ *
+ * <p>There are no linear readers yet to run a symbology's reference decode algorithm, so
+ * decodability is measured against whole modules, as suits (n, k) symbologies such as EAN/UPC
+ * and Code 128, and a scan counts as decoded when its elements resolve into whole modules with
+ * a quiet zone of at least 7 modules either side.</p>
+ */
+pub struct LinearVerifier;
+
+impl Default for LinearVerifier {
+    fn default() -> Self {
+        LinearVerifier::new()
+    }
+}
+
+impl LinearVerifier {
+    pub fn new() -> LinearVerifier {
+        LinearVerifier
+    }
+
+    /**
+     * Finds the first and last rows of an image in which {@link BinaryBitmap#get_black_row}
+     * sees a bar.
+     *
+     * @throws NotFoundException if no row has a bar
+     */
+    pub fn locate<B, S>(&self, image: &BinaryBitmap<B, S>) -> ResultError<(u32, u32)>
+    where
+        B: Binarizer<S>,
+        S: LuminanceSource,
+    {
+        let mut rows: Option<(u32, u32)> = None;
+        for y in 0..image.get_height() {
+            let row = match image.get_black_row(y) {
+                Ok(row) => row,
+                // Rows without contrast do not binarize
+                Err(Error::NotFoundException(_)) => continue,
+                Err(e) => return Err(e),
+            };
+            if (0..row.get_size()).any(|x| row.get(x)) {
+                rows = Some((rows.map_or(y, |(top, _)| top), y));
+            }
+        }
+        rows.ok_or_else(|| Error::NotFoundException(String::from("No bars found")))
+    }
+
+    /**
+     * Locates a linear symbol and grades it.
+     *
+     * @throws NotFoundException if no bars are found
+     */
+    pub fn verify_image<B, S>(&self, image: &BinaryBitmap<B, S>) -> ResultError<LinearSymbolQuality>
+    where
+        B: Binarizer<S>,
+        S: LuminanceSource,
+    {
+        let (top, bottom) = self.locate(image)?;
+        self.verify(image.get_luminance_source()?, top, bottom)
+    }
+
+    /**
+     * Grades a linear symbol from 10 scans between two rows.
+     *
+     * @param source the image the symbol was printed on
+     * @param top the first row of the bars
+     * @param bottom the last row of the bars
+     */
+    pub fn verify<S: LuminanceSource + ?Sized>(
+        &self,
+        source: &S,
+        top: u32,
+        bottom: u32,
+    ) -> ResultError<LinearSymbolQuality> {
+        if bottom < top {
+            return Err(Error::IllegalArgumentException(format!(
+                "Bottom row {} is above top row {}",
+                bottom, top
+            )));
+        }
+        let height = (bottom - top) as f32;
+        let scans = (0..SCAN_LINES)
+            .map(|i| {
+                let offset = 0.1 + 0.8 * i as f32 / (SCAN_LINES - 1) as f32;
+                self.verify_scan(source, top + (height * offset).round() as u32)
+            })
+            .collect::<ResultError<Vec<_>>>()?;
+        Ok(LinearSymbolQuality::new(scans))
+    }
+
+    /**
+     * Grades the scan reflectance profile of one row.
+     *
+     * @param source the image the symbol was printed on
+     * @param y the row to scan
+     */
+    pub fn verify_scan<S: LuminanceSource + ?Sized>(
+        &self,
+        source: &S,
+        y: u32,
+    ) -> ResultError<ScanProfile> {
+        if y >= source.get_height() {
+            return Err(Error::IllegalArgumentException(format!(
+                "Requested row is outside the image: {}",
+                y
+            )));
+        }
+        let reflectances: Vec<f32> = source
+            .get_row(y as i32, &Vec::new())?
+            .iter()
+            .map(|&luminance| luminance as f32 * 100.0 / 255.0)
+            .collect();
+        let r_max = reflectances.iter().cloned().fold(0.0f32, f32::max);
+        let r_min = reflectances.iter().cloned().fold(100.0f32, f32::min);
+        let contrast = r_max - r_min;
+        let threshold = (r_max + r_min) / 2.0;
+        let elements = LinearVerifier::elements(&reflectances, threshold);
+
+        // Bars reflect as their darkest pixel and spaces as their lightest
+        let element_reflectance = |element: &Element| {
+            let pixels = reflectances[element.start..element.end].iter().cloned();
+            if element.bar {
+                pixels.fold(100.0f32, f32::min)
+            } else {
+                pixels.fold(0.0f32, f32::max)
+            }
+        };
+        let min_edge_contrast = if elements.len() < 2 {
+            0.0
+        } else {
+            elements
+                .windows(2)
+                .map(|pair| (element_reflectance(&pair[0]) - element_reflectance(&pair[1])).abs())
+                .fold(100.0f32, f32::min)
+        };
+        let max_nonuniformity = elements
+            .iter()
+            .map(|element| LinearVerifier::nonuniformity(&reflectances[element.start..element.end]))
+            .fold(0.0f32, f32::max);
+        let (modulation, defects) = if contrast > 0.0 {
+            (min_edge_contrast / contrast, max_nonuniformity / contrast)
+        } else {
+            (0.0, 1.0)
+        };
+        let (decodability, decode) = LinearVerifier::decodability(&elements);
+
+        Ok(ScanProfile::new(
+            y,
+            Measurement::new(
+                r_min,
+                if r_min <= 0.5 * r_max {
+                    Grade::A
+                } else {
+                    Grade::F
+                },
+            ),
+            r_max,
+            Measurement::new(contrast, Grade::at_least(contrast, SYMBOL_CONTRAST)),
+            Measurement::new(
+                min_edge_contrast,
+                if min_edge_contrast >= MIN_EDGE_CONTRAST {
+                    Grade::A
+                } else {
+                    Grade::F
+                },
+            ),
+            Measurement::new(modulation, Grade::at_least(modulation, MODULATION)),
+            Measurement::new(defects, Grade::at_most(defects, DEFECTS)),
+            Measurement::new(decodability, Grade::at_least(decodability, DECODABILITY)),
+            decode,
+        ))
+    }
+
+    // Splits a scan into runs on either side of the threshold. Leading and trailing spaces are
+    // the quiet zones.
+    fn elements(reflectances: &[f32], threshold: f32) -> Vec<Element> {
+        let mut elements = Vec::new();
+        let mut start = 0;
+        for i in 1..=reflectances.len() {
+            let bar = reflectances[start] < threshold;
+            if i == reflectances.len() || (reflectances[i] < threshold) != bar {
+                elements.push(Element { start, end: i, bar });
+                start = i;
+            }
+        }
+        elements
+    }
+
+    // The highest peak less the lowest valley within an element, edges excluded.
+    fn nonuniformity(reflectances: &[f32]) -> f32 {
+        let mut peak = None;
+        let mut valley = None;
+        for i in 1..reflectances.len().saturating_sub(1) {
+            let (before, here, after) = (reflectances[i - 1], reflectances[i], reflectances[i + 1]);
+            if here >= before && here >= after {
+                peak = Some(peak.map_or(here, |peak: f32| peak.max(here)));
+            }
+            if here <= before && here <= after {
+                valley = Some(valley.map_or(here, |valley: f32| valley.min(here)));
+            }
+        }
+        match (peak, valley) {
+            (Some(peak), Some(valley)) => (peak - valley).max(0.0),
+            _ => 0.0,
+        }
+    }
+
+    /**
+     * Measures how close the widths of the bars and spaces between the quiet zones come to whole
+     * modules. The module width is estimated by counting the narrowest element as one module.
+     *
+     * @return the decodability, and A if the scan decoded or F otherwise
+     */
+    fn decodability(elements: &[Element]) -> (f32, Grade) {
+        let first = elements.iter().position(|element| element.bar);
+        let last = elements.iter().rposition(|element| element.bar);
+        let (first, last) = match (first, last) {
+            (Some(first), Some(last)) if first < last => (first, last),
+            _ => return (0.0, Grade::F),
+        };
+        let widths: Vec<f32> = elements[first..=last]
+            .iter()
+            .map(|element| (element.end - element.start) as f32)
+            .collect();
+        let narrowest = widths.iter().cloned().fold(f32::MAX, f32::min);
+        let modules: f32 = widths.iter().map(|width| (width / narrowest).round()).sum();
+        let module_width = widths.iter().sum::<f32>() / modules;
+
+        let decodability = widths
+            .iter()
+            .map(|width| {
+                let modules = width / module_width;
+                1.0 - 2.0 * (modules - modules.round().max(1.0)).abs()
+            })
+            .fold(1.0f32, f32::min)
+            .max(0.0);
+        let quiet_zone = elements[first]
+            .start
+            .min(elements[elements.len() - 1].end - elements[last].end);
+        let decode = if decodability > 0.0 && quiet_zone as f32 >= QUIET_ZONE * module_width {
+            Grade::A
+        } else {
+            Grade::F
+        };
+        (decodability, decode)
+    }
+}
+
+#[cfg(test)]
+mod linear_verifier_tests {
+    use super::*;
+    use crate::common::HybridBinarizer;
+    use crate::GrayLuminanceSource;
+
+    // Bars and spaces, in modules, starting with a bar
+    const WIDTHS: [usize; 19] = [1, 1, 1, 2, 3, 1, 1, 2, 2, 1, 1, 4, 1, 1, 3, 2, 1, 1, 1];
+
+    // The bars 4 pixels a module and 30 rows high, with the given quiet zone in modules either
+    // side and 5 light rows above and below.
+    fn linear_image(quiet_zone: usize, dark: u8, light: u8) -> GrayLuminanceSource<'static> {
+        let mut row = vec![light; quiet_zone * 4];
+        for (i, &width) in WIDTHS.iter().enumerate() {
+            let luminance = if i % 2 == 0 { dark } else { light };
+            row.extend(std::iter::repeat_n(luminance, width * 4));
+        }
+        row.extend(std::iter::repeat_n(light, quiet_zone * 4));
+        let width = row.len();
+        let mut luminances = vec![light; width * 5];
+        for _ in 0..30 {
+            luminances.extend_from_slice(&row);
+        }
+        luminances.extend(std::iter::repeat_n(light, width * 5));
+        GrayLuminanceSource::from_vec(luminances, width as u32, 40).unwrap()
+    }
+
+    fn verify(source: GrayLuminanceSource<'static>) -> LinearSymbolQuality {
+        LinearVerifier::new()
+            .verify_image(&BinaryBitmap::new(HybridBinarizer::new(source)))
+            .unwrap()
+    }
+
+    #[test]
+    fn grades_a_clean_symbol_a() {
+        let quality = verify(linear_image(10, 0, 0xFF));
+        assert_eq!(10, quality.get_scans().len());
+        assert_eq!(Grade::A, quality.get_overall().get_grade(), "{}", quality);
+        for scan in quality.get_scans() {
+            assert!((5..35).contains(&scan.get_y()));
+            assert_eq!(100.0, scan.get_symbol_contrast().get_value());
+            assert_eq!(1.0, scan.get_modulation().get_value());
+            assert_eq!(0.0, scan.get_defects().get_value());
+            assert_eq!(1.0, scan.get_decodability().get_value());
+        }
+    }
+
+    #[test]
+    fn grades_down_low_contrast_defects_and_quiet_zones() {
+        let quality = verify(linear_image(10, 0x60, 0xCD));
+        let scan = &quality.get_scans()[0];
+        assert_eq!(Grade::A, scan.get_r_min().get_grade());
+        assert_eq!(Grade::C, scan.get_symbol_contrast().get_grade());
+        assert_eq!(Grade::C, quality.get_overall().get_grade(), "{}", quality);
+
+        // A grey spot in the middle of the widest space
+        let source = linear_image(10, 0, 0xFF);
+        let mut luminances = source.get_matrix().unwrap();
+        let width = source.get_width() as usize;
+        let spot = (10 + WIDTHS[..11].iter().sum::<usize>() + 1) * 4;
+        for y in 5..35 {
+            for x in spot..spot + 8 {
+                luminances[y * width + x] = 0xC0;
+            }
+        }
+        let quality = verify(GrayLuminanceSource::from_vec(luminances, width as u32, 40).unwrap());
+        let scan = &quality.get_scans()[0];
+        assert_eq!(Grade::C, scan.get_defects().get_grade(), "{}", scan);
+        assert_eq!(Grade::A, scan.get_decode());
+
+        let quality = verify(linear_image(3, 0, 0xFF));
+        assert_eq!(Grade::F, quality.get_scans()[0].get_decode());
+        assert_eq!(Grade::F, quality.get_overall().get_grade());
+    }
+}
@@ -0,0 +1,19 @@
+// ISO/IEC 15415 grading is only done for QR Codes. Data Matrix grading needs a Data Matrix
+// detector and decoder, which this tree does not have yet, and is left for when they are
+// ported. QR Codes are located the way QRCodeReader finds pure symbols, as there is no QR Code
+// detector either; QRCodeVerifier::verify grades a SampledGrid located by any other means.
+mod grade;
+mod linear_verifier;
+#[cfg(feature = "qrcode")]
+mod qrcode_verifier;
+mod sampled_grid;
+mod scan_profile;
+mod symbol_quality;
+
+pub use grade::{Grade, Measurement};
+pub use linear_verifier::LinearVerifier;
+#[cfg(feature = "qrcode")]
+pub use qrcode_verifier::QRCodeVerifier;
+pub use sampled_grid::SampledGrid;
+pub use scan_profile::{LinearSymbolQuality, ScanProfile};
+pub use symbol_quality::SymbolQuality;
@@ -0,0 +1,375 @@
+use crate::common::BitMatrix;
+use crate::qrcode::{Decoder, QRCodeReader};
+use crate::BlockCorrections;
+use crate::ResultPoint;
+use crate::{Binarizer, BinaryBitmap, LuminanceSource};
+use crate::{Error, ResultError};
+
+use super::{Grade, Measurement, SampledGrid, SymbolQuality};
+
+// Least symbol contrast, in percent, for grades A to D.
+const SYMBOL_CONTRAST: [f32; 4] = [70.0, 55.0, 40.0, 20.0];
+// Least modulation and reflectance margin of a module for grades A to D.
+const MODULATION: [f32; 4] = [0.50, 0.40, 0.30, 0.20];
+// Least share of unused error correction for grades A to D.
+const UNUSED_ERROR_CORRECTION: [f32; 4] = [0.62, 0.50, 0.37, 0.25];
+// Greatest axial nonuniformity for grades A to D.
+const AXIAL_NONUNIFORMITY: [f32; 4] = [0.06, 0.08, 0.10, 0.12];
+// Greatest grid nonuniformity, in modules, for grades A to D.
+const GRID_NONUNIFORMITY: [f32; 4] = [0.38, 0.50, 0.63, 0.75];
+// Greatest number of damaged modules in one finder pattern for grades A to D.
+const FINDER_DAMAGE: [f32; 4] = [0.0, 1.0, 2.0, 3.0];
+// Greatest share of damaged modules in one timing pattern for grades A to D.
+const TIMING_DAMAGE: [f32; 4] = [0.0, 0.07, 0.11, 0.14];
+
+/**
+ * <p>Grades the print quality of QR Codes after ISO/IEC 15415, from the image they were
+ * printed on and where their modules were sampled.</p>
+ *
+ * <p>Reflectance is taken to be the luminance, scaled to percent, averaged over a square
+ * aperture of 0.8 module widths around each module centre. Modulation and reflectance margin
+ * are graded per codeword: for each grade, codewords graded lower are counted as erasures, and
+ * the grade holds if enough error correction would be left over.</p>
+ *
+ * <p>Only unmirrored symbols are graded. {@link #locate} only finds pure, upright symbols,
+ * since there is no QR Code detector to build on yet; {@link #verify} grades a grid found by
+ * any other means.</p>
+ */
+pub struct QRCodeVerifier {
+    decoder: Decoder,
+}
+
+impl Default for QRCodeVerifier {
+    fn default() -> Self {
+        QRCodeVerifier::new()
+    }
+}
+
+impl QRCodeVerifier {
+    pub fn new() -> QRCodeVerifier {
+        QRCodeVerifier {
+            decoder: Decoder::new(),
+        }
+    }
+
+    /**
+     * Finds where the modules of a "pure" QR Code, as {@link QRCodeReader} reads it, are
+     * sampled.
+     *
+     * @throws NotFoundException if no QR Code is found
+     */
+    pub fn locate<B, S>(&self, image: &BinaryBitmap<B, S>) -> ResultError<SampledGrid>
+    where
+        B: Binarizer<S>,
+        S: LuminanceSource,
+    {
+        let (left, top, module_size, dimension) =
+            QRCodeReader::pure_geometry(image.getBlackMatrix()?)?;
+        let mut centers = Vec::with_capacity((dimension * dimension) as usize);
+        for y in 0..dimension {
+            for x in 0..dimension {
+                centers.push(ResultPoint::new(
+                    (left + (x as f32 * module_size) as i32) as f32,
+                    (top + (y as f32 * module_size) as i32) as f32,
+                ));
+            }
+        }
+        SampledGrid::new(dimension as u32, centers)
+    }
+
+    /**
+     * Locates a "pure" QR Code and grades it.
+     *
+     * @throws NotFoundException if no QR Code is found
+     */
+    pub fn verify_image<B, S>(&self, image: &BinaryBitmap<B, S>) -> ResultError<SymbolQuality>
+    where
+        B: Binarizer<S>,
+        S: LuminanceSource,
+    {
+        let grid = self.locate(image)?;
+        self.verify(image.get_luminance_source()?, &grid)
+    }
+
+    /**
+     * Grades a QR Code. A symbol which does not decode is graded F, with what can be measured
+     * without decoding it still filled in.
+     *
+     * @param source the image the symbol was printed on
+     * @param grid where its modules were sampled
+     */
+    pub fn verify<S: LuminanceSource + ?Sized>(
+        &self,
+        source: &S,
+        grid: &SampledGrid,
+    ) -> ResultError<SymbolQuality> {
+        let dimension = grid.get_dimension();
+        let reflectances = QRCodeVerifier::sample_reflectances(source, grid)?;
+        let r_max = reflectances.iter().cloned().fold(0.0f32, f32::max);
+        let r_min = reflectances.iter().cloned().fold(100.0f32, f32::min);
+        let contrast = r_max - r_min;
+        let threshold = (r_max + r_min) / 2.0;
+
+        let mut bits = BitMatrix::new2(dimension as i32, dimension as i32)?;
+        let mut modulations = Vec::with_capacity(reflectances.len());
+        for (i, &reflectance) in reflectances.iter().enumerate() {
+            if reflectance < threshold {
+                bits.set(i as u32 % dimension, i as u32 / dimension);
+            }
+            modulations.push(if contrast > 0.0 {
+                (2.0 * (reflectance - threshold).abs() / contrast).min(1.0)
+            } else {
+                0.0
+            });
+        }
+
+        let (pitch_x, pitch_y) = grid.get_pitch();
+        let module_size = grid.get_module_size();
+        let axial = (pitch_x - pitch_y).abs() / module_size;
+        let deviation = grid.get_max_deviation() / module_size;
+
+        let (decode, modulation, reflectance_margin, unused) = match self
+            .decoder
+            .decode_codewords(&bits)
+        {
+            Ok(codewords) => {
+                let mut modulation_grades = Vec::with_capacity(codewords.modules.len());
+                let mut margin_grades = Vec::with_capacity(codewords.modules.len());
+                for (i, modules) in codewords.modules.iter().enumerate() {
+                    let wrong = codewords.read[i] ^ codewords.corrected[i];
+                    let mut modulation = Grade::A;
+                    let mut margin = Grade::A;
+                    for (bit, &(x, y)) in modules.iter().enumerate() {
+                        let grade =
+                            Grade::at_least(modulations[(y * dimension + x) as usize], MODULATION);
+                        modulation = modulation.min(grade);
+                        margin = if wrong & (0x80 >> bit) != 0 {
+                            Grade::F
+                        } else {
+                            margin.min(grade)
+                        };
+                    }
+                    modulation_grades.push(modulation);
+                    margin_grades.push(margin);
+                }
+                let blocks = codewords.result.get_block_corrections();
+                let unused = blocks
+                    .iter()
+                    .map(|block| 1.0 - block.get_budget_used())
+                    .fold(1.0f32, f32::min);
+                (
+                    Grade::A,
+                    QRCodeVerifier::grade_codewords(&modulation_grades, &codewords.blocks, blocks),
+                    QRCodeVerifier::grade_codewords(&margin_grades, &codewords.blocks, blocks),
+                    Measurement::new(unused, Grade::at_least(unused, UNUSED_ERROR_CORRECTION)),
+                )
+            }
+            Err(Error::FormatException(_)) | Err(Error::ChecksumException(_)) => (
+                Grade::F,
+                Grade::F,
+                Grade::F,
+                Measurement::new(0.0, Grade::F),
+            ),
+            Err(err) => return Err(err),
+        };
+
+        Ok(SymbolQuality::new(
+            decode,
+            Measurement::new(contrast, Grade::at_least(contrast, SYMBOL_CONTRAST)),
+            modulation,
+            reflectance_margin,
+            QRCodeVerifier::fixed_pattern_damage(&bits),
+            Measurement::new(axial, Grade::at_most(axial, AXIAL_NONUNIFORMITY)),
+            Measurement::new(deviation, Grade::at_most(deviation, GRID_NONUNIFORMITY)),
+            unused,
+        ))
+    }
+
+    // The reflectance of each module, row by row, in percent.
+    fn sample_reflectances<S: LuminanceSource + ?Sized>(
+        source: &S,
+        grid: &SampledGrid,
+    ) -> ResultError<Vec<f32>> {
+        let width = source.get_width() as i32;
+        let height = source.get_height() as i32;
+        let matrix = source.get_matrix()?;
+        let radius = (grid.get_module_size() * 0.4) as i32;
+
+        let dimension = grid.get_dimension();
+        let mut reflectances = Vec::with_capacity((dimension * dimension) as usize);
+        for y in 0..dimension {
+            for x in 0..dimension {
+                let center = grid.get_center(x, y);
+                let center_x = center.get_x().round() as i32;
+                let center_y = center.get_y().round() as i32;
+                if center_x < 0 || center_y < 0 || center_x >= width || center_y >= height {
+                    return Err(Error::IllegalArgumentException(format!(
+                        "Module centre {} is outside the image",
+                        center
+                    )));
+                }
+                let mut sum = 0u32;
+                let mut count = 0u32;
+                for j in (center_y - radius).max(0)..=(center_y + radius).min(height - 1) {
+                    for i in (center_x - radius).max(0)..=(center_x + radius).min(width - 1) {
+                        sum += matrix[(j * width + i) as usize] as u32;
+                        count += 1;
+                    }
+                }
+                reflectances.push(sum as f32 * 100.0 / (count as f32 * 255.0));
+            }
+        }
+        Ok(reflectances)
+    }
+
+    /**
+     * Grades codewords together, allowing for error correction: a grade holds for the symbol if,
+     * with all codewords graded lower counted as erasures, the unused error correction earns it
+     * too.
+     */
+    fn grade_codewords(
+        grades: &[Grade],
+        codeword_blocks: &[usize],
+        blocks: &[BlockCorrections],
+    ) -> Grade {
+        let mut best = Grade::F;
+        for &level in &[Grade::A, Grade::B, Grade::C, Grade::D] {
+            let mut erasures = vec![0; blocks.len()];
+            for (grade, &block) in grades.iter().zip(codeword_blocks) {
+                if *grade < level {
+                    erasures[block] += 1;
+                }
+            }
+            let unused = blocks
+                .iter()
+                .zip(erasures)
+                .map(|(block, erasures)| {
+                    1.0 - BlockCorrections::new(block.get_ec_codewords(), 0, erasures)
+                        .get_budget_used()
+                })
+                .fold(1.0f32, f32::min);
+            best = best.max(level.min(Grade::at_least(unused, UNUSED_ERROR_CORRECTION)));
+        }
+        best
+    }
+
+    // Grades the finder patterns with their separators, and the timing patterns, by how many of
+    // their modules came out the wrong color; the worst of them counts.
+    fn fixed_pattern_damage(bits: &BitMatrix) -> Measurement {
+        let dimension = bits.getWidth();
+        let mut damaged = 0;
+        let mut grade = Grade::A;
+
+        for &(center_x, center_y) in &[(3, 3), (dimension - 4, 3), (3, dimension - 4)] {
+            let mut finder_damage = 0;
+            for y in (center_y - 4).max(0)..=(center_y + 4).min(dimension - 1) {
+                for x in (center_x - 4).max(0)..=(center_x + 4).min(dimension - 1) {
+                    // Rings around the centre: dark, light, dark and the light separator
+                    let ring = (x - center_x).abs().max((y - center_y).abs());
+                    let dark = ring != 2 && ring != 4;
+                    if bits.get(x as u32, y as u32) != dark {
+                        finder_damage += 1;
+                    }
+                }
+            }
+            damaged += finder_damage;
+            grade = grade.min(Grade::at_most(finder_damage as f32, FINDER_DAMAGE));
+        }
+
+        for &vertical in &[false, true] {
+            let mut timing_damage = 0;
+            for i in 8..dimension - 8 {
+                let (x, y) = if vertical { (6, i) } else { (i, 6) };
+                if bits.get(x as u32, y as u32) != (i % 2 == 0) {
+                    timing_damage += 1;
+                }
+            }
+            damaged += timing_damage;
+            let share = timing_damage as f32 / (dimension - 16) as f32;
+            grade = grade.min(Grade::at_most(share, TIMING_DAMAGE));
+        }
+
+        Measurement::new(damaged as f32, grade)
+    }
+}
+
+#[cfg(all(test, feature = "encode"))]
+mod qrcode_verifier_tests {
+    use super::*;
+    use crate::common::HybridBinarizer;
+    use crate::qrcode::encoder::Encoder;
+    use crate::ErrorCorrectionLevel;
+    use crate::GrayLuminanceSource;
+
+    // A QR code with square modules of the given size, a 4 module quiet zone and the given
+    // luminances for dark and light modules.
+    fn qr_image(content: &str, module_size: i32, dark: u8, light: u8) -> Vec<u8> {
+        let code = Encoder::new()
+            .encode(&String::from(content), ErrorCorrectionLevel::Q)
+            .unwrap();
+        let matrix = code.get_matrix();
+        let dimension = (matrix.get_width() + 8) * module_size;
+        (0..dimension * dimension)
+            .map(|offset| {
+                let x = (offset % dimension) / module_size - 4;
+                let y = (offset / dimension) / module_size - 4;
+                let inside =
+                    (0..matrix.get_width()).contains(&x) && (0..matrix.get_height()).contains(&y);
+                if inside && matrix.get(x, y) == 1 {
+                    dark
+                } else {
+                    light
+                }
+            })
+            .collect()
+    }
+
+    fn verify(luminances: Vec<u8>) -> SymbolQuality {
+        let dimension = (luminances.len() as f64).sqrt() as u32;
+        let source = GrayLuminanceSource::from_vec(luminances, dimension, dimension).unwrap();
+        QRCodeVerifier::new()
+            .verify_image(&BinaryBitmap::new(HybridBinarizer::new(source)))
+            .unwrap()
+    }
+
+    #[test]
+    fn grades_a_clean_symbol_a() {
+        let quality = verify(qr_image("print quality", 5, 0, 0xFF));
+        assert_eq!(Grade::A, quality.get_overall(), "{}", quality);
+        assert_eq!(100.0, quality.get_symbol_contrast().get_value());
+        assert_eq!(1.0, quality.get_unused_error_correction().get_value());
+        assert_eq!(0.0, quality.get_fixed_pattern_damage().get_value());
+    }
+
+    #[test]
+    fn grades_down_damage_and_low_contrast() {
+        // The same symbol printed with 30% contrast
+        let clean = qr_image("print quality", 5, 0, 0xFF);
+        let dimension = (clean.len() as f64).sqrt() as u32;
+        let clean = GrayLuminanceSource::from_vec(clean, dimension, dimension).unwrap();
+        let verifier = QRCodeVerifier::new();
+        let grid = verifier
+            .locate(&BinaryBitmap::new(HybridBinarizer::new(clean)))
+            .unwrap();
+        let faint = qr_image("print quality", 5, 0x80, 0xCD);
+        let faint = GrayLuminanceSource::from_vec(faint, dimension, dimension).unwrap();
+        let quality = verifier.verify(&faint, &grid).unwrap();
+        assert_eq!(Grade::D, quality.get_symbol_contrast().get_grade());
+        assert_eq!(Grade::D, quality.get_overall(), "{}", quality);
+        assert_eq!(Grade::A, quality.get_modulation());
+
+        // The centre of the bottom left finder pattern turned light
+        let mut luminances = qr_image("print quality", 5, 0, 0xFF);
+        let dimension = (luminances.len() as f64).sqrt() as usize;
+        let top = dimension - 8 * 5;
+        for y in top..top + 5 {
+            for x in 7 * 5..8 * 5 {
+                luminances[y * dimension + x] = 0xFF;
+            }
+        }
+        let quality = verify(luminances);
+        assert_eq!(1.0, quality.get_fixed_pattern_damage().get_value());
+        assert_eq!(Grade::B, quality.get_fixed_pattern_damage().get_grade());
+        assert_eq!(Grade::A, quality.get_decode());
+    }
+}
@@ -0,0 +1,96 @@
+use crate::ResultPoint;
+use crate::{Error, ResultError};
+
+/**
+ * Where each module of a 2D symbol was sampled in the image: the centre of every module, row
+ * by row, as a detector found them. Print-quality grading measures the reflectance around
+ * these points and how far they stray from a regular grid.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct SampledGrid {
+    dimension: u32,
+    centers: Vec<ResultPoint>,
+}
+
+impl SampledGrid {
+    /**
+     * @param dimension number of modules on each side of the symbol
+     * @param centers centres of the modules, row by row from the top left
+     * @throws IllegalArgumentException if there is not one centre per module
+     */
+    pub fn new(dimension: u32, centers: Vec<ResultPoint>) -> ResultError<SampledGrid> {
+        if dimension < 2 || centers.len() != (dimension * dimension) as usize {
+            return Err(Error::IllegalArgumentException(format!(
+                "Expected {} module centres but got {}",
+                dimension * dimension,
+                centers.len()
+            )));
+        }
+        Ok(SampledGrid { dimension, centers })
+    }
+
+    pub fn get_dimension(&self) -> u32 {
+        self.dimension
+    }
+
+    /**
+     * @return the centre of the module in column x and row y
+     */
+    pub fn get_center(&self, x: u32, y: u32) -> &ResultPoint {
+        &self.centers[(y * self.dimension + x) as usize]
+    }
+
+    /**
+     * @return the average distance between neighbouring module centres, along rows and along
+     *  columns
+     */
+    pub fn get_pitch(&self) -> (f32, f32) {
+        let last = self.dimension - 1;
+        let mut x_span = 0.0;
+        let mut y_span = 0.0;
+        for i in 0..self.dimension {
+            x_span += ResultPoint::distance(self.get_center(0, i), self.get_center(last, i));
+            y_span += ResultPoint::distance(self.get_center(i, 0), self.get_center(i, last));
+        }
+        let spans = (self.dimension * last) as f32;
+        (x_span / spans, y_span / spans)
+    }
+
+    /**
+     * @return the average module size, in pixels
+     */
+    pub fn get_module_size(&self) -> f32 {
+        let (x, y) = self.get_pitch();
+        (x + y) / 2.0
+    }
+
+    /**
+     * @return the greatest distance of a module centre from where a regular grid through the
+     *  four corner modules would put it, in pixels
+     */
+    pub fn get_max_deviation(&self) -> f32 {
+        let last = self.dimension - 1;
+        let corners = [
+            self.get_center(0, 0),
+            self.get_center(last, 0),
+            self.get_center(0, last),
+            self.get_center(last, last),
+        ];
+        let mut max = 0.0f32;
+        for y in 0..self.dimension {
+            let v = y as f32 / last as f32;
+            for x in 0..self.dimension {
+                let u = x as f32 / last as f32;
+                let weights = [(1.0 - u) * (1.0 - v), u * (1.0 - v), (1.0 - u) * v, u * v];
+                let (mut ideal_x, mut ideal_y) = (0.0, 0.0);
+                for (corner, weight) in corners.iter().zip(weights.iter()) {
+                    ideal_x += corner.get_x() * weight;
+                    ideal_y += corner.get_y() * weight;
+                }
+                let ideal = ResultPoint::new(ideal_x, ideal_y);
+                max = max.max(ResultPoint::distance(&ideal, self.get_center(x, y)));
+            }
+        }
+        max
+    }
+}
@@ -0,0 +1,198 @@
+use super::{Grade, Measurement};
+
+use std::fmt;
+
+/**
+ * The print quality of one scan across a linear symbol, graded after ISO/IEC 15416 from its
+ * scan reflectance profile. Reflectances are in percent. The scan grade is the lowest of the
+ * parameter grades.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScanProfile {
+    y: u32,
+    r_min: Measurement,
+    r_max: f32,
+    symbol_contrast: Measurement,
+    min_edge_contrast: Measurement,
+    modulation: Measurement,
+    defects: Measurement,
+    decodability: Measurement,
+    decode: Grade,
+    grade: Grade,
+}
+
+impl ScanProfile {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        y: u32,
+        r_min: Measurement,
+        r_max: f32,
+        symbol_contrast: Measurement,
+        min_edge_contrast: Measurement,
+        modulation: Measurement,
+        defects: Measurement,
+        decodability: Measurement,
+        decode: Grade,
+    ) -> ScanProfile {
+        let grade = *[
+            r_min.get_grade(),
+            symbol_contrast.get_grade(),
+            min_edge_contrast.get_grade(),
+            modulation.get_grade(),
+            defects.get_grade(),
+            decodability.get_grade(),
+            decode,
+        ]
+        .iter()
+        .min()
+        .unwrap();
+        ScanProfile {
+            y,
+            r_min,
+            r_max,
+            symbol_contrast,
+            min_edge_contrast,
+            modulation,
+            defects,
+            decodability,
+            decode,
+            grade,
+        }
+    }
+
+    /**
+     * @return the image row which was scanned
+     */
+    pub fn get_y(&self) -> u32 {
+        self.y
+    }
+
+    /**
+     * @return the lowest reflectance, graded A if it is at most half the highest and F otherwise
+     */
+    pub fn get_r_min(&self) -> Measurement {
+        self.r_min
+    }
+
+    /**
+     * @return the highest reflectance
+     */
+    pub fn get_r_max(&self) -> f32 {
+        self.r_max
+    }
+
+    /**
+     * @return Rmax - Rmin
+     */
+    pub fn get_symbol_contrast(&self) -> Measurement {
+        self.symbol_contrast
+    }
+
+    /**
+     * @return the least difference between the reflectances of a space and a neighbouring bar,
+     *  graded A from 15 and F below
+     */
+    pub fn get_min_edge_contrast(&self) -> Measurement {
+        self.min_edge_contrast
+    }
+
+    /**
+     * @return the minimum edge contrast relative to the symbol contrast
+     */
+    pub fn get_modulation(&self) -> Measurement {
+        self.modulation
+    }
+
+    /**
+     * @return the greatest reflectance nonuniformity within an element or quiet zone, relative
+     *  to the symbol contrast
+     */
+    pub fn get_defects(&self) -> Measurement {
+        self.defects
+    }
+
+    /**
+     * @return the share of the half module tolerance left over by the element whose width lies
+     *  furthest from a whole number of modules, from 0 to 1
+     */
+    pub fn get_decodability(&self) -> Measurement {
+        self.decodability
+    }
+
+    /**
+     * @return A if the scan decoded, F otherwise
+     */
+    pub fn get_decode(&self) -> Grade {
+        self.decode
+    }
+
+    pub fn get_grade(&self) -> Grade {
+        self.grade
+    }
+}
+
+impl fmt::Display for ScanProfile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "y={} Rmin: {} Rmax: {} SC: {} ECmin: {} MOD: {} Defects: {} Decodability: {} \
+             Decode: {} Grade: {}",
+            self.y,
+            self.r_min,
+            self.r_max,
+            self.symbol_contrast,
+            self.min_edge_contrast,
+            self.modulation,
+            self.defects,
+            self.decodability,
+            self.decode,
+            self.grade
+        )
+    }
+}
+
+/**
+ * The print quality of a linear symbol after ISO/IEC 15416: its scan profiles, and the mean of
+ * their grades as the overall grade.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearSymbolQuality {
+    scans: Vec<ScanProfile>,
+    overall: Measurement,
+}
+
+impl LinearSymbolQuality {
+    pub(crate) fn new(scans: Vec<ScanProfile>) -> LinearSymbolQuality {
+        let mean = if scans.is_empty() {
+            0.0
+        } else {
+            scans
+                .iter()
+                .map(|scan| scan.get_grade().get_value() as f32)
+                .sum::<f32>()
+                / scans.len() as f32
+        };
+        let overall = Measurement::new(mean, Grade::at_least(mean, [3.5, 2.5, 1.5, 0.5]));
+        LinearSymbolQuality { scans, overall }
+    }
+
+    pub fn get_scans(&self) -> &[ScanProfile] {
+        &self.scans
+    }
+
+    /**
+     * @return the mean scan grade, from 0 to 4, and the grade it rounds to
+     */
+    pub fn get_overall(&self) -> Measurement {
+        self.overall
+    }
+}
+
+impl fmt::Display for LinearSymbolQuality {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for scan in &self.scans {
+            writeln!(f, "{}", scan)?;
+        }
+        write!(f, "Overall: {}", self.overall)
+    }
+}
@@ -0,0 +1,142 @@
+use super::{Grade, Measurement};
+
+use std::fmt;
+
+/**
+ * The print quality of a 2D symbol, graded after ISO/IEC 15415. The overall grade is the lowest
+ * of the parameter grades.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolQuality {
+    decode: Grade,
+    symbol_contrast: Measurement,
+    modulation: Grade,
+    reflectance_margin: Grade,
+    fixed_pattern_damage: Measurement,
+    axial_nonuniformity: Measurement,
+    grid_nonuniformity: Measurement,
+    unused_error_correction: Measurement,
+    overall: Grade,
+}
+
+impl SymbolQuality {
+    #[allow(clippy::too_many_arguments)]
+    #[cfg(feature = "qrcode")]
+    pub(crate) fn new(
+        decode: Grade,
+        symbol_contrast: Measurement,
+        modulation: Grade,
+        reflectance_margin: Grade,
+        fixed_pattern_damage: Measurement,
+        axial_nonuniformity: Measurement,
+        grid_nonuniformity: Measurement,
+        unused_error_correction: Measurement,
+    ) -> SymbolQuality {
+        let overall = *[
+            decode,
+            symbol_contrast.get_grade(),
+            modulation,
+            reflectance_margin,
+            fixed_pattern_damage.get_grade(),
+            axial_nonuniformity.get_grade(),
+            grid_nonuniformity.get_grade(),
+            unused_error_correction.get_grade(),
+        ]
+        .iter()
+        .min()
+        .unwrap();
+        SymbolQuality {
+            decode,
+            symbol_contrast,
+            modulation,
+            reflectance_margin,
+            fixed_pattern_damage,
+            axial_nonuniformity,
+            grid_nonuniformity,
+            unused_error_correction,
+            overall,
+        }
+    }
+
+    /**
+     * @return A if the symbol decoded, F otherwise
+     */
+    pub fn get_decode(&self) -> Grade {
+        self.decode
+    }
+
+    /**
+     * @return the difference between the highest and lowest module reflectance, in percent
+     */
+    pub fn get_symbol_contrast(&self) -> Measurement {
+        self.symbol_contrast
+    }
+
+    /**
+     * @return how evenly dark and light modules stand apart from the global threshold, graded
+     *  per codeword and allowing for unused error correction
+     */
+    pub fn get_modulation(&self) -> Grade {
+        self.modulation
+    }
+
+    /**
+     * @return like the modulation, but modules on the wrong side of the threshold count as
+     *  having no margin at all
+     */
+    pub fn get_reflectance_margin(&self) -> Grade {
+        self.reflectance_margin
+    }
+
+    /**
+     * @return the number of damaged modules in the finder, separator and timing patterns
+     */
+    pub fn get_fixed_pattern_damage(&self) -> Measurement {
+        self.fixed_pattern_damage
+    }
+
+    /**
+     * @return how much the module pitch along rows and along columns differ, relative to their
+     *  mean
+     */
+    pub fn get_axial_nonuniformity(&self) -> Measurement {
+        self.axial_nonuniformity
+    }
+
+    /**
+     * @return the greatest distance of a module from its ideal position, in modules
+     */
+    pub fn get_grid_nonuniformity(&self) -> Measurement {
+        self.grid_nonuniformity
+    }
+
+    /**
+     * @return the share of error correction left over by the block that needed the most, from
+     *  0 to 1
+     */
+    pub fn get_unused_error_correction(&self) -> Measurement {
+        self.unused_error_correction
+    }
+
+    pub fn get_overall(&self) -> Grade {
+        self.overall
+    }
+}
+
+impl fmt::Display for SymbolQuality {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Decode: {}", self.decode)?;
+        writeln!(f, "Symbol contrast: {}", self.symbol_contrast)?;
+        writeln!(f, "Modulation: {}", self.modulation)?;
+        writeln!(f, "Reflectance margin: {}", self.reflectance_margin)?;
+        writeln!(f, "Fixed pattern damage: {}", self.fixed_pattern_damage)?;
+        writeln!(f, "Axial nonuniformity: {}", self.axial_nonuniformity)?;
+        writeln!(f, "Grid nonuniformity: {}", self.grid_nonuniformity)?;
+        writeln!(
+            f,
+            "Unused error correction: {}",
+            self.unused_error_correction
+        )?;
+        write!(f, "Overall: {}", self.overall)
+    }
+}
@@ -1,7 +1,13 @@
 use crate::barcode_format::BarcodeFormat;
 use crate::common::BitMatrix;
+#[cfg(feature = "std")]
 use crate::encode_hint_type::EncodeHintType;
+use crate::encode_options::EncodeOptions;
 use crate::ResultError;
+use alloc::string::String;
+#[cfg(feature = "std")]
+use core::convert::TryFrom;
+#[cfg(feature = "std")]
 use std::collections::HashMap;
 
 pub trait Writer {
@@ -21,7 +27,21 @@ pub trait Writer {
         format: &BarcodeFormat,
         width: i32,
         height: i32,
-    ) -> ResultError<BitMatrix>;
+    ) -> ResultError<BitMatrix> {
+        self.encode_options(contents, format, width, height, &EncodeOptions::new())
+    }
+
+    /**
+     * @param contents The contents to encode in the barcode
+     * @param format The barcode format to generate
+     * @param width The preferred width in pixels
+     * @param height The preferred height in pixels
+     * @param hints Additional parameters to supply to the encoder
+     * @return {@link BitMatrix} representing encoded barcode image
+     * @throws IllegalArgumentException if a hint value cannot be parsed or is out of range
+     * @throws WriterException if contents cannot be encoded legally in a format
+     */
+    #[cfg(feature = "std")]
     fn encode_hints(
         &self,
         contents: &String,
@@ -29,5 +49,27 @@ pub trait Writer {
         width: i32,
         height: i32,
         hints: HashMap<EncodeHintType, &String>,
+    ) -> ResultError<BitMatrix> {
+        let options = EncodeOptions::try_from(&hints)?;
+        self.encode_options(contents, format, width, height, &options)
+    }
+
+    /**
+     * @param contents The contents to encode in the barcode
+     * @param format The barcode format to generate
+     * @param width The preferred width in pixels
+     * @param height The preferred height in pixels
+     * @param options Typed parameters to supply to the encoder
+     * @return {@link BitMatrix} representing encoded barcode image
+     * @throws IllegalArgumentException if an option is out of range
+     * @throws WriterException if contents cannot be encoded legally in a format
+     */
+    fn encode_options(
+        &self,
+        contents: &String,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+        options: &EncodeOptions,
     ) -> ResultError<BitMatrix>;
 }
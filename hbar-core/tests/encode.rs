@@ -0,0 +1,77 @@
+//! Encodes through the public API only; run with `--no-default-features --features
+//! encode,qrcode` to check that it is all reachable without std.
+#![cfg(all(feature = "encode", feature = "qrcode"))]
+
+use hbar_core::{
+    BarcodeFormat, BitMatrix, EncodeOptions, ErrorCorrectionLevel, GenericGFEnum, QRCodeEncoder,
+    QRCodeWriter, ReedSolomonEncoder, Writer,
+};
+
+#[test]
+fn qrcode_writer() {
+    let matrix: BitMatrix = QRCodeWriter::new()
+        .encode_options(
+            &"hbar".to_string(),
+            &BarcodeFormat::QRCode,
+            29,
+            29,
+            &EncodeOptions::new(),
+        )
+        .unwrap();
+    // Version 1 is 21 modules across, with a quiet zone of 4 on either side.
+    assert_eq!(matrix.getWidth(), 29);
+    assert_eq!(matrix.getHeight(), 29);
+    // The top left finder pattern starts just inside the quiet zone.
+    assert!(!matrix.get(3, 3));
+    assert!(matrix.get(4, 4));
+}
+
+#[test]
+fn qrcode_encoder() {
+    let code = QRCodeEncoder::new()
+        .encode_options(
+            &"hbar".to_string(),
+            ErrorCorrectionLevel::H,
+            &EncodeOptions::new(),
+        )
+        .unwrap();
+    let matrix = code.get_matrix();
+    assert_eq!(matrix.get_width(), 21);
+    assert_eq!(matrix.get(0, 0), 1);
+}
+
+#[test]
+fn qrcode_encoder_checks_gs1() {
+    let encoder = QRCodeEncoder::new();
+    let options = EncodeOptions::new().gs1_format(true);
+    // GS1 data is checked against the AI table before it is encoded
+    assert!(encoder
+        .encode_options(
+            &"0109501101020917".to_string(),
+            ErrorCorrectionLevel::M,
+            &options
+        )
+        .is_ok());
+    assert!(encoder
+        .encode_options(
+            &"0109501101020918".to_string(),
+            ErrorCorrectionLevel::M,
+            &options
+        )
+        .is_err());
+}
+
+#[test]
+fn reed_solomon_encoder() {
+    let encoder = ReedSolomonEncoder::for_field(GenericGFEnum::QrCodeField256).unwrap();
+    // The data codewords of "01234567" at version 1-M, from ISO/IEC 18004 Annex I.
+    let mut codewords = vec![
+        0x10, 0x20, 0x0C, 0x56, 0x61, 0x80, 0xEC, 0x11, 0xEC, 0x11, 0xEC, 0x11, 0xEC, 0x11, 0xEC,
+        0x11, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ];
+    encoder.encode(&mut codewords, 10).unwrap();
+    assert_eq!(
+        &codewords[16..],
+        &[0xA5, 0x24, 0xD4, 0xC1, 0xED, 0x36, 0xC7, 0x87, 0x2C, 0x55]
+    );
+}
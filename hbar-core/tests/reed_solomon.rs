@@ -0,0 +1,17 @@
+#![cfg(all(feature = "encode", feature = "decode"))]
+
+use hbar_core::{GenericGFEnum, ReedSolomonDecoder, ReedSolomonEncoder};
+
+#[test]
+fn round_trip_large_buffer() {
+    let data: Vec<u8> = (0..20_000u32).map(|i| (i % 251) as u8).collect();
+    let encoder = ReedSolomonEncoder::for_field(GenericGFEnum::DataMatrixField256).unwrap();
+    let mut codewords = encoder.encode_u8(&data, 32).unwrap();
+    // The data goes in 90 blocks, so a burst of 1000 bytes leaves at most 12 bad bytes in each,
+    // fewer than the 16 that 32 error-correction bytes can correct.
+    for byte in &mut codewords[5000..6000] {
+        *byte = !*byte;
+    }
+    let decoder = ReedSolomonDecoder::new(GenericGFEnum::DataMatrixField256.get());
+    assert_eq!(data, decoder.decode_u8(&codewords, 32).unwrap());
+}
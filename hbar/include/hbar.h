@@ -0,0 +1,273 @@
+/* hbar: a C ABI over hbar-core. Licensed under the Apache License, Version 2.0. */
+
+#ifndef HBAR_H
+#define HBAR_H
+
+/* Generated with cbindgen from hbar/src; do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The outcome of a call.
+ */
+typedef enum HbarStatus {
+  HBAR_STATUS_OK = 0,
+  /**
+   * A required pointer was null
+   */
+  HBAR_STATUS_NULL_POINTER = 1,
+  /**
+   * An argument was out of range, or a string was not UTF-8
+   */
+  HBAR_STATUS_INVALID_ARGUMENT = 2,
+  /**
+   * No barcode was found
+   */
+  HBAR_STATUS_NOT_FOUND = 3,
+  /**
+   * A barcode was found, but its data is malformed
+   */
+  HBAR_STATUS_FORMAT = 4,
+  /**
+   * A barcode was found, but could not be corrected
+   */
+  HBAR_STATUS_CHECKSUM = 5,
+  /**
+   * The contents could not be encoded
+   */
+  HBAR_STATUS_WRITER = 6,
+  /**
+   * An image could not be read or written
+   */
+  HBAR_STATUS_IO = 7,
+  /**
+   * The operation is not supported, like an unknown format
+   */
+  HBAR_STATUS_UNSUPPORTED = 8,
+  /**
+   * Something unexpected went wrong inside the library
+   */
+  HBAR_STATUS_INTERNAL = 9,
+} HbarStatus;
+
+/**
+ * Options for decoding.
+ */
+typedef struct HbarDecodeOptions {
+  /**
+   * Spend more time to find a barcode
+   */
+  bool try_harder;
+  /**
+   * The image is a barcode alone, like a rendered one, without finding it
+   */
+  bool pure_barcode;
+  /**
+   * Also look for light barcodes on a dark background
+   */
+  bool also_inverted;
+  /**
+   * The only format to look for, like "QRCode", or null for any
+   */
+  const char *format;
+} HbarDecodeOptions;
+
+/**
+ * A point of interest in the image, like the centre of a finder pattern.
+ */
+typedef struct HbarPoint {
+  float x;
+  float y;
+} HbarPoint;
+
+/**
+ * A decoded barcode. Its data belongs to the library and is released with
+ * hbar_result_free.
+ */
+typedef struct HbarResult {
+  /**
+   * The text, NUL-terminated UTF-8, which may also contain NULs of its own
+   */
+  char *text;
+  /**
+   * The number of bytes of text, without the terminating NUL
+   */
+  size_t text_len;
+  /**
+   * The raw bytes of the barcode, if the format has them
+   */
+  uint8_t *raw_bytes;
+  size_t raw_bytes_len;
+  /**
+   * The name of the format, like "QRCode"
+   */
+  char *format;
+  struct HbarPoint *points;
+  size_t points_len;
+} HbarResult;
+
+/**
+ * Options for encoding. Null strings and a negative margin leave the writer's defaults.
+ */
+typedef struct HbarEncodeOptions {
+  /**
+   * The error correction level, like "M" for QR Code
+   */
+  const char *error_correction;
+  /**
+   * The character set of the contents, like "UTF-8"
+   */
+  const char *character_set;
+  /**
+   * The quiet zone in modules
+   */
+  int32_t margin;
+  /**
+   * Whether the contents are GS1 element strings, to be marked with FNC1
+   */
+  bool gs1_format;
+} HbarEncodeOptions;
+
+/**
+ * An encoded barcode, one byte per pixel row by row: 1 for black, 0 for white. The data
+ * belongs to the library and is released with hbar_matrix_free.
+ */
+typedef struct HbarMatrix {
+  uint8_t *data;
+  uint32_t width;
+  uint32_t height;
+} HbarMatrix;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * The message of the last failed call on this thread, or an empty string if the last call
+ * succeeded. It stays valid until the next call on this thread.
+ */
+const char *hbar_last_error(void);
+
+/**
+ * The version of the library, like "0.1.0".
+ */
+const char *hbar_version(void);
+
+/**
+ * Decodes a barcode from an 8 bit grayscale image.
+ *
+ * @param data the pixels, row by row
+ * @param data_len the number of bytes of pixels
+ * @param width the width of the image
+ * @param height the height of the image
+ * @param stride the number of bytes from the start of one row to the next, at least width
+ * @param options the options, or null for the defaults
+ * @param result set to the decoded barcode
+ *
+ * # Safety
+ *
+ * data must point to data_len readable bytes, options must be null or valid, and
+ * result must be null or writable.
+ */
+enum HbarStatus hbar_decode_gray(const uint8_t *data,
+                                 size_t data_len,
+                                 uint32_t width,
+                                 uint32_t height,
+                                 uint32_t stride,
+                                 const struct HbarDecodeOptions *options,
+                                 struct HbarResult *result);
+
+/**
+ * Decodes a barcode from an image file, in any format the image crate reads, like PNG or JPEG.
+ *
+ * @param path the NUL-terminated UTF-8 path of the file
+ * @param options the options, or null for the defaults
+ * @param result set to the decoded barcode
+ *
+ * # Safety
+ *
+ * path must be null or a NUL-terminated string, options must be null or valid, and
+ * result must be null or writable.
+ */
+enum HbarStatus hbar_decode_path(const char *path,
+                                 const struct HbarDecodeOptions *options,
+                                 struct HbarResult *result);
+
+/**
+ * Releases the data of a result filled in by a decode function, and empties it. A null
+ * result, or one already released, is ignored.
+ *
+ * # Safety
+ *
+ * result must be null, or a result filled in by this library and not changed since.
+ */
+void hbar_result_free(struct HbarResult *result);
+
+/**
+ * Encodes the contents as a barcode at least width by height pixels. With a
+ * size of 1 by 1 and a margin of 0, each pixel is one module.
+ *
+ * @param contents NUL-terminated UTF-8 contents
+ * @param format the name of the format, like "QRCode"
+ * @param options the options, or null for the defaults
+ * @param matrix set to the encoded barcode
+ *
+ * # Safety
+ *
+ * The strings must be null or NUL-terminated, options must be null or valid, and
+ * matrix must be null or writable.
+ */
+enum HbarStatus hbar_encode(const char *contents,
+                            const char *format,
+                            int32_t width,
+                            int32_t height,
+                            const struct HbarEncodeOptions *options,
+                            struct HbarMatrix *matrix);
+
+/**
+ * Encodes the contents like hbar_encode, as an 8 bit grayscale PNG image.
+ *
+ * @param png set to the PNG data, released with hbar_buffer_free
+ * @param png_len set to the number of bytes of PNG data
+ *
+ * # Safety
+ *
+ * The strings must be null or NUL-terminated, options must be null or valid, and
+ * png and png_len must be null or writable.
+ */
+enum HbarStatus hbar_encode_png(const char *contents,
+                                const char *format,
+                                int32_t width,
+                                int32_t height,
+                                const struct HbarEncodeOptions *options,
+                                uint8_t **png,
+                                size_t *png_len);
+
+/**
+ * Releases the data of a matrix filled in by hbar_encode, and empties it. A null
+ * matrix, or one already released, is ignored.
+ *
+ * # Safety
+ *
+ * matrix must be null, or a matrix filled in by this library and not changed since.
+ */
+void hbar_matrix_free(struct HbarMatrix *matrix);
+
+/**
+ * Releases a buffer returned by the library, like the PNG data of hbar_encode_png.
+ *
+ * # Safety
+ *
+ * data must be null, or a buffer of len bytes returned by this library.
+ */
+void hbar_buffer_free(uint8_t *data, size_t len);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* HBAR_H */
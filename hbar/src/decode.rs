@@ -0,0 +1,212 @@
+use crate::{free_slice, into_raw_slice, run, to_str, Failure, HbarStatus};
+
+use hbar_core::{
+    BarcodeFormat, BinaryBitmap, BufferedImage, BufferedImageLuminanceSource, DecodeOptions,
+    GrayLuminanceSource, HybridBinarizer, LuminanceSource, MultiFormatReader, Reader, Results,
+};
+
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::path::Path;
+use std::ptr;
+use std::slice;
+use std::str::FromStr;
+
+/// Options for decoding.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct HbarDecodeOptions {
+    /// Spend more time to find a barcode
+    pub try_harder: bool,
+    /// The image is a barcode alone, like a rendered one, without finding it
+    pub pure_barcode: bool,
+    /// Also look for light barcodes on a dark background
+    pub also_inverted: bool,
+    /// The only format to look for, like "QRCode", or null for any
+    pub format: *const c_char,
+}
+
+/// A point of interest in the image, like the centre of a finder pattern.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HbarPoint {
+    pub x: f32,
+    pub y: f32,
+}
+
+/// A decoded barcode. Its data belongs to the library and is released with
+/// hbar_result_free.
+#[repr(C)]
+#[derive(Debug)]
+pub struct HbarResult {
+    /// The text, NUL-terminated UTF-8, which may also contain NULs of its own
+    pub text: *mut c_char,
+    /// The number of bytes of text, without the terminating NUL
+    pub text_len: usize,
+    /// The raw bytes of the barcode, if the format has them
+    pub raw_bytes: *mut u8,
+    pub raw_bytes_len: usize,
+    /// The name of the format, like "QRCode"
+    pub format: *mut c_char,
+    pub points: *mut HbarPoint,
+    pub points_len: usize,
+}
+
+impl HbarResult {
+    fn empty() -> HbarResult {
+        HbarResult {
+            text: ptr::null_mut(),
+            text_len: 0,
+            raw_bytes: ptr::null_mut(),
+            raw_bytes_len: 0,
+            format: ptr::null_mut(),
+            points: ptr::null_mut(),
+            points_len: 0,
+        }
+    }
+
+    fn new(result: &Results) -> HbarResult {
+        let mut text = result.get_text().clone().into_bytes();
+        let text_len = text.len();
+        text.push(0);
+        let (text, _) = into_raw_slice(text);
+        let (raw_bytes, raw_bytes_len) = into_raw_slice(result.get_raw_bytes().clone());
+        let format = CString::new(result.get_barcode_format().to_string())
+            .unwrap_or_default()
+            .into_raw();
+        let points = result
+            .get_result_points()
+            .iter()
+            .flatten()
+            .map(|point| HbarPoint {
+                x: point.get_x(),
+                y: point.get_y(),
+            })
+            .collect();
+        let (points, points_len) = into_raw_slice(points);
+        HbarResult {
+            text: text as *mut c_char,
+            text_len,
+            raw_bytes,
+            raw_bytes_len,
+            format,
+            points,
+            points_len,
+        }
+    }
+}
+
+/// Decodes a barcode from an 8 bit grayscale image.
+///
+/// @param data the pixels, row by row
+/// @param data_len the number of bytes of pixels
+/// @param width the width of the image
+/// @param height the height of the image
+/// @param stride the number of bytes from the start of one row to the next, at least width
+/// @param options the options, or null for the defaults
+/// @param result set to the decoded barcode
+///
+/// # Safety
+///
+/// data must point to data_len readable bytes, options must be null or valid, and
+/// result must be null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn hbar_decode_gray(
+    data: *const u8,
+    data_len: usize,
+    width: u32,
+    height: u32,
+    stride: u32,
+    options: *const HbarDecodeOptions,
+    result: *mut HbarResult,
+) -> HbarStatus {
+    run(|| {
+        let out = result
+            .as_mut()
+            .ok_or_else(|| Failure::new(HbarStatus::NullPointer, "result is null"))?;
+        *out = HbarResult::empty();
+        if data.is_null() {
+            return Err(Failure::new(HbarStatus::NullPointer, "data is null"));
+        }
+        let pixels = slice::from_raw_parts(data, data_len);
+        let source = GrayLuminanceSource::new(pixels, width, height, stride)?;
+        *out = HbarResult::new(&decode(source, options)?);
+        Ok(())
+    })
+}
+
+/// Decodes a barcode from an image file, in any format the image crate reads, like PNG or JPEG.
+///
+/// @param path the NUL-terminated UTF-8 path of the file
+/// @param options the options, or null for the defaults
+/// @param result set to the decoded barcode
+///
+/// # Safety
+///
+/// path must be null or a NUL-terminated string, options must be null or valid, and
+/// result must be null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn hbar_decode_path(
+    path: *const c_char,
+    options: *const HbarDecodeOptions,
+    result: *mut HbarResult,
+) -> HbarStatus {
+    run(|| {
+        let out = result
+            .as_mut()
+            .ok_or_else(|| Failure::new(HbarStatus::NullPointer, "result is null"))?;
+        *out = HbarResult::empty();
+        let path = to_str("path", path)?;
+        let image = BufferedImage::open(Path::new(path))?;
+        let source = BufferedImageLuminanceSource::new(&image)?;
+        *out = HbarResult::new(&decode(source, options)?);
+        Ok(())
+    })
+}
+
+/// Releases the data of a result filled in by a decode function, and empties it. A null
+/// result, or one already released, is ignored.
+///
+/// # Safety
+///
+/// result must be null, or a result filled in by this library and not changed since.
+#[no_mangle]
+pub unsafe extern "C" fn hbar_result_free(result: *mut HbarResult) {
+    if let Some(result) = result.as_mut() {
+        if !result.text.is_null() {
+            free_slice(result.text as *mut u8, result.text_len + 1);
+        }
+        free_slice(result.raw_bytes, result.raw_bytes_len);
+        if !result.format.is_null() {
+            drop(CString::from_raw(result.format));
+        }
+        free_slice(result.points, result.points_len);
+        *result = HbarResult::empty();
+    }
+}
+
+unsafe fn decode<S: LuminanceSource>(
+    source: S,
+    options: *const HbarDecodeOptions,
+) -> Result<Results, Failure> {
+    let mut decode_options = DecodeOptions::new();
+    if let Some(options) = options.as_ref() {
+        decode_options = decode_options
+            .try_harder(options.try_harder)
+            .pure_barcode(options.pure_barcode)
+            .also_inverted(options.also_inverted);
+        if !options.format.is_null() {
+            let format_name = to_str("format", options.format)?;
+            let format = BarcodeFormat::from_str(format_name).map_err(|_| {
+                Failure::new(
+                    HbarStatus::Unsupported,
+                    &format!("Unknown format: {}", format_name),
+                )
+            })?;
+            decode_options = decode_options.possible_formats(vec![format]);
+        }
+    }
+    let image = BinaryBitmap::new(HybridBinarizer::new(source));
+    let reader: MultiFormatReader<HybridBinarizer<S>, S> = MultiFormatReader::new()?;
+    Ok(reader.decode_options(&image, &decode_options)?)
+}
@@ -0,0 +1,210 @@
+use crate::{free_slice, into_raw_slice, run, to_str, Failure, HbarStatus};
+
+use hbar_core::{BarcodeFormat, EncodeHintType, EncodeOptions, MultiFormatWriter, Writer};
+use image::codecs::png::PngEncoder;
+use image::ColorType;
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::os::raw::c_char;
+use std::ptr;
+use std::str::FromStr;
+
+/// Options for encoding. Null strings and a negative margin leave the writer's defaults.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct HbarEncodeOptions {
+    /// The error correction level, like "M" for QR Code
+    pub error_correction: *const c_char,
+    /// The character set of the contents, like "UTF-8"
+    pub character_set: *const c_char,
+    /// The quiet zone in modules
+    pub margin: i32,
+    /// Whether the contents are GS1 element strings, to be marked with FNC1
+    pub gs1_format: bool,
+}
+
+/// An encoded barcode, one byte per pixel row by row: 1 for black, 0 for white. The data
+/// belongs to the library and is released with hbar_matrix_free.
+#[repr(C)]
+#[derive(Debug)]
+pub struct HbarMatrix {
+    pub data: *mut u8,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl HbarMatrix {
+    fn empty() -> HbarMatrix {
+        HbarMatrix {
+            data: ptr::null_mut(),
+            width: 0,
+            height: 0,
+        }
+    }
+}
+
+/// Encodes the contents as a barcode at least width by height pixels. With a
+/// size of 1 by 1 and a margin of 0, each pixel is one module.
+///
+/// @param contents NUL-terminated UTF-8 contents
+/// @param format the name of the format, like "QRCode"
+/// @param options the options, or null for the defaults
+/// @param matrix set to the encoded barcode
+///
+/// # Safety
+///
+/// The strings must be null or NUL-terminated, options must be null or valid, and
+/// matrix must be null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn hbar_encode(
+    contents: *const c_char,
+    format: *const c_char,
+    width: i32,
+    height: i32,
+    options: *const HbarEncodeOptions,
+    matrix: *mut HbarMatrix,
+) -> HbarStatus {
+    run(|| {
+        let out = matrix
+            .as_mut()
+            .ok_or_else(|| Failure::new(HbarStatus::NullPointer, "matrix is null"))?;
+        *out = HbarMatrix::empty();
+        let (modules, width, height) = encode(contents, format, width, height, options)?;
+        let (data, _) = into_raw_slice(modules);
+        *out = HbarMatrix {
+            data,
+            width,
+            height,
+        };
+        Ok(())
+    })
+}
+
+/// Encodes the contents like hbar_encode, as an 8 bit grayscale PNG image.
+///
+/// @param png set to the PNG data, released with hbar_buffer_free
+/// @param png_len set to the number of bytes of PNG data
+///
+/// # Safety
+///
+/// The strings must be null or NUL-terminated, options must be null or valid, and
+/// png and png_len must be null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn hbar_encode_png(
+    contents: *const c_char,
+    format: *const c_char,
+    width: i32,
+    height: i32,
+    options: *const HbarEncodeOptions,
+    png: *mut *mut u8,
+    png_len: *mut usize,
+) -> HbarStatus {
+    run(|| {
+        if png.is_null() || png_len.is_null() {
+            return Err(Failure::new(
+                HbarStatus::NullPointer,
+                "png or png_len is null",
+            ));
+        }
+        *png = ptr::null_mut();
+        *png_len = 0;
+        let (modules, width, height) = encode(contents, format, width, height, options)?;
+        let pixels: Vec<u8> = modules
+            .iter()
+            .map(|&module| if module == 1 { 0 } else { 255 })
+            .collect();
+        let mut buffer = Vec::new();
+        PngEncoder::new(&mut buffer)
+            .encode(&pixels, width, height, ColorType::L8)
+            .map_err(|err| Failure::new(HbarStatus::Io, &err.to_string()))?;
+        let (data, len) = into_raw_slice(buffer);
+        *png = data;
+        *png_len = len;
+        Ok(())
+    })
+}
+
+/// Releases the data of a matrix filled in by hbar_encode, and empties it. A null
+/// matrix, or one already released, is ignored.
+///
+/// # Safety
+///
+/// matrix must be null, or a matrix filled in by this library and not changed since.
+#[no_mangle]
+pub unsafe extern "C" fn hbar_matrix_free(matrix: *mut HbarMatrix) {
+    if let Some(matrix) = matrix.as_mut() {
+        free_slice(matrix.data, (matrix.width * matrix.height) as usize);
+        *matrix = HbarMatrix::empty();
+    }
+}
+
+/// Releases a buffer returned by the library, like the PNG data of hbar_encode_png.
+///
+/// # Safety
+///
+/// data must be null, or a buffer of len bytes returned by this library.
+#[no_mangle]
+pub unsafe extern "C" fn hbar_buffer_free(data: *mut u8, len: usize) {
+    free_slice(data, len);
+}
+
+unsafe fn encode(
+    contents: *const c_char,
+    format: *const c_char,
+    width: i32,
+    height: i32,
+    options: *const HbarEncodeOptions,
+) -> Result<(Vec<u8>, u32, u32), Failure> {
+    let contents = to_str("contents", contents)?.to_string();
+    let format_name = to_str("format", format)?;
+    let format = BarcodeFormat::from_str(format_name).map_err(|_| {
+        Failure::new(
+            HbarStatus::Unsupported,
+            &format!("Unknown format: {}", format_name),
+        )
+    })?;
+    let options = match options.as_ref() {
+        Some(options) => encode_options(options)?,
+        None => EncodeOptions::new(),
+    };
+    let bit_matrix =
+        MultiFormatWriter::new().encode_options(&contents, &format, width, height, &options)?;
+    let width = bit_matrix.getWidth() as u32;
+    let height = bit_matrix.getHeight() as u32;
+    let mut modules = Vec::with_capacity((width * height) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            modules.push(bit_matrix.get(x, y) as u8);
+        }
+    }
+    Ok((modules, width, height))
+}
+
+unsafe fn encode_options(options: &HbarEncodeOptions) -> Result<EncodeOptions, Failure> {
+    let error_correction = if options.error_correction.is_null() {
+        None
+    } else {
+        Some(to_str("error_correction", options.error_correction)?.to_string())
+    };
+    let character_set = if options.character_set.is_null() {
+        None
+    } else {
+        Some(to_str("character_set", options.character_set)?.to_string())
+    };
+    let margin = options.margin.to_string();
+    let gs1_format = options.gs1_format.to_string();
+
+    let mut hints: HashMap<EncodeHintType, &String> = HashMap::new();
+    if let Some(error_correction) = &error_correction {
+        hints.insert(EncodeHintType::ErrorCorrection, error_correction);
+    }
+    if let Some(character_set) = &character_set {
+        hints.insert(EncodeHintType::CharacterSet, character_set);
+    }
+    if options.margin >= 0 {
+        hints.insert(EncodeHintType::MARGIN, &margin);
+    }
+    hints.insert(EncodeHintType::Gs1Format, &gs1_format);
+    Ok(EncodeOptions::try_from(&hints)?)
+}
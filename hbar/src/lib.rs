@@ -1,7 +1,153 @@
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn it_works() {
-        assert_eq!(2 + 2, 4);
+//! A C ABI over hbar-core, for the ports to other languages. The declarations are in
+//! include/hbar.h, generated with `cbindgen --config cbindgen.toml --output include/hbar.h`.
+//!
+//! Functions which can fail return an HbarStatus, and hbar_last_error then tells what went
+//! wrong. Whatever the library allocates is released with the matching free function.
+
+mod decode;
+mod encode;
+
+pub use crate::decode::{
+    hbar_decode_gray, hbar_decode_path, hbar_result_free, HbarDecodeOptions, HbarPoint, HbarResult,
+};
+pub use crate::encode::{
+    hbar_buffer_free, hbar_encode, hbar_encode_png, hbar_matrix_free, HbarEncodeOptions, HbarMatrix,
+};
+
+use hbar_core::Error;
+
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// The outcome of a call.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HbarStatus {
+    Ok = 0,
+    /// A required pointer was null
+    NullPointer = 1,
+    /// An argument was out of range, or a string was not UTF-8
+    InvalidArgument = 2,
+    /// No barcode was found
+    NotFound = 3,
+    /// A barcode was found, but its data is malformed
+    Format = 4,
+    /// A barcode was found, but could not be corrected
+    Checksum = 5,
+    /// The contents could not be encoded
+    Writer = 6,
+    /// An image could not be read or written
+    Io = 7,
+    /// The operation is not supported, like an unknown format
+    Unsupported = 8,
+    /// Something unexpected went wrong inside the library
+    Internal = 9,
+}
+
+impl HbarStatus {
+    fn from_error(error: &Error) -> HbarStatus {
+        match error {
+            Error::IllegalArgumentException(_)
+            | Error::TryFromIntError(_)
+            | Error::Infallible(_) => HbarStatus::InvalidArgument,
+            Error::NotFoundException(_) => HbarStatus::NotFound,
+            Error::FormatException(_) => HbarStatus::Format,
+            Error::ChecksumException(_) | Error::ReedSolomonException(_) => HbarStatus::Checksum,
+            Error::WriterException(_) => HbarStatus::Writer,
+            Error::ImageErrorException(_) => HbarStatus::Io,
+            Error::UnsupportedOperationException(_) => HbarStatus::Unsupported,
+            Error::ArithmeticException(_) | Error::IllegalStateException(_) => HbarStatus::Internal,
+        }
+    }
+}
+
+/// A failed call, with its status and a message for hbar_last_error.
+pub(crate) struct Failure {
+    status: HbarStatus,
+    message: String,
+}
+
+impl Failure {
+    pub(crate) fn new(status: HbarStatus, message: &str) -> Failure {
+        Failure {
+            status,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl From<Error> for Failure {
+    fn from(error: Error) -> Failure {
+        Failure {
+            status: HbarStatus::from_error(&error),
+            message: format!("{:?}", error),
+        }
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+/// Runs the body of an exported function: records the message of a failure for
+/// hbar_last_error, and keeps panics from unwinding into the caller.
+pub(crate) fn run<F: FnOnce() -> Result<(), Failure>>(body: F) -> HbarStatus {
+    let failure = match catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(())) => {
+            set_last_error("");
+            return HbarStatus::Ok;
+        }
+        Ok(Err(failure)) => failure,
+        Err(_) => Failure::new(HbarStatus::Internal, "Panicked inside hbar"),
+    };
+    set_last_error(&failure.message);
+    failure.status
+}
+
+fn set_last_error(message: &str) {
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = message);
+}
+
+/// Reads a NUL-terminated UTF-8 string argument.
+pub(crate) unsafe fn to_str<'a>(name: &str, value: *const c_char) -> Result<&'a str, Failure> {
+    if value.is_null() {
+        return Err(Failure::new(
+            HbarStatus::NullPointer,
+            &format!("{} is null", name),
+        ));
+    }
+    CStr::from_ptr(value).to_str().map_err(|_| {
+        Failure::new(
+            HbarStatus::InvalidArgument,
+            &format!("{} is not UTF-8", name),
+        )
+    })
+}
+
+/// Hands a buffer over to the caller, who gives it back to free_slice.
+pub(crate) fn into_raw_slice<T>(values: Vec<T>) -> (*mut T, usize) {
+    let len = values.len();
+    (Box::into_raw(values.into_boxed_slice()) as *mut T, len)
+}
+
+pub(crate) unsafe fn free_slice<T>(data: *mut T, len: usize) {
+    if !data.is_null() {
+        drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(data, len)));
     }
 }
+
+/// The message of the last failed call on this thread, or an empty string if the last call
+/// succeeded. It stays valid until the next call on this thread.
+#[no_mangle]
+pub extern "C" fn hbar_last_error() -> *const c_char {
+    LAST_ERROR.with(|last_error| last_error.borrow().as_ptr())
+}
+
+/// The version of the library, like "0.1.0".
+#[no_mangle]
+pub extern "C" fn hbar_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr() as *const c_char
+}
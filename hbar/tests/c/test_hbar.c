@@ -0,0 +1,141 @@
+/*
+ * Exercises the C ABI of hbar: encodes a QR Code to a matrix and to a PNG file, decodes both
+ * back, and checks the error codes. Built and run by tests/c_abi.rs, or by hand:
+ *
+ *   cc tests/c/test_hbar.c -Iinclude -L../target/debug -lhbar -o test_hbar
+ *   LD_LIBRARY_PATH=../target/debug ./test_hbar /tmp/test_hbar.png
+ */
+#include "hbar.h"
+
+#include <stdio.h>
+#include <string.h>
+
+#define SCALE 4
+
+static int failures = 0;
+
+#define CHECK(condition)                                                   \
+  do {                                                                     \
+    if (!(condition)) {                                                    \
+      fprintf(stderr, "%s:%d: check failed: %s (last error: %s)\n",        \
+              __FILE__, __LINE__, #condition, hbar_last_error());          \
+      failures++;                                                          \
+    }                                                                      \
+  } while (0)
+
+static const char *CONTENTS = "Hello, hbar";
+
+static void test_matrix(void) {
+  HbarEncodeOptions options = {"M", NULL, 4, false};
+  HbarMatrix matrix;
+  CHECK(hbar_encode(CONTENTS, "QRCode", 1, 1, &options, &matrix) == HBAR_STATUS_OK);
+  CHECK(matrix.data != NULL);
+  /* Version 1 is 21 modules wide, plus the quiet zone on each side */
+  CHECK(matrix.width == 29 && matrix.height == 29);
+
+  /* Scale the modules up to a grayscale image, black 0 and white 255 */
+  uint32_t width = matrix.width * SCALE;
+  uint32_t height = matrix.height * SCALE;
+  uint8_t *pixels = malloc(width * height);
+  for (uint32_t y = 0; y < height; y++) {
+    for (uint32_t x = 0; x < width; x++) {
+      pixels[y * width + x] = matrix.data[(y / SCALE) * matrix.width + x / SCALE] ? 0 : 255;
+    }
+  }
+  hbar_matrix_free(&matrix);
+  CHECK(matrix.data == NULL);
+  /* Releasing twice is harmless */
+  hbar_matrix_free(&matrix);
+
+  HbarDecodeOptions decode_options = {false, false, false, "QRCode"};
+  HbarResult result;
+  CHECK(hbar_decode_gray(pixels, width * height, width, height, width, &decode_options,
+                         &result) == HBAR_STATUS_OK);
+  CHECK(result.text_len == strlen(CONTENTS) && strcmp(result.text, CONTENTS) == 0);
+  CHECK(strcmp(result.format, "QRCode") == 0);
+  CHECK(result.raw_bytes_len > 0);
+  /* The centres of the three finder patterns */
+  CHECK(result.points_len == 3 && result.points != NULL);
+  CHECK(result.points[1].x < result.points[2].x && result.points[1].y < result.points[0].y);
+  hbar_result_free(&result);
+  CHECK(result.text == NULL && result.points == NULL);
+
+  /* Light on dark is only found when asked for */
+  for (uint32_t i = 0; i < width * height; i++) {
+    pixels[i] = 255 - pixels[i];
+  }
+  CHECK(hbar_decode_gray(pixels, width * height, width, height, width, &decode_options,
+                         &result) == HBAR_STATUS_NOT_FOUND);
+  HbarDecodeOptions inverted_options = {false, false, true, "QRCode"};
+  CHECK(hbar_decode_gray(pixels, width * height, width, height, width, &inverted_options,
+                         &result) == HBAR_STATUS_OK);
+  CHECK(result.text != NULL && strcmp(result.text, CONTENTS) == 0);
+  hbar_result_free(&result);
+
+  /* A blank image holds no barcode */
+  memset(pixels, 255, width * height);
+  CHECK(hbar_decode_gray(pixels, width * height, width, height, width, NULL, &result) ==
+        HBAR_STATUS_NOT_FOUND);
+  CHECK(strlen(hbar_last_error()) > 0);
+  CHECK(result.text == NULL);
+  /* Too few pixels for the geometry */
+  CHECK(hbar_decode_gray(pixels, 10, width, height, width, NULL, &result) ==
+        HBAR_STATUS_INVALID_ARGUMENT);
+  free(pixels);
+}
+
+static void test_png(const char *path) {
+  uint8_t *png;
+  size_t png_len;
+  CHECK(hbar_encode_png(CONTENTS, "QRCode", 200, 200, NULL, &png, &png_len) == HBAR_STATUS_OK);
+  CHECK(png_len > 8 && memcmp(png, "\x89PNG", 4) == 0);
+
+  FILE *file = fopen(path, "wb");
+  CHECK(file != NULL);
+  if (file != NULL) {
+    fwrite(png, 1, png_len, file);
+    fclose(file);
+  }
+  hbar_buffer_free(png, png_len);
+
+  HbarResult result;
+  CHECK(hbar_decode_path(path, NULL, &result) == HBAR_STATUS_OK);
+  CHECK(result.text != NULL && strcmp(result.text, CONTENTS) == 0);
+  hbar_result_free(&result);
+  remove(path);
+
+  CHECK(hbar_decode_path(path, NULL, &result) == HBAR_STATUS_IO);
+}
+
+static void test_errors(void) {
+  HbarMatrix matrix;
+  CHECK(hbar_encode(CONTENTS, "NoSuchFormat", 1, 1, NULL, &matrix) ==
+        HBAR_STATUS_UNSUPPORTED);
+  CHECK(strstr(hbar_last_error(), "NoSuchFormat") != NULL);
+  CHECK(hbar_encode(NULL, "QRCode", 1, 1, NULL, &matrix) == HBAR_STATUS_NULL_POINTER);
+  CHECK(hbar_encode(CONTENTS, "QRCode", 1, 1, NULL, NULL) == HBAR_STATUS_NULL_POINTER);
+
+  /* GS1 contents must pass the AI table */
+  HbarEncodeOptions gs1 = {NULL, NULL, -1, true};
+  CHECK(hbar_encode("0109501101020917", "QRCode", 1, 1, &gs1, &matrix) == HBAR_STATUS_OK);
+  hbar_matrix_free(&matrix);
+  CHECK(hbar_encode("0109501101020918", "QRCode", 1, 1, &gs1, &matrix) == HBAR_STATUS_WRITER);
+
+  CHECK(strlen(hbar_version()) > 0);
+  CHECK(hbar_encode(CONTENTS, "QRCode", 1, 1, NULL, &matrix) == HBAR_STATUS_OK);
+  CHECK(strlen(hbar_last_error()) == 0);
+  hbar_matrix_free(&matrix);
+}
+
+int main(int argc, char **argv) {
+  const char *png_path = argc > 1 ? argv[1] : "test_hbar.png";
+  test_matrix();
+  test_png(png_path);
+  test_errors();
+  if (failures > 0) {
+    fprintf(stderr, "%d checks failed\n", failures);
+    return 1;
+  }
+  printf("hbar %s: all checks passed\n", hbar_version());
+  return 0;
+}
@@ -0,0 +1,57 @@
+use std::env;
+use std::path::PathBuf;
+use std::process::Command;
+
+/**
+ * Builds tests/c/test_hbar.c against include/hbar.h and the cdylib, and runs it. Skipped when
+ * there is no C compiler.
+ */
+#[test]
+fn c_test_program() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // The test runs from target/<profile>/deps, where cargo puts the libhbar it was built
+    // with; the copy in target/<profile> is only refreshed by cargo build
+    let library_dir = env::current_exe().unwrap().parent().unwrap().to_path_buf();
+    let out_dir = env::temp_dir().join(format!("hbar_c_abi_{}", std::process::id()));
+    std::fs::create_dir_all(&out_dir).unwrap();
+    let program = out_dir.join("test_hbar");
+
+    let compiler = env::var("CC").unwrap_or_else(|_| String::from("cc"));
+    let compiled = Command::new(&compiler)
+        .arg(manifest_dir.join("tests/c/test_hbar.c"))
+        .arg("-std=c99")
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg("-I")
+        .arg(manifest_dir.join("include"))
+        .arg("-L")
+        .arg(&library_dir)
+        .arg(format!("-Wl,-rpath,{}", library_dir.display()))
+        .arg("-lhbar")
+        .arg("-o")
+        .arg(&program)
+        .status();
+    match compiled {
+        Ok(status) => assert!(status.success(), "{} failed to build the C test", compiler),
+        Err(err) => {
+            eprintln!(
+                "Skipping the C test, {} is not available: {}",
+                compiler, err
+            );
+            return;
+        }
+    }
+
+    // cargo's own library path, which also holds the stale copy, would win over the rpath
+    let output = Command::new(&program)
+        .env("LD_LIBRARY_PATH", &library_dir)
+        .arg(out_dir.join("test_hbar.png"))
+        .output()
+        .unwrap();
+    std::fs::remove_dir_all(&out_dir).ok();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -1,22 +1,80 @@
-from ctypes import cdll
+# Encodes a QR Code to a temporary PNG file and decodes it back, through the C ABI of hbar.
+# Build the library first with `cargo build -p hbar`, then run from the repository root:
+#   python3 ports/python/main.py
+import ctypes
+import os
+import tempfile
+from ctypes import (POINTER, Structure, byref, c_bool, c_char_p, c_float, c_int32, c_size_t,
+                    c_uint8, c_void_p)
 
-try:
-    lib = cdll.LoadLibrary('target/debug/libzxing_rust.so')
-except Exception as e:
-    lib = cdll.LoadLibrary('target/release/libzxing_rust.so')
+HBAR_STATUS_OK = 0
 
 
+class HbarPoint(Structure):
+    _fields_ = [('x', c_float), ('y', c_float)]
 
-def test_rust_add():
-    c = lib.add(2, 3)
-    print('c: ', c)
 
-test_rust_add()
+class HbarResult(Structure):
+    _fields_ = [
+        ('text', POINTER(c_uint8)),
+        ('text_len', c_size_t),
+        ('raw_bytes', POINTER(c_uint8)),
+        ('raw_bytes_len', c_size_t),
+        ('format', c_char_p),
+        ('points', POINTER(HbarPoint)),
+        ('points_len', c_size_t),
+    ]
 
 
-def test_rust_decode():
-    msg = lib.decode("test.png")
-    print('msg: ', msg)
+def load_library():
+    for profile in ('debug', 'release'):
+        path = os.path.join('target', profile, 'libhbar.so')
+        if os.path.exists(path):
+            break
+    lib = ctypes.cdll.LoadLibrary(path)
+    lib.hbar_last_error.restype = c_char_p
+    lib.hbar_version.restype = c_char_p
+    lib.hbar_encode_png.argtypes = [c_char_p, c_char_p, c_int32, c_int32, c_void_p,
+                                    POINTER(POINTER(c_uint8)), POINTER(c_size_t)]
+    lib.hbar_buffer_free.argtypes = [POINTER(c_uint8), c_size_t]
+    lib.hbar_decode_path.argtypes = [c_char_p, c_void_p, POINTER(HbarResult)]
+    lib.hbar_result_free.argtypes = [POINTER(HbarResult)]
+    return lib
 
 
-test_rust_decode()
\ No newline at end of file
+lib = load_library()
+
+
+def check(status):
+    if status != HBAR_STATUS_OK:
+        raise RuntimeError('hbar error %d: %s' % (status, lib.hbar_last_error().decode()))
+
+
+def encode_png(contents, path, size=200):
+    png = POINTER(c_uint8)()
+    png_len = c_size_t()
+    check(lib.hbar_encode_png(contents.encode(), b'QRCode', size, size, None, byref(png),
+                              byref(png_len)))
+    try:
+        with open(path, 'wb') as file:
+            file.write(ctypes.string_at(png, png_len.value))
+    finally:
+        lib.hbar_buffer_free(png, png_len)
+
+
+def decode(path):
+    result = HbarResult()
+    check(lib.hbar_decode_path(path.encode(), None, byref(result)))
+    try:
+        text = ctypes.string_at(result.text, result.text_len).decode()
+        return text, result.format.decode()
+    finally:
+        lib.hbar_result_free(byref(result))
+
+
+if __name__ == '__main__':
+    print('hbar', lib.hbar_version().decode())
+    with tempfile.TemporaryDirectory() as directory:
+        path = os.path.join(directory, 'test.png')
+        encode_png('Hello, hbar', path)
+        print('decoded: %s (%s)' % decode(path))
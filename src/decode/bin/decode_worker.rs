@@ -1,27 +1,33 @@
 use hbar_core::MultiFormatReader;
-use hbar_core::{BinaryBitmap, BufferedImage, HybridBinarizer, Reader};
+use hbar_core::{
+    BinaryBitmap, BufferedImage, BufferedImageLuminanceSource, HybridBinarizer, Reader,
+};
 use hbar_core::{DecodeHintType, DecodeHintValue};
 use hbar_core::{Error, ResultError, Results};
 
-use crate::BufferedImageLuminanceSource;
 use crate::DecoderConfig;
 
 use std::borrow::Borrow;
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
-pub struct DecodeWorker<'a> {
+/**
+ * One worker per thread. Workers share the queue of inputs and each takes the next image
+ * from it until the queue is empty.
+ */
+pub struct DecodeWorker {
     config: DecoderConfig,
-    inputs: Vec<&'a Path>,
+    inputs: Arc<Mutex<VecDeque<PathBuf>>>,
     hints: HashMap<DecodeHintType, DecodeHintValue>,
 }
 
-impl<'a> DecodeWorker<'a> {
+impl DecodeWorker {
     const RED: u32 = 0xFFFF0000;
     const BLACK: u32 = 0xFF000000;
     const WHITE: u32 = 0xFFFFFFFF;
 
-    pub fn new(config: DecoderConfig, inputs: Vec<&'a Path>) -> DecodeWorker {
+    pub fn new(config: DecoderConfig, inputs: Arc<Mutex<VecDeque<PathBuf>>>) -> DecodeWorker {
         DecodeWorker {
             hints: config.build_hints(),
             config: config,
@@ -31,12 +37,36 @@ impl<'a> DecodeWorker<'a> {
 
     pub fn call(&self) -> i32 {
         let mut successful = 0;
-        for input in &self.inputs {
-            println!("DecodeWorker  input: {:?}", input);
-            let results = self.decode(input, &self.hints).unwrap();
+        while let Some(input) = self.next_input() {
+            match self.decode(&input, &self.hints) {
+                Ok(results) => {
+                    successful += 1;
+                    for result in &results {
+                        if self.config.brief {
+                            println!("{}: Success", input.display());
+                        } else {
+                            println!(
+                                "{} ({:?}):\n{}",
+                                input.display(),
+                                result.get_barcode_format(),
+                                result.get_text()
+                            );
+                        }
+                    }
+                }
+                Err(Error::NotFoundException(_)) => {
+                    println!("{}: No barcode found", input.display());
+                }
+                Err(e) => {
+                    println!("{}: {:?}", input.display(), e);
+                }
+            }
         }
+        successful
+    }
 
-        todo!()
+    fn next_input(&self) -> Option<PathBuf> {
+        self.inputs.lock().ok()?.pop_front()
     }
 
     fn decode(
@@ -76,6 +106,6 @@ impl<'a> DecodeWorker<'a> {
             results.push(multi_format_reader.decode_hints(&bitmap, hints)?);
         }
 
-        todo!()
+        Ok(results)
     }
 }
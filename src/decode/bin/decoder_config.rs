@@ -47,6 +47,10 @@ pub struct DecoderConfig {
     #[structopt(short, long)]
     pub crop: Vec<i32>,
 
+    /// Character set used to decode byte segments, instead of guessing it
+    #[structopt(long)]
+    pub character_set: Option<String>,
+
     /// Formats to decode, where format is any value in BarcodeFormat
     #[structopt(long)]
     pub possible_formats: Vec<BarcodeFormat>,
@@ -91,6 +95,13 @@ impl DecoderConfig {
             hints.insert(DecodeHintType::PureBarcode, DecodeHintValue::BOOL(true));
         }
 
+        if let Some(character_set) = &self.character_set {
+            hints.insert(
+                DecodeHintType::CharacterSet,
+                DecodeHintValue::STRING(character_set.clone()),
+            );
+        }
+
         hints
     }
 }
@@ -1,12 +1,13 @@
-mod buffered_image_luminance_source;
 mod decode_worker;
 mod decoder_config;
 
-use buffered_image_luminance_source::BufferedImageLuminanceSource;
 use decode_worker::DecodeWorker;
 use decoder_config::DecoderConfig;
 
+use std::collections::VecDeque;
 use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::thread;
 use structopt::StructOpt;
 
 use hbar_core::ResultPoint;
@@ -15,7 +16,7 @@ fn main() {
     let config = DecoderConfig::from_args();
     println!("config: {:#?}", config);
 
-    let mut inputs = Vec::new();
+    let mut inputs = VecDeque::new();
     for input_path in &config.input_paths {
         let path = Path::new(input_path);
 
@@ -26,7 +27,7 @@ fn main() {
         if path.is_dir() {
             todo!("Will support in future")
         }
-        inputs.push(path);
+        inputs.push_back(path.to_path_buf());
     }
 
     let num_inputs = inputs.len();
@@ -35,12 +36,31 @@ fn main() {
         return;
     }
 
-    let mut num_threads = num_inputs;
-    num_threads = num_threads.min(1);
-    if num_threads > 1 {
-        todo!("")
+    let available = thread::available_parallelism().map_or(1, |n| n.get());
+    let num_threads = num_inputs.min(available);
+    let inputs = Arc::new(Mutex::new(inputs));
+    let successful: i32 = if num_threads > 1 {
+        let workers: Vec<_> = (0..num_threads)
+            .map(|_| {
+                let worker = DecodeWorker::new(config.clone(), Arc::clone(&inputs));
+                thread::spawn(move || worker.call())
+            })
+            .collect();
+        workers
+            .into_iter()
+            .map(|worker| worker.join().unwrap_or(0))
+            .sum()
     } else {
-        DecodeWorker::new(config.clone(), inputs).call();
+        DecodeWorker::new(config.clone(), inputs).call()
+    };
+
+    if num_inputs > 1 {
+        println!(
+            "\nDecoded {} files out of {} successfully ({}%)\n",
+            successful,
+            num_inputs,
+            successful as usize * 100 / num_inputs
+        );
     }
 
     println!("----------")